@@ -0,0 +1,300 @@
+//! Support for the [Confluent wire format][wire_format], where each message is prefixed with the
+//! ID of its schema in a schema registry.
+//!
+//! Fetching the schemas from the registry is left to the components, this module only reads and
+//! writes the message prefixes and builds the serializers and deserializers of the schemas.
+//!
+//! [wire_format]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use prost_reflect::{
+    prost::Message as _, prost_types::FileDescriptorProto, DescriptorPool, FileDescriptor,
+    MessageDescriptor,
+};
+
+use crate::decoding::format::{AvroDeserializer, ProtobufDeserializer};
+use crate::encoding::format::ProtobufSerializerOptions;
+
+/// The first byte of a message in the Confluent wire format.
+pub const MAGIC_BYTE: u8 = 0;
+
+/// Reads the schema ID that prefixes a message, returning the ID and the rest of the message.
+pub fn read_schema_id(mut message: Bytes) -> vector_common::Result<(u32, Bytes)> {
+    if message.len() < 5 || message[0] != MAGIC_BYTE {
+        return Err("Expected the message to be prefixed with a schema ID".into());
+    }
+    message.advance(1);
+    let schema_id = message.get_u32();
+    Ok((schema_id, message))
+}
+
+/// Reads the indexes of the Protobuf message type that follow the schema ID of a message,
+/// returning the indexes and the rest of the message.
+pub fn read_message_indexes(mut message: Bytes) -> vector_common::Result<(Vec<i32>, Bytes)> {
+    let count = read_varint(&mut message)?;
+    // The first message type of the file is written as an empty list of indexes.
+    if count == 0 {
+        return Ok((vec![0], message));
+    }
+    if count < 0 || count as usize > message.len() {
+        return Err(format!("Invalid number of message indexes: {count}").into());
+    }
+    let indexes = (0..count)
+        .map(|_| read_varint(&mut message))
+        .collect::<vector_common::Result<_>>()?;
+    Ok((indexes, message))
+}
+
+/// Writes the prefix of a message with the given schema ID, and the indexes of its message type
+/// for Protobuf schemas.
+pub fn write_header(schema_id: u32, message_indexes: Option<&[i32]>) -> Bytes {
+    let mut header = BytesMut::with_capacity(6);
+    header.put_u8(MAGIC_BYTE);
+    header.put_u32(schema_id);
+    match message_indexes {
+        None => {}
+        Some([0]) => write_varint(0, &mut header),
+        Some(indexes) => {
+            write_varint(indexes.len() as i32, &mut header);
+            for index in indexes {
+                write_varint(*index, &mut header);
+            }
+        }
+    }
+    header.freeze()
+}
+
+/// Reads a zigzag-encoded variable-length integer.
+fn read_varint(bytes: &mut Bytes) -> vector_common::Result<i32> {
+    let mut value = 0u32;
+    for shift in (0..35).step_by(7) {
+        if !bytes.has_remaining() {
+            break;
+        }
+        let byte = bytes.get_u8();
+        value |= u32::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok((value >> 1) as i32 ^ -((value & 1) as i32));
+        }
+    }
+    Err("Invalid variable-length integer".into())
+}
+
+/// Writes a zigzag-encoded variable-length integer.
+fn write_varint(value: i32, buffer: &mut BytesMut) {
+    let mut value = ((value << 1) ^ (value >> 31)) as u32;
+    while value >= 0x80 {
+        buffer.put_u8(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buffer.put_u8(value as u8);
+}
+
+/// Builds a deserializer for messages written with the given Avro schema, without their prefix.
+pub fn avro_deserializer(schema: &str) -> vector_common::Result<AvroDeserializer> {
+    let schema = apache_avro::Schema::parse_str(schema)
+        .map_err(|error| format!("Invalid Avro schema: {error}"))?;
+    Ok(AvroDeserializer::new(schema, false))
+}
+
+/// Returns the full name of the record defined by an Avro schema.
+pub fn avro_record_name(schema: &str) -> vector_common::Result<String> {
+    match apache_avro::Schema::parse_str(schema)
+        .map_err(|error| format!("Invalid Avro schema: {error}"))?
+    {
+        apache_avro::Schema::Record(record) => Ok(record.name.fullname(None)),
+        _ => Err("Expected the Avro schema to be a record".into()),
+    }
+}
+
+/// A Protobuf schema, made of a file and the files that it imports.
+#[derive(Clone, Debug)]
+pub struct ProtobufSchema {
+    file: FileDescriptor,
+}
+
+impl ProtobufSchema {
+    /// Decodes a schema from serialized file descriptors, where each file comes after the files
+    /// that it imports.
+    pub fn decode(files: &[Bytes]) -> vector_common::Result<Self> {
+        // The global pool already contains the well-known types, which schema registries don't
+        // store as references.
+        let mut pool = DescriptorPool::global();
+        let mut name = None;
+        for file in files {
+            let file = FileDescriptorProto::decode(file.clone())?;
+            name = Some(file.name().to_owned());
+            if pool.get_file_by_name(file.name()).is_none() {
+                pool.add_file_descriptor_proto(file)?;
+            }
+        }
+
+        let file = name
+            .and_then(|name| pool.get_file_by_name(&name))
+            .ok_or("Expected at least one Protobuf file")?;
+        Ok(Self { file })
+    }
+
+    /// Builds a deserializer for the message type with the given indexes, for messages without
+    /// their prefix.
+    pub fn deserializer(
+        &self,
+        message_indexes: &[i32],
+    ) -> vector_common::Result<ProtobufDeserializer> {
+        let message = |mut messages: Box<dyn Iterator<Item = MessageDescriptor>>, index: i32| {
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| messages.nth(index))
+                .ok_or_else(|| format!("Unknown message index {index} in {}", self.file.name()))
+        };
+
+        let (first, nested) = message_indexes.split_first().unwrap_or((&0, &[]));
+        let mut descriptor = message(Box::new(self.file.messages()), *first)?;
+        for index in nested {
+            descriptor = message(Box::new(descriptor.child_messages()), *index)?;
+        }
+        Ok(ProtobufDeserializer::new(descriptor))
+    }
+}
+
+/// The schema of the messages written by a `ProtobufSerializer`, as stored in a schema registry.
+#[derive(Clone, Debug)]
+pub struct ProtobufMessageSchema {
+    /// The serialized descriptor of the file that defines the message type.
+    pub file: Bytes,
+
+    /// The full name of the message type.
+    pub full_name: String,
+
+    /// The indexes of the message type within its file.
+    pub message_indexes: Vec<i32>,
+}
+
+impl ProtobufMessageSchema {
+    /// Reads the schema of the messages written with the given options.
+    pub fn new(options: &ProtobufSerializerOptions) -> vector_common::Result<Self> {
+        let descriptor =
+            vrl::protobuf::get_message_descriptor(&options.desc_file, &options.message_type)?;
+        let file = descriptor.parent_file();
+        if let Some(import) = file
+            .dependencies()
+            .find(|import| !import.name().starts_with("google/protobuf/"))
+        {
+            return Err(format!(
+                "Protobuf file {} imports {}, but imports are not supported by schema registries",
+                file.name(),
+                import.name()
+            )
+            .into());
+        }
+
+        Ok(Self {
+            file: file.file_descriptor_proto().encode_to_vec().into(),
+            full_name: descriptor.full_name().to_owned(),
+            message_indexes: message_indexes(&descriptor),
+        })
+    }
+}
+
+/// Returns the indexes of a message type within its file.
+fn message_indexes(descriptor: &MessageDescriptor) -> Vec<i32> {
+    let (mut indexes, mut siblings): (_, Box<dyn Iterator<Item = MessageDescriptor>>) =
+        match descriptor.parent_message() {
+            Some(parent) => (message_indexes(&parent), Box::new(parent.child_messages())),
+            None => (Vec::new(), Box::new(descriptor.parent_file().messages())),
+        };
+    let index = siblings
+        .position(|sibling| sibling.full_name() == descriptor.full_name())
+        .expect("message is defined by its parent");
+    indexes.push(index as i32);
+    indexes
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use vector_core::config::LogNamespace;
+
+    use super::*;
+    use crate::decoding::format::Deserializer as _;
+
+    fn test_data_dir() -> PathBuf {
+        PathBuf::from(std::env::var_os("CARGO_MANIFEST_DIR").unwrap()).join("tests/data/protobuf")
+    }
+
+    #[test]
+    fn header_roundtrip() {
+        for indexes in [None, Some(vec![0]), Some(vec![1, 0, 2]), Some(vec![-1])] {
+            let mut message = BytesMut::from(&write_header(42, indexes.as_deref())[..]);
+            message.put_slice(b"payload");
+
+            let (schema_id, rest) = read_schema_id(message.freeze()).unwrap();
+            assert_eq!(schema_id, 42);
+            let rest = match indexes {
+                None => rest,
+                Some(expected) => {
+                    let (indexes, rest) = read_message_indexes(rest).unwrap();
+                    assert_eq!(indexes, expected);
+                    rest
+                }
+            };
+            assert_eq!(&rest[..], b"payload");
+        }
+    }
+
+    #[test]
+    fn first_message_type_is_written_as_zero() {
+        assert_eq!(&write_header(1, Some(&[0]))[..], &[0, 0, 0, 0, 1, 0]);
+        assert_eq!(&write_header(1, Some(&[1]))[..], &[0, 0, 0, 0, 1, 2, 2]);
+    }
+
+    #[test]
+    fn rejects_messages_without_prefix() {
+        assert!(read_schema_id(Bytes::from_static(b"\x01\0\0\0\x01")).is_err());
+        assert!(read_schema_id(Bytes::from_static(b"\0\0")).is_err());
+    }
+
+    #[test]
+    fn avro_schema() {
+        let schema = r#"{ "type": "record", "name": "Log", "namespace": "com.example",
+            "fields": [{ "name": "message", "type": "string" }] }"#;
+        assert_eq!(avro_record_name(schema).unwrap(), "com.example.Log");
+
+        let events = avro_deserializer(schema)
+            .unwrap()
+            .parse(Bytes::from_static(b"\x06abc"), LogNamespace::Vector)
+            .unwrap();
+        assert_eq!(events[0].as_log()["message"], "abc".into());
+    }
+
+    #[test]
+    fn protobuf_schema_roundtrip() {
+        let schema = ProtobufMessageSchema::new(&ProtobufSerializerOptions {
+            desc_file: test_data_dir().join("protos/test_protobuf.desc"),
+            message_type: "test_protobuf.Person".to_string(),
+        })
+        .unwrap();
+        assert_eq!(schema.full_name, "test_protobuf.Person");
+        assert_eq!(schema.message_indexes, vec![0]);
+
+        let decoded = ProtobufSchema::decode(&[schema.file]).unwrap();
+        assert!(decoded.deserializer(&schema.message_indexes).is_ok());
+        assert!(decoded.deserializer(&[1000]).is_err());
+    }
+
+    #[test]
+    fn protobuf_message_indexes() {
+        for (message_type, expected) in [
+            ("test_protobuf.AddressBook", vec![1]),
+            ("test_protobuf.Person.PhoneNumber", vec![0, 0]),
+        ] {
+            let schema = ProtobufMessageSchema::new(&ProtobufSerializerOptions {
+                desc_file: test_data_dir().join("protos/test_protobuf.desc"),
+                message_type: message_type.to_string(),
+            })
+            .unwrap();
+            assert_eq!(schema.message_indexes, expected);
+        }
+    }
+}
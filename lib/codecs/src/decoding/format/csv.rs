@@ -0,0 +1,527 @@
+use std::sync::Mutex;
+
+use bytes::Bytes;
+use chrono::Utc;
+use csv_core::{ReadRecordResult, ReaderBuilder};
+use lookup::owned_value_path;
+use smallvec::{smallvec, SmallVec};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent},
+    schema,
+};
+use vrl::compiler::{conversion::Conversion, TimeZone};
+use vrl::value::{kind::Collection, Kind, ObjectMap, Value};
+
+use super::Deserializer;
+
+/// Config used to build a `CsvDeserializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct CsvDeserializerConfig {
+    /// CSV-specific decoding options.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub csv: CsvDeserializerOptions,
+}
+
+impl CsvDeserializerConfig {
+    /// Creates a new `CsvDeserializerConfig`.
+    pub fn new(options: CsvDeserializerOptions) -> Self {
+        Self { csv: options }
+    }
+
+    /// Build the `CsvDeserializer` from this configuration.
+    pub fn build(&self) -> vector_common::Result<CsvDeserializer> {
+        if self.csv.columns.is_empty() && !self.csv.has_header {
+            return Err("Either `columns` must be set or `has_header` must be enabled".into());
+        }
+        Ok(CsvDeserializer::new(self.csv.clone()))
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        // Columns named by the header row are only known at runtime, and are kept as strings
+        // unless they are also configured.
+        let unknown = self.csv.has_header.then(Kind::bytes);
+
+        let mut definition = match log_namespace {
+            LogNamespace::Legacy => {
+                let definition = schema::Definition::empty_legacy_namespace();
+                match unknown {
+                    Some(kind) => definition.unknown_fields(kind),
+                    None => definition,
+                }
+            }
+            LogNamespace::Vector => {
+                let collection = match unknown {
+                    Some(kind) => Collection::from_unknown(kind),
+                    None => Collection::empty(),
+                };
+                schema::Definition::new_with_default_metadata(
+                    Kind::object(collection),
+                    [log_namespace],
+                )
+            }
+        };
+
+        // Records may have fewer fields than there are columns, and empty fields are skipped.
+        for column in &self.csv.columns {
+            definition = definition.optional_field(
+                &owned_value_path!(column.name.as_str()),
+                column.r#type.kind(),
+                None,
+            );
+        }
+
+        if log_namespace == LogNamespace::Legacy {
+            if let Some(timestamp_key) = log_schema().timestamp_key() {
+                definition = definition.try_with_field(
+                    timestamp_key,
+                    Kind::bytes().or_timestamp(),
+                    Some("timestamp"),
+                );
+            }
+        }
+        definition
+    }
+}
+
+/// CSV-specific decoding options.
+#[configurable_component]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvDeserializerOptions {
+    /// The columns of each record, in order.
+    ///
+    /// When `has_header` is enabled, the names of the columns are read from the header row
+    /// instead, and the configured columns only set the type of the columns with the same name.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub columns: Vec<CsvColumn>,
+
+    /// Determines whether or not the first record is a header row that names the columns.
+    ///
+    /// The header row is read at the start of each stream of frames, such as each connection or
+    /// request.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub has_header: bool,
+
+    /// The field delimiter to use when reading CSV.
+    #[configurable(metadata(docs::type_override = "ascii_char"))]
+    #[serde(
+        default = "default_delimiter",
+        with = "vector_core::serde::ascii_char",
+        skip_serializing_if = "vector_core::serde::is_default"
+    )]
+    pub delimiter: u8,
+
+    /// The quote character to use when reading CSV.
+    #[configurable(metadata(docs::type_override = "ascii_char"))]
+    #[serde(
+        default = "default_quote",
+        with = "vector_core::serde::ascii_char",
+        skip_serializing_if = "vector_core::serde::is_default"
+    )]
+    pub quote: u8,
+
+    /// Enable double quote escapes.
+    ///
+    /// This is enabled by default, but it may be disabled. When disabled, quotes in
+    /// field data are expected to be escaped with the `escape` character instead of doubled.
+    #[serde(
+        default = "default_double_quote",
+        skip_serializing_if = "vector_core::serde::is_default"
+    )]
+    pub double_quote: bool,
+
+    /// The escape character to use when reading CSV.
+    ///
+    /// To use this, `double_quote` needs to be disabled as well, otherwise it is ignored.
+    #[configurable(metadata(docs::type_override = "ascii_char"))]
+    #[serde(
+        default = "default_escape",
+        with = "vector_core::serde::ascii_char",
+        skip_serializing_if = "vector_core::serde::is_default"
+    )]
+    pub escape: u8,
+
+    /// The name of the timezone to apply to timestamp conversions that do not contain an explicit
+    /// time zone. The time zone name may be any name in the [TZ database][tz_database], or `local`
+    /// to indicate system local time.
+    ///
+    /// If not set, `local` will be used.
+    ///
+    /// [tz_database]: https://en.wikipedia.org/wiki/List_of_tz_database_time_zones
+    #[serde(default)]
+    #[configurable(metadata(docs::advanced))]
+    pub timezone: Option<TimeZone>,
+}
+
+const fn default_delimiter() -> u8 {
+    b','
+}
+
+const fn default_quote() -> u8 {
+    b'"'
+}
+
+const fn default_double_quote() -> bool {
+    true
+}
+
+const fn default_escape() -> u8 {
+    b'\\'
+}
+
+impl Default for CsvDeserializerOptions {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            has_header: false,
+            delimiter: default_delimiter(),
+            quote: default_quote(),
+            double_quote: default_double_quote(),
+            escape: default_escape(),
+            timezone: None,
+        }
+    }
+}
+
+/// A column of a CSV record.
+#[configurable_component]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsvColumn {
+    /// The name of the field that the column is decoded into.
+    #[configurable(metadata(docs::examples = "status"))]
+    pub name: String,
+
+    /// The type that the values of the column are converted to.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub r#type: CsvColumnType,
+}
+
+/// The type of a CSV column.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CsvColumnType {
+    /// The value is kept as a string.
+    #[default]
+    String,
+
+    /// The value is converted to an integer.
+    Integer,
+
+    /// The value is converted to a float.
+    Float,
+
+    /// The value is converted to a boolean.
+    ///
+    /// The values `true`, `t`, `yes`, `y`, and non-zero numbers are converted to `true`.
+    Boolean,
+
+    /// The value is converted to a timestamp.
+    Timestamp,
+}
+
+impl CsvColumnType {
+    fn kind(self) -> Kind {
+        match self {
+            CsvColumnType::String => Kind::bytes(),
+            CsvColumnType::Integer => Kind::integer(),
+            CsvColumnType::Float => Kind::float(),
+            CsvColumnType::Boolean => Kind::boolean(),
+            CsvColumnType::Timestamp => Kind::timestamp(),
+        }
+    }
+
+    fn conversion(self, timezone: TimeZone) -> Conversion {
+        match self {
+            CsvColumnType::String => Conversion::Bytes,
+            CsvColumnType::Integer => Conversion::Integer,
+            CsvColumnType::Float => Conversion::Float,
+            CsvColumnType::Boolean => Conversion::Boolean,
+            CsvColumnType::Timestamp => Conversion::Timestamp(timezone),
+        }
+    }
+}
+
+/// Deserializer that builds an `Event` from a byte frame containing a CSV record.
+#[derive(Debug)]
+pub struct CsvDeserializer {
+    options: CsvDeserializerOptions,
+    /// The column names read from the header row, once it was received.
+    header: Mutex<Option<Vec<String>>>,
+}
+
+impl CsvDeserializer {
+    /// Creates a new `CsvDeserializer`.
+    pub fn new(options: CsvDeserializerOptions) -> Self {
+        Self {
+            options,
+            header: Mutex::new(None),
+        }
+    }
+
+    /// Splits a byte frame into the fields of a single record.
+    fn read_record(&self, bytes: &[u8]) -> vector_common::Result<Vec<String>> {
+        let mut reader = ReaderBuilder::new()
+            .delimiter(self.options.delimiter)
+            .quote(self.options.quote)
+            .double_quote(self.options.double_quote)
+            .escape((!self.options.double_quote).then_some(self.options.escape))
+            .build();
+
+        // Unquoting never makes a field longer, and each byte ends at most one field.
+        let mut output = vec![0; bytes.len()];
+        let mut ends = vec![0; bytes.len() + 1];
+        let (mut input, mut output_len, mut ends_len) = (bytes, 0, 0);
+        loop {
+            let (result, read, written, ended) =
+                reader.read_record(input, &mut output[output_len..], &mut ends[ends_len..]);
+            input = &input[read..];
+            output_len += written;
+            ends_len += ended;
+            match result {
+                // An empty input marks the end of the record on the next call.
+                ReadRecordResult::InputEmpty => continue,
+                ReadRecordResult::Record | ReadRecordResult::End => break,
+                ReadRecordResult::OutputFull | ReadRecordResult::OutputEndsFull => {
+                    return Err("CSV record is larger than its input".into())
+                }
+            }
+        }
+
+        let mut start = 0;
+        ends[..ends_len]
+            .iter()
+            .map(|&end| {
+                let field = std::str::from_utf8(&output[start..end])
+                    .map(ToOwned::to_owned)
+                    .map_err(Into::into);
+                start = end;
+                field
+            })
+            .collect()
+    }
+
+    fn column_type(&self, name: &str) -> CsvColumnType {
+        self.options
+            .columns
+            .iter()
+            .find(|column| column.name == name)
+            .map_or(CsvColumnType::String, |column| column.r#type)
+    }
+
+    fn value(
+        &self,
+        name: &str,
+        r#type: CsvColumnType,
+        field: String,
+    ) -> vector_common::Result<Value> {
+        let timezone = self.options.timezone.unwrap_or(TimeZone::Local);
+        r#type
+            .conversion(timezone)
+            .convert(Bytes::from(field))
+            .map_err(|error| format!("Invalid value for column `{name}`: {error}").into())
+    }
+}
+
+impl Clone for CsvDeserializer {
+    /// Clones the options, but not the header row, which belongs to the stream of the original.
+    fn clone(&self) -> Self {
+        Self::new(self.options.clone())
+    }
+}
+
+impl Deserializer for CsvDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        if bytes.iter().all(u8::is_ascii_whitespace) {
+            return Ok(smallvec![]);
+        }
+
+        let fields = self.read_record(&bytes)?;
+
+        let mut map = ObjectMap::new();
+        if self.options.has_header {
+            let mut header = self.header.lock().expect("CSV header lock poisoned");
+            let Some(names) = header.as_ref() else {
+                *header = Some(fields);
+                return Ok(smallvec![]);
+            };
+            if fields.len() > names.len() {
+                return Err(format!(
+                    "CSV record has {} fields but the header has {} columns",
+                    fields.len(),
+                    names.len()
+                )
+                .into());
+            }
+            for (name, field) in names.iter().zip(fields) {
+                if !field.is_empty() {
+                    let value = self.value(name, self.column_type(name), field)?;
+                    map.insert(name.as_str().into(), value);
+                }
+            }
+        } else {
+            let columns = &self.options.columns;
+            if fields.len() > columns.len() {
+                return Err(format!(
+                    "CSV record has {} fields but {} columns are configured",
+                    fields.len(),
+                    columns.len()
+                )
+                .into());
+            }
+            for (column, field) in columns.iter().zip(fields) {
+                if !field.is_empty() {
+                    let value = self.value(&column.name, column.r#type, field)?;
+                    map.insert(column.name.as_str().into(), value);
+                }
+            }
+        }
+
+        let mut log = LogEvent::from(map);
+        if log_namespace == LogNamespace::Legacy {
+            if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
+                if !log.contains(timestamp_key) {
+                    log.insert(timestamp_key, Utc::now());
+                }
+            }
+        }
+
+        Ok(smallvec![log.into()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone as _;
+    use vrl::btreemap;
+
+    use super::*;
+
+    fn column(name: &str, r#type: CsvColumnType) -> CsvColumn {
+        CsvColumn {
+            name: name.to_string(),
+            r#type,
+        }
+    }
+
+    fn parse(deserializer: &CsvDeserializer, input: &'static str) -> SmallVec<[Event; 1]> {
+        deserializer
+            .parse(Bytes::from(input), LogNamespace::Vector)
+            .unwrap()
+    }
+
+    #[test]
+    fn deserialize_with_columns() {
+        let deserializer = CsvDeserializerConfig::new(CsvDeserializerOptions {
+            columns: vec![
+                column("host", CsvColumnType::String),
+                column("status", CsvColumnType::Integer),
+                column("took", CsvColumnType::Float),
+                column("cached", CsvColumnType::Boolean),
+                column("at", CsvColumnType::Timestamp),
+                column("note", CsvColumnType::String),
+            ],
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+
+        let events = parse(
+            &deserializer,
+            r#"web-1,200,1.5,true,2024-03-05T07:08:09Z,"a ""quoted"", note""#,
+        );
+
+        assert_eq!(
+            *events[0].as_log().value(),
+            Value::from(btreemap! {
+                "host" => "web-1",
+                "status" => 200,
+                "took" => 1.5,
+                "cached" => true,
+                "at" => chrono::Utc.with_ymd_and_hms(2024, 3, 5, 7, 8, 9).unwrap(),
+                "note" => "a \"quoted\", note",
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_with_header() {
+        let deserializer = CsvDeserializerConfig::new(CsvDeserializerOptions {
+            columns: vec![column("status", CsvColumnType::Integer)],
+            has_header: true,
+            delimiter: b';',
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+
+        assert!(parse(&deserializer, "path;status").is_empty());
+        let events = parse(&deserializer, "/index.html;404");
+        assert_eq!(
+            *events[0].as_log().value(),
+            Value::from(btreemap! {
+                "path" => "/index.html",
+                "status" => 404,
+            })
+        );
+
+        // Clones start a new stream, which begins with its own header row.
+        let clone = deserializer.clone();
+        assert!(parse(&clone, "a;b").is_empty());
+        let events = parse(&clone, "1;2");
+        assert_eq!(events[0].as_log()["a"], "1".into());
+    }
+
+    #[test]
+    fn deserialize_skips_empty_fields() {
+        let deserializer = CsvDeserializerConfig::new(CsvDeserializerOptions {
+            columns: vec![
+                column("a", CsvColumnType::Integer),
+                column("b", CsvColumnType::String),
+                column("c", CsvColumnType::String),
+            ],
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+
+        let events = parse(&deserializer, ",x");
+        assert_eq!(
+            *events[0].as_log().value(),
+            Value::from(btreemap! { "b" => "x" })
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_records() {
+        let deserializer = CsvDeserializerConfig::new(CsvDeserializerOptions {
+            columns: vec![column("a", CsvColumnType::Integer)],
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+
+        for input in ["1,2", "nope"] {
+            assert!(deserializer
+                .parse(Bytes::from(input), LogNamespace::Vector)
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn requires_columns_or_header() {
+        assert!(CsvDeserializerConfig::default().build().is_err());
+    }
+}
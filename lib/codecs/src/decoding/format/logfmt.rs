@@ -0,0 +1,303 @@
+use bytes::Bytes;
+use chrono::Utc;
+use derivative::Derivative;
+use smallvec::{smallvec, SmallVec};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{log_schema, DataType, LogNamespace},
+    event::{Event, LogEvent},
+    schema,
+};
+use vrl::value::{kind::Collection, Kind, ObjectMap, Value};
+
+use super::{default_lossy, Deserializer};
+
+/// Config used to build a `LogfmtDeserializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct LogfmtDeserializerConfig {
+    /// Logfmt-specific decoding options.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub logfmt: LogfmtDeserializerOptions,
+}
+
+impl LogfmtDeserializerConfig {
+    /// Creates a new `LogfmtDeserializerConfig`.
+    pub fn new(options: LogfmtDeserializerOptions) -> Self {
+        Self { logfmt: options }
+    }
+
+    /// Build the `LogfmtDeserializer` from this configuration.
+    pub fn build(&self) -> LogfmtDeserializer {
+        LogfmtDeserializer::new(self.logfmt.clone())
+    }
+
+    /// Return the type of event build by this deserializer.
+    pub fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema produced by the deserializer.
+    pub fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        let kind = if self.logfmt.coerce_types {
+            Kind::bytes().or_integer().or_float().or_boolean()
+        } else {
+            Kind::bytes()
+        };
+
+        match log_namespace {
+            LogNamespace::Legacy => {
+                let mut definition =
+                    schema::Definition::empty_legacy_namespace().unknown_fields(kind.clone());
+
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition = definition.try_with_field(
+                        timestamp_key,
+                        kind.or_timestamp(),
+                        Some("timestamp"),
+                    );
+                }
+                definition
+            }
+            LogNamespace::Vector => schema::Definition::new_with_default_metadata(
+                Kind::object(Collection::from_unknown(kind)),
+                [log_namespace],
+            ),
+        }
+    }
+}
+
+/// Logfmt-specific decoding options.
+#[configurable_component]
+#[derive(Debug, Clone, PartialEq, Eq, Derivative)]
+#[derivative(Default)]
+pub struct LogfmtDeserializerOptions {
+    /// Determines whether or not to convert values to integers, floats and booleans.
+    ///
+    /// When true, values that parse as an integer, a float, or `true`/`false` are converted to
+    /// that type, and keys without a value are set to `true`. When false, every value is kept as
+    /// a string, and keys without a value are set to an empty string.
+    #[serde(
+        default = "default_coerce_types",
+        skip_serializing_if = "vector_core::serde::is_default"
+    )]
+    #[derivative(Default(value = "default_coerce_types()"))]
+    pub coerce_types: bool,
+
+    /// Determines whether or not to replace invalid UTF-8 sequences instead of failing.
+    ///
+    /// When true, invalid UTF-8 sequences are replaced with the [`U+FFFD REPLACEMENT CHARACTER`][U+FFFD].
+    ///
+    /// [U+FFFD]: https://en.wikipedia.org/wiki/Specials_(Unicode_block)#Replacement_character
+    #[serde(
+        default = "default_lossy",
+        skip_serializing_if = "vector_core::serde::is_default"
+    )]
+    #[derivative(Default(value = "default_lossy()"))]
+    pub lossy: bool,
+}
+
+const fn default_coerce_types() -> bool {
+    true
+}
+
+/// Deserializer that builds an `Event` from a byte frame containing a logfmt message.
+#[derive(Debug, Clone, Default)]
+pub struct LogfmtDeserializer {
+    options: LogfmtDeserializerOptions,
+}
+
+impl LogfmtDeserializer {
+    /// Creates a new `LogfmtDeserializer`.
+    pub fn new(options: LogfmtDeserializerOptions) -> Self {
+        Self { options }
+    }
+
+    fn value(&self, value: Option<String>) -> Value {
+        match (value, self.options.coerce_types) {
+            (None, true) => Value::Boolean(true),
+            (None, false) => Value::from(""),
+            (Some(value), true) => coerce(value),
+            (Some(value), false) => Value::from(value),
+        }
+    }
+}
+
+/// Converts a value to the first type that it parses as.
+fn coerce(value: String) -> Value {
+    if let Ok(integer) = value.parse::<i64>() {
+        Value::from(integer)
+    } else if let Some(float) = value.parse::<f64>().ok().filter(|float| float.is_finite()) {
+        Value::from(float)
+    } else if let Ok(boolean) = value.parse::<bool>() {
+        Value::from(boolean)
+    } else {
+        Value::from(value)
+    }
+}
+
+/// Splits a logfmt line into its keys and their optional values.
+fn parse_pairs(line: &str) -> Result<Vec<(String, Option<String>)>, String> {
+    let mut pairs = Vec::new();
+    let mut chars = line.chars().peekable();
+
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            return Ok(pairs);
+        }
+
+        let mut key = String::new();
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '=') {
+            key.push(c);
+        }
+        if key.is_empty() {
+            return Err("Missing key before `=`".to_string());
+        }
+
+        if chars.next_if_eq(&'=').is_none() {
+            pairs.push((key, None));
+            continue;
+        }
+
+        let mut value = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some('n') => value.push('\n'),
+                        Some('r') => value.push('\r'),
+                        Some('t') => value.push('\t'),
+                        Some(c) => value.push(c),
+                        None => return Err(format!("Unterminated value for key `{}`", key)),
+                    },
+                    Some(c) => value.push(c),
+                    None => return Err(format!("Unterminated value for key `{}`", key)),
+                }
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace()) {
+                value.push(c);
+            }
+        }
+        pairs.push((key, Some(value)));
+    }
+}
+
+impl Deserializer for LogfmtDeserializer {
+    fn parse(
+        &self,
+        bytes: Bytes,
+        log_namespace: LogNamespace,
+    ) -> vector_common::Result<SmallVec<[Event; 1]>> {
+        let line = match self.options.lossy {
+            true => String::from_utf8_lossy(&bytes),
+            false => std::str::from_utf8(&bytes)?.into(),
+        };
+        if line.trim().is_empty() {
+            return Ok(smallvec![]);
+        }
+
+        let pairs = parse_pairs(&line).map_err(|error| format!("Error parsing logfmt: {error}"))?;
+        let map = pairs
+            .into_iter()
+            .map(|(key, value)| (key.into(), self.value(value)))
+            .collect::<ObjectMap>();
+        let mut log = LogEvent::from(map);
+
+        if log_namespace == LogNamespace::Legacy {
+            if let Some(timestamp_key) = log_schema().timestamp_key_target_path() {
+                if !log.contains(timestamp_key) {
+                    log.insert(timestamp_key, Utc::now());
+                }
+            }
+        }
+
+        Ok(smallvec![log.into()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vrl::btreemap;
+
+    use super::*;
+
+    fn parse(deserializer: &LogfmtDeserializer, input: &str) -> Event {
+        let mut events = deserializer
+            .parse(
+                Bytes::copy_from_slice(input.as_bytes()),
+                LogNamespace::Vector,
+            )
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        events.remove(0)
+    }
+
+    #[test]
+    fn deserialize_logfmt() {
+        let event = parse(
+            &LogfmtDeserializer::default(),
+            r#"level=info msg="request done \"ok\"" status=200 took=1.5 cached=false debug"#,
+        );
+
+        assert_eq!(
+            *event.as_log().value(),
+            Value::from(btreemap! {
+                "level" => "info",
+                "msg" => "request done \"ok\"",
+                "status" => 200,
+                "took" => 1.5,
+                "cached" => false,
+                "debug" => true,
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_logfmt_without_coercion() {
+        let deserializer = LogfmtDeserializer::new(LogfmtDeserializerOptions {
+            coerce_types: false,
+            ..Default::default()
+        });
+        let event = parse(&deserializer, "status=200 debug");
+
+        assert_eq!(
+            *event.as_log().value(),
+            Value::from(btreemap! {
+                "status" => "200",
+                "debug" => "",
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_legacy_namespace_adds_timestamp() {
+        let events = LogfmtDeserializer::default()
+            .parse(Bytes::from("a=1"), LogNamespace::Legacy)
+            .unwrap();
+        let log = events[0].as_log();
+
+        assert_eq!(log["a"], 1.into());
+        assert!(log.get_timestamp().is_some());
+    }
+
+    #[test]
+    fn deserialize_empty_line() {
+        let events = LogfmtDeserializer::default()
+            .parse(Bytes::from("  "), LogNamespace::Vector)
+            .unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn deserialize_invalid_logfmt() {
+        let deserializer = LogfmtDeserializer::default();
+        for input in [r#"a="unterminated"#, "=value"] {
+            assert!(deserializer
+                .parse(Bytes::from(input), LogNamespace::Vector)
+                .is_err());
+        }
+    }
+}
@@ -5,9 +5,11 @@
 
 mod avro;
 mod bytes;
+mod csv;
 mod gelf;
 mod influxdb;
 mod json;
+mod logfmt;
 mod native;
 mod native_json;
 mod protobuf;
@@ -17,10 +19,14 @@ mod vrl;
 
 use ::bytes::Bytes;
 pub use avro::{AvroDeserializer, AvroDeserializerConfig, AvroDeserializerOptions};
+pub use csv::{
+    CsvColumn, CsvColumnType, CsvDeserializer, CsvDeserializerConfig, CsvDeserializerOptions,
+};
 use dyn_clone::DynClone;
 pub use gelf::{GelfDeserializer, GelfDeserializerConfig, GelfDeserializerOptions};
 pub use influxdb::{InfluxdbDeserializer, InfluxdbDeserializerConfig};
 pub use json::{JsonDeserializer, JsonDeserializerConfig, JsonDeserializerOptions};
+pub use logfmt::{LogfmtDeserializer, LogfmtDeserializerConfig, LogfmtDeserializerOptions};
 pub use native::{NativeDeserializer, NativeDeserializerConfig};
 pub use native_json::{
     NativeJsonDeserializer, NativeJsonDeserializerConfig, NativeJsonDeserializerOptions,
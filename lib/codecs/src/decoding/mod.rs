@@ -9,12 +9,14 @@ use crate::decoding::format::{VrlDeserializer, VrlDeserializerConfig};
 use bytes::{Bytes, BytesMut};
 pub use error::StreamDecodingError;
 pub use format::{
-    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, GelfDeserializer,
+    BoxedDeserializer, BytesDeserializer, BytesDeserializerConfig, CsvColumn, CsvColumnType,
+    CsvDeserializer, CsvDeserializerConfig, CsvDeserializerOptions, GelfDeserializer,
     GelfDeserializerConfig, GelfDeserializerOptions, InfluxdbDeserializer,
     InfluxdbDeserializerConfig, JsonDeserializer, JsonDeserializerConfig, JsonDeserializerOptions,
-    NativeDeserializer, NativeDeserializerConfig, NativeJsonDeserializer,
-    NativeJsonDeserializerConfig, NativeJsonDeserializerOptions, ProtobufDeserializer,
-    ProtobufDeserializerConfig, ProtobufDeserializerOptions,
+    LogfmtDeserializer, LogfmtDeserializerConfig, LogfmtDeserializerOptions, NativeDeserializer,
+    NativeDeserializerConfig, NativeJsonDeserializer, NativeJsonDeserializerConfig,
+    NativeJsonDeserializerOptions, ProtobufDeserializer, ProtobufDeserializerConfig,
+    ProtobufDeserializerOptions,
 };
 #[cfg(feature = "syslog")]
 pub use format::{SyslogDeserializer, SyslogDeserializerConfig, SyslogDeserializerOptions};
@@ -203,6 +205,18 @@ pub enum DeserializerConfig {
     /// [json]: https://www.json.org/
     Json(JsonDeserializerConfig),
 
+    /// Decodes the raw bytes as a [logfmt][logfmt] message.
+    ///
+    /// [logfmt]: https://brandur.org/logfmt
+    Logfmt(LogfmtDeserializerConfig),
+
+    /// Decodes the raw bytes as a [CSV][csv] record.
+    ///
+    /// The fields are named after the configured columns, or after the header row.
+    ///
+    /// [csv]: https://datatracker.ietf.org/doc/html/rfc4180
+    Csv(CsvDeserializerConfig),
+
     /// Decodes the raw bytes as [protobuf][protobuf].
     ///
     /// [protobuf]: https://protobuf.dev/
@@ -284,6 +298,18 @@ impl From<JsonDeserializerConfig> for DeserializerConfig {
     }
 }
 
+impl From<LogfmtDeserializerConfig> for DeserializerConfig {
+    fn from(config: LogfmtDeserializerConfig) -> Self {
+        Self::Logfmt(config)
+    }
+}
+
+impl From<CsvDeserializerConfig> for DeserializerConfig {
+    fn from(config: CsvDeserializerConfig) -> Self {
+        Self::Csv(config)
+    }
+}
+
 #[cfg(feature = "syslog")]
 impl From<SyslogDeserializerConfig> for DeserializerConfig {
     fn from(config: SyslogDeserializerConfig) -> Self {
@@ -327,6 +353,8 @@ impl DeserializerConfig {
             )),
             DeserializerConfig::Bytes => Ok(Deserializer::Bytes(BytesDeserializerConfig.build())),
             DeserializerConfig::Json(config) => Ok(Deserializer::Json(config.build())),
+            DeserializerConfig::Logfmt(config) => Ok(Deserializer::Logfmt(config.build())),
+            DeserializerConfig::Csv(config) => Ok(Deserializer::Csv(config.build()?)),
             DeserializerConfig::Protobuf(config) => Ok(Deserializer::Protobuf(config.build()?)),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(config) => Ok(Deserializer::Syslog(config.build())),
@@ -347,6 +375,8 @@ impl DeserializerConfig {
             DeserializerConfig::Native => FramingConfig::LengthDelimited(Default::default()),
             DeserializerConfig::Bytes
             | DeserializerConfig::Json(_)
+            | DeserializerConfig::Logfmt(_)
+            | DeserializerConfig::Csv(_)
             | DeserializerConfig::Influxdb(_)
             | DeserializerConfig::NativeJson(_) => {
                 FramingConfig::NewlineDelimited(Default::default())
@@ -370,6 +400,8 @@ impl DeserializerConfig {
             .output_type(),
             DeserializerConfig::Bytes => BytesDeserializerConfig.output_type(),
             DeserializerConfig::Json(config) => config.output_type(),
+            DeserializerConfig::Logfmt(config) => config.output_type(),
+            DeserializerConfig::Csv(config) => config.output_type(),
             DeserializerConfig::Protobuf(config) => config.output_type(),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(config) => config.output_type(),
@@ -390,6 +422,8 @@ impl DeserializerConfig {
             .schema_definition(log_namespace),
             DeserializerConfig::Bytes => BytesDeserializerConfig.schema_definition(log_namespace),
             DeserializerConfig::Json(config) => config.schema_definition(log_namespace),
+            DeserializerConfig::Logfmt(config) => config.schema_definition(log_namespace),
+            DeserializerConfig::Csv(config) => config.schema_definition(log_namespace),
             DeserializerConfig::Protobuf(config) => config.schema_definition(log_namespace),
             #[cfg(feature = "syslog")]
             DeserializerConfig::Syslog(config) => config.schema_definition(log_namespace),
@@ -424,10 +458,12 @@ impl DeserializerConfig {
                 "application/octet-stream"
             }
             (DeserializerConfig::Protobuf(_), _) => "application/octet-stream",
+            (DeserializerConfig::Csv(_), _) => "text/csv",
             (
                 DeserializerConfig::Json(_)
                 | DeserializerConfig::NativeJson(_)
                 | DeserializerConfig::Bytes
+                | DeserializerConfig::Logfmt(_)
                 | DeserializerConfig::Gelf(_)
                 | DeserializerConfig::Influxdb(_)
                 | DeserializerConfig::Vrl(_),
@@ -448,6 +484,10 @@ pub enum Deserializer {
     Bytes(BytesDeserializer),
     /// Uses a `JsonDeserializer` for deserialization.
     Json(JsonDeserializer),
+    /// Uses a `LogfmtDeserializer` for deserialization.
+    Logfmt(LogfmtDeserializer),
+    /// Uses a `CsvDeserializer` for deserialization.
+    Csv(CsvDeserializer),
     /// Uses a `ProtobufDeserializer` for deserialization.
     Protobuf(ProtobufDeserializer),
     #[cfg(feature = "syslog")]
@@ -477,6 +517,8 @@ impl format::Deserializer for Deserializer {
             Deserializer::Avro(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Bytes(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Json(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Logfmt(deserializer) => deserializer.parse(bytes, log_namespace),
+            Deserializer::Csv(deserializer) => deserializer.parse(bytes, log_namespace),
             Deserializer::Protobuf(deserializer) => deserializer.parse(bytes, log_namespace),
             #[cfg(feature = "syslog")]
             Deserializer::Syslog(deserializer) => deserializer.parse(bytes, log_namespace),
@@ -11,13 +11,21 @@ mod json;
 mod logfmt;
 mod native;
 mod native_json;
+#[cfg(feature = "parquet")]
+mod parquet;
 mod protobuf;
 mod raw_message;
+mod syslog;
 mod text;
 
 use std::fmt::Debug;
 
 pub use self::csv::{CsvSerializer, CsvSerializerConfig};
+#[cfg(feature = "parquet")]
+pub use self::parquet::{
+    ParquetColumn, ParquetColumnType, ParquetCompression, ParquetSerializer,
+    ParquetSerializerConfig, ParquetSerializerOptions, ParquetWriter, RejectedEvent,
+};
 pub use avro::{AvroSerializer, AvroSerializerConfig, AvroSerializerOptions};
 use dyn_clone::DynClone;
 pub use gelf::{GelfSerializer, GelfSerializerConfig};
@@ -27,6 +35,10 @@ pub use native::{NativeSerializer, NativeSerializerConfig};
 pub use native_json::{NativeJsonSerializer, NativeJsonSerializerConfig};
 pub use protobuf::{ProtobufSerializer, ProtobufSerializerConfig, ProtobufSerializerOptions};
 pub use raw_message::{RawMessageSerializer, RawMessageSerializerConfig};
+pub use syslog::{
+    SyslogFacility, SyslogRfc, SyslogSerializer, SyslogSerializerConfig, SyslogSerializerOptions,
+    SyslogSeverity,
+};
 pub use text::{TextSerializer, TextSerializerConfig};
 use vector_core::event::Event;
 
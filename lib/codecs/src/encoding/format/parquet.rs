@@ -0,0 +1,805 @@
+use std::{num::NonZeroUsize, sync::Arc};
+
+use arrow_array::{
+    builder::{
+        BooleanBuilder, Float64Builder, Int64Builder, StringBuilder, TimestampMicrosecondBuilder,
+    },
+    ArrayRef, RecordBatch,
+};
+use arrow_schema::{DataType as ArrowDataType, Field, Schema, SchemaRef, TimeUnit};
+use bytes::{BufMut, BytesMut};
+use lookup::lookup_v2::ConfigTargetPath;
+use parquet::{
+    arrow::ArrowWriter,
+    basic::{Compression, GzipLevel, ZstdLevel},
+    file::properties::WriterProperties,
+};
+use tokio_util::codec::Encoder;
+use vector_core::{
+    config::DataType,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+use vrl::value::Kind;
+
+use crate::encoding::BuildError;
+
+/// Config used to build a `ParquetSerializer`.
+#[crate::configurable_component]
+#[derive(Debug, Clone)]
+pub struct ParquetSerializerConfig {
+    /// Apache Parquet-specific encoder options.
+    pub parquet: ParquetSerializerOptions,
+}
+
+impl ParquetSerializerConfig {
+    /// Creates a new `ParquetSerializerConfig`.
+    pub const fn new(parquet: ParquetSerializerOptions) -> Self {
+        Self { parquet }
+    }
+
+    /// Build the `ParquetSerializer` from this configuration.
+    pub fn build(&self) -> Result<ParquetSerializer, BuildError> {
+        ParquetSerializer::new(self.parquet.clone())
+    }
+
+    /// The data type of events that are accepted by `ParquetSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        // Events that do not match the column schema are rejected one by one at encoding time,
+        // so there is no need to reject the whole configuration upfront.
+        schema::Requirement::empty()
+    }
+}
+
+/// Apache Parquet serializer options.
+#[crate::configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct ParquetSerializerOptions {
+    /// The columns written to the Parquet file, in order.
+    ///
+    /// If not set, the columns are derived from the schema definition attached to the events by
+    /// the upstream components. This requires [schema support][schema_support] to be enabled,
+    /// and only the top-level fields of the definition are used.
+    ///
+    /// [schema_support]: https://vector.dev/docs/reference/configuration/global-options/#schema
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<Vec<ParquetColumn>>,
+
+    /// The maximum number of rows in a single row group.
+    ///
+    /// Larger row groups compress better and are faster to scan, at the cost of more memory
+    /// used while encoding. If not set, the Parquet library default of 1048576 rows is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::examples = 100000))]
+    pub row_group_size: Option<NonZeroUsize>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub compression: ParquetCompression,
+}
+
+/// A single column of a Parquet file.
+#[crate::configurable_component]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParquetColumn {
+    /// The name of the column.
+    #[configurable(metadata(docs::examples = "message"))]
+    pub name: String,
+
+    /// The event field the column is populated from.
+    ///
+    /// If not set, the top-level field with the same name as the column is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::examples = ".http.status"))]
+    pub field: Option<ConfigTargetPath>,
+
+    #[configurable(derived)]
+    #[serde(rename = "type")]
+    pub column_type: ParquetColumnType,
+
+    /// Whether the column accepts missing and `null` values.
+    ///
+    /// Events that are missing a value for a non-nullable column are rejected.
+    #[serde(default = "default_nullable")]
+    pub nullable: bool,
+}
+
+const fn default_nullable() -> bool {
+    true
+}
+
+/// The type of a Parquet column.
+#[crate::configurable_component]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetColumnType {
+    /// A UTF-8 string.
+    ///
+    /// Only string values are accepted.
+    String,
+
+    /// A signed 64-bit integer.
+    Int64,
+
+    /// A 64-bit floating point number.
+    ///
+    /// Integer values are converted to floating point numbers.
+    Float64,
+
+    /// A boolean.
+    Boolean,
+
+    /// A timestamp with microsecond precision, in UTC.
+    Timestamp,
+
+    /// Any value, encoded as a JSON string.
+    Json,
+}
+
+/// Compression codec applied to the column chunks of a Parquet file.
+#[crate::configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ParquetCompression {
+    /// No compression.
+    None,
+
+    /// [Snappy][snappy] compression.
+    ///
+    /// [snappy]: https://github.com/google/snappy
+    #[default]
+    Snappy,
+
+    /// [Gzip][gzip] compression.
+    ///
+    /// [gzip]: https://www.gzip.org/
+    Gzip,
+
+    /// [Zstandard][zstd] compression.
+    ///
+    /// [zstd]: https://facebook.github.io/zstd/
+    Zstd,
+
+    /// [LZ4][lz4] compression, in the raw block format.
+    ///
+    /// [lz4]: https://lz4.org/
+    Lz4,
+}
+
+impl From<ParquetCompression> for Compression {
+    fn from(compression: ParquetCompression) -> Self {
+        match compression {
+            ParquetCompression::None => Compression::UNCOMPRESSED,
+            ParquetCompression::Snappy => Compression::SNAPPY,
+            ParquetCompression::Gzip => Compression::GZIP(GzipLevel::default()),
+            ParquetCompression::Zstd => Compression::ZSTD(ZstdLevel::default()),
+            ParquetCompression::Lz4 => Compression::LZ4_RAW,
+        }
+    }
+}
+
+/// An event of a batch that could not be written because it does not fit the column schema.
+#[derive(Debug)]
+pub struct RejectedEvent {
+    /// The position of the event in the batch.
+    pub index: usize,
+    /// Why the event was rejected.
+    pub error: vector_common::Error,
+}
+
+/// Serializer that converts a batch of `Event`s to bytes using the Apache Parquet format.
+///
+/// Unlike row formats, a Parquet file can only be produced for a whole batch of events, so sinks
+/// are expected to call [`ParquetSerializer::encode_batch`] with all the events of an object.
+/// Encoding a single event produces a Parquet file with a single row.
+#[derive(Debug, Clone)]
+pub struct ParquetSerializer {
+    columns: Option<Arc<ColumnSchema>>,
+    properties: Arc<WriterProperties>,
+}
+
+impl ParquetSerializer {
+    /// Creates a new `ParquetSerializer`.
+    pub fn new(options: ParquetSerializerOptions) -> Result<Self, BuildError> {
+        let columns = match options.schema {
+            Some(columns) if columns.is_empty() => {
+                return Err("At least one Parquet column must be specified".into())
+            }
+            Some(columns) => Some(Arc::new(ColumnSchema::new(columns)?)),
+            None => None,
+        };
+
+        let mut properties =
+            WriterProperties::builder().set_compression(options.compression.into());
+        if let Some(row_group_size) = options.row_group_size {
+            properties = properties.set_max_row_group_size(row_group_size.get());
+        }
+
+        Ok(Self {
+            columns,
+            properties: Arc::new(properties.build()),
+        })
+    }
+
+    /// Encodes a batch of events as a complete Parquet file.
+    ///
+    /// Events that do not fit the column schema are left out of the file and returned, so the
+    /// caller can report them.
+    pub fn encode_batch(
+        &self,
+        events: &[Event],
+        buffer: &mut BytesMut,
+    ) -> Result<Vec<RejectedEvent>, vector_common::Error> {
+        let mut writer = self.writer();
+        let rejected = writer.write(events)?;
+        buffer.put_slice(&writer.finish()?);
+        Ok(rejected)
+    }
+
+    /// Creates a writer that incrementally encodes events into a single Parquet file.
+    pub fn writer(&self) -> ParquetWriter {
+        ParquetWriter {
+            columns: self.columns.clone(),
+            properties: Arc::clone(&self.properties),
+            writer: None,
+        }
+    }
+}
+
+impl Encoder<Event> for ParquetSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        match self
+            .encode_batch(std::slice::from_ref(&event), buffer)?
+            .pop()
+        {
+            Some(rejected) => Err(rejected.error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Incrementally encodes events into a single Parquet file.
+///
+/// Rows are buffered until a row group is complete, at which point the encoded row group can be
+/// retrieved with [`ParquetWriter::take_bytes`]. The file footer is written by
+/// [`ParquetWriter::finish`].
+pub struct ParquetWriter {
+    columns: Option<Arc<ColumnSchema>>,
+    properties: Arc<WriterProperties>,
+    writer: Option<(Arc<ColumnSchema>, ArrowWriter<Vec<u8>>)>,
+}
+
+impl ParquetWriter {
+    /// Writes a batch of events, returning the events that do not fit the column schema.
+    ///
+    /// If no column schema was configured, it is derived from the schema definition of the first
+    /// event written.
+    pub fn write(&mut self, events: &[Event]) -> Result<Vec<RejectedEvent>, vector_common::Error> {
+        let Some(first) = events.first() else {
+            return Ok(Vec::new());
+        };
+
+        if self.writer.is_none() {
+            let columns = match &self.columns {
+                Some(columns) => Arc::clone(columns),
+                None => Arc::new(ColumnSchema::from_definition(
+                    first.metadata().schema_definition(),
+                )?),
+            };
+            let writer = ArrowWriter::try_new(
+                Vec::new(),
+                Arc::clone(&columns.arrow),
+                Some(WriterProperties::clone(&self.properties)),
+            )?;
+            self.writer = Some((columns, writer));
+        }
+        let (columns, writer) = self.writer.as_mut().expect("writer was just created");
+
+        let (batch, rejected) = columns.record_batch(events)?;
+        if batch.num_rows() > 0 {
+            writer.write(&batch)?;
+        }
+        Ok(rejected)
+    }
+
+    /// Takes the bytes of the row groups that were completed so far.
+    pub fn take_bytes(&mut self) -> Vec<u8> {
+        self.writer
+            .as_mut()
+            .map(|(_, writer)| std::mem::take(writer.inner_mut()))
+            .unwrap_or_default()
+    }
+
+    /// The number of rows buffered in the current row group, which are not part of the bytes
+    /// returned by [`ParquetWriter::take_bytes`] yet.
+    pub fn buffered_rows(&self) -> usize {
+        self.writer
+            .as_ref()
+            .map_or(0, |(_, writer)| writer.in_progress_rows())
+    }
+
+    /// The estimated encoded size of the rows buffered in the current row group.
+    pub fn buffered_size(&self) -> usize {
+        self.writer
+            .as_ref()
+            .map_or(0, |(_, writer)| writer.in_progress_size())
+    }
+
+    /// Flushes the buffered rows and writes the file footer, returning the remaining bytes of
+    /// the file.
+    ///
+    /// If no event was ever written, the file is written with the configured columns and no
+    /// rows. Without configured columns there is no schema to write, so nothing is returned.
+    pub fn finish(self) -> Result<Vec<u8>, vector_common::Error> {
+        let writer = match (self.writer, self.columns) {
+            (Some((_, writer)), _) => writer,
+            (None, Some(columns)) => ArrowWriter::try_new(
+                Vec::new(),
+                Arc::clone(&columns.arrow),
+                Some(WriterProperties::clone(&self.properties)),
+            )?,
+            (None, None) => return Ok(Vec::new()),
+        };
+        Ok(writer.into_inner()?)
+    }
+}
+
+#[derive(Debug)]
+struct ColumnSchema {
+    columns: Vec<ParquetColumn>,
+    arrow: SchemaRef,
+}
+
+impl ColumnSchema {
+    fn new(columns: Vec<ParquetColumn>) -> Result<Self, BuildError> {
+        let mut fields = Vec::with_capacity(columns.len());
+        for column in &columns {
+            if fields
+                .iter()
+                .any(|field: &Field| field.name() == &column.name)
+            {
+                return Err(format!("Duplicate Parquet column {:?}", column.name).into());
+            }
+            fields.push(Field::new(
+                column.name.clone(),
+                column.column_type.arrow_type(),
+                column.nullable,
+            ));
+        }
+
+        Ok(Self {
+            columns,
+            arrow: Arc::new(Schema::new(fields)),
+        })
+    }
+
+    /// Derives the columns from the known top-level fields of a schema definition.
+    fn from_definition(definition: &schema::Definition) -> Result<Self, BuildError> {
+        let columns = definition
+            .event_kind()
+            .as_object()
+            .map(|object| {
+                object
+                    .known()
+                    .iter()
+                    .map(|(field, kind)| ParquetColumn {
+                        name: field.to_string(),
+                        field: None,
+                        column_type: ParquetColumnType::from_kind(kind),
+                        nullable: kind.contains_null() || kind.contains_undefined(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        if columns.is_empty() {
+            return Err(
+                "The schema definition of the events has no known fields, so the Parquet \
+                        columns must be configured explicitly"
+                    .into(),
+            );
+        }
+        Self::new(columns)
+    }
+
+    /// Converts the events into a record batch, leaving out the events that don't fit.
+    fn record_batch(
+        &self,
+        events: &[Event],
+    ) -> Result<(RecordBatch, Vec<RejectedEvent>), vector_common::Error> {
+        let mut builders = self
+            .columns
+            .iter()
+            .map(|column| ColumnBuilder::new(column.column_type, events.len()))
+            .collect::<Vec<_>>();
+        let mut rejected = Vec::new();
+        let mut row = Vec::with_capacity(self.columns.len());
+
+        for (index, event) in events.iter().enumerate() {
+            row.clear();
+            let result = match event {
+                Event::Log(log) => self.convert_row(log, &mut row),
+                _ => Err("Only log events can be encoded as Parquet".into()),
+            };
+            match result {
+                Ok(()) => {
+                    for (builder, cell) in builders.iter_mut().zip(row.drain(..)) {
+                        builder.append(cell);
+                    }
+                }
+                Err(error) => rejected.push(RejectedEvent { index, error }),
+            }
+        }
+
+        let arrays = builders
+            .into_iter()
+            .map(ColumnBuilder::finish)
+            .collect::<Vec<_>>();
+        let batch = RecordBatch::try_new(Arc::clone(&self.arrow), arrays)?;
+        Ok((batch, rejected))
+    }
+
+    /// Converts the values of a log event for all columns, failing if any of them doesn't fit.
+    fn convert_row(&self, log: &LogEvent, row: &mut Vec<Cell>) -> Result<(), vector_common::Error> {
+        for column in &self.columns {
+            let value = match &column.field {
+                Some(field) => log.get(field),
+                None => log.as_map().and_then(|map| map.get(column.name.as_str())),
+            };
+            let cell = match value {
+                None | Some(Value::Null) if column.nullable => Cell::Null,
+                None | Some(Value::Null) => {
+                    return Err(
+                        format!("Missing value for non-nullable column {:?}", column.name).into(),
+                    )
+                }
+                Some(value) => Cell::convert(column.column_type, value).ok_or_else(|| {
+                    format!(
+                        "Value of type {} does not fit column {:?} of type {:?}",
+                        value.kind_str(),
+                        column.name,
+                        column.column_type,
+                    )
+                })?,
+            };
+            row.push(cell);
+        }
+        Ok(())
+    }
+}
+
+impl ParquetColumnType {
+    fn arrow_type(self) -> ArrowDataType {
+        match self {
+            Self::String | Self::Json => ArrowDataType::Utf8,
+            Self::Int64 => ArrowDataType::Int64,
+            Self::Float64 => ArrowDataType::Float64,
+            Self::Boolean => ArrowDataType::Boolean,
+            Self::Timestamp => ArrowDataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+        }
+    }
+
+    /// Picks the column type for a field of the given kind.
+    ///
+    /// Fields that can only hold a single scalar type get the matching column type, fields that
+    /// can be either integers or floats are stored as floats, and everything else is stored as
+    /// JSON.
+    fn from_kind(kind: &Kind) -> Self {
+        if kind.as_object().is_some() || kind.as_array().is_some() || kind.contains_regex() {
+            return Self::Json;
+        }
+
+        let scalars = [
+            (kind.contains_bytes(), Self::String),
+            (kind.contains_integer(), Self::Int64),
+            (kind.contains_float(), Self::Float64),
+            (kind.contains_boolean(), Self::Boolean),
+            (kind.contains_timestamp(), Self::Timestamp),
+        ];
+        let mut types = scalars
+            .into_iter()
+            .filter_map(|(contained, column_type)| contained.then_some(column_type));
+
+        match (types.next(), types.next(), types.next()) {
+            (Some(column_type), None, _) => column_type,
+            (Some(Self::Int64), Some(Self::Float64), None) => Self::Float64,
+            _ => Self::Json,
+        }
+    }
+}
+
+/// A value converted to the type of its column.
+enum Cell {
+    Null,
+    String(String),
+    Int64(i64),
+    Float64(f64),
+    Boolean(bool),
+    Timestamp(i64),
+}
+
+impl Cell {
+    fn convert(column_type: ParquetColumnType, value: &Value) -> Option<Self> {
+        Some(match (column_type, value) {
+            (ParquetColumnType::String, Value::Bytes(bytes)) => {
+                Self::String(String::from_utf8_lossy(bytes).into_owned())
+            }
+            (ParquetColumnType::Int64, Value::Integer(int)) => Self::Int64(*int),
+            (ParquetColumnType::Float64, Value::Float(float)) => Self::Float64(float.into_inner()),
+            (ParquetColumnType::Float64, Value::Integer(int)) => Self::Float64(*int as f64),
+            (ParquetColumnType::Boolean, Value::Boolean(bool)) => Self::Boolean(*bool),
+            (ParquetColumnType::Timestamp, Value::Timestamp(timestamp)) => {
+                Self::Timestamp(timestamp.timestamp_micros())
+            }
+            (ParquetColumnType::Json, value) => Self::String(serde_json::to_string(value).ok()?),
+            _ => return None,
+        })
+    }
+}
+
+enum ColumnBuilder {
+    String(StringBuilder),
+    Int64(Int64Builder),
+    Float64(Float64Builder),
+    Boolean(BooleanBuilder),
+    Timestamp(TimestampMicrosecondBuilder),
+}
+
+impl ColumnBuilder {
+    fn new(column_type: ParquetColumnType, capacity: usize) -> Self {
+        match column_type {
+            ParquetColumnType::String | ParquetColumnType::Json => {
+                Self::String(StringBuilder::with_capacity(capacity, capacity * 16))
+            }
+            ParquetColumnType::Int64 => Self::Int64(Int64Builder::with_capacity(capacity)),
+            ParquetColumnType::Float64 => Self::Float64(Float64Builder::with_capacity(capacity)),
+            ParquetColumnType::Boolean => Self::Boolean(BooleanBuilder::with_capacity(capacity)),
+            ParquetColumnType::Timestamp => Self::Timestamp(
+                TimestampMicrosecondBuilder::with_capacity(capacity).with_timezone("UTC"),
+            ),
+        }
+    }
+
+    fn append(&mut self, cell: Cell) {
+        match (self, cell) {
+            (Self::String(builder), Cell::String(value)) => builder.append_value(value),
+            (Self::Int64(builder), Cell::Int64(value)) => builder.append_value(value),
+            (Self::Float64(builder), Cell::Float64(value)) => builder.append_value(value),
+            (Self::Boolean(builder), Cell::Boolean(value)) => builder.append_value(value),
+            (Self::Timestamp(builder), Cell::Timestamp(value)) => builder.append_value(value),
+            (Self::String(builder), Cell::Null) => builder.append_null(),
+            (Self::Int64(builder), Cell::Null) => builder.append_null(),
+            (Self::Float64(builder), Cell::Null) => builder.append_null(),
+            (Self::Boolean(builder), Cell::Null) => builder.append_null(),
+            (Self::Timestamp(builder), Cell::Null) => builder.append_null(),
+            _ => unreachable!("cells are always converted to the type of their column"),
+        }
+    }
+
+    fn finish(self) -> ArrayRef {
+        match self {
+            Self::String(mut builder) => Arc::new(builder.finish()),
+            Self::Int64(mut builder) => Arc::new(builder.finish()),
+            Self::Float64(mut builder) => Arc::new(builder.finish()),
+            Self::Boolean(mut builder) => Arc::new(builder.finish()),
+            Self::Timestamp(mut builder) => Arc::new(builder.finish()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use arrow_array::{Array, Float64Array, Int64Array, StringArray};
+    use bytes::Bytes;
+    use lookup::owned_value_path;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use vrl::{btreemap, value::kind::Collection};
+
+    use super::*;
+
+    fn column(name: &str, column_type: ParquetColumnType, nullable: bool) -> ParquetColumn {
+        ParquetColumn {
+            name: name.to_owned(),
+            field: None,
+            column_type,
+            nullable,
+        }
+    }
+
+    fn serializer(columns: Vec<ParquetColumn>) -> ParquetSerializer {
+        ParquetSerializerConfig::new(ParquetSerializerOptions {
+            schema: Some(columns),
+            ..Default::default()
+        })
+        .build()
+        .unwrap()
+    }
+
+    fn read(bytes: Bytes) -> RecordBatch {
+        let mut reader = ParquetRecordBatchReaderBuilder::try_new(bytes)
+            .unwrap()
+            .build()
+            .unwrap();
+        reader.next().unwrap().unwrap()
+    }
+
+    fn log(fields: BTreeMap<&str, Value>) -> Event {
+        Event::Log(LogEvent::from(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect::<vrl::value::ObjectMap>(),
+        ))
+    }
+
+    #[test]
+    fn encodes_batch() {
+        let serializer = serializer(vec![
+            column("message", ParquetColumnType::String, false),
+            column("status", ParquetColumnType::Int64, true),
+            column("duration", ParquetColumnType::Float64, true),
+            column("tags", ParquetColumnType::Json, true),
+        ]);
+
+        let events = vec![
+            log(btreemap! {
+                "message" => "first",
+                "status" => 200,
+                "duration" => 1.5,
+                "tags" => vec!["a", "b"],
+            }),
+            log(btreemap! {
+                "message" => "second",
+                "duration" => 2,
+            }),
+        ];
+
+        let mut buffer = BytesMut::new();
+        let rejected = serializer.encode_batch(&events, &mut buffer).unwrap();
+        assert!(rejected.is_empty());
+
+        let batch = read(buffer.freeze());
+        assert_eq!(batch.num_rows(), 2);
+
+        let message = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(message.value(0), "first");
+        assert_eq!(message.value(1), "second");
+
+        let status = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(status.value(0), 200);
+        assert!(status.is_null(1));
+
+        let duration = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(duration.value(1), 2.0);
+
+        let tags = batch
+            .column(3)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+        assert_eq!(tags.value(0), r#"["a","b"]"#);
+    }
+
+    #[test]
+    fn rejects_events_not_fitting_schema() {
+        let serializer = serializer(vec![
+            column("message", ParquetColumnType::String, false),
+            column("status", ParquetColumnType::Int64, true),
+        ]);
+
+        let events = vec![
+            log(btreemap! { "message" => "ok", "status" => 200 }),
+            log(btreemap! { "status" => 500 }),
+            log(btreemap! { "message" => "wrong type", "status" => "500" }),
+        ];
+
+        let mut buffer = BytesMut::new();
+        let rejected = serializer.encode_batch(&events, &mut buffer).unwrap();
+        assert_eq!(
+            rejected.iter().map(|event| event.index).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+
+        let batch = read(buffer.freeze());
+        assert_eq!(batch.num_rows(), 1);
+    }
+
+    #[test]
+    fn single_event_error() {
+        let mut serializer = serializer(vec![column("message", ParquetColumnType::String, false)]);
+
+        let mut buffer = BytesMut::new();
+        assert!(serializer
+            .encode(log(btreemap! { "status" => 200 }), &mut buffer)
+            .is_err());
+    }
+
+    #[test]
+    fn derives_columns_from_definition() {
+        let definition = schema::Definition::empty_legacy_namespace()
+            .with_event_field(&owned_value_path!("message"), Kind::bytes(), None)
+            .with_event_field(
+                &owned_value_path!("count"),
+                Kind::integer().or_float(),
+                None,
+            )
+            .with_event_field(
+                &owned_value_path!("extra"),
+                Kind::object(Collection::empty()),
+                None,
+            );
+
+        let columns = ColumnSchema::from_definition(&definition).unwrap();
+        let types = columns
+            .columns
+            .iter()
+            .map(|column| (column.name.as_str(), column.column_type))
+            .collect::<BTreeMap<_, _>>();
+
+        assert_eq!(types["message"], ParquetColumnType::String);
+        assert_eq!(types["count"], ParquetColumnType::Float64);
+        assert_eq!(types["extra"], ParquetColumnType::Json);
+    }
+
+    #[test]
+    fn requires_columns() {
+        let config = ParquetSerializerConfig::new(ParquetSerializerOptions {
+            schema: Some(Vec::new()),
+            ..Default::default()
+        });
+        assert!(config.build().is_err());
+    }
+
+    #[test]
+    fn writer_streams_row_groups() {
+        let serializer = ParquetSerializerConfig::new(ParquetSerializerOptions {
+            schema: Some(vec![column("message", ParquetColumnType::String, false)]),
+            row_group_size: NonZeroUsize::new(1),
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+
+        let mut writer = serializer.writer();
+        let mut bytes = Vec::new();
+        for message in ["one", "two", "three"] {
+            let rejected = writer
+                .write(&[log(btreemap! { "message" => message })])
+                .unwrap();
+            assert!(rejected.is_empty());
+            assert_eq!(writer.buffered_rows(), 0);
+            bytes.extend(writer.take_bytes());
+        }
+        bytes.extend(writer.finish().unwrap());
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(bytes)).unwrap();
+        assert_eq!(reader.metadata().num_row_groups(), 3);
+        let rows: usize = reader
+            .build()
+            .unwrap()
+            .map(|batch| batch.unwrap().num_rows())
+            .sum();
+        assert_eq!(rows, 3);
+    }
+}
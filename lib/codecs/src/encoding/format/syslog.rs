@@ -0,0 +1,502 @@
+use bytes::{BufMut, BytesMut};
+use chrono::{DateTime, SecondsFormat, Utc};
+use lookup::lookup_v2::ConfigTargetPath;
+use tokio_util::codec::Encoder;
+use vector_core::{
+    config::DataType,
+    event::{Event, LogEvent, Value},
+    schema,
+};
+
+/// The value of an RFC 5424 header field that is not set.
+const NIL_VALUE: &str = "-";
+
+/// Config used to build a `SyslogSerializer`.
+#[crate::configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct SyslogSerializerConfig {
+    /// Options for the syslog serializer.
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub syslog: SyslogSerializerOptions,
+}
+
+impl SyslogSerializerConfig {
+    /// Creates a new `SyslogSerializerConfig`.
+    pub const fn new(syslog: SyslogSerializerOptions) -> Self {
+        Self { syslog }
+    }
+
+    /// Build the `SyslogSerializer` from this configuration.
+    pub fn build(&self) -> SyslogSerializer {
+        SyslogSerializer::new(self.syslog.clone())
+    }
+
+    /// The data type of events that are accepted by `SyslogSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Syslog serializer options.
+///
+/// Each part of the syslog message is read from the configured field of the event. Fields that
+/// are not configured, or that are missing from the event, use the defaults.
+#[crate::configurable_component]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct SyslogSerializerOptions {
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub rfc: SyslogRfc,
+
+    /// The field containing the facility, either as a name such as `local0`, or as a number.
+    #[configurable(metadata(docs::examples = ".facility"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub facility: Option<ConfigTargetPath>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub default_facility: SyslogFacility,
+
+    /// The field containing the severity, either as a name such as `err`, or as a number.
+    #[configurable(metadata(docs::examples = ".severity"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub severity: Option<ConfigTargetPath>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub default_severity: SyslogSeverity,
+
+    /// The field containing the hostname.
+    #[configurable(metadata(docs::examples = ".host"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub hostname: Option<ConfigTargetPath>,
+
+    /// The field containing the application name, used as the tag of RFC 3164 messages.
+    ///
+    /// Defaults to `vector`.
+    #[configurable(metadata(docs::examples = ".appname"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub app_name: Option<ConfigTargetPath>,
+
+    /// The field containing the process ID.
+    #[configurable(metadata(docs::examples = ".procid"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub proc_id: Option<ConfigTargetPath>,
+
+    /// The field containing the message ID.
+    ///
+    /// Only used by RFC 5424 messages.
+    #[configurable(metadata(docs::examples = ".msgid"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub msg_id: Option<ConfigTargetPath>,
+
+    /// The field containing the structured data.
+    ///
+    /// The field must be an object mapping each SD-ID to an object of its parameters, for example
+    /// `{"origin@32473": {"ip": "192.0.2.1"}}`. Only used by RFC 5424 messages.
+    #[configurable(metadata(docs::examples = ".structured_data"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub structured_data: Option<ConfigTargetPath>,
+
+    /// The field containing the message.
+    ///
+    /// Defaults to the message of the event, as defined by the log schema.
+    #[configurable(metadata(docs::examples = ".message"))]
+    #[serde(default, skip_serializing_if = "vector_core::serde::is_default")]
+    pub payload_key: Option<ConfigTargetPath>,
+}
+
+/// The syslog format of the messages.
+#[crate::configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogRfc {
+    /// The [RFC 3164][rfc3164] format, also known as BSD syslog.
+    ///
+    /// [rfc3164]: https://www.ietf.org/rfc/rfc3164.txt
+    Rfc3164,
+
+    /// The [RFC 5424][rfc5424] format.
+    ///
+    /// [rfc5424]: https://www.ietf.org/rfc/rfc5424.txt
+    #[default]
+    Rfc5424,
+}
+
+/// The facility of a syslog message.
+#[crate::configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[allow(missing_docs)]
+pub enum SyslogFacility {
+    Kern,
+    #[default]
+    User,
+    Mail,
+    Daemon,
+    Auth,
+    Syslog,
+    Lpr,
+    News,
+    Uucp,
+    Cron,
+    Authpriv,
+    Ftp,
+    Ntp,
+    Security,
+    Console,
+    SolarisCron,
+    Local0,
+    Local1,
+    Local2,
+    Local3,
+    Local4,
+    Local5,
+    Local6,
+    Local7,
+}
+
+const FACILITIES: [&str; 24] = [
+    "kern",
+    "user",
+    "mail",
+    "daemon",
+    "auth",
+    "syslog",
+    "lpr",
+    "news",
+    "uucp",
+    "cron",
+    "authpriv",
+    "ftp",
+    "ntp",
+    "security",
+    "console",
+    "solaris-cron",
+    "local0",
+    "local1",
+    "local2",
+    "local3",
+    "local4",
+    "local5",
+    "local6",
+    "local7",
+];
+
+/// The severity of a syslog message.
+#[crate::configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[allow(missing_docs)]
+pub enum SyslogSeverity {
+    Emerg,
+    Alert,
+    Crit,
+    Err,
+    Warning,
+    Notice,
+    #[default]
+    Info,
+    Debug,
+}
+
+const SEVERITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+/// Reads a facility or severity from a field, given its name or its number.
+fn code(value: Option<&Value>, names: &[&str], aliases: &[(&str, usize)]) -> Option<u8> {
+    let code = match value? {
+        Value::Integer(code) => usize::try_from(*code).ok()?,
+        Value::Bytes(bytes) => {
+            let name = String::from_utf8_lossy(bytes).to_lowercase();
+            names
+                .iter()
+                .position(|candidate| *candidate == name)
+                .or_else(|| {
+                    aliases
+                        .iter()
+                        .find(|(alias, _)| *alias == name)
+                        .map(|(_, code)| *code)
+                })
+                .or_else(|| name.parse().ok())?
+        }
+        _ => return None,
+    };
+    (code < names.len()).then_some(code as u8)
+}
+
+/// Serializer that converts an `Event` to bytes using the syslog format.
+#[derive(Debug, Clone)]
+pub struct SyslogSerializer {
+    options: SyslogSerializerOptions,
+}
+
+impl SyslogSerializer {
+    /// Creates a new `SyslogSerializer`.
+    pub const fn new(options: SyslogSerializerOptions) -> Self {
+        Self { options }
+    }
+
+    fn field<'a>(&self, log: &'a LogEvent, path: &Option<ConfigTargetPath>) -> Option<&'a Value> {
+        path.as_ref().and_then(|path| log.get(path))
+    }
+
+    /// Reads a header field, keeping only the printable characters that it allows.
+    fn header(
+        &self,
+        log: &LogEvent,
+        path: &Option<ConfigTargetPath>,
+        max_length: usize,
+    ) -> Option<String> {
+        let value = self.field(log, path)?.to_string_lossy();
+        let header = value
+            .chars()
+            .filter(|c| c.is_ascii_graphic())
+            .take(max_length)
+            .collect::<String>();
+        (!header.is_empty()).then_some(header)
+    }
+
+    fn priority(&self, log: &LogEvent) -> u8 {
+        let facility = code(
+            self.field(log, &self.options.facility),
+            &FACILITIES,
+            &[("solaris_cron", 15)],
+        )
+        .unwrap_or(self.options.default_facility as u8);
+        let severity = code(
+            self.field(log, &self.options.severity),
+            &SEVERITIES,
+            &[
+                ("emergency", 0),
+                ("critical", 2),
+                ("error", 3),
+                ("warn", 4),
+                ("informational", 6),
+            ],
+        )
+        .unwrap_or(self.options.default_severity as u8);
+        facility * 8 + severity
+    }
+
+    fn structured_data(&self, log: &LogEvent) -> Option<String> {
+        let Value::Object(elements) = self.field(log, &self.options.structured_data)? else {
+            return None;
+        };
+
+        let mut structured_data = String::new();
+        for (id, params) in elements {
+            structured_data.push('[');
+            structured_data.push_str(&sd_name(id));
+            if let Value::Object(params) = params {
+                for (name, value) in params {
+                    structured_data.push(' ');
+                    structured_data.push_str(&sd_name(name));
+                    structured_data.push_str("=\"");
+                    for c in value.to_string_lossy().chars() {
+                        if matches!(c, '"' | '\\' | ']') {
+                            structured_data.push('\\');
+                        }
+                        structured_data.push(c);
+                    }
+                    structured_data.push('"');
+                }
+            }
+            structured_data.push(']');
+        }
+        (!structured_data.is_empty()).then_some(structured_data)
+    }
+}
+
+/// Keeps the characters allowed in the SD-ID and PARAM-NAME of structured data.
+fn sd_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"'))
+        .take(32)
+        .collect()
+}
+
+impl Encoder<Event> for SyslogSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.into_log();
+
+        let priority = self.priority(&log);
+        let timestamp = match log.get_timestamp() {
+            Some(Value::Timestamp(timestamp)) => *timestamp,
+            _ => Utc::now(),
+        };
+        let hostname = self.header(&log, &self.options.hostname, 255);
+        let app_name = self.header(&log, &self.options.app_name, 48);
+        let proc_id = self.header(&log, &self.options.proc_id, 128);
+        let message = match &self.options.payload_key {
+            Some(path) => log.get(path),
+            None => log.get_message(),
+        }
+        .map(Value::to_string_lossy)
+        .unwrap_or_default();
+
+        match self.options.rfc {
+            SyslogRfc::Rfc5424 => {
+                let msg_id = self.header(&log, &self.options.msg_id, 32);
+                let structured_data = self.structured_data(&log);
+                buffer.put_slice(
+                    format!(
+                        "<{}>1 {} {} {} {} {} {}",
+                        priority,
+                        timestamp.to_rfc3339_opts(SecondsFormat::Micros, true),
+                        hostname.as_deref().unwrap_or(NIL_VALUE),
+                        app_name.as_deref().unwrap_or("vector"),
+                        proc_id.as_deref().unwrap_or(NIL_VALUE),
+                        msg_id.as_deref().unwrap_or(NIL_VALUE),
+                        structured_data.as_deref().unwrap_or(NIL_VALUE),
+                    )
+                    .as_bytes(),
+                );
+            }
+            SyslogRfc::Rfc3164 => {
+                // The tag is at most 32 alphanumeric characters.
+                let tag = app_name
+                    .as_deref()
+                    .unwrap_or("vector")
+                    .chars()
+                    .filter(char::is_ascii_alphanumeric)
+                    .take(32)
+                    .collect::<String>();
+                buffer.put_slice(
+                    format!(
+                        "<{}>{} {} {}",
+                        priority,
+                        rfc3164_timestamp(timestamp),
+                        hostname.as_deref().unwrap_or("localhost"),
+                        tag,
+                    )
+                    .as_bytes(),
+                );
+                if let Some(proc_id) = proc_id {
+                    buffer.put_slice(format!("[{proc_id}]").as_bytes());
+                }
+                buffer.put_u8(b':');
+            }
+        }
+
+        if !message.is_empty() {
+            buffer.put_u8(b' ');
+            buffer.put_slice(message.as_bytes());
+        }
+
+        Ok(())
+    }
+}
+
+fn rfc3164_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.format("%b %e %H:%M:%S").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use vector_core::event::LogEvent;
+    use vrl::{btreemap, path::parse_target_path};
+
+    use super::*;
+
+    fn path(path: &str) -> Option<ConfigTargetPath> {
+        Some(ConfigTargetPath(parse_target_path(path).unwrap()))
+    }
+
+    fn event() -> Event {
+        let mut log = LogEvent::from("Something happened");
+        log.insert(
+            "timestamp",
+            Utc.with_ymd_and_hms(2024, 3, 5, 7, 8, 9).unwrap(),
+        );
+        log.insert("host", "web-1");
+        log.insert("facility", "local3");
+        log.insert("severity", 3);
+        log.insert("app", "my app");
+        log.insert("pid", 1234);
+        log.insert("msgid", "ID47");
+        log.insert(
+            "sd",
+            btreemap! {
+                "origin@32473" => btreemap! {
+                    "ip" => "192.0.2.1",
+                    "note" => "a \"quoted\" [value]",
+                },
+            },
+        );
+        Event::Log(log)
+    }
+
+    fn options() -> SyslogSerializerOptions {
+        SyslogSerializerOptions {
+            facility: path(".facility"),
+            severity: path(".severity"),
+            hostname: path(".host"),
+            app_name: path(".app"),
+            proc_id: path(".pid"),
+            msg_id: path(".msgid"),
+            structured_data: path(".sd"),
+            ..Default::default()
+        }
+    }
+
+    fn serialize(options: SyslogSerializerOptions, event: Event) -> String {
+        let mut buffer = BytesMut::new();
+        SyslogSerializer::new(options)
+            .encode(event, &mut buffer)
+            .unwrap();
+        String::from_utf8(buffer.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn serialize_rfc5424() {
+        assert_eq!(
+            serialize(options(), event()),
+            "<155>1 2024-03-05T07:08:09.000000Z web-1 myapp 1234 ID47 \
+             [origin@32473 ip=\"192.0.2.1\" note=\"a \\\"quoted\\\" [value\\]\"] \
+             Something happened"
+        );
+    }
+
+    #[test]
+    fn serialize_rfc3164() {
+        let options = SyslogSerializerOptions {
+            rfc: SyslogRfc::Rfc3164,
+            ..options()
+        };
+        assert_eq!(
+            serialize(options, event()),
+            "<155>Mar  5 07:08:09 web-1 myapp[1234]: Something happened"
+        );
+    }
+
+    #[test]
+    fn serialize_defaults() {
+        let options = SyslogSerializerOptions {
+            default_facility: SyslogFacility::Daemon,
+            default_severity: SyslogSeverity::Warning,
+            ..Default::default()
+        };
+        let message = serialize(options, event());
+        assert!(message.starts_with("<28>1 2024-03-05T07:08:09.000000Z - vector - - - "));
+        assert!(message.ends_with(" Something happened"));
+    }
+
+    #[test]
+    fn invalid_priority_uses_defaults() {
+        let mut event = event();
+        event.as_mut_log().insert("facility", "nope");
+        event.as_mut_log().insert("severity", 42);
+        assert!(serialize(options(), event).starts_with("<14>1 "));
+    }
+}
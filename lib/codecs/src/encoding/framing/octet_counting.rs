@@ -0,0 +1,66 @@
+use bytes::{BufMut, BytesMut};
+use serde::{Deserialize, Serialize};
+use tokio_util::codec::Encoder;
+
+use super::BoxedFramingError;
+
+/// Config used to build an `OctetCountingEncoder`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct OctetCountingEncoderConfig;
+
+impl OctetCountingEncoderConfig {
+    /// Creates a new `OctetCountingEncoderConfig`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Build the `OctetCountingEncoder` from this configuration.
+    pub fn build(&self) -> OctetCountingEncoder {
+        OctetCountingEncoder
+    }
+}
+
+/// An encoder that prefixes each frame with its length in bytes followed by a space, as described
+/// in [RFC 6587][rfc6587].
+///
+/// [rfc6587]: https://tools.ietf.org/html/rfc6587#section-3.4.1
+#[derive(Debug, Clone, Default)]
+pub struct OctetCountingEncoder;
+
+impl Encoder<()> for OctetCountingEncoder {
+    type Error = BoxedFramingError;
+
+    fn encode(&mut self, _: (), buffer: &mut BytesMut) -> Result<(), BoxedFramingError> {
+        let frame = buffer.split();
+        let prefix = format!("{} ", frame.len());
+        buffer.reserve(prefix.len() + frame.len());
+        buffer.put_slice(prefix.as_bytes());
+        buffer.unsplit(frame);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        let mut codec = OctetCountingEncoder;
+
+        let mut buffer = BytesMut::from("<13>1 - - - - - - abc");
+        codec.encode((), &mut buffer).unwrap();
+
+        assert_eq!(&buffer[..], b"21 <13>1 - - - - - - abc");
+    }
+
+    #[test]
+    fn encode_empty() {
+        let mut codec = OctetCountingEncoder;
+
+        let mut buffer = BytesMut::new();
+        codec.encode((), &mut buffer).unwrap();
+
+        assert_eq!(&buffer[..], b"0 ");
+    }
+}
@@ -13,12 +13,20 @@ pub use format::{
     JsonSerializerConfig, LogfmtSerializer, LogfmtSerializerConfig, NativeJsonSerializer,
     NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig, ProtobufSerializer,
     ProtobufSerializerConfig, ProtobufSerializerOptions, RawMessageSerializer,
-    RawMessageSerializerConfig, TextSerializer, TextSerializerConfig,
+    RawMessageSerializerConfig, SyslogFacility, SyslogRfc, SyslogSerializer,
+    SyslogSerializerConfig, SyslogSerializerOptions, SyslogSeverity, TextSerializer,
+    TextSerializerConfig,
+};
+#[cfg(feature = "parquet")]
+pub use format::{
+    ParquetColumn, ParquetColumnType, ParquetCompression, ParquetSerializer,
+    ParquetSerializerConfig, ParquetSerializerOptions, ParquetWriter, RejectedEvent,
 };
 pub use framing::{
     BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
     CharacterDelimitedEncoderConfig, CharacterDelimitedEncoderOptions, LengthDelimitedEncoder,
     LengthDelimitedEncoderConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
+    OctetCountingEncoder, OctetCountingEncoderConfig,
 };
 use vector_config::configurable_component;
 use vector_core::{config::DataType, event::Event, schema};
@@ -71,6 +79,14 @@ pub enum FramingConfig {
 
     /// Event data is delimited by a newline (LF) character.
     NewlineDelimited,
+
+    /// Event data is prefixed with its length in bytes, written as a decimal number followed by a
+    /// space.
+    ///
+    /// This is the octet counting framing of syslog over TCP, as described in [RFC 6587][rfc6587].
+    ///
+    /// [rfc6587]: https://tools.ietf.org/html/rfc6587#section-3.4.1
+    OctetCounting,
 }
 
 impl From<BytesEncoderConfig> for FramingConfig {
@@ -97,6 +113,12 @@ impl From<NewlineDelimitedEncoderConfig> for FramingConfig {
     }
 }
 
+impl From<OctetCountingEncoderConfig> for FramingConfig {
+    fn from(_: OctetCountingEncoderConfig) -> Self {
+        Self::OctetCounting
+    }
+}
+
 impl FramingConfig {
     /// Build the `Framer` from this configuration.
     pub fn build(&self) -> Framer {
@@ -107,6 +129,9 @@ impl FramingConfig {
             FramingConfig::NewlineDelimited => {
                 Framer::NewlineDelimited(NewlineDelimitedEncoderConfig.build())
             }
+            FramingConfig::OctetCounting => {
+                Framer::OctetCounting(OctetCountingEncoderConfig.build())
+            }
         }
     }
 }
@@ -122,6 +147,8 @@ pub enum Framer {
     LengthDelimited(LengthDelimitedEncoder),
     /// Uses a `NewlineDelimitedEncoder` for framing.
     NewlineDelimited(NewlineDelimitedEncoder),
+    /// Uses an `OctetCountingEncoder` for framing.
+    OctetCounting(OctetCountingEncoder),
     /// Uses an opaque `Encoder` implementation for framing.
     Boxed(BoxedFramer),
 }
@@ -150,6 +177,12 @@ impl From<NewlineDelimitedEncoder> for Framer {
     }
 }
 
+impl From<OctetCountingEncoder> for Framer {
+    fn from(encoder: OctetCountingEncoder) -> Self {
+        Self::OctetCounting(encoder)
+    }
+}
+
 impl From<BoxedFramer> for Framer {
     fn from(encoder: BoxedFramer) -> Self {
         Self::Boxed(encoder)
@@ -165,6 +198,7 @@ impl tokio_util::codec::Encoder<()> for Framer {
             Framer::CharacterDelimited(framer) => framer.encode((), buffer),
             Framer::LengthDelimited(framer) => framer.encode((), buffer),
             Framer::NewlineDelimited(framer) => framer.encode((), buffer),
+            Framer::OctetCounting(framer) => framer.encode((), buffer),
             Framer::Boxed(framer) => framer.encode((), buffer),
         }
     }
@@ -234,6 +268,19 @@ pub enum SerializerConfig {
     /// [experimental]: https://vector.dev/highlights/2022-03-31-native-event-codecs
     NativeJson,
 
+    /// Encodes a batch of events as an [Apache Parquet][apache_parquet] file.
+    ///
+    /// This codec can only be used by sinks that write whole objects or files, such as the
+    /// `aws_s3`, `gcp_cloud_storage`, `azure_blob` and `file` sinks. Each batch of events is
+    /// written as a single Parquet file, and framing is not applied.
+    ///
+    /// [apache_parquet]: https://parquet.apache.org/
+    #[cfg(feature = "parquet")]
+    Parquet {
+        /// Apache Parquet-specific encoder options.
+        parquet: ParquetSerializerOptions,
+    },
+
     /// Encodes an event as a [Protobuf][protobuf] message.
     ///
     /// [protobuf]: https://protobuf.dev/
@@ -248,6 +295,11 @@ pub enum SerializerConfig {
     /// could lead to the encoding emitting empty strings for the given event.
     RawMessage,
 
+    /// Encodes an event as a [syslog][syslog] message, in either the RFC 5424 or RFC 3164 format.
+    ///
+    /// [syslog]: https://www.rfc-editor.org/rfc/rfc5424
+    Syslog(SyslogSerializerConfig),
+
     /// Plain text encoding.
     ///
     /// This encoding uses the `message` field of a log event. For metrics, it uses an
@@ -301,6 +353,15 @@ impl From<NativeJsonSerializerConfig> for SerializerConfig {
     }
 }
 
+#[cfg(feature = "parquet")]
+impl From<ParquetSerializerConfig> for SerializerConfig {
+    fn from(config: ParquetSerializerConfig) -> Self {
+        Self::Parquet {
+            parquet: config.parquet,
+        }
+    }
+}
+
 impl From<ProtobufSerializerConfig> for SerializerConfig {
     fn from(config: ProtobufSerializerConfig) -> Self {
         Self::Protobuf(config)
@@ -313,6 +374,12 @@ impl From<RawMessageSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<SyslogSerializerConfig> for SerializerConfig {
+    fn from(config: SyslogSerializerConfig) -> Self {
+        Self::Syslog(config)
+    }
+}
+
 impl From<TextSerializerConfig> for SerializerConfig {
     fn from(config: TextSerializerConfig) -> Self {
         Self::Text(config)
@@ -334,10 +401,15 @@ impl SerializerConfig {
             SerializerConfig::NativeJson => {
                 Ok(Serializer::NativeJson(NativeJsonSerializerConfig.build()))
             }
+            #[cfg(feature = "parquet")]
+            SerializerConfig::Parquet { parquet } => Ok(Serializer::Parquet(
+                ParquetSerializerConfig::new(parquet.clone()).build()?,
+            )),
             SerializerConfig::Protobuf(config) => Ok(Serializer::Protobuf(config.build()?)),
             SerializerConfig::RawMessage => {
                 Ok(Serializer::RawMessage(RawMessageSerializerConfig.build()))
             }
+            SerializerConfig::Syslog(config) => Ok(Serializer::Syslog(config.build())),
             SerializerConfig::Text(config) => Ok(Serializer::Text(config.build())),
         }
     }
@@ -366,10 +438,14 @@ impl SerializerConfig {
             | SerializerConfig::Logfmt
             | SerializerConfig::NativeJson
             | SerializerConfig::RawMessage
+            | SerializerConfig::Syslog(_)
             | SerializerConfig::Text(_) => FramingConfig::NewlineDelimited,
             SerializerConfig::Gelf => {
                 FramingConfig::CharacterDelimited(CharacterDelimitedEncoderConfig::new(0))
             }
+            // A Parquet file is a self-contained binary format that can't be split into frames.
+            #[cfg(feature = "parquet")]
+            SerializerConfig::Parquet { .. } => FramingConfig::Bytes,
         }
     }
 
@@ -385,8 +461,13 @@ impl SerializerConfig {
             SerializerConfig::Logfmt => LogfmtSerializerConfig.input_type(),
             SerializerConfig::Native => NativeSerializerConfig.input_type(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.input_type(),
+            #[cfg(feature = "parquet")]
+            SerializerConfig::Parquet { parquet } => {
+                ParquetSerializerConfig::new(parquet.clone()).input_type()
+            }
             SerializerConfig::Protobuf(config) => config.input_type(),
             SerializerConfig::RawMessage => RawMessageSerializerConfig.input_type(),
+            SerializerConfig::Syslog(config) => config.input_type(),
             SerializerConfig::Text(config) => config.input_type(),
         }
     }
@@ -403,8 +484,13 @@ impl SerializerConfig {
             SerializerConfig::Logfmt => LogfmtSerializerConfig.schema_requirement(),
             SerializerConfig::Native => NativeSerializerConfig.schema_requirement(),
             SerializerConfig::NativeJson => NativeJsonSerializerConfig.schema_requirement(),
+            #[cfg(feature = "parquet")]
+            SerializerConfig::Parquet { parquet } => {
+                ParquetSerializerConfig::new(parquet.clone()).schema_requirement()
+            }
             SerializerConfig::Protobuf(config) => config.schema_requirement(),
             SerializerConfig::RawMessage => RawMessageSerializerConfig.schema_requirement(),
+            SerializerConfig::Syslog(config) => config.schema_requirement(),
             SerializerConfig::Text(config) => config.schema_requirement(),
         }
     }
@@ -427,10 +513,15 @@ pub enum Serializer {
     Native(NativeSerializer),
     /// Uses a `NativeJsonSerializer` for serialization.
     NativeJson(NativeJsonSerializer),
+    /// Uses a `ParquetSerializer` for serialization.
+    #[cfg(feature = "parquet")]
+    Parquet(ParquetSerializer),
     /// Uses a `ProtobufSerializer` for serialization.
     Protobuf(ProtobufSerializer),
     /// Uses a `RawMessageSerializer` for serialization.
     RawMessage(RawMessageSerializer),
+    /// Uses a `SyslogSerializer` for serialization.
+    Syslog(SyslogSerializer),
     /// Uses a `TextSerializer` for serialization.
     Text(TextSerializer),
 }
@@ -446,7 +537,10 @@ impl Serializer {
             | Serializer::Text(_)
             | Serializer::Native(_)
             | Serializer::Protobuf(_)
-            | Serializer::RawMessage(_) => false,
+            | Serializer::RawMessage(_)
+            | Serializer::Syslog(_) => false,
+            #[cfg(feature = "parquet")]
+            Serializer::Parquet(_) => false,
         }
     }
 
@@ -467,9 +561,12 @@ impl Serializer {
             | Serializer::Text(_)
             | Serializer::Native(_)
             | Serializer::Protobuf(_)
-            | Serializer::RawMessage(_) => {
+            | Serializer::RawMessage(_)
+            | Serializer::Syslog(_) => {
                 panic!("Serializer does not support JSON")
             }
+            #[cfg(feature = "parquet")]
+            Serializer::Parquet(_) => panic!("Serializer does not support JSON"),
         }
     }
 }
@@ -516,6 +613,13 @@ impl From<NativeJsonSerializer> for Serializer {
     }
 }
 
+#[cfg(feature = "parquet")]
+impl From<ParquetSerializer> for Serializer {
+    fn from(serializer: ParquetSerializer) -> Self {
+        Self::Parquet(serializer)
+    }
+}
+
 impl From<ProtobufSerializer> for Serializer {
     fn from(serializer: ProtobufSerializer) -> Self {
         Self::Protobuf(serializer)
@@ -528,6 +632,12 @@ impl From<RawMessageSerializer> for Serializer {
     }
 }
 
+impl From<SyslogSerializer> for Serializer {
+    fn from(serializer: SyslogSerializer) -> Self {
+        Self::Syslog(serializer)
+    }
+}
+
 impl From<TextSerializer> for Serializer {
     fn from(serializer: TextSerializer) -> Self {
         Self::Text(serializer)
@@ -546,8 +656,11 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
             Serializer::Logfmt(serializer) => serializer.encode(event, buffer),
             Serializer::Native(serializer) => serializer.encode(event, buffer),
             Serializer::NativeJson(serializer) => serializer.encode(event, buffer),
+            #[cfg(feature = "parquet")]
+            Serializer::Parquet(serializer) => serializer.encode(event, buffer),
             Serializer::Protobuf(serializer) => serializer.encode(event, buffer),
             Serializer::RawMessage(serializer) => serializer.encode(event, buffer),
+            Serializer::Syslog(serializer) => serializer.encode(event, buffer),
             Serializer::Text(serializer) => serializer.encode(event, buffer),
         }
     }
@@ -5,6 +5,7 @@
 #![deny(warnings)]
 
 mod common;
+pub mod confluent;
 pub mod decoding;
 pub mod encoding;
 pub mod gelf;
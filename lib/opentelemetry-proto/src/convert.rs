@@ -5,7 +5,10 @@ use ordered_float::NotNan;
 use std::collections::BTreeMap;
 use vector_core::{
     config::{log_schema, LegacyKey, LogNamespace},
-    event::{Event, LogEvent, TraceEvent},
+    event::{
+        metric::{Bucket, ExponentialBuckets, ExponentialHistogram, MetricTags, Quantile},
+        Event, LogEvent, Metric, MetricKind, MetricValue, TraceEvent,
+    },
 };
 use vrl::value::KeyString;
 use vrl::{
@@ -13,9 +16,14 @@ use vrl::{
     value::{ObjectMap, Value},
 };
 
+use super::encode::RESOURCE_TAG_PREFIX;
 use super::proto::{
     common::v1::{any_value::Value as PBValue, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, SeverityNumber},
+    metrics::v1::{
+        exponential_histogram_data_point, metric::Data, number_data_point, AggregationTemporality,
+        Metric as PBMetric, NumberDataPoint, ResourceMetrics,
+    },
     resource::v1::Resource,
     trace::v1::{
         span::{Event as SpanEvent, Link},
@@ -71,6 +79,24 @@ impl ResourceSpans {
     }
 }
 
+impl ResourceMetrics {
+    pub fn into_event_iter(self) -> impl Iterator<Item = Event> {
+        let resource_tags = self
+            .resource
+            .map(|resource| resource.attributes)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|kv| kv_into_tag(kv, RESOURCE_TAG_PREFIX))
+            .collect::<Vec<_>>();
+        let now = Utc::now();
+
+        self.scope_metrics
+            .into_iter()
+            .flat_map(|scope_metrics| scope_metrics.metrics)
+            .flat_map(move |metric| metric_into_events(metric, &resource_tags, now))
+    }
+}
+
 impl From<PBValue> for Value {
     fn from(av: PBValue) -> Self {
         match av {
@@ -115,6 +141,174 @@ fn kv_list_into_value(arr: Vec<KeyValue>) -> Value {
     )
 }
 
+/// Converts an attribute into a metric tag, as tags can only hold strings.
+fn kv_into_tag(kv: KeyValue, prefix: &str) -> Option<(String, String)> {
+    let value = Value::from(kv.value?.value?);
+    let value = match value {
+        Value::Bytes(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        value => value.to_string_lossy().into_owned(),
+    };
+    Some((format!("{prefix}{}", kv.key), value))
+}
+
+const fn metric_kind(aggregation_temporality: i32) -> MetricKind {
+    if aggregation_temporality == AggregationTemporality::Delta as i32 {
+        MetricKind::Incremental
+    } else {
+        MetricKind::Absolute
+    }
+}
+
+fn number_value(point: &NumberDataPoint) -> Option<f64> {
+    match point.value? {
+        number_data_point::Value::AsDouble(value) => Some(value),
+        number_data_point::Value::AsInt(value) => Some(value as f64),
+    }
+}
+
+fn exponential_buckets(
+    buckets: Option<exponential_histogram_data_point::Buckets>,
+) -> ExponentialBuckets {
+    buckets
+        .map(|buckets| ExponentialBuckets {
+            offset: buckets.offset,
+            counts: buckets.bucket_counts,
+        })
+        .unwrap_or_default()
+}
+
+/// Converts each data point of an OTLP metric into a metric event.
+///
+/// This is the inverse of [`crate::encode::metric_into_proto`]: resource attributes become tags
+/// prefixed with [`RESOURCE_TAG_PREFIX`], and the aggregation temporality determines whether the
+/// metric is incremental or absolute.
+fn metric_into_events(
+    metric: PBMetric,
+    resource_tags: &[(String, String)],
+    now: DateTime<Utc>,
+) -> Vec<Event> {
+    let name = metric.name;
+    let event = |kind, value, attributes: Vec<KeyValue>, time_unix_nano: u64| -> Event {
+        let tags = resource_tags
+            .iter()
+            .cloned()
+            .chain(attributes.into_iter().filter_map(|kv| kv_into_tag(kv, "")))
+            .collect::<MetricTags>();
+        let timestamp = if time_unix_nano > 0 {
+            Utc.timestamp_nanos(time_unix_nano as i64)
+        } else {
+            now
+        };
+        Metric::new(name.clone(), kind, value)
+            .with_tags(tags.as_option())
+            .with_timestamp(Some(timestamp))
+            .into()
+    };
+
+    match metric.data {
+        None => Vec::new(),
+        Some(Data::Gauge(gauge)) => gauge
+            .data_points
+            .into_iter()
+            .filter_map(|point| {
+                let value = number_value(&point)?;
+                Some(event(
+                    MetricKind::Absolute,
+                    MetricValue::Gauge { value },
+                    point.attributes,
+                    point.time_unix_nano,
+                ))
+            })
+            .collect(),
+        Some(Data::Sum(sum)) => {
+            let kind = metric_kind(sum.aggregation_temporality);
+            sum.data_points
+                .into_iter()
+                .filter_map(|point| {
+                    let value = number_value(&point)?;
+                    let value = if sum.is_monotonic {
+                        MetricValue::Counter { value }
+                    } else {
+                        MetricValue::Gauge { value }
+                    };
+                    Some(event(kind, value, point.attributes, point.time_unix_nano))
+                })
+                .collect()
+        }
+        Some(Data::Histogram(histogram)) => {
+            let kind = metric_kind(histogram.aggregation_temporality);
+            histogram
+                .data_points
+                .into_iter()
+                .map(|point| {
+                    // The last bucket counts the values above the last bound, which are only
+                    // included in the total count.
+                    let buckets = point
+                        .explicit_bounds
+                        .iter()
+                        .zip(&point.bucket_counts)
+                        .map(|(upper_limit, count)| Bucket {
+                            upper_limit: *upper_limit,
+                            count: *count,
+                        })
+                        .collect();
+                    let value = MetricValue::AggregatedHistogram {
+                        buckets,
+                        count: point.count,
+                        sum: point.sum.unwrap_or_default(),
+                    };
+                    event(kind, value, point.attributes, point.time_unix_nano)
+                })
+                .collect()
+        }
+        Some(Data::ExponentialHistogram(histogram)) => {
+            let kind = metric_kind(histogram.aggregation_temporality);
+            histogram
+                .data_points
+                .into_iter()
+                .map(|point| {
+                    let value = MetricValue::ExponentialHistogram {
+                        histogram: ExponentialHistogram {
+                            scale: point.scale,
+                            zero_count: point.zero_count,
+                            zero_threshold: point.zero_threshold,
+                            positive: exponential_buckets(point.positive),
+                            negative: exponential_buckets(point.negative),
+                            count: point.count,
+                            sum: point.sum.unwrap_or_default(),
+                        },
+                    };
+                    event(kind, value, point.attributes, point.time_unix_nano)
+                })
+                .collect()
+        }
+        Some(Data::Summary(summary)) => summary
+            .data_points
+            .into_iter()
+            .map(|point| {
+                let value = MetricValue::AggregatedSummary {
+                    quantiles: point
+                        .quantile_values
+                        .into_iter()
+                        .map(|quantile| Quantile {
+                            quantile: quantile.quantile,
+                            value: quantile.value,
+                        })
+                        .collect(),
+                    count: point.count,
+                    sum: point.sum,
+                };
+                event(
+                    MetricKind::Absolute,
+                    value,
+                    point.attributes,
+                    point.time_unix_nano,
+                )
+            })
+            .collect(),
+    }
+}
+
 fn to_hex(d: &[u8]) -> String {
     if d.is_empty() {
         return "".to_string();
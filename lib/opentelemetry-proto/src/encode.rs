@@ -0,0 +1,661 @@
+//! Conversions from Vector events into OTLP messages.
+//!
+//! These are the inverse of the conversions in [`crate::convert`]: an event received by the
+//! `opentelemetry` source and passed unmodified through a pipeline is mapped back to an equivalent
+//! OTLP record.
+
+use chrono::{DateTime, Utc};
+use vector_core::{
+    config::{log_schema, LogNamespace},
+    event::{
+        metric::{self, Bucket, MetricSketch, Quantile},
+        LogEvent, Metric, MetricKind, MetricValue, TraceEvent,
+    },
+};
+use vrl::{
+    event_path, metadata_path,
+    value::{ObjectMap, Value},
+};
+
+use super::{
+    convert::{
+        ATTRIBUTES_KEY, DROPPED_ATTRIBUTES_COUNT_KEY, FLAGS_KEY, OBSERVED_TIMESTAMP_KEY,
+        RESOURCE_KEY, SEVERITY_NUMBER_KEY, SEVERITY_TEXT_KEY, SPAN_ID_KEY, TRACE_ID_KEY,
+    },
+    proto::{
+        collector::{
+            logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+            trace::v1::ExportTraceServiceRequest,
+        },
+        common::v1::{any_value::Value as PBValue, AnyValue, ArrayValue, KeyValue, KeyValueList},
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        metrics::v1::{
+            exponential_histogram_data_point, metric::Data, number_data_point,
+            summary_data_point::ValueAtQuantile, AggregationTemporality, ExponentialHistogram,
+            ExponentialHistogramDataPoint, Gauge, Histogram, HistogramDataPoint,
+            Metric as PBMetric, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum, Summary,
+            SummaryDataPoint,
+        },
+        resource::v1::Resource,
+        trace::v1::{
+            span::{Event as SpanEvent, Link},
+            ResourceSpans, ScopeSpans, Span, Status as SpanStatus,
+        },
+    },
+};
+
+const SOURCE_NAME: &str = "opentelemetry";
+
+/// Prefix of metric tags that are mapped to resource attributes instead of data point attributes.
+pub const RESOURCE_TAG_PREFIX: &str = "resource.";
+
+/// Bucket boundaries used when converting distributions into OTLP histograms.
+pub const DEFAULT_HISTOGRAM_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Quantiles reported when converting sketches into OTLP summaries.
+const SKETCH_QUANTILES: [f64; 5] = [0.5, 0.75, 0.9, 0.95, 0.99];
+
+impl From<Value> for AnyValue {
+    fn from(value: Value) -> Self {
+        let value = match value {
+            Value::Bytes(bytes) => Some(match String::from_utf8(bytes.to_vec()) {
+                Ok(string) => PBValue::StringValue(string),
+                Err(_) => PBValue::BytesValue(bytes.to_vec()),
+            }),
+            Value::Regex(regex) => Some(PBValue::StringValue(regex.as_str().to_owned())),
+            Value::Integer(int) => Some(PBValue::IntValue(int)),
+            Value::Float(float) => Some(PBValue::DoubleValue(float.into_inner())),
+            Value::Boolean(boolean) => Some(PBValue::BoolValue(boolean)),
+            Value::Timestamp(timestamp) => Some(PBValue::StringValue(
+                timestamp.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+            )),
+            Value::Object(object) => Some(PBValue::KvlistValue(KeyValueList {
+                values: object_into_kv_list(object),
+            })),
+            Value::Array(array) => Some(PBValue::ArrayValue(ArrayValue {
+                values: array.into_iter().map(Into::into).collect(),
+            })),
+            Value::Null => None,
+        };
+        AnyValue { value }
+    }
+}
+
+fn object_into_kv_list(object: ObjectMap) -> Vec<KeyValue> {
+    object
+        .into_iter()
+        .map(|(key, value)| KeyValue {
+            key: key.into(),
+            value: Some(value.into()),
+        })
+        .collect()
+}
+
+fn value_into_kv_list(value: Option<Value>) -> Vec<KeyValue> {
+    match value {
+        Some(Value::Object(object)) => object_into_kv_list(object),
+        _ => Vec::new(),
+    }
+}
+
+fn value_into_resource(value: Option<Value>) -> Option<Resource> {
+    let attributes = value_into_kv_list(value);
+    (!attributes.is_empty()).then(|| Resource {
+        attributes,
+        dropped_attributes_count: 0,
+    })
+}
+
+fn value_into_u64(value: Option<&Value>) -> u64 {
+    match value {
+        Some(Value::Integer(int)) => u64::try_from(*int).unwrap_or_default(),
+        Some(Value::Float(float)) => float.into_inner() as u64,
+        _ => 0,
+    }
+}
+
+fn value_into_i64(value: Option<&Value>) -> i64 {
+    match value {
+        Some(Value::Integer(int)) => *int,
+        Some(Value::Float(float)) => float.into_inner() as i64,
+        _ => 0,
+    }
+}
+
+fn value_into_string(value: Option<Value>) -> String {
+    value
+        .map(|value| value.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn value_into_unix_nanos(value: Option<&Value>) -> u64 {
+    match value {
+        Some(Value::Timestamp(timestamp)) => timestamp_into_unix_nanos(*timestamp),
+        Some(Value::Integer(int)) => u64::try_from(*int).unwrap_or_default(),
+        _ => 0,
+    }
+}
+
+fn timestamp_into_unix_nanos(timestamp: DateTime<Utc>) -> u64 {
+    timestamp
+        .timestamp_nanos_opt()
+        .and_then(|nanos| u64::try_from(nanos).ok())
+        .unwrap_or_default()
+}
+
+fn from_hex(value: Option<Value>) -> Vec<u8> {
+    match value {
+        Some(Value::Bytes(bytes)) if !bytes.is_empty() => hex::decode(&bytes).unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+fn take_log_field(log: &mut LogEvent, log_namespace: LogNamespace, key: &str) -> Option<Value> {
+    match log_namespace {
+        LogNamespace::Vector => log.remove(metadata_path!(SOURCE_NAME, key)),
+        LogNamespace::Legacy => log.remove(event_path!(key)),
+    }
+}
+
+/// Converts a log event into an OTLP log record and the resource it belongs to.
+///
+/// With the `Vector` log namespace the event itself becomes the record body and the OTLP fields
+/// are read from the `opentelemetry` source metadata. With the `Legacy` namespace the OTLP fields
+/// are read from the event root, the message becomes the body and any remaining fields are added
+/// to the record attributes.
+pub fn log_into_record(mut log: LogEvent) -> (Option<Resource>, LogRecord) {
+    let log_namespace = log.namespace();
+
+    let resource = value_into_resource(take_log_field(&mut log, log_namespace, RESOURCE_KEY));
+    let mut attributes =
+        value_into_kv_list(take_log_field(&mut log, log_namespace, ATTRIBUTES_KEY));
+    let trace_id = from_hex(take_log_field(&mut log, log_namespace, TRACE_ID_KEY));
+    let span_id = from_hex(take_log_field(&mut log, log_namespace, SPAN_ID_KEY));
+    let severity_text =
+        value_into_string(take_log_field(&mut log, log_namespace, SEVERITY_TEXT_KEY));
+    let severity_number =
+        value_into_i64(take_log_field(&mut log, log_namespace, SEVERITY_NUMBER_KEY).as_ref());
+    let flags = value_into_u64(take_log_field(&mut log, log_namespace, FLAGS_KEY).as_ref());
+    let dropped_attributes_count = value_into_u64(
+        take_log_field(&mut log, log_namespace, DROPPED_ATTRIBUTES_COUNT_KEY).as_ref(),
+    );
+    let observed_time_unix_nano = value_into_unix_nanos(
+        take_log_field(&mut log, log_namespace, OBSERVED_TIMESTAMP_KEY).as_ref(),
+    );
+
+    let (time_unix_nano, body) = match log_namespace {
+        LogNamespace::Vector => {
+            let time_unix_nano = value_into_unix_nanos(
+                log.get(metadata_path!(SOURCE_NAME, "timestamp"))
+                    .or_else(|| log.get(metadata_path!("vector", "ingest_timestamp"))),
+            );
+            let (value, _) = log.into_parts();
+            (time_unix_nano, value)
+        }
+        LogNamespace::Legacy => {
+            let time_unix_nano = value_into_unix_nanos(log.remove_timestamp().as_ref());
+            if let Some(source_type_key) = log_schema().source_type_key_target_path() {
+                log.remove(source_type_key);
+            }
+            match log_schema()
+                .message_key_target_path()
+                .and_then(|path| log.remove(path))
+            {
+                Some(message) => {
+                    let (value, _) = log.into_parts();
+                    attributes.extend(value_into_kv_list(Some(value)));
+                    (time_unix_nano, message)
+                }
+                None => {
+                    let (value, _) = log.into_parts();
+                    (time_unix_nano, value)
+                }
+            }
+        }
+    };
+
+    let record = LogRecord {
+        time_unix_nano,
+        observed_time_unix_nano,
+        severity_number: severity_number as i32,
+        severity_text,
+        body: Some(body.into()),
+        attributes,
+        dropped_attributes_count: dropped_attributes_count as u32,
+        flags: flags as u32,
+        trace_id,
+        span_id,
+    };
+
+    (resource, record)
+}
+
+fn value_into_span_event(value: Value) -> SpanEvent {
+    let mut object = match value {
+        Value::Object(object) => object,
+        _ => ObjectMap::new(),
+    };
+    SpanEvent {
+        time_unix_nano: value_into_unix_nanos(object.get("time_unix_nano")),
+        name: value_into_string(object.remove("name")),
+        attributes: value_into_kv_list(object.remove("attributes")),
+        dropped_attributes_count: value_into_u64(object.get("dropped_attributes_count")) as u32,
+    }
+}
+
+fn value_into_link(value: Value) -> Link {
+    let mut object = match value {
+        Value::Object(object) => object,
+        _ => ObjectMap::new(),
+    };
+    Link {
+        trace_id: from_hex(object.remove("trace_id")),
+        span_id: from_hex(object.remove("span_id")),
+        trace_state: value_into_string(object.remove("trace_state")),
+        attributes: value_into_kv_list(object.remove("attributes")),
+        dropped_attributes_count: value_into_u64(object.get("dropped_attributes_count")) as u32,
+    }
+}
+
+fn value_into_array(value: Option<Value>) -> Vec<Value> {
+    match value {
+        Some(Value::Array(array)) => array,
+        _ => Vec::new(),
+    }
+}
+
+/// Converts a trace event into an OTLP span and the resource it belongs to.
+pub fn trace_into_span(mut trace: TraceEvent) -> (Option<Resource>, Span) {
+    let resource = value_into_resource(trace.remove(event_path!(RESOURCE_KEY)));
+    let status = match trace.remove(event_path!("status")) {
+        Some(Value::Object(mut object)) => Some(SpanStatus {
+            message: value_into_string(object.remove("message")),
+            code: value_into_i64(object.get("code")) as i32,
+        }),
+        _ => None,
+    };
+
+    let span = Span {
+        trace_id: from_hex(trace.remove(event_path!(TRACE_ID_KEY))),
+        span_id: from_hex(trace.remove(event_path!(SPAN_ID_KEY))),
+        trace_state: value_into_string(trace.remove(event_path!("trace_state"))),
+        parent_span_id: from_hex(trace.remove(event_path!("parent_span_id"))),
+        name: value_into_string(trace.remove(event_path!("name"))),
+        kind: value_into_i64(trace.get(event_path!("kind"))) as i32,
+        start_time_unix_nano: value_into_unix_nanos(trace.get(event_path!("start_time_unix_nano"))),
+        end_time_unix_nano: value_into_unix_nanos(trace.get(event_path!("end_time_unix_nano"))),
+        attributes: value_into_kv_list(trace.remove(event_path!(ATTRIBUTES_KEY))),
+        dropped_attributes_count: value_into_u64(
+            trace.get(event_path!(DROPPED_ATTRIBUTES_COUNT_KEY)),
+        ) as u32,
+        events: value_into_array(trace.remove(event_path!("events")))
+            .into_iter()
+            .map(value_into_span_event)
+            .collect(),
+        dropped_events_count: value_into_u64(trace.get(event_path!("dropped_events_count"))) as u32,
+        links: value_into_array(trace.remove(event_path!("links")))
+            .into_iter()
+            .map(value_into_link)
+            .collect(),
+        dropped_links_count: value_into_u64(trace.get(event_path!("dropped_links_count"))) as u32,
+        status,
+    };
+
+    (resource, span)
+}
+
+fn number_data_point(
+    attributes: Vec<KeyValue>,
+    start_time_unix_nano: u64,
+    time_unix_nano: u64,
+    value: f64,
+) -> NumberDataPoint {
+    NumberDataPoint {
+        attributes,
+        start_time_unix_nano,
+        time_unix_nano,
+        exemplars: Vec::new(),
+        flags: 0,
+        value: Some(number_data_point::Value::AsDouble(value)),
+    }
+}
+
+fn temporality(kind: MetricKind) -> i32 {
+    match kind {
+        MetricKind::Incremental => AggregationTemporality::Delta as i32,
+        MetricKind::Absolute => AggregationTemporality::Cumulative as i32,
+    }
+}
+
+fn exponential_histogram_data_point(
+    attributes: Vec<KeyValue>,
+    start_time_unix_nano: u64,
+    time_unix_nano: u64,
+    histogram: metric::ExponentialHistogram,
+) -> ExponentialHistogramDataPoint {
+    let buckets = |buckets: metric::ExponentialBuckets| {
+        Some(exponential_histogram_data_point::Buckets {
+            offset: buckets.offset,
+            bucket_counts: buckets.counts,
+        })
+    };
+    ExponentialHistogramDataPoint {
+        attributes,
+        start_time_unix_nano,
+        time_unix_nano,
+        count: histogram.count,
+        sum: Some(histogram.sum),
+        scale: histogram.scale,
+        zero_count: histogram.zero_count,
+        positive: buckets(histogram.positive),
+        negative: buckets(histogram.negative),
+        flags: 0,
+        exemplars: Vec::new(),
+        min: None,
+        max: None,
+        zero_threshold: histogram.zero_threshold,
+    }
+}
+
+fn histogram_data_point(
+    attributes: Vec<KeyValue>,
+    start_time_unix_nano: u64,
+    time_unix_nano: u64,
+    buckets: &[Bucket],
+    count: u64,
+    sum: f64,
+) -> HistogramDataPoint {
+    let mut explicit_bounds = Vec::with_capacity(buckets.len());
+    let mut bucket_counts = Vec::with_capacity(buckets.len() + 1);
+    let mut bounded_count = 0;
+    for bucket in buckets
+        .iter()
+        .filter(|bucket| bucket.upper_limit.is_finite())
+    {
+        explicit_bounds.push(bucket.upper_limit);
+        bucket_counts.push(bucket.count);
+        bounded_count += bucket.count;
+    }
+    // OTLP expects one more bucket than there are bounds, counting values above the last bound.
+    bucket_counts.push(count.saturating_sub(bounded_count));
+
+    HistogramDataPoint {
+        attributes,
+        start_time_unix_nano,
+        time_unix_nano,
+        count,
+        sum: Some(sum),
+        bucket_counts,
+        explicit_bounds,
+        exemplars: Vec::new(),
+        flags: 0,
+        min: None,
+        max: None,
+    }
+}
+
+fn summary_data_point(
+    attributes: Vec<KeyValue>,
+    start_time_unix_nano: u64,
+    time_unix_nano: u64,
+    quantiles: &[Quantile],
+    count: u64,
+    sum: f64,
+) -> SummaryDataPoint {
+    SummaryDataPoint {
+        attributes,
+        start_time_unix_nano,
+        time_unix_nano,
+        count,
+        sum,
+        quantile_values: quantiles
+            .iter()
+            .map(|quantile| ValueAtQuantile {
+                quantile: quantile.quantile,
+                value: quantile.value,
+            })
+            .collect(),
+        flags: 0,
+    }
+}
+
+/// Converts a metric into an OTLP metric and the resource it belongs to.
+///
+/// Tags prefixed with [`RESOURCE_TAG_PREFIX`] are mapped to resource attributes, all other tags
+/// become data point attributes. Sets are sent as gauges of their cardinality, distributions as
+/// histograms using [`DEFAULT_HISTOGRAM_BUCKETS`], exponential histograms natively, and sketches
+/// as summaries.
+pub fn metric_into_proto(metric: Metric) -> (Option<Resource>, PBMetric) {
+    let (series, data, _) = metric.into_parts();
+
+    let name = match series.name.namespace {
+        Some(namespace) => format!("{}.{}", namespace, series.name.name),
+        None => series.name.name,
+    };
+
+    let mut resource_attributes = Vec::new();
+    let mut attributes = Vec::new();
+    for (key, value) in series
+        .tags
+        .into_iter()
+        .flat_map(|tags| tags.into_iter_single())
+    {
+        let value = Some(AnyValue {
+            value: Some(PBValue::StringValue(value)),
+        });
+        match key.strip_prefix(RESOURCE_TAG_PREFIX) {
+            Some(key) => resource_attributes.push(KeyValue {
+                key: key.to_string(),
+                value,
+            }),
+            None => attributes.push(KeyValue { key, value }),
+        }
+    }
+    let resource = (!resource_attributes.is_empty()).then(|| Resource {
+        attributes: resource_attributes,
+        dropped_attributes_count: 0,
+    });
+
+    let timestamp = data.time.timestamp.unwrap_or_else(Utc::now);
+    let time_unix_nano = timestamp_into_unix_nanos(timestamp);
+    let start_time_unix_nano = data
+        .time
+        .interval_ms
+        .map(|interval| time_unix_nano.saturating_sub(u64::from(interval.get()) * 1_000_000))
+        .unwrap_or_default();
+    let kind = data.kind;
+
+    let data = match data.value {
+        MetricValue::Counter { value } => Data::Sum(Sum {
+            data_points: vec![number_data_point(
+                attributes,
+                start_time_unix_nano,
+                time_unix_nano,
+                value,
+            )],
+            aggregation_temporality: temporality(kind),
+            is_monotonic: true,
+        }),
+        MetricValue::Gauge { value } => Data::Gauge(Gauge {
+            data_points: vec![number_data_point(
+                attributes,
+                start_time_unix_nano,
+                time_unix_nano,
+                value,
+            )],
+        }),
+        MetricValue::Set { values } => Data::Gauge(Gauge {
+            data_points: vec![number_data_point(
+                attributes,
+                start_time_unix_nano,
+                time_unix_nano,
+                values.len() as f64,
+            )],
+        }),
+        value @ MetricValue::Distribution { .. } => {
+            let Some(MetricValue::AggregatedHistogram {
+                buckets,
+                count,
+                sum,
+            }) = value.distribution_to_agg_histogram(&DEFAULT_HISTOGRAM_BUCKETS)
+            else {
+                unreachable!("distributions always convert to histograms")
+            };
+            Data::Histogram(Histogram {
+                data_points: vec![histogram_data_point(
+                    attributes,
+                    start_time_unix_nano,
+                    time_unix_nano,
+                    &buckets,
+                    count,
+                    sum,
+                )],
+                aggregation_temporality: temporality(kind),
+            })
+        }
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => Data::Histogram(Histogram {
+            data_points: vec![histogram_data_point(
+                attributes,
+                start_time_unix_nano,
+                time_unix_nano,
+                &buckets,
+                count,
+                sum,
+            )],
+            aggregation_temporality: temporality(kind),
+        }),
+        MetricValue::ExponentialHistogram { histogram } => {
+            Data::ExponentialHistogram(ExponentialHistogram {
+                data_points: vec![exponential_histogram_data_point(
+                    attributes,
+                    start_time_unix_nano,
+                    time_unix_nano,
+                    histogram,
+                )],
+                aggregation_temporality: temporality(kind),
+            })
+        }
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => Data::Summary(Summary {
+            data_points: vec![summary_data_point(
+                attributes,
+                start_time_unix_nano,
+                time_unix_nano,
+                &quantiles,
+                count,
+                sum,
+            )],
+        }),
+        MetricValue::Sketch {
+            sketch: MetricSketch::AgentDDSketch(sketch),
+        } => {
+            let quantiles = SKETCH_QUANTILES
+                .iter()
+                .filter_map(|&quantile| {
+                    sketch
+                        .quantile(quantile)
+                        .map(|value| Quantile { quantile, value })
+                })
+                .collect::<Vec<_>>();
+            Data::Summary(Summary {
+                data_points: vec![summary_data_point(
+                    attributes,
+                    start_time_unix_nano,
+                    time_unix_nano,
+                    &quantiles,
+                    u64::from(sketch.count()),
+                    sketch.sum().unwrap_or_default(),
+                )],
+            })
+        }
+    };
+
+    let metric = PBMetric {
+        name,
+        description: String::new(),
+        unit: String::new(),
+        data: Some(data),
+    };
+
+    (resource, metric)
+}
+
+/// Groups items by resource, preserving the order in which resources are first seen.
+fn group_by_resource<T>(
+    items: impl IntoIterator<Item = (Option<Resource>, T)>,
+) -> Vec<(Option<Resource>, Vec<T>)> {
+    let mut groups: Vec<(Option<Resource>, Vec<T>)> = Vec::new();
+    for (resource, item) in items {
+        match groups
+            .iter_mut()
+            .find(|(existing, _)| *existing == resource)
+        {
+            Some((_, group)) => group.push(item),
+            None => groups.push((resource, vec![item])),
+        }
+    }
+    groups
+}
+
+impl FromIterator<(Option<Resource>, LogRecord)> for ExportLogsServiceRequest {
+    fn from_iter<I: IntoIterator<Item = (Option<Resource>, LogRecord)>>(iter: I) -> Self {
+        let resource_logs = group_by_resource(iter)
+            .into_iter()
+            .map(|(resource, log_records)| ResourceLogs {
+                resource,
+                scope_logs: vec![ScopeLogs {
+                    scope: None,
+                    log_records,
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            })
+            .collect();
+        Self { resource_logs }
+    }
+}
+
+impl FromIterator<(Option<Resource>, PBMetric)> for ExportMetricsServiceRequest {
+    fn from_iter<I: IntoIterator<Item = (Option<Resource>, PBMetric)>>(iter: I) -> Self {
+        let resource_metrics = group_by_resource(iter)
+            .into_iter()
+            .map(|(resource, metrics)| ResourceMetrics {
+                resource,
+                scope_metrics: vec![ScopeMetrics {
+                    scope: None,
+                    metrics,
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            })
+            .collect();
+        Self { resource_metrics }
+    }
+}
+
+impl FromIterator<(Option<Resource>, Span)> for ExportTraceServiceRequest {
+    fn from_iter<I: IntoIterator<Item = (Option<Resource>, Span)>>(iter: I) -> Self {
+        let resource_spans = group_by_resource(iter)
+            .into_iter()
+            .map(|(resource, spans)| ResourceSpans {
+                resource,
+                scope_spans: vec![ScopeSpans {
+                    scope: None,
+                    spans,
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            })
+            .collect();
+        Self { resource_spans }
+    }
+}
@@ -12,6 +12,10 @@ use line::{Line, Metric, MetricKind};
 
 pub const METRIC_NAME_LABEL: &str = "__name__";
 
+/// The range of the exponential bucket schemas of native histograms. Other schemas, such as the
+/// one used for custom bucket boundaries, are not supported.
+pub const NATIVE_HISTOGRAM_SCHEMAS: std::ops::RangeInclusive<i32> = -4..=8;
+
 #[allow(warnings)] // Ignore some clippy warnings
 pub mod proto {
     include!(concat!(env!("OUT_DIR"), "/prometheus.rs"));
@@ -60,6 +64,11 @@ pub enum ParserError {
     MultipleMetricKinds { name: String },
     #[snafu(display("request is missing metric name label"))]
     RequestNoNameLabel,
+
+    #[snafu(display("unsupported native histogram schema: {}", schema))]
+    UnsupportedHistogramSchema { schema: i32 },
+    #[snafu(display("native histogram bucket spans don't match the number of buckets"))]
+    HistogramSpanMismatch,
 }
 
 vector_common::impl_event_data_eq!(ParserError);
@@ -96,6 +105,119 @@ pub struct HistogramMetric {
     pub count: u64,
 }
 
+/// The buckets of one sign of a native histogram.
+///
+/// The bucket at index `i` counts the values whose magnitude is within `(base^(i-1), base^i]`,
+/// where `base` is `2^(2^-schema)`.
+#[derive(Debug, Default, PartialEq)]
+pub struct NativeHistogramBuckets {
+    pub offset: i32,
+    pub counts: Vec<u64>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct NativeHistogramMetric {
+    pub schema: i32,
+    pub zero_threshold: f64,
+    pub zero_count: u64,
+    pub positive: NativeHistogramBuckets,
+    pub negative: NativeHistogramBuckets,
+    pub sum: f64,
+    pub count: u64,
+}
+
+impl TryFrom<&proto::Histogram> for NativeHistogramMetric {
+    type Error = ParserError;
+
+    fn try_from(histogram: &proto::Histogram) -> Result<Self, Self::Error> {
+        use proto::histogram::{Count, ZeroCount};
+
+        if !NATIVE_HISTOGRAM_SCHEMAS.contains(&histogram.schema) {
+            return Err(ParserError::UnsupportedHistogramSchema {
+                schema: histogram.schema,
+            });
+        }
+        let count = match histogram.count {
+            Some(Count::CountInt(count)) => count,
+            Some(Count::CountFloat(count)) => try_f64_to_u64(count)?,
+            None => 0,
+        };
+        let zero_count = match histogram.zero_count {
+            Some(ZeroCount::ZeroCountInt(count)) => count,
+            Some(ZeroCount::ZeroCountFloat(count)) => try_f64_to_u64(count)?,
+            None => 0,
+        };
+        Ok(Self {
+            schema: histogram.schema,
+            zero_threshold: histogram.zero_threshold,
+            zero_count,
+            positive: native_histogram_buckets(
+                &histogram.positive_spans,
+                &histogram.positive_deltas,
+                &histogram.positive_counts,
+            )?,
+            negative: native_histogram_buckets(
+                &histogram.negative_spans,
+                &histogram.negative_deltas,
+                &histogram.negative_counts,
+            )?,
+            sum: histogram.sum,
+            count,
+        })
+    }
+}
+
+/// Expands the sparse buckets of a native histogram, given either as deltas for integer
+/// histograms or as absolute counts for float histograms, into a dense range of buckets.
+fn native_histogram_buckets(
+    spans: &[proto::BucketSpan],
+    deltas: &[i64],
+    counts: &[f64],
+) -> Result<NativeHistogramBuckets, ParserError> {
+    let counts = if counts.is_empty() {
+        let mut count = 0_i64;
+        deltas
+            .iter()
+            .map(|delta| {
+                count = count.saturating_add(*delta);
+                u64::try_from(count).map_err(|_| ParserError::ValueOutOfRange {
+                    value: count as f64,
+                    max: u64::MAX,
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        counts
+            .iter()
+            .map(|count| try_f64_to_u64(*count))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    let mut buckets = NativeHistogramBuckets::default();
+    let mut counts = counts.into_iter();
+    // The offset of the first span is the index of its first bucket, while the offsets of the
+    // following spans are the gaps after the previous span.
+    let mut index = 0_i32;
+    for span in spans {
+        index += span.offset;
+        for _ in 0..span.length {
+            let count = counts.next().ok_or(ParserError::HistogramSpanMismatch)?;
+            if buckets.counts.is_empty() {
+                buckets.offset = index;
+            }
+            buckets
+                .counts
+                .resize(index.abs_diff(buckets.offset) as usize, 0);
+            buckets.counts.push(count);
+            index += 1;
+        }
+    }
+    if counts.next().is_some() {
+        return Err(ParserError::HistogramSpanMismatch);
+    }
+    Ok(buckets)
+}
+
 #[derive(Debug, Default, PartialEq)]
 pub struct SimpleMetric {
     pub value: f64,
@@ -107,6 +229,7 @@ type MetricMap<T> = IndexMap<GroupKey, T>;
 pub enum GroupKind {
     Summary(MetricMap<SummaryMetric>),
     Histogram(MetricMap<HistogramMetric>),
+    NativeHistogram(MetricMap<NativeHistogramMetric>),
     Gauge(MetricMap<SimpleMetric>),
     Counter(MetricMap<SimpleMetric>),
     Untyped(MetricMap<SimpleMetric>),
@@ -133,7 +256,7 @@ impl GroupKind {
         match self {
             Self::Counter { .. } => kind == MetricKind::Counter,
             Self::Gauge { .. } => kind == MetricKind::Gauge,
-            Self::Histogram { .. } => kind == MetricKind::Histogram,
+            Self::Histogram { .. } | Self::NativeHistogram { .. } => kind == MetricKind::Histogram,
             Self::Summary { .. } => kind == MetricKind::Summary,
             Self::Untyped { .. } => true,
         }
@@ -196,6 +319,15 @@ impl GroupKind {
                     }))
                 }
             },
+            // Native histograms are never sent as individual samples.
+            Self::NativeHistogram(_) => {
+                return Ok(Some(Metric {
+                    name: metric.name,
+                    timestamp: key.timestamp,
+                    labels: key.labels,
+                    value,
+                }))
+            }
             Self::Summary(ref mut metrics) => match suffix {
                 "" => {
                     let quantile = key
@@ -376,6 +508,41 @@ impl MetricGroupSet {
         Ok(())
     }
 
+    fn insert_histogram(
+        &mut self,
+        name: &str,
+        labels: &BTreeMap<String, String>,
+        histogram: proto::Histogram,
+    ) -> Result<(), ParserError> {
+        let metric = NativeHistogramMetric::try_from(&histogram)?;
+        let group = self
+            .0
+            .entry(name.into())
+            .or_insert_with(|| GroupKind::NativeHistogram(IndexMap::default()));
+        // Histogram metadata creates a classic histogram group, which is replaced as long as no
+        // classic samples were added to it.
+        match group {
+            GroupKind::Histogram(metrics) if metrics.is_empty() => {
+                *group = GroupKind::NativeHistogram(IndexMap::default());
+            }
+            GroupKind::Untyped(metrics) if metrics.is_empty() => {
+                *group = GroupKind::NativeHistogram(IndexMap::default());
+            }
+            _ => (),
+        }
+        match group {
+            GroupKind::NativeHistogram(metrics) => {
+                let key = GroupKey {
+                    timestamp: Some(histogram.timestamp),
+                    labels: labels.clone(),
+                };
+                metrics.insert(key, metric);
+                Ok(())
+            }
+            _ => Err(ParserError::MultipleMetricKinds { name: name.into() }),
+        }
+    }
+
     fn finish(self) -> Vec<MetricGroup> {
         self.0
             .into_iter()
@@ -411,6 +578,10 @@ pub fn parse_request(request: proto::WriteRequest) -> Result<Vec<MetricGroup>, P
         for sample in timeseries.samples {
             groups.insert_sample(&name, &labels, sample)?;
         }
+
+        for histogram in timeseries.histograms {
+            groups.insert_histogram(&name, &labels, histogram)?;
+        }
     }
 
     Ok(groups.finish())
@@ -736,6 +907,7 @@ mod test {
                     samples: vec![
                         $( proto::Sample { value: $sample as f64, timestamp: $timestamp as i64 }, )*
                     ],
+                    histograms: vec![],
                 }, )* ],
             }
         };
@@ -886,4 +1058,93 @@ mod test {
             assert_eq!(metrics.get_index(0).unwrap(), simple_metric!(Some(1395066367700), labels!(), 24.0));
         });
     }
+
+    fn native_histogram(schema: i32) -> proto::Histogram {
+        proto::Histogram {
+            count: Some(proto::histogram::Count::CountInt(11)),
+            sum: 42.5,
+            schema,
+            zero_threshold: 0.001,
+            zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(2)),
+            negative_spans: vec![proto::BucketSpan {
+                offset: 0,
+                length: 1,
+            }],
+            negative_deltas: vec![3],
+            positive_spans: vec![
+                proto::BucketSpan {
+                    offset: -1,
+                    length: 2,
+                },
+                proto::BucketSpan {
+                    offset: 1,
+                    length: 1,
+                },
+            ],
+            positive_deltas: vec![1, 2, -1],
+            timestamp: 1395066367700,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse_request_native_histogram() {
+        let mut request = write_request!(["one" = Histogram], []);
+        request.timeseries.push(proto::TimeSeries {
+            labels: vec![proto::Label {
+                name: METRIC_NAME_LABEL.into(),
+                value: "one".into(),
+            }],
+            samples: vec![],
+            histograms: vec![native_histogram(3)],
+        });
+        let parsed = parse_request(request).unwrap();
+
+        assert_eq!(parsed.len(), 1);
+        match_group!(parsed[0], "one", NativeHistogram => |metrics: &MetricMap<NativeHistogramMetric>| {
+            assert_eq!(metrics.len(), 1);
+            assert_eq!(
+                metrics.get_index(0).unwrap(), (
+                    &GroupKey {
+                        timestamp: Some(1395066367700),
+                        labels: labels!(),
+                    },
+                    &NativeHistogramMetric {
+                        schema: 3,
+                        zero_threshold: 0.001,
+                        zero_count: 2,
+                        positive: NativeHistogramBuckets { offset: -1, counts: vec![1, 3, 0, 2] },
+                        negative: NativeHistogramBuckets { offset: 0, counts: vec![3] },
+                        sum: 42.5,
+                        count: 11,
+                    })
+            );
+        });
+    }
+
+    #[test]
+    fn parse_request_native_histogram_errors() {
+        let request = |histogram: proto::Histogram| {
+            let mut request = write_request!([], [ [__name__ => "one"] => [ 1 @ 1395066367700 ] ]);
+            request.timeseries[0].histograms.push(histogram);
+            request
+        };
+
+        assert_eq!(
+            parse_request(request(native_histogram(-53))).unwrap_err(),
+            ParserError::UnsupportedHistogramSchema { schema: -53 }
+        );
+
+        let mut histogram = native_histogram(0);
+        histogram.positive_deltas.pop();
+        assert_eq!(
+            parse_request(request(histogram)).unwrap_err(),
+            ParserError::HistogramSpanMismatch
+        );
+
+        assert_eq!(
+            parse_request(request(native_histogram(0))).unwrap_err(),
+            ParserError::MultipleMetricKinds { name: "one".into() }
+        );
+    }
 }
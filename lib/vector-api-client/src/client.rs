@@ -12,12 +12,20 @@ pub type QueryResult<T> =
 #[derive(Debug)]
 pub struct Client {
     url: Url,
+    token: Option<String>,
 }
 
 impl Client {
     /// Returns a new GraphQL query client, bound to the provided URL.
     pub fn new(url: Url) -> Self {
-        Self { url }
+        Self { url, token: None }
+    }
+
+    /// Sets the API token sent with each query, which must match the one the API server is
+    /// configured with for mutations to be allowed.
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
     }
 
     /// Send a health query
@@ -33,9 +41,12 @@ impl Client {
     ) -> QueryResult<T> {
         let client = reqwest::Client::new();
 
-        client
-            .post(self.url.clone())
-            .json(request_body)
+        let mut request = client.post(self.url.clone()).json(request_body);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        request
             .send()
             .await
             .with_context(|| {
@@ -0,0 +1,109 @@
+//! Mutations for changing the state of a running Vector instance.
+
+use graphql_client::GraphQLQuery;
+
+/// ReloadConfigMutation reloads the configuration of the queried Vector instance from disk.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/mutations/reload_config.graphql",
+    response_derives = "Debug"
+)]
+pub struct ReloadConfigMutation;
+
+/// PauseSourceMutation pauses a source of the queried Vector instance.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/mutations/pause_source.graphql",
+    response_derives = "Debug"
+)]
+pub struct PauseSourceMutation;
+
+/// ResumeSourceMutation resumes a paused source of the queried Vector instance.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/mutations/resume_source.graphql",
+    response_derives = "Debug"
+)]
+pub struct ResumeSourceMutation;
+
+/// SetLogFilterMutation replaces the filter applied to the internal logs of the queried Vector
+/// instance.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/mutations/set_log_filter.graphql",
+    response_derives = "Debug"
+)]
+pub struct SetLogFilterMutation;
+
+/// DrainSinkMutation drains a sink of the queried Vector instance.
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/mutations/drain_sink.graphql",
+    response_derives = "Debug"
+)]
+pub struct DrainSinkMutation;
+
+/// Extension methods for control mutations.
+pub trait ControlMutationExt {
+    /// Executes a reload config mutation.
+    async fn reload_config(&self) -> crate::QueryResult<ReloadConfigMutation>;
+
+    /// Executes a pause source mutation.
+    async fn pause_source(&self, id: String) -> crate::QueryResult<PauseSourceMutation>;
+
+    /// Executes a resume source mutation.
+    async fn resume_source(&self, id: String) -> crate::QueryResult<ResumeSourceMutation>;
+
+    /// Executes a set log filter mutation.
+    async fn set_log_filter(&self, filter: String) -> crate::QueryResult<SetLogFilterMutation>;
+
+    /// Executes a drain sink mutation.
+    async fn drain_sink(&self, id: String) -> crate::QueryResult<DrainSinkMutation>;
+}
+
+impl ControlMutationExt for crate::Client {
+    /// Executes a reload config mutation.
+    async fn reload_config(&self) -> crate::QueryResult<ReloadConfigMutation> {
+        self.query::<ReloadConfigMutation>(&ReloadConfigMutation::build_query(
+            reload_config_mutation::Variables,
+        ))
+        .await
+    }
+
+    /// Executes a pause source mutation.
+    async fn pause_source(&self, id: String) -> crate::QueryResult<PauseSourceMutation> {
+        self.query::<PauseSourceMutation>(&PauseSourceMutation::build_query(
+            pause_source_mutation::Variables { id },
+        ))
+        .await
+    }
+
+    /// Executes a resume source mutation.
+    async fn resume_source(&self, id: String) -> crate::QueryResult<ResumeSourceMutation> {
+        self.query::<ResumeSourceMutation>(&ResumeSourceMutation::build_query(
+            resume_source_mutation::Variables { id },
+        ))
+        .await
+    }
+
+    /// Executes a set log filter mutation.
+    async fn set_log_filter(&self, filter: String) -> crate::QueryResult<SetLogFilterMutation> {
+        self.query::<SetLogFilterMutation>(&SetLogFilterMutation::build_query(
+            set_log_filter_mutation::Variables { filter },
+        ))
+        .await
+    }
+
+    /// Executes a drain sink mutation.
+    async fn drain_sink(&self, id: String) -> crate::QueryResult<DrainSinkMutation> {
+        self.query::<DrainSinkMutation>(&DrainSinkMutation::build_query(
+            drain_sink_mutation::Variables { id },
+        ))
+        .await
+    }
+}
@@ -3,7 +3,14 @@
 //! as it flows through transforms, being duplicated and merged, and
 //! then report its status when the last copy is delivered or dropped.
 
-use std::{cmp, future::Future, mem, pin::Pin, sync::Arc, task::Poll};
+use std::{
+    cmp,
+    future::Future,
+    mem,
+    pin::Pin,
+    sync::{Arc, OnceLock},
+    task::Poll,
+};
 
 use crossbeam_utils::atomic::AtomicCell;
 use futures::future::FutureExt;
@@ -86,6 +93,21 @@ impl EventFinalizers {
         }
     }
 
+    /// Updates the status of all event finalizers in the collection, recording why the events
+    /// could not be delivered.
+    ///
+    /// Only the first reason recorded for a batch is kept.
+    pub fn update_status_with_reason(&self, status: EventStatus, reason: &str) {
+        let reason: Arc<str> = reason.into();
+        let failed = matches!(status, EventStatus::Errored | EventStatus::Rejected);
+        for finalizer in &self.0 {
+            finalizer.update_status(status);
+            if failed {
+                finalizer.batch.set_reason(&reason);
+            }
+        }
+    }
+
     /// Consumes all event finalizers and updates their underlying batches immediately.
     pub fn update_sources(&mut self) {
         let finalizers = mem::take(&mut self.0);
@@ -160,19 +182,12 @@ impl Drop for EventFinalizer {
 /// A convenience newtype wrapper for the one-shot receiver for an
 /// individual batch status.
 #[pin_project::pin_project]
-pub struct BatchStatusReceiver(oneshot::Receiver<BatchStatus>);
+pub struct BatchStatusReceiver(oneshot::Receiver<(BatchStatus, Option<Arc<str>>)>);
 
 impl Future for BatchStatusReceiver {
     type Output = BatchStatus;
-    fn poll(mut self: Pin<&mut Self>, ctx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-        match self.0.poll_unpin(ctx) {
-            Poll::Pending => Poll::Pending,
-            Poll::Ready(Ok(status)) => Poll::Ready(status),
-            Poll::Ready(Err(error)) => {
-                error!(%error, "Batch status receiver dropped before sending.");
-                Poll::Ready(BatchStatus::Errored)
-            }
-        }
+    fn poll(self: Pin<&mut Self>, ctx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+        self.poll_with_reason(ctx).map(|(status, _)| status)
     }
 }
 
@@ -184,7 +199,27 @@ impl BatchStatusReceiver {
     /// - `TryRecvError::Empty` if no value has been sent yet.
     /// - `TryRecvError::Closed` if the sender has dropped without sending a value.
     pub fn try_recv(&mut self) -> Result<BatchStatus, oneshot::error::TryRecvError> {
-        self.0.try_recv()
+        self.0.try_recv().map(|(status, _)| status)
+    }
+
+    /// Waits for the status of the batch, along with the reason recorded when its events could
+    /// not be delivered, if any.
+    pub async fn with_reason(mut self) -> (BatchStatus, Option<Arc<str>>) {
+        std::future::poll_fn(|ctx| Pin::new(&mut self).poll_with_reason(ctx)).await
+    }
+
+    fn poll_with_reason(
+        mut self: Pin<&mut Self>,
+        ctx: &mut std::task::Context<'_>,
+    ) -> Poll<(BatchStatus, Option<Arc<str>>)> {
+        match self.0.poll_unpin(ctx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Ok(result)) => Poll::Ready(result),
+            Poll::Ready(Err(error)) => {
+                error!(%error, "Batch status receiver dropped before sending.");
+                Poll::Ready((BatchStatus::Errored, None))
+            }
+        }
     }
 }
 
@@ -201,6 +236,7 @@ impl BatchNotifier {
         let (sender, receiver) = oneshot::channel();
         let notifier = OwnedBatchNotifier {
             status: AtomicCell::new(BatchStatus::Delivered),
+            reason: OnceLock::new(),
             notifier: Some(sender),
         };
         (Self(Arc::new(notifier)), BatchStatusReceiver(receiver))
@@ -250,13 +286,20 @@ impl BatchNotifier {
                 .unwrap_or_else(|_| unreachable!());
         }
     }
+
+    /// Records why the events of the batch could not be delivered, unless a reason was already
+    /// recorded.
+    fn set_reason(&self, reason: &Arc<str>) {
+        _ = self.0.reason.set(Arc::clone(reason));
+    }
 }
 
 /// The non-shared data underlying the shared `BatchNotifier`
 #[derive(Debug)]
 pub struct OwnedBatchNotifier {
     status: AtomicCell<BatchStatus>,
-    notifier: Option<oneshot::Sender<BatchStatus>>,
+    reason: OnceLock<Arc<str>>,
+    notifier: Option<oneshot::Sender<(BatchStatus, Option<Arc<str>>)>>,
 }
 
 impl OwnedBatchNotifier {
@@ -264,9 +307,14 @@ impl OwnedBatchNotifier {
     fn send_status(&mut self) {
         if let Some(notifier) = self.notifier.take() {
             let status = self.status.load();
+            // The reason is only relevant when the events weren't delivered.
+            let reason = match status {
+                BatchStatus::Delivered => None,
+                BatchStatus::Errored | BatchStatus::Rejected => self.reason.take(),
+            };
             // Ignore the error case, as it will happen during normal
             // source shutdown and we can't detect that here.
-            _ = notifier.send(status);
+            _ = notifier.send((status, reason));
         }
     }
 }
@@ -418,6 +466,25 @@ mod tests {
         assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
     }
 
+    #[tokio::test]
+    async fn sends_first_reason() {
+        let (fin, receiver) = make_finalizer();
+        fin.update_status_with_reason(EventStatus::Rejected, "first");
+        fin.update_status_with_reason(EventStatus::Rejected, "second");
+        drop(fin);
+        let (status, reason) = receiver.with_reason().await;
+        assert_eq!(status, BatchStatus::Rejected);
+        assert_eq!(reason.as_deref(), Some("first"));
+    }
+
+    #[tokio::test]
+    async fn ignores_reason_when_delivered() {
+        let (fin, receiver) = make_finalizer();
+        fin.update_status_with_reason(EventStatus::Delivered, "unused");
+        drop(fin);
+        assert_eq!(receiver.with_reason().await, (BatchStatus::Delivered, None));
+    }
+
     #[test]
     fn early_update() {
         let (mut fin, mut receiver) = make_finalizer();
@@ -4,7 +4,9 @@ use mlua::prelude::*;
 
 use super::super::{
     metric::TagValue,
-    metric::{self, MetricSketch, MetricTags, TagValueSet},
+    metric::{
+        self, ExponentialBuckets, ExponentialHistogram, MetricSketch, MetricTags, TagValueSet,
+    },
     Metric, MetricKind, MetricValue, StatisticKind,
 };
 use super::util::{table_to_timestamp, timestamp_to_table};
@@ -202,6 +204,17 @@ impl<'a> IntoLua<'a> for LuaMetric {
                 aggregated_summary.raw_set("sum", sum)?;
                 tbl.raw_set("aggregated_summary", aggregated_summary)?;
             }
+            MetricValue::ExponentialHistogram { histogram } => {
+                let exponential_histogram = lua.create_table()?;
+                exponential_histogram.raw_set("scale", histogram.scale)?;
+                exponential_histogram.raw_set("zero_count", histogram.zero_count)?;
+                exponential_histogram.raw_set("zero_threshold", histogram.zero_threshold)?;
+                exponential_histogram.raw_set("positive", histogram.positive)?;
+                exponential_histogram.raw_set("negative", histogram.negative)?;
+                exponential_histogram.raw_set("count", histogram.count)?;
+                exponential_histogram.raw_set("sum", histogram.sum)?;
+                tbl.raw_set("exponential_histogram", exponential_histogram)?;
+            }
             MetricValue::Sketch { sketch } => {
                 let sketch_tbl = match sketch {
                     MetricSketch::AgentDDSketch(ddsketch) => {
@@ -228,6 +241,31 @@ impl<'a> IntoLua<'a> for LuaMetric {
     }
 }
 
+impl<'a> IntoLua<'a> for ExponentialBuckets {
+    fn into_lua(self, lua: &'a Lua) -> LuaResult<LuaValue> {
+        let buckets = lua.create_table()?;
+        buckets.raw_set("offset", self.offset)?;
+        buckets.raw_set("counts", self.counts)?;
+        Ok(LuaValue::Table(buckets))
+    }
+}
+
+impl<'a> FromLua<'a> for ExponentialBuckets {
+    fn from_lua(value: LuaValue<'a>, _: &'a Lua) -> LuaResult<Self> {
+        match value {
+            LuaValue::Table(table) => Ok(Self {
+                offset: table.raw_get("offset")?,
+                counts: table.raw_get("counts")?,
+            }),
+            _ => Err(LuaError::FromLuaConversionError {
+                from: value.type_name(),
+                to: "ExponentialBuckets",
+                message: Some("Exponential histogram buckets should be a Lua table".to_string()),
+            }),
+        }
+    }
+}
+
 impl<'a> FromLua<'a> for Metric {
     #[allow(clippy::too_many_lines)]
     fn from_lua(value: LuaValue<'a>, _: &'a Lua) -> LuaResult<Self> {
@@ -294,6 +332,28 @@ impl<'a> FromLua<'a> for Metric {
                 count: aggregated_summary.raw_get("count")?,
                 sum: aggregated_summary.raw_get("sum")?,
             }
+        } else if let Some(exponential_histogram) =
+            table.raw_get::<_, Option<LuaTable>>("exponential_histogram")?
+        {
+            MetricValue::ExponentialHistogram {
+                histogram: ExponentialHistogram {
+                    scale: exponential_histogram.raw_get("scale")?,
+                    zero_count: exponential_histogram
+                        .raw_get::<_, Option<u64>>("zero_count")?
+                        .unwrap_or_default(),
+                    zero_threshold: exponential_histogram
+                        .raw_get::<_, Option<f64>>("zero_threshold")?
+                        .unwrap_or_default(),
+                    positive: exponential_histogram
+                        .raw_get::<_, Option<ExponentialBuckets>>("positive")?
+                        .unwrap_or_default(),
+                    negative: exponential_histogram
+                        .raw_get::<_, Option<ExponentialBuckets>>("negative")?
+                        .unwrap_or_default(),
+                    count: exponential_histogram.raw_get("count")?,
+                    sum: exponential_histogram.raw_get("sum")?,
+                },
+            }
         } else if let Some(sketch) = table.raw_get::<_, Option<LuaTable>>("sketch")? {
             let sketch_type: String = sketch.raw_get("type")?;
             match sketch_type.as_str() {
@@ -330,7 +390,7 @@ impl<'a> FromLua<'a> for Metric {
             return Err(LuaError::FromLuaConversionError {
                 from: value.type_name(),
                 to: "Metric",
-                message: Some("Cannot find metric value, expected presence one of \"counter\", \"gauge\", \"set\", \"distribution\", \"aggregated_histogram\", \"aggregated_summary\"".to_string()),
+                message: Some("Cannot find metric value, expected presence one of \"counter\", \"gauge\", \"set\", \"distribution\", \"aggregated_histogram\", \"aggregated_summary\", \"exponential_histogram\"".to_string()),
             });
         };
 
@@ -600,6 +660,45 @@ mod test {
         );
     }
 
+    #[test]
+    fn into_lua_exponential_histogram() {
+        let metric = Metric::new(
+            "example exponential histogram",
+            MetricKind::Incremental,
+            MetricValue::ExponentialHistogram {
+                histogram: ExponentialHistogram {
+                    scale: 2,
+                    zero_count: 3,
+                    zero_threshold: 0.5,
+                    positive: ExponentialBuckets {
+                        offset: -1,
+                        counts: vec![4, 5],
+                    },
+                    negative: ExponentialBuckets::default(),
+                    count: 12,
+                    sum: 17.5,
+                },
+            },
+        );
+
+        assert_metric(
+            metric,
+            false,
+            vec![
+                "type(metric.exponential_histogram) == 'table'",
+                "metric.exponential_histogram.scale == 2",
+                "metric.exponential_histogram.zero_count == 3",
+                "metric.exponential_histogram.zero_threshold == 0.5",
+                "metric.exponential_histogram.positive.offset == -1",
+                "#metric.exponential_histogram.positive.counts == 2",
+                "metric.exponential_histogram.positive.counts[2] == 5",
+                "#metric.exponential_histogram.negative.counts == 0",
+                "metric.exponential_histogram.count == 12",
+                "metric.exponential_histogram.sum == 17.5",
+            ],
+        );
+    }
+
     #[test]
     fn from_lua_counter_minimal() {
         let value = r#"{
@@ -775,6 +874,36 @@ mod test {
         assert_event_data_eq!(Lua::new().load(value).eval::<Metric>().unwrap(), expected);
     }
 
+    #[test]
+    fn from_lua_exponential_histogram() {
+        let value = r#"{
+            name = "example exponential histogram",
+            exponential_histogram = {
+                scale = 2,
+                positive = { offset = -1, counts = { 4, 5 } },
+                count = 9,
+                sum = 17.5
+            }
+        }"#;
+        let expected = Metric::new(
+            "example exponential histogram",
+            MetricKind::Absolute,
+            MetricValue::ExponentialHistogram {
+                histogram: ExponentialHistogram {
+                    scale: 2,
+                    positive: ExponentialBuckets {
+                        offset: -1,
+                        counts: vec![4, 5],
+                    },
+                    count: 9,
+                    sum: 17.5,
+                    ..Default::default()
+                },
+            },
+        );
+        assert_event_data_eq!(Lua::new().load(value).eval::<Metric>().unwrap(), expected);
+    }
+
     #[test]
     fn from_lua_aggregated_summary() {
         let value = r#"{
@@ -1,12 +1,12 @@
-use proptest::collection::{btree_set, hash_map, hash_set};
+use proptest::collection::{btree_set, hash_map, hash_set, vec as arb_vec};
 use proptest::option;
 use proptest::prelude::*;
 
 use crate::metrics::AgentDDSketch;
 
 use super::{
-    samples_to_buckets, Bucket, MetricSketch, MetricTags, MetricValue, Quantile, Sample,
-    StatisticKind, TagValue, TagValueSet,
+    samples_to_buckets, Bucket, ExponentialBuckets, ExponentialHistogram, MetricSketch, MetricTags,
+    MetricValue, Quantile, Sample, StatisticKind, TagValue, TagValueSet,
 };
 
 fn realistic_float() -> proptest::num::f64::Any {
@@ -59,6 +59,8 @@ impl Arbitrary for MetricValue {
                 }
             }),
             any::<MetricSketch>().prop_map(|sketch| MetricValue::Sketch { sketch }),
+            any::<ExponentialHistogram>()
+                .prop_map(|histogram| MetricValue::ExponentialHistogram { histogram }),
         ];
         strategy.boxed()
     }
@@ -106,6 +108,46 @@ impl Arbitrary for Bucket {
     }
 }
 
+impl Arbitrary for ExponentialBuckets {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<ExponentialBuckets>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        (-64..64, arb_vec(0..1_000_u64, 0..16))
+            .prop_map(|(offset, counts)| ExponentialBuckets { offset, counts })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ExponentialHistogram {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<ExponentialHistogram>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        (
+            -4..8,
+            0..1_000_u64,
+            any::<(ExponentialBuckets, ExponentialBuckets)>(),
+            realistic_float(),
+        )
+            .prop_map(|(scale, zero_count, (positive, negative), sum)| {
+                let count = zero_count
+                    + positive.counts.iter().sum::<u64>()
+                    + negative.counts.iter().sum::<u64>();
+                ExponentialHistogram {
+                    scale,
+                    zero_count,
+                    zero_threshold: 0.0,
+                    positive,
+                    negative,
+                    count,
+                    sum,
+                }
+            })
+            .boxed()
+    }
+}
+
 impl Arbitrary for Quantile {
     type Parameters = ();
     type Strategy = BoxedStrategy<Quantile>;
@@ -122,8 +164,6 @@ impl Arbitrary for AgentDDSketch {
     type Strategy = BoxedStrategy<AgentDDSketch>;
 
     fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
-        use proptest::collection::vec as arb_vec;
-
         arb_vec(realistic_float(), 16..128)
             .prop_map(|samples| {
                 let mut sketch = AgentDDSketch::with_agent_defaults();
@@ -0,0 +1,391 @@
+use vector_common::byte_size_of::ByteSizeOf;
+use vector_config::configurable_component;
+
+use crate::float_eq;
+
+use super::Bucket;
+
+/// A histogram whose buckets grow exponentially in size.
+///
+/// This is the representation used by OpenTelemetry exponential histograms and Prometheus native
+/// histograms. Rather than being configured explicitly, the bucket boundaries are derived from the
+/// `scale`: the buckets have a base of `2^(2^-scale)`, and the bucket with index `i` counts the
+/// values whose magnitude is within `(base^i, base^(i+1)]`. Values whose magnitude is at most
+/// `zero_threshold` are counted in the zero bucket instead.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+pub struct ExponentialHistogram {
+    /// The resolution of the histogram.
+    ///
+    /// Every increment of the scale doubles the number of buckets within each power of two.
+    pub scale: i32,
+
+    /// The number of values in the zero bucket.
+    pub zero_count: u64,
+
+    /// The largest magnitude of the values in the zero bucket.
+    pub zero_threshold: f64,
+
+    /// The buckets of the positive values.
+    pub positive: ExponentialBuckets,
+
+    /// The buckets of the negative values, indexed by the magnitude of the values.
+    pub negative: ExponentialBuckets,
+
+    /// The total number of observations contained within this histogram.
+    pub count: u64,
+
+    /// The sum of all observations contained within this histogram.
+    pub sum: f64,
+}
+
+impl ExponentialHistogram {
+    /// Returns `true` if the histogram has no observations.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Lowers the scale of the histogram by `by`, merging every `2^by` adjacent buckets into one.
+    pub fn downscale(&mut self, by: u32) {
+        if by == 0 {
+            return;
+        }
+        self.scale -= i32::try_from(by).unwrap_or(i32::MAX);
+        self.positive.downscale(by);
+        self.negative.downscale(by);
+    }
+
+    /// Merges another histogram into this one.
+    ///
+    /// The histogram with the higher scale is downscaled to the lower scale, and the histogram
+    /// with the smaller zero bucket has the buckets overlapping the larger zero bucket moved into
+    /// it, so merging never fails but may lose resolution.
+    pub fn merge(&mut self, other: &Self) {
+        let other = self.align(other);
+        self.positive.merge(&other.positive);
+        self.negative.merge(&other.negative);
+        self.zero_count += other.zero_count;
+        self.count += other.count;
+        self.sum += other.sum;
+    }
+
+    /// Subtracts another histogram from this one.
+    ///
+    /// If any of the resulting counts would be negative, the histogram is left unchanged and
+    /// `false` is returned.
+    #[must_use]
+    pub fn subtract(&mut self, other: &Self) -> bool {
+        let mut result = self.clone();
+        let other = result.align(other);
+        if result.count < other.count
+            || result.zero_count < other.zero_count
+            || !result.positive.subtract(&other.positive)
+            || !result.negative.subtract(&other.negative)
+        {
+            return false;
+        }
+        result.zero_count -= other.zero_count;
+        result.count -= other.count;
+        result.sum -= other.sum;
+        *self = result;
+        true
+    }
+
+    /// Zeroes out all the counts of this histogram, keeping its bucket layout intact.
+    pub fn zero(&mut self) {
+        self.positive.counts.fill(0);
+        self.negative.counts.fill(0);
+        self.zero_count = 0;
+        self.count = 0;
+        self.sum = 0.0;
+    }
+
+    /// Converts the buckets of this histogram into buckets with explicit upper limits, in
+    /// increasing order of their upper limits.
+    ///
+    /// This is intended for destinations that don't support exponential histograms.
+    pub fn to_buckets(&self) -> Vec<Bucket> {
+        let negative = self.negative.iter().rev().map(|(index, count)| Bucket {
+            upper_limit: -lower_bound(self.scale, index),
+            count,
+        });
+        let zero = Bucket {
+            upper_limit: self.zero_threshold,
+            count: self.zero_count,
+        };
+        let positive = self.positive.iter().map(|(index, count)| Bucket {
+            upper_limit: lower_bound(self.scale, index + 1),
+            count,
+        });
+        negative
+            .chain(std::iter::once(zero))
+            .chain(positive)
+            .collect()
+    }
+
+    /// Brings this histogram and `other` to the same scale and zero threshold, returning the
+    /// adjusted copy of `other`.
+    fn align(&mut self, other: &Self) -> Self {
+        let mut other = other.clone();
+        if other.scale < self.scale {
+            self.downscale(self.scale.abs_diff(other.scale));
+        } else {
+            other.downscale(other.scale.abs_diff(self.scale));
+        }
+
+        let threshold = self.zero_threshold.max(other.zero_threshold);
+        self.widen_zero_bucket(threshold);
+        other.widen_zero_bucket(self.zero_threshold);
+        // Widening the other histogram may have raised its threshold further.
+        self.widen_zero_bucket(other.zero_threshold);
+        other
+    }
+
+    /// Moves all the buckets overlapping the zero bucket widened to `threshold` into it.
+    ///
+    /// If a bucket only partially overlaps the zero bucket, the threshold is raised to the upper
+    /// limit of that bucket.
+    fn widen_zero_bucket(&mut self, mut threshold: f64) {
+        if threshold <= self.zero_threshold {
+            return;
+        }
+        loop {
+            let (positive, positive_limit) = self.positive.drain_below(self.scale, threshold);
+            let (negative, negative_limit) = self.negative.drain_below(self.scale, threshold);
+            self.zero_count += positive + negative;
+
+            let limit = positive_limit.max(negative_limit);
+            if limit <= threshold {
+                break;
+            }
+            threshold = limit;
+        }
+        self.zero_threshold = threshold;
+    }
+}
+
+impl PartialEq for ExponentialHistogram {
+    fn eq(&self, other: &Self) -> bool {
+        self.scale == other.scale
+            && self.zero_count == other.zero_count
+            && float_eq(self.zero_threshold, other.zero_threshold)
+            && self.positive == other.positive
+            && self.negative == other.negative
+            && self.count == other.count
+            && float_eq(self.sum, other.sum)
+    }
+}
+
+impl ByteSizeOf for ExponentialHistogram {
+    fn allocated_bytes(&self) -> usize {
+        self.positive.allocated_bytes() + self.negative.allocated_bytes()
+    }
+}
+
+/// A contiguous range of buckets of an exponential histogram.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ExponentialBuckets {
+    /// The index of the first bucket.
+    pub offset: i32,
+
+    /// The number of values in each bucket, starting with the bucket at `offset`.
+    pub counts: Vec<u64>,
+}
+
+impl ExponentialBuckets {
+    /// Iterates over the index and count of each bucket.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (i32, u64)> + '_ {
+        let end = self.offset + self.counts.len() as i32;
+        (self.offset..end).zip(self.counts.iter().copied())
+    }
+
+    fn downscale(&mut self, by: u32) {
+        if self.counts.is_empty() {
+            return;
+        }
+        // Shifting signed integers rounds towards negative infinity, which maps every bucket to
+        // the bucket containing it at the lower scale.
+        let by = by.min(i32::BITS - 1);
+        let offset = self.offset >> by;
+        let mut counts = Vec::new();
+        for (index, count) in self.iter() {
+            let position = ((index >> by) - offset) as usize;
+            if position == counts.len() {
+                counts.push(0);
+            }
+            counts[position] += count;
+        }
+        self.offset = offset;
+        self.counts = counts;
+    }
+
+    fn merge(&mut self, other: &Self) {
+        for (index, count) in other.iter() {
+            *self.count_mut(index) += count;
+        }
+    }
+
+    fn subtract(&mut self, other: &Self) -> bool {
+        for (index, count) in other.iter().filter(|(_, count)| *count > 0) {
+            let current = self.count_mut(index);
+            match current.checked_sub(count) {
+                Some(remaining) => *current = remaining,
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /// Returns a mutable reference to the count of the bucket at `index`, extending the range of
+    /// buckets to include it if needed.
+    fn count_mut(&mut self, index: i32) -> &mut u64 {
+        if self.counts.is_empty() {
+            self.offset = index;
+        }
+        if index < self.offset {
+            let missing = self.offset.abs_diff(index) as usize;
+            self.counts.splice(0..0, std::iter::repeat(0).take(missing));
+            self.offset = index;
+        }
+        let position = self.offset.abs_diff(index) as usize;
+        if position >= self.counts.len() {
+            self.counts.resize(position + 1, 0);
+        }
+        &mut self.counts[position]
+    }
+
+    /// Removes the buckets whose lower limit is below `threshold`, returning their total count and
+    /// the highest upper limit among them.
+    fn drain_below(&mut self, scale: i32, threshold: f64) -> (u64, f64) {
+        let drained = self
+            .iter()
+            .take_while(|(index, _)| lower_bound(scale, *index) < threshold)
+            .count();
+        if drained == 0 {
+            return (0, 0.0);
+        }
+        let limit = lower_bound(scale, self.offset + drained as i32);
+        let count = self.counts.drain(..drained).sum();
+        self.offset += drained as i32;
+        (count, limit)
+    }
+}
+
+impl ByteSizeOf for ExponentialBuckets {
+    fn allocated_bytes(&self) -> usize {
+        self.counts.allocated_bytes()
+    }
+}
+
+/// Returns the lower limit of the magnitude of the values in the bucket at `index`.
+fn lower_bound(scale: i32, index: i32) -> f64 {
+    (f64::from(index) * (-f64::from(scale)).exp2()).exp2()
+}
+
+#[cfg(test)]
+mod tests {
+    use similar_asserts::assert_eq;
+
+    use super::*;
+
+    fn buckets(offset: i32, counts: &[u64]) -> ExponentialBuckets {
+        ExponentialBuckets {
+            offset,
+            counts: counts.to_vec(),
+        }
+    }
+
+    fn histogram(scale: i32, positive: ExponentialBuckets) -> ExponentialHistogram {
+        let count = positive.counts.iter().sum();
+        ExponentialHistogram {
+            scale,
+            positive,
+            count,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn downscale_merges_adjacent_buckets() {
+        let mut positive = buckets(-3, &[1, 2, 3, 4, 5]);
+        positive.downscale(1);
+        assert_eq!(positive, buckets(-2, &[1, 5, 9]));
+
+        let mut positive = buckets(1, &[1, 2, 3, 4]);
+        positive.downscale(2);
+        assert_eq!(positive, buckets(0, &[6, 4]));
+    }
+
+    #[test]
+    fn merge_downscales_to_lower_scale() {
+        let mut a = histogram(1, buckets(0, &[1, 1, 1, 1]));
+        let b = histogram(0, buckets(1, &[2]));
+
+        a.merge(&b);
+
+        assert_eq!(a.scale, 0);
+        assert_eq!(a.positive, buckets(0, &[2, 4]));
+        assert_eq!(a.count, 6);
+    }
+
+    #[test]
+    fn merge_widens_zero_bucket() {
+        // Buckets at scale 0 cover (1, 2], (2, 4], (4, 8], ...
+        let mut a = histogram(0, buckets(0, &[1, 2, 3]));
+        let mut b = histogram(0, buckets(2, &[4]));
+        b.zero_threshold = 3.0;
+        b.zero_count = 5;
+        b.count += 5;
+
+        a.merge(&b);
+
+        // The (2, 4] bucket partially overlaps the zero bucket, which widens it to 4.
+        assert_eq!(a.zero_threshold, 4.0);
+        assert_eq!(a.zero_count, 8);
+        assert_eq!(a.positive, buckets(2, &[7]));
+        assert_eq!(a.count, 15);
+    }
+
+    #[test]
+    fn subtract_rejects_negative_counts() {
+        let mut a = histogram(0, buckets(0, &[3, 3]));
+        let b = histogram(0, buckets(0, &[1, 2]));
+
+        assert!(a.subtract(&b));
+        assert_eq!(a.positive, buckets(0, &[2, 1]));
+
+        let c = histogram(0, buckets(1, &[5]));
+        assert!(!a.subtract(&c));
+        assert_eq!(a.positive, buckets(0, &[2, 1]));
+    }
+
+    #[test]
+    fn converts_to_buckets() {
+        let mut value = histogram(0, buckets(0, &[1, 2]));
+        value.negative = buckets(1, &[3]);
+        value.zero_count = 4;
+
+        assert_eq!(
+            value.to_buckets(),
+            vec![
+                Bucket {
+                    upper_limit: -2.0,
+                    count: 3
+                },
+                Bucket {
+                    upper_limit: 0.0,
+                    count: 4
+                },
+                Bucket {
+                    upper_limit: 2.0,
+                    count: 1
+                },
+                Bucket {
+                    upper_limit: 4.0,
+                    count: 2
+                },
+            ]
+        );
+    }
+}
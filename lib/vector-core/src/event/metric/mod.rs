@@ -32,6 +32,9 @@ mod arbitrary;
 mod data;
 pub use self::data::*;
 
+mod exponential_histogram;
+pub use self::exponential_histogram::*;
+
 mod series;
 pub use self::series::*;
 
@@ -1014,6 +1017,30 @@ mod test {
             ),
             r"six{} = count=2 sum=127 1@63 2@64"
         );
+
+        assert_eq!(
+            format!(
+                "{}",
+                Metric::new(
+                    "seven",
+                    MetricKind::Absolute,
+                    MetricValue::ExponentialHistogram {
+                        histogram: ExponentialHistogram {
+                            scale: 0,
+                            zero_count: 1,
+                            positive: ExponentialBuckets {
+                                offset: 1,
+                                counts: vec![2, 3],
+                            },
+                            count: 6,
+                            sum: 21.0,
+                            ..Default::default()
+                        },
+                    }
+                )
+            ),
+            r"seven{} = count=6 sum=21 scale=0 1@0 2@4 3@8"
+        );
     }
 
     #[test]
@@ -8,7 +8,7 @@ use vector_config::configurable_component;
 
 use crate::{float_eq, metrics::AgentDDSketch};
 
-use super::{samples_to_buckets, write_list, write_word};
+use super::{samples_to_buckets, write_list, write_word, ExponentialHistogram};
 
 const INFINITY: &str = "inf";
 const NEG_INFINITY: &str = "-inf";
@@ -76,6 +76,15 @@ pub enum MetricValue {
         sum: f64,
     },
 
+    /// A set of observations which are counted into exponentially sized buckets.
+    ///
+    /// Unlike aggregated histograms, the bucket boundaries are derived from the scale of the histogram, so
+    /// histograms with different resolutions can still be merged without converting them to fixed buckets.
+    ExponentialHistogram {
+        #[configurable(derived)]
+        histogram: ExponentialHistogram,
+    },
+
     /// A data structure that can answer questions about the cumulative distribution of the contained samples in
     /// space-efficient way.
     ///
@@ -100,6 +109,7 @@ impl MetricValue {
             MetricValue::Distribution { samples, .. } => samples.is_empty(),
             MetricValue::AggregatedSummary { count, .. }
             | MetricValue::AggregatedHistogram { count, .. } => *count == 0,
+            MetricValue::ExponentialHistogram { histogram } => histogram.is_empty(),
             MetricValue::Sketch { sketch } => sketch.is_empty(),
         }
     }
@@ -115,6 +125,7 @@ impl MetricValue {
             Self::Distribution { .. } => "distribution",
             Self::AggregatedHistogram { .. } => "aggregated histogram",
             Self::AggregatedSummary { .. } => "aggregated summary",
+            Self::ExponentialHistogram { .. } => "exponential histogram",
             Self::Sketch { sketch } => sketch.as_name(),
         }
     }
@@ -196,6 +207,7 @@ impl MetricValue {
                 *count = 0;
                 *sum = 0.0;
             }
+            Self::ExponentialHistogram { histogram } => histogram.zero(),
             Self::Sketch { sketch } => match sketch {
                 MetricSketch::AgentDDSketch(ddsketch) => {
                     ddsketch.clear();
@@ -258,6 +270,15 @@ impl MetricValue {
                 *sum += sum2;
                 true
             }
+            (
+                Self::ExponentialHistogram { histogram },
+                Self::ExponentialHistogram {
+                    histogram: histogram2,
+                },
+            ) => {
+                histogram.merge(histogram2);
+                true
+            }
             (Self::Sketch { sketch }, Self::Sketch { sketch: sketch2 }) => {
                 match (sketch, sketch2) {
                     (
@@ -356,6 +377,13 @@ impl MetricValue {
                 *sum -= sum2;
                 true
             }
+            // Exponential histograms are monotonic in the same way, and refuse to produce negative bucket counts.
+            (
+                Self::ExponentialHistogram { histogram },
+                Self::ExponentialHistogram {
+                    histogram: histogram2,
+                },
+            ) => histogram.subtract(histogram2),
             _ => false,
         }
     }
@@ -369,6 +397,7 @@ impl ByteSizeOf for MetricValue {
             Self::Distribution { samples, .. } => samples.allocated_bytes(),
             Self::AggregatedHistogram { buckets, .. } => buckets.allocated_bytes(),
             Self::AggregatedSummary { quantiles, .. } => quantiles.allocated_bytes(),
+            Self::ExponentialHistogram { histogram } => histogram.allocated_bytes(),
             Self::Sketch { sketch } => sketch.allocated_bytes(),
         }
     }
@@ -418,6 +447,14 @@ impl PartialEq for MetricValue {
                     sum: r_sum,
                 },
             ) => l_quantiles == r_quantiles && l_count == r_count && float_eq(*l_sum, *r_sum),
+            (
+                Self::ExponentialHistogram {
+                    histogram: l_histogram,
+                },
+                Self::ExponentialHistogram {
+                    histogram: r_histogram,
+                },
+            ) => l_histogram == r_histogram,
             (Self::Sketch { sketch: l_sketch }, Self::Sketch { sketch: r_sketch }) => {
                 l_sketch == r_sketch
             }
@@ -468,6 +505,16 @@ impl fmt::Display for MetricValue {
                     write!(fmt, "{}@{}", quantile.quantile, quantile.value)
                 })
             }
+            MetricValue::ExponentialHistogram { histogram } => {
+                write!(
+                    fmt,
+                    "count={} sum={} scale={} ",
+                    histogram.count, histogram.sum, histogram.scale
+                )?;
+                write_list(fmt, " ", histogram.to_buckets(), |fmt, bucket| {
+                    write!(fmt, "{}@{}", bucket.count, bucket.upper_limit)
+                })
+            }
             MetricValue::Sketch { sketch } => {
                 let quantiles = [0.5, 0.75, 0.9, 0.99]
                     .iter()
@@ -503,6 +550,12 @@ impl fmt::Display for MetricValue {
     }
 }
 
+impl From<ExponentialHistogram> for MetricValue {
+    fn from(histogram: ExponentialHistogram) -> Self {
+        MetricValue::ExponentialHistogram { histogram }
+    }
+}
+
 impl From<AgentDDSketch> for MetricValue {
     fn from(ddsketch: AgentDDSketch) -> Self {
         MetricValue::Sketch {
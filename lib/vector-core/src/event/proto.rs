@@ -194,6 +194,9 @@ impl From<MetricValue> for super::MetricValue {
                 count: summary.count,
                 sum: summary.sum,
             },
+            MetricValue::ExponentialHistogram(histogram) => Self::ExponentialHistogram {
+                histogram: histogram.into(),
+            },
             MetricValue::Sketch(sketch) => match sketch.sketch.unwrap() {
                 sketch::Sketch::AgentDdSketch(ddsketch) => Self::Sketch {
                     sketch: ddsketch.into(),
@@ -391,6 +394,9 @@ impl From<super::MetricValue> for MetricValue {
                 count,
                 sum,
             }),
+            super::MetricValue::ExponentialHistogram { histogram } => {
+                Self::ExponentialHistogram(histogram.into())
+            }
             super::MetricValue::Sketch { sketch } => match sketch {
                 MetricSketch::AgentDDSketch(ddsketch) => {
                     let bin_map = ddsketch.bin_map();
@@ -510,6 +516,52 @@ impl From<super::Event> for WithMetadata<EventWrapper> {
     }
 }
 
+impl From<ExponentialHistogram> for super::metric::ExponentialHistogram {
+    fn from(histogram: ExponentialHistogram) -> Self {
+        Self {
+            scale: histogram.scale,
+            zero_count: histogram.zero_count,
+            zero_threshold: histogram.zero_threshold,
+            positive: histogram.positive.map(Into::into).unwrap_or_default(),
+            negative: histogram.negative.map(Into::into).unwrap_or_default(),
+            count: histogram.count,
+            sum: histogram.sum,
+        }
+    }
+}
+
+impl From<super::metric::ExponentialHistogram> for ExponentialHistogram {
+    fn from(histogram: super::metric::ExponentialHistogram) -> Self {
+        Self {
+            scale: histogram.scale,
+            zero_count: histogram.zero_count,
+            zero_threshold: histogram.zero_threshold,
+            positive: Some(histogram.positive.into()),
+            negative: Some(histogram.negative.into()),
+            count: histogram.count,
+            sum: histogram.sum,
+        }
+    }
+}
+
+impl From<ExponentialHistogramBuckets> for super::metric::ExponentialBuckets {
+    fn from(buckets: ExponentialHistogramBuckets) -> Self {
+        Self {
+            offset: buckets.offset,
+            counts: buckets.counts,
+        }
+    }
+}
+
+impl From<super::metric::ExponentialBuckets> for ExponentialHistogramBuckets {
+    fn from(buckets: super::metric::ExponentialBuckets) -> Self {
+        Self {
+            offset: buckets.offset,
+            counts: buckets.counts,
+        }
+    }
+}
+
 impl From<AgentDDSketch> for Sketch {
     fn from(ddsketch: AgentDDSketch) -> Self {
         let bin_map = ddsketch.bin_map();
@@ -6,8 +6,8 @@ use vrl::value::{ObjectMap, Value};
 
 use super::super::{
     metric::{
-        Bucket, MetricData, MetricName, MetricSeries, MetricSketch, MetricTags, MetricTime,
-        Quantile, Sample,
+        Bucket, ExponentialBuckets, ExponentialHistogram, MetricData, MetricName, MetricSeries,
+        MetricSketch, MetricTags, MetricTime, Quantile, Sample,
     },
     Event, EventMetadata, LogEvent, Metric, MetricKind, MetricValue, StatisticKind, TraceEvent,
 };
@@ -173,7 +173,7 @@ impl Arbitrary for MetricValue {
         // constant here are the number of fields in `MetricValue`. Because the
         // field total is not a power of two we introduce a bias into choice
         // here toward `MetricValue::Counter` and `MetricValue::Gauge`.
-        match u8::arbitrary(g) % 7 {
+        match u8::arbitrary(g) % 8 {
             0 => MetricValue::Counter {
                 value: f64::arbitrary(g) % MAX_F64_SIZE,
             },
@@ -217,6 +217,17 @@ impl Arbitrary for MetricValue {
                     sketch: MetricSketch::AgentDDSketch(sketch),
                 }
             }
+            7 => MetricValue::ExponentialHistogram {
+                histogram: ExponentialHistogram {
+                    scale: i32::from(i8::arbitrary(g) % 8),
+                    zero_count: u64::arbitrary(g),
+                    zero_threshold: (f64::arbitrary(g) % MAX_F64_SIZE).abs(),
+                    positive: ExponentialBuckets::arbitrary(g),
+                    negative: ExponentialBuckets::arbitrary(g),
+                    count: u64::arbitrary(g),
+                    sum: f64::arbitrary(g) % MAX_F64_SIZE,
+                },
+            },
 
             _ => unreachable!(),
         }
@@ -354,6 +365,11 @@ impl Arbitrary for MetricValue {
             MetricValue::Sketch { sketch } => Box::new(iter::once(MetricValue::Sketch {
                 sketch: sketch.clone(),
             })),
+            MetricValue::ExponentialHistogram { histogram } => {
+                Box::new(iter::once(MetricValue::ExponentialHistogram {
+                    histogram: histogram.clone(),
+                }))
+            }
         }
     }
 }
@@ -448,6 +464,24 @@ impl Arbitrary for Bucket {
     }
 }
 
+impl Arbitrary for ExponentialBuckets {
+    fn arbitrary(g: &mut Gen) -> Self {
+        ExponentialBuckets {
+            offset: i32::from(i16::arbitrary(g)),
+            counts: Vec::arbitrary(g),
+        }
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let offset = self.offset;
+        Box::new(
+            self.counts
+                .shrink()
+                .map(move |counts| ExponentialBuckets { offset, counts }),
+        )
+    }
+}
+
 impl Arbitrary for StatisticKind {
     fn arbitrary(g: &mut Gen) -> Self {
         let choice: u8 = u8::arbitrary(g);
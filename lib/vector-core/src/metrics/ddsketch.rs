@@ -796,6 +796,11 @@ impl AgentDDSketch {
                 sketch.insert_interpolate_buckets(delta_buckets)?;
                 Some(sketch)
             }
+            MetricValue::ExponentialHistogram { histogram } => {
+                let mut sketch = AgentDDSketch::with_agent_defaults();
+                sketch.insert_interpolate_buckets(histogram.to_buckets())?;
+                Some(sketch)
+            }
             // We can't convert from any other metric value.
             _ => None,
         };
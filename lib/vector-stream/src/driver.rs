@@ -70,7 +70,7 @@ where
     St: Stream,
     St::Item: Finalizable + MetaDescriptive,
     Svc: Service<St::Item>,
-    Svc::Error: fmt::Debug + 'static,
+    Svc::Error: fmt::Debug + fmt::Display + 'static,
     Svc::Future: Send + 'static,
     Svc::Response: DriverResponse,
 {
@@ -205,8 +205,9 @@ where
     ) {
         match result {
             Err(error) => {
+                let reason = error.to_string();
                 Self::emit_call_error(Some(error), request_id, event_count);
-                finalizers.update_status(EventStatus::Rejected);
+                finalizers.update_status_with_reason(EventStatus::Rejected, &reason);
             }
             Ok(response) => {
                 trace!(message = "Service call succeeded.", request_id);
@@ -244,6 +245,7 @@ where
 #[cfg(test)]
 mod tests {
     use std::{
+        convert::Infallible,
         future::Future,
         pin::Pin,
         sync::{atomic::AtomicUsize, atomic::Ordering, Arc},
@@ -376,7 +378,7 @@ mod tests {
 
     impl Service<DelayRequest> for DelayService {
         type Response = DelayResponse;
-        type Error = ();
+        type Error = Infallible;
         type Future =
             Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + Sync>>;
 
@@ -0,0 +1,108 @@
+use async_graphql::{Context, Object};
+use openssl::memcmp;
+use tokio::sync::oneshot;
+
+use crate::{
+    config::ComponentKey,
+    topology::{ControlRequest, ControlTx},
+};
+
+/// Whether the request is allowed to change the state of Vector, based on the API token.
+#[derive(Clone, Copy, Debug)]
+pub struct Authorized(pub bool);
+
+impl Authorized {
+    /// Checks the `Authorization` header of a request against the configured API token.
+    ///
+    /// Requests are never authorized when no token is configured.
+    pub fn from_header(token: Option<&str>, header: Option<&str>) -> Self {
+        Self(match token {
+            None => false,
+            Some(token) => header
+                .and_then(|header| header.strip_prefix("Bearer "))
+                .is_some_and(|provided| {
+                    provided.len() == token.len()
+                        && memcmp::eq(provided.as_bytes(), token.as_bytes())
+                }),
+        })
+    }
+}
+
+/// Forwards a control request to the topology controller, and waits for its outcome.
+async fn control(ctx: &Context<'_>, request: ControlRequest) -> async_graphql::Result<bool> {
+    if !ctx
+        .data_opt::<Authorized>()
+        .is_some_and(|authorized| authorized.0)
+    {
+        return Err(
+            "Missing or invalid API token. Control mutations require `api.token` to be set.".into(),
+        );
+    }
+
+    let (reply_tx, reply_rx) = oneshot::channel();
+    ctx.data::<ControlTx>()?
+        .send((request, reply_tx))
+        .map_err(|_| "Vector is shutting down.")?;
+    reply_rx.await.map_err(|_| "Vector is shutting down.")??;
+
+    Ok(true)
+}
+
+#[derive(Default)]
+pub struct ControlMutation;
+
+#[Object]
+impl ControlMutation {
+    /// Reloads the configuration from disk, returning `true` once the new configuration is running
+    async fn reload_config(&self, ctx: &Context<'_>) -> async_graphql::Result<bool> {
+        control(ctx, ControlRequest::ReloadConfig).await
+    }
+
+    /// Pauses a source, holding back the events it sends to downstream components until it is
+    /// resumed
+    async fn pause_source(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<bool> {
+        control(ctx, ControlRequest::PauseSource(ComponentKey::from(id))).await
+    }
+
+    /// Resumes a paused source
+    async fn resume_source(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<bool> {
+        control(ctx, ControlRequest::ResumeSource(ComponentKey::from(id))).await
+    }
+
+    /// Replaces the filter applied to Vector's internal logs, using the same format as the
+    /// `VECTOR_LOG` environment variable, e.g. `vector=debug,rdkafka=warn`
+    async fn set_log_filter(
+        &self,
+        ctx: &Context<'_>,
+        filter: String,
+    ) -> async_graphql::Result<bool> {
+        control(ctx, ControlRequest::SetLogFilter(filter)).await
+    }
+
+    /// Disconnects a sink from its inputs, returning `true` once it has processed all of its
+    /// buffered events. The sink is started again by the next configuration reload
+    async fn drain_sink(&self, ctx: &Context<'_>, id: String) -> async_graphql::Result<bool> {
+        control(ctx, ControlRequest::DrainSink(ComponentKey::from(id))).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Authorized;
+
+    #[test]
+    fn authorizes_matching_token_only() {
+        let token = Some("secret");
+        assert!(Authorized::from_header(token, Some("Bearer secret")).0);
+        assert!(!Authorized::from_header(token, Some("Bearer secret2")).0);
+        assert!(!Authorized::from_header(token, Some("Bearer other")).0);
+        assert!(!Authorized::from_header(token, Some("secret")).0);
+        assert!(!Authorized::from_header(token, None).0);
+    }
+
+    #[test]
+    fn denies_without_token() {
+        assert!(!Authorized::from_header(None, Some("Bearer secret")).0);
+        assert!(!Authorized::from_header(None, None).0);
+    }
+}
@@ -1,7 +1,7 @@
 use std::{
     convert::Infallible,
     net::SocketAddr,
-    sync::{atomic::AtomicBool, Arc},
+    sync::{atomic::AtomicBool, Arc, RwLock},
 };
 
 use async_graphql::{
@@ -17,16 +17,21 @@ use tracing::Span;
 use vector_lib::tap::topology;
 use warp::{filters::BoxedFilter, http::Response, ws::Ws, Filter, Reply};
 
-use super::{handler, schema};
+use super::{handler, schema, schema::control::Authorized};
 use crate::{
     config::{self, api},
     http::build_http_trace_layer,
     internal_events::{SocketBindError, SocketMode},
+    topology::ControlTx,
 };
 
+/// The API token, shared with the routes so that reloading the configuration replaces it.
+type Token = Arc<RwLock<Option<String>>>;
+
 pub struct Server {
     _shutdown: oneshot::Sender<()>,
     addr: SocketAddr,
+    token: Token,
 }
 
 impl Server {
@@ -36,9 +41,17 @@ impl Server {
         config: &config::Config,
         watch_rx: topology::WatchRx,
         running: Arc<AtomicBool>,
+        control_tx: ControlTx,
         handle: &Handle,
     ) -> crate::Result<Self> {
-        let routes = make_routes(config.api, watch_rx, running);
+        let token = Arc::new(RwLock::new(config.api.token.clone().map(String::from)));
+        let routes = make_routes(
+            config.api.clone(),
+            Arc::clone(&token),
+            watch_rx,
+            running,
+            control_tx,
+        );
 
         let (_shutdown, rx) = oneshot::channel();
         // warp uses `tokio::spawn` and so needs us to enter the runtime context.
@@ -79,7 +92,11 @@ impl Server {
         // Spawn the server in the background.
         handle.spawn(server);
 
-        Ok(Self { _shutdown, addr })
+        Ok(Self {
+            _shutdown,
+            addr,
+            token,
+        })
     }
 
     /// Returns a copy of the SocketAddr that the server was started on.
@@ -87,18 +104,21 @@ impl Server {
         self.addr
     }
 
-    /// Update the configuration of a running server. While this instance method doesn't
-    /// directly involve `self`, it provides a neater API to expose an internal implementation
-    /// detail than exposing the function of the sub-mod directly.
+    /// Update the configuration of a running server, including the API token that control
+    /// mutations are authorized with.
     pub fn update_config(&self, config: &config::Config) {
+        *self.token.write().expect("API token lock poisoned") =
+            config.api.token.clone().map(String::from);
         schema::components::update_config(config)
     }
 }
 
 fn make_routes(
     api: api::Options,
+    token: Token,
     watch_tx: topology::WatchRx,
     running: Arc<AtomicBool>,
+    control_tx: ControlTx,
 ) -> BoxedFilter<(impl Reply,)> {
     // Routes...
 
@@ -111,20 +131,32 @@ fn make_routes(
     let not_found_graphql = warp::any().and_then(|| async { Err(warp::reject::not_found()) });
     let not_found = warp::any().and_then(|| async { Err(warp::reject::not_found()) });
 
+    // Whether requests may change the state of Vector, based on the API token, if any.
+    let authorized =
+        warp::header::optional::<String>("authorization").map(move |header: Option<String>| {
+            let token = token.read().expect("API token lock poisoned");
+            Authorized::from_header(token.as_deref(), header.as_deref())
+        });
+
     // GraphQL subscription handler. Creates a Warp WebSocket handler and for each connection,
     // parses the required headers for GraphQL and builds per-connection context based on the
     // provided `WatchTx` channel sender. This allows GraphQL resolvers to subscribe to
     // topology changes.
-    let graphql_subscription_handler =
-        warp::ws()
-            .and(graphql_protocol())
-            .map(move |ws: Ws, protocol: WebSocketProtocols| {
-                let schema = schema::build_schema().finish();
+    let subscription_control_tx = control_tx.clone();
+    let graphql_subscription_handler = warp::ws()
+        .and(graphql_protocol())
+        .and(authorized.clone())
+        .map(
+            move |ws: Ws, protocol: WebSocketProtocols, authorized: Authorized| {
+                let schema = schema::build_schema()
+                    .data(subscription_control_tx.clone())
+                    .finish();
                 let watch_tx = watch_tx.clone();
 
                 let reply = ws.on_upgrade(move |socket| {
                     let mut data = Data::default();
                     data.insert(watch_tx);
+                    data.insert(authorized);
 
                     GraphQLWebSocket::new(socket, schema, protocol)
                         .with_data(data)
@@ -136,20 +168,28 @@ fn make_routes(
                     "Sec-WebSocket-Protocol",
                     protocol.sec_websocket_protocol(),
                 )
-            });
+            },
+        );
 
     // Handle GraphQL queries. Headers will first be parsed to determine whether the query is
     // a subscription and if so, an attempt will be made to upgrade the connection to WebSockets.
     // All other queries will fall back to the default HTTP handler.
     let graphql_handler = if api.graphql {
         warp::path("graphql")
-            .and(graphql_subscription_handler.or(
-                async_graphql_warp::graphql(schema::build_schema().finish()).and_then(
-                    |(schema, request): (Schema<_, _, _>, Request)| async move {
-                        Ok::<_, Infallible>(GraphQLResponse::from(schema.execute(request).await))
-                    },
+            .and(
+                graphql_subscription_handler.or(
+                    async_graphql_warp::graphql(schema::build_schema().data(control_tx).finish())
+                        .and(authorized)
+                        .and_then(
+                            |(schema, request): (Schema<_, _, _>, Request),
+                             authorized: Authorized| async move {
+                                Ok::<_, Infallible>(GraphQLResponse::from(
+                                    schema.execute(request.data(authorized)).await,
+                                ))
+                            },
+                        ),
                 ),
-            ))
+            )
             .boxed()
     } else {
         not_found_graphql.boxed()
@@ -171,7 +211,9 @@ fn make_routes(
     };
 
     // Wire up the health + GraphQL endpoints. Provides a permissive CORS policy to allow for
-    // cross-origin interaction with the Vector API.
+    // cross-origin interaction with the Vector API. The `Authorization` header is deliberately
+    // not allowed, so that browsers can't send the API token cross-origin, which leaves the control
+    // mutations to same-origin and non-browser clients.
     health
         .or(graphql_handler)
         .or(graphql_playground)
@@ -204,3 +246,62 @@ fn with_shared(
 ) -> impl Filter<Extract = (Arc<AtomicBool>,), Error = Infallible> + Clone {
     warp::any().map(move || Arc::<AtomicBool>::clone(&shared))
 }
+
+#[cfg(test)]
+mod tests {
+    use hyper::{Body, Client, Request};
+    use tokio::sync::{mpsc, watch};
+
+    use super::*;
+    use crate::{test_util::next_addr, topology::ControlRequest};
+
+    fn config(address: SocketAddr, token: &str) -> config::Config {
+        let mut config = config::Config::default();
+        config.api.enabled = true;
+        config.api.address = Some(address);
+        config.api.token = Some(token.to_owned().into());
+        config
+    }
+
+    /// Sends the `reloadConfig` mutation with the given token, returning whether it was
+    /// authorized.
+    async fn reload_config(address: SocketAddr, token: &str) -> bool {
+        let request = Request::post(format!("http://{address}/graphql"))
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::from(r#"{"query":"mutation { reloadConfig }"}"#))
+            .unwrap();
+        let response = Client::new().request(request).await.unwrap();
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        body["data"]["reloadConfig"] == serde_json::Value::Bool(true)
+    }
+
+    #[tokio::test]
+    async fn reloads_token() {
+        let address = next_addr();
+        let (control_tx, mut control_rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some((request, reply)) = control_rx.recv().await {
+                assert!(matches!(request, ControlRequest::ReloadConfig));
+                _ = reply.send(Ok(()));
+            }
+        });
+        let (_watch_tx, watch_rx) = watch::channel(Default::default());
+
+        let server = Server::start(
+            &config(address, "old"),
+            watch_rx,
+            Arc::new(AtomicBool::new(true)),
+            control_tx,
+            &Handle::current(),
+        )
+        .unwrap();
+        assert!(reload_config(address, "old").await);
+        assert!(!reload_config(address, "new").await);
+
+        server.update_config(&config(address, "new"));
+        assert!(!reload_config(address, "old").await);
+        assert!(reload_config(address, "new").await);
+    }
+}
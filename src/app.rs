@@ -5,7 +5,7 @@ use exitcode::ExitCode;
 use futures::StreamExt;
 use once_cell::race::OnceNonZeroUsize;
 use tokio::runtime::{self, Runtime};
-use tokio::sync::{broadcast::error::RecvError, MutexGuard};
+use tokio::sync::{broadcast::error::RecvError, mpsc, MutexGuard};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use crate::extra_context::ExtraContext;
@@ -18,8 +18,8 @@ use crate::{
     internal_events::{VectorConfigLoadError, VectorQuit, VectorStarted, VectorStopped},
     signal::{SignalHandler, SignalPair, SignalRx, SignalTo},
     topology::{
-        ReloadOutcome, RunningTopology, SharedTopologyController, ShutdownErrorReceiver,
-        TopologyController,
+        ControlError, ControlReply, ControlRequest, ControlRx, ReloadOutcome, RunningTopology,
+        SharedTopologyController, ShutdownErrorReceiver, TopologyController,
     },
     trace,
 };
@@ -78,7 +78,7 @@ impl ApplicationConfig {
         extra_context: ExtraContext,
     ) -> Result<Self, ExitCode> {
         #[cfg(feature = "api")]
-        let api = config.api;
+        let api = config.api.clone();
 
         let (topology, graceful_crash_receiver) =
             RunningTopology::start_init_validated(config, extra_context.clone())
@@ -112,12 +112,17 @@ impl ApplicationConfig {
 
     /// Configure the API server, if applicable
     #[cfg(feature = "api")]
-    pub fn setup_api(&self, handle: &Handle) -> Option<api::Server> {
+    pub fn setup_api(
+        &self,
+        control_tx: crate::topology::ControlTx,
+        handle: &Handle,
+    ) -> Option<api::Server> {
         if self.api.enabled {
             match api::Server::start(
                 self.topology.config(),
                 self.topology.watch(),
                 std::sync::Arc::clone(&self.topology.running),
+                control_tx,
                 handle,
             ) {
                 Ok(api_server) => {
@@ -230,9 +235,13 @@ impl Application {
             signals,
         } = self;
 
+        // Requests to change the state of Vector, as issued through the API.
+        let (control_tx, control_rx) = mpsc::unbounded_channel();
+
         let topology_controller = SharedTopologyController::new(TopologyController {
             #[cfg(feature = "api")]
-            api_server: config.setup_api(handle),
+            api_server: config.setup_api(control_tx.clone(), handle),
+            control_tx,
             topology: config.topology,
             config_paths: config.config_paths.clone(),
             require_healthy: root_opts.require_healthy,
@@ -245,6 +254,7 @@ impl Application {
             graceful_crash_receiver: config.graceful_crash_receiver,
             signals,
             topology_controller,
+            control_rx,
             allow_empty_config: root_opts.allow_empty_config,
         })
     }
@@ -256,6 +266,7 @@ pub struct StartedApplication {
     pub graceful_crash_receiver: ShutdownErrorReceiver,
     pub signals: SignalPair,
     pub topology_controller: SharedTopologyController,
+    pub control_rx: ControlRx,
     pub allow_empty_config: bool,
 }
 
@@ -270,6 +281,7 @@ impl StartedApplication {
             graceful_crash_receiver,
             signals,
             topology_controller,
+            mut control_rx,
             internal_topologies,
             allow_empty_config,
         } = self;
@@ -291,6 +303,16 @@ impl StartedApplication {
                 ).await {
                     break signal;
                 },
+                Some((request, reply)) = control_rx.recv() => if let Some(signal) = handle_control(
+                    request,
+                    reply,
+                    &topology_controller,
+                    &config_paths,
+                    &mut signal_handler,
+                    allow_empty_config,
+                ).await {
+                    break signal;
+                },
                 // Trigger graceful shutdown if a component crashed, or all sources have ended.
                 error = graceful_crash.next() => break SignalTo::Shutdown(error),
                 _ = TopologyController::sources_finished(topology_controller.clone()), if has_sources => {
@@ -324,15 +346,9 @@ async fn handle_signal(
         }
         Ok(SignalTo::ReloadFromDisk) => {
             let mut topology_controller = topology_controller.lock().await;
-
-            // Reload paths
-            if let Some(paths) = config::process_paths(config_paths) {
-                topology_controller.config_paths = paths;
-            }
-
-            // Reload config
-            let new_config = config::load_from_paths_with_provider_and_secrets(
-                &topology_controller.config_paths,
+            let new_config = load_config_from_disk(
+                &mut topology_controller,
+                config_paths,
                 signal_handler,
                 allow_empty_config,
             )
@@ -349,6 +365,87 @@ async fn handle_signal(
     }
 }
 
+async fn handle_control(
+    request: ControlRequest,
+    reply: ControlReply,
+    topology_controller: &SharedTopologyController,
+    config_paths: &[ConfigPath],
+    signal_handler: &mut SignalHandler,
+    allow_empty_config: bool,
+) -> Option<SignalTo> {
+    let mut signal = None;
+    let result = match request {
+        ControlRequest::ReloadConfig => {
+            let mut topology_controller = topology_controller.lock().await;
+            match load_config_from_disk(
+                &mut topology_controller,
+                config_paths,
+                signal_handler,
+                allow_empty_config,
+            )
+            .await
+            {
+                Ok(new_config) => match topology_controller.reload(new_config).await {
+                    ReloadOutcome::Success => Ok(()),
+                    ReloadOutcome::FatalError(error) => {
+                        signal = Some(SignalTo::Shutdown(Some(error)));
+                        Err(ControlError::ReloadFailed)
+                    }
+                    _ => Err(ControlError::ReloadFailed),
+                },
+                Err(errors) => {
+                    handle_config_errors(errors.clone());
+                    emit!(VectorConfigLoadError);
+                    Err(ControlError::InvalidConfig { errors })
+                }
+            }
+        }
+        ControlRequest::PauseSource(key) => topology_controller.lock().await.pause_source(&key),
+        ControlRequest::ResumeSource(key) => topology_controller.lock().await.resume_source(&key),
+        ControlRequest::SetLogFilter(filter) => match trace::set_log_filter(&filter) {
+            Ok(()) => {
+                info!(message = "Log filter changed.", %filter);
+                Ok(())
+            }
+            Err(source) => Err(ControlError::InvalidLogFilter { source }),
+        },
+        ControlRequest::DrainSink(key) => match topology_controller.lock().await.drain_sink(&key) {
+            // Draining can take a while, so it's awaited separately to keep handling signals.
+            Ok(drained) => {
+                tokio::spawn(async move {
+                    _ = reply.send(drained.await);
+                });
+                return None;
+            }
+            Err(error) => Err(error),
+        },
+    };
+
+    _ = reply.send(result);
+    signal
+}
+
+/// Reloads the configuration paths and loads the configuration from them.
+async fn load_config_from_disk(
+    topology_controller: &mut TopologyController,
+    config_paths: &[ConfigPath],
+    signal_handler: &mut SignalHandler,
+    allow_empty_config: bool,
+) -> Result<Config, Vec<String>> {
+    // Reload paths
+    if let Some(paths) = config::process_paths(config_paths) {
+        topology_controller.config_paths = paths;
+    }
+
+    // Reload config
+    config::load_from_paths_with_provider_and_secrets(
+        &topology_controller.config_paths,
+        signal_handler,
+        allow_empty_config,
+    )
+    .await
+}
+
 async fn reload_config_from_result(
     mut topology_controller: MutexGuard<'_, TopologyController>,
     config: Result<Config, Vec<String>>,
@@ -476,15 +573,19 @@ pub async fn load_configs(
 
     if watch_config {
         // Start listening for config changes immediately.
-        config::watcher::spawn_thread(
-            signal_handler.clone_tx(),
-            config_paths.iter().map(Into::into),
-            None,
-        )
-        .map_err(|error| {
-            error!(message = "Unable to start config watcher.", %error);
-            exitcode::CONFIG
-        })?;
+        // Secret backends can ask for the files they read secrets from to be watched as well.
+        let watched_paths = config_paths
+            .iter()
+            .map(<&PathBuf>::from)
+            .cloned()
+            .chain(config::watched_secret_paths(&config_paths))
+            .collect::<Vec<_>>();
+        config::watcher::spawn_thread(signal_handler.clone_tx(), &watched_paths, None).map_err(
+            |error| {
+                error!(message = "Unable to start config watcher.", %error);
+                exitcode::CONFIG
+            },
+        )?;
     }
 
     info!(
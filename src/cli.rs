@@ -4,6 +4,8 @@ use std::{num::NonZeroU64, path::PathBuf};
 
 use clap::{ArgAction, CommandFactory, FromArgMatches, Parser};
 
+#[cfg(feature = "api-client")]
+use crate::control;
 #[cfg(windows)]
 use crate::service;
 #[cfg(feature = "api-client")]
@@ -287,6 +289,11 @@ pub enum SubCommand {
     #[cfg(feature = "api-client")]
     Tap(tap::Opts),
 
+    /// Change the state of a local or remote Vector instance through its API, e.g. reload its
+    /// configuration or pause one of its sources
+    #[cfg(feature = "api-client")]
+    Control(control::Opts),
+
     /// Manage the vector service.
     #[cfg(windows)]
     Service(service::Opts),
@@ -303,6 +310,8 @@ impl SubCommand {
     ) -> exitcode::ExitCode {
         match self {
             Self::Config(c) => config::cmd(c),
+            #[cfg(feature = "api-client")]
+            Self::Control(c) => control::cmd(c).await,
             Self::ConvertConfig(opts) => convert_config::cmd(opts),
             Self::Generate(g) => generate::cmd(g),
             Self::GenerateSchema => generate_schema::cmd(),
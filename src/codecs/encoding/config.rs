@@ -38,10 +38,25 @@ impl EncodingConfig {
 
     /// Build the `Serializer` for this config.
     pub fn build(&self) -> crate::Result<Serializer> {
+        #[cfg(feature = "codecs-parquet")]
+        reject_batch_serializer(&self.encoding)?;
         self.encoding.build()
     }
 }
 
+/// Rejects serializers that can only encode a whole batch of events at once, for sinks that
+/// encode events one by one.
+#[cfg(feature = "codecs-parquet")]
+fn reject_batch_serializer(config: &SerializerConfig) -> crate::Result<()> {
+    if matches!(config, SerializerConfig::Parquet { .. }) {
+        return Err(
+            "The `parquet` codec is only supported by sinks that write whole objects or files."
+                .into(),
+        );
+    }
+    Ok(())
+}
+
 impl<T> From<T> for EncodingConfig
 where
     T: Into<SerializerConfig>,
@@ -95,6 +110,17 @@ impl EncodingConfigWithFraming {
 
     /// Build the `Framer` and `Serializer` for this config.
     pub fn build(&self, sink_type: SinkType) -> crate::Result<(Framer, Serializer)> {
+        #[cfg(feature = "codecs-parquet")]
+        reject_batch_serializer(&self.encoding.encoding)?;
+        self.build_batched(sink_type)
+    }
+
+    /// Build the `Framer` and `Serializer` for this config, for sinks that write each batch of
+    /// events as a whole object or file.
+    ///
+    /// Unlike [`EncodingConfigWithFraming::build`], this also accepts serializers that can only
+    /// encode a whole batch of events at once, such as Parquet.
+    pub fn build_batched(&self, sink_type: SinkType) -> crate::Result<(Framer, Serializer)> {
         let framer = self.framing.as_ref().map(|framing| framing.build());
         let serializer = self.encoding.build()?;
 
@@ -123,8 +149,11 @@ impl EncodingConfigWithFraming {
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
                 | Serializer::RawMessage(_)
+                | Serializer::Syslog(_)
                 | Serializer::Text(_),
             ) => NewlineDelimitedEncoder::default().into(),
+            #[cfg(feature = "codecs-parquet")]
+            (None, Serializer::Parquet(_)) => vector_lib::codecs::BytesEncoder.into(),
         };
 
         Ok((framer, serializer))
@@ -114,6 +114,8 @@ impl Encoder<Framer> {
                 Framer::CharacterDelimited(CharacterDelimitedEncoder { delimiter: b',' }),
             ) => "application/json",
             (Serializer::Native(_), _) | (Serializer::Protobuf(_), _) => "application/octet-stream",
+            #[cfg(feature = "codecs-parquet")]
+            (Serializer::Parquet(_), _) => "application/vnd.apache.parquet",
             (
                 Serializer::Avro(_)
                 | Serializer::Csv(_)
@@ -122,6 +124,7 @@ impl Encoder<Framer> {
                 | Serializer::Logfmt(_)
                 | Serializer::NativeJson(_)
                 | Serializer::RawMessage(_)
+                | Serializer::Syslog(_)
                 | Serializer::Text(_),
                 _,
             ) => "text/plain",
@@ -15,5 +15,11 @@ pub mod datadog;
 ))]
 pub(crate) mod sqs;
 
+#[cfg(any(feature = "sources-kafka", feature = "sinks-kafka"))]
+pub(crate) mod schema_registry;
+
 #[cfg(any(feature = "sources-aws_s3", feature = "sinks-aws_s3"))]
 pub(crate) mod s3;
+
+#[cfg(any(feature = "sources-websocket", feature = "sinks-websocket"))]
+pub(crate) mod websocket;
@@ -0,0 +1,710 @@
+//! A client for the [Confluent Schema Registry][schema_registry], shared by the `kafka` source
+//! and sink to read and write messages in the Confluent wire format.
+//!
+//! [schema_registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use http::{
+    header::{ACCEPT, CONTENT_TYPE},
+    Request, StatusCode,
+};
+use hyper::Body;
+use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use vector_lib::codecs::{
+    confluent::{self, ProtobufMessageSchema, ProtobufSchema},
+    decoding::{format::AvroDeserializer, BytesDecoder, Deserializer, Framer},
+    encoding::SerializerConfig,
+};
+use vector_lib::configurable::configurable_component;
+use vector_lib::{
+    config::{log_schema, LogNamespace},
+    schema,
+};
+use vrl::value::Kind;
+
+use crate::{
+    codecs::Decoder,
+    config::ProxyConfig,
+    http::{Auth, HttpClient, HttpError},
+    oauth2,
+    tls::{TlsConfig, TlsSettings},
+};
+
+const CONTENT_TYPE_SCHEMA_REGISTRY: &str = "application/vnd.schemaregistry.v1+json";
+
+#[derive(Debug, Snafu)]
+pub(crate) enum SchemaRegistryError {
+    #[snafu(display("Failed to build schema registry request: {}", source))]
+    BuildRequest { source: http::Error },
+    #[snafu(display("Schema registry request failed: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("Failed to read schema registry response: {}", source))]
+    ReadResponse { source: hyper::Error },
+    #[snafu(display("Schema registry responded with {}: {}", status, body))]
+    ResponseStatus { status: StatusCode, body: String },
+    #[snafu(display("Invalid schema registry response: {}", source))]
+    ParseResponse { source: serde_json::Error },
+    #[snafu(display("Invalid message: {}", source))]
+    InvalidMessage { source: crate::Error },
+    #[snafu(display("Invalid schema: {}", source))]
+    InvalidSchema { source: crate::Error },
+    #[snafu(display("Unsupported schema: {}", reason))]
+    UnsupportedSchema { reason: &'static str },
+}
+
+impl SchemaRegistryError {
+    /// Whether the error comes from the message or its schema, rather than from reaching the
+    /// registry, so that retrying can't fix it.
+    pub(crate) fn is_permanent(&self) -> bool {
+        match self {
+            Self::InvalidMessage { .. }
+            | Self::InvalidSchema { .. }
+            | Self::UnsupportedSchema { .. } => true,
+            Self::ResponseStatus { status, .. } => *status == StatusCode::NOT_FOUND,
+            Self::BuildRequest { .. }
+            | Self::Request { .. }
+            | Self::ReadResponse { .. }
+            | Self::ParseResponse { .. } => false,
+        }
+    }
+}
+
+/// Schema registry configuration.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaRegistryConfig {
+    /// The URL of the schema registry.
+    #[configurable(metadata(docs::examples = "http://localhost:8081"))]
+    pub url: String,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+impl SchemaRegistryConfig {
+    pub(crate) fn build(&self, proxy: &ProxyConfig) -> crate::Result<SchemaRegistry> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        Ok(SchemaRegistry {
+            client: HttpClient::new(tls, proxy)?,
+            url: self.url.trim_end_matches('/').to_owned(),
+            auth: self.auth.clone(),
+            schemas: Default::default(),
+        })
+    }
+
+    /// The schema of the events decoded with the schemas of the registry, which is only known
+    /// when the messages are read.
+    pub(crate) fn schema_definition(&self, log_namespace: LogNamespace) -> schema::Definition {
+        match log_namespace {
+            LogNamespace::Legacy => {
+                let mut definition =
+                    schema::Definition::empty_legacy_namespace().unknown_fields(Kind::any());
+
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition = definition.try_with_field(
+                        timestamp_key,
+                        Kind::any().or_timestamp(),
+                        Some("timestamp"),
+                    );
+                }
+                definition
+            }
+            LogNamespace::Vector => {
+                schema::Definition::new_with_default_metadata(Kind::any(), [log_namespace])
+            }
+        }
+    }
+}
+
+/// The strategy used to name the subject that the schema of a message is registered under.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SubjectNameStrategy {
+    /// The subject is named after the topic, as `<topic>-value`.
+    #[default]
+    TopicName,
+
+    /// The subject is named after the Avro record or the Protobuf message type.
+    RecordName,
+
+    /// The subject is named after the topic and the Avro record or the Protobuf message type, as
+    /// `<topic>-<record>`.
+    TopicRecordName,
+}
+
+/// Schema registry configuration for writing messages.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaRegistrySinkConfig {
+    #[serde(flatten)]
+    #[configurable(derived)]
+    pub registry: SchemaRegistryConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub subject_name_strategy: SubjectNameStrategy,
+
+    /// Whether to register the schema of the encoding when it is not registered under the subject
+    /// yet.
+    ///
+    /// When disabled, the schema must already be registered, and events are dropped otherwise.
+    #[serde(default = "crate::serde::default_true")]
+    pub auto_register: bool,
+}
+
+impl SchemaRegistrySinkConfig {
+    pub(crate) fn build(
+        &self,
+        serializer: &SerializerConfig,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<SchemaRegistryEncoder> {
+        let schema = match serializer {
+            SerializerConfig::Avro { avro } => WriterSchema {
+                schema_type: SchemaType::Avro,
+                schema: avro.schema.clone(),
+                record_name: confluent::avro_record_name(&avro.schema)?,
+                message_indexes: None,
+            },
+            SerializerConfig::Protobuf(config) => {
+                let schema = ProtobufMessageSchema::new(&config.protobuf)?;
+                WriterSchema {
+                    schema_type: SchemaType::Protobuf,
+                    schema: BASE64_STANDARD.encode(&schema.file),
+                    record_name: schema.full_name,
+                    message_indexes: Some(schema.message_indexes),
+                }
+            }
+            _ => return Err("Schema registries require the `avro` or `protobuf` encoding.".into()),
+        };
+
+        Ok(SchemaRegistryEncoder {
+            registry: self.registry.build(proxy)?,
+            subject_name_strategy: self.subject_name_strategy,
+            auto_register: self.auto_register,
+            schema: Arc::new(schema),
+            headers: Default::default(),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "UPPERCASE")]
+enum SchemaType {
+    #[default]
+    Avro,
+    Protobuf,
+    Json,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SchemaResponse {
+    schema: String,
+    #[serde(default)]
+    schema_type: SchemaType,
+    #[serde(default)]
+    references: Vec<SchemaReference>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SchemaReference {
+    subject: String,
+    version: i32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RegisterRequest<'a> {
+    schema: &'a str,
+    schema_type: SchemaType,
+}
+
+#[derive(Debug, Deserialize)]
+struct RegisterResponse {
+    id: u32,
+}
+
+#[derive(Clone, Debug)]
+enum ReaderSchema {
+    Avro(AvroDeserializer),
+    Protobuf(ProtobufSchema),
+}
+
+/// Resolves the schemas of messages from their ID, caching them for the lifetime of the client.
+#[derive(Clone)]
+pub(crate) struct SchemaRegistry {
+    client: HttpClient,
+    url: String,
+    auth: Option<Auth>,
+    schemas: Arc<Mutex<HashMap<u32, ReaderSchema>>>,
+}
+
+impl SchemaRegistry {
+    /// Builds a decoder for a message in the Confluent wire format, returning it along with the
+    /// message stripped of its prefix.
+    pub(crate) async fn decoder(
+        &self,
+        message: Bytes,
+        log_namespace: LogNamespace,
+    ) -> Result<(Decoder, Bytes), SchemaRegistryError> {
+        let (schema_id, message) =
+            confluent::read_schema_id(message).context(InvalidMessageSnafu)?;
+        let (deserializer, message) = match self.schema(schema_id).await? {
+            ReaderSchema::Avro(deserializer) => (Deserializer::Avro(deserializer), message),
+            ReaderSchema::Protobuf(schema) => {
+                let (message_indexes, message) =
+                    confluent::read_message_indexes(message).context(InvalidMessageSnafu)?;
+                let deserializer = schema
+                    .deserializer(&message_indexes)
+                    .context(InvalidMessageSnafu)?;
+                (Deserializer::Protobuf(deserializer), message)
+            }
+        };
+
+        let decoder = Decoder::new(Framer::Bytes(BytesDecoder::new()), deserializer)
+            .with_log_namespace(log_namespace);
+        Ok((decoder, message))
+    }
+
+    async fn schema(&self, schema_id: u32) -> Result<ReaderSchema, SchemaRegistryError> {
+        if let Some(schema) = self.schemas.lock().unwrap().get(&schema_id) {
+            return Ok(schema.clone());
+        }
+
+        let response: SchemaResponse = self
+            .get(&format!("/schemas/ids/{schema_id}?format=serialized"))
+            .await?;
+        let schema = match response.schema_type {
+            SchemaType::Avro if response.references.is_empty() => ReaderSchema::Avro(
+                confluent::avro_deserializer(&response.schema).context(InvalidSchemaSnafu)?,
+            ),
+            SchemaType::Avro => {
+                return UnsupportedSchemaSnafu {
+                    reason: "Avro schema references are not supported",
+                }
+                .fail()
+            }
+            SchemaType::Protobuf => {
+                let mut files = Vec::new();
+                self.protobuf_references(response.references, &mut files)
+                    .await?;
+                files.push(decode_protobuf_file(&response.schema)?);
+                ReaderSchema::Protobuf(ProtobufSchema::decode(&files).context(InvalidSchemaSnafu)?)
+            }
+            SchemaType::Json => {
+                return UnsupportedSchemaSnafu {
+                    reason: "JSON schemas are not supported",
+                }
+                .fail()
+            }
+        };
+
+        self.schemas
+            .lock()
+            .unwrap()
+            .insert(schema_id, schema.clone());
+        Ok(schema)
+    }
+
+    /// Fetches the files imported by a Protobuf schema, each after the files that it imports.
+    fn protobuf_references<'a>(
+        &'a self,
+        references: Vec<SchemaReference>,
+        files: &'a mut Vec<Bytes>,
+    ) -> BoxFuture<'a, Result<(), SchemaRegistryError>> {
+        Box::pin(async move {
+            for reference in references {
+                let response: SchemaResponse = self
+                    .get(&format!(
+                        "/subjects/{}/versions/{}?format=serialized",
+                        encode_subject(&reference.subject),
+                        reference.version
+                    ))
+                    .await?;
+                self.protobuf_references(response.references, files).await?;
+                files.push(decode_protobuf_file(&response.schema)?);
+            }
+            Ok(())
+        })
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, SchemaRegistryError> {
+        let request = Request::get(format!("{}{path}", self.url))
+            .header(ACCEPT, CONTENT_TYPE_SCHEMA_REGISTRY)
+            .body(Bytes::new())
+            .context(BuildRequestSnafu)?;
+        self.send(request).await
+    }
+
+    async fn post<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &impl Serialize,
+    ) -> Result<T, SchemaRegistryError> {
+        let body = serde_json::to_vec(body).expect("Schema registry requests are valid JSON");
+        let request = Request::post(format!("{}{path}", self.url))
+            .header(ACCEPT, CONTENT_TYPE_SCHEMA_REGISTRY)
+            .header(CONTENT_TYPE, CONTENT_TYPE_SCHEMA_REGISTRY)
+            .body(Bytes::from(body))
+            .context(BuildRequestSnafu)?;
+        self.send(request).await
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        mut request: Request<Bytes>,
+    ) -> Result<T, SchemaRegistryError> {
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let response =
+            oauth2::send_authorized(request, |request| self.client.send(request.map(Body::from)))
+                .await
+                .context(RequestSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadResponseSnafu)?;
+
+        if !status.is_success() {
+            return ResponseStatusSnafu {
+                status,
+                body: String::from_utf8_lossy(&body),
+            }
+            .fail();
+        }
+        serde_json::from_slice(&body).context(ParseResponseSnafu)
+    }
+}
+
+/// The schema of the messages written by a sink.
+#[derive(Debug)]
+struct WriterSchema {
+    schema_type: SchemaType,
+    schema: String,
+    record_name: String,
+    message_indexes: Option<Vec<i32>>,
+}
+
+/// Resolves the prefix of the messages written to each topic, caching it per subject.
+#[derive(Clone)]
+pub(crate) struct SchemaRegistryEncoder {
+    registry: SchemaRegistry,
+    subject_name_strategy: SubjectNameStrategy,
+    auto_register: bool,
+    schema: Arc<WriterSchema>,
+    headers: Arc<Mutex<HashMap<String, Bytes>>>,
+}
+
+impl SchemaRegistryEncoder {
+    /// Returns the prefix of the messages written to the given topic, registering their schema
+    /// if needed.
+    pub(crate) async fn header(&self, topic: &str) -> Result<Bytes, SchemaRegistryError> {
+        let subject = match self.subject_name_strategy {
+            SubjectNameStrategy::TopicName => format!("{topic}-value"),
+            SubjectNameStrategy::RecordName => self.schema.record_name.clone(),
+            SubjectNameStrategy::TopicRecordName => {
+                format!("{topic}-{}", self.schema.record_name)
+            }
+        };
+        if let Some(header) = self.headers.lock().unwrap().get(&subject) {
+            return Ok(header.clone());
+        }
+
+        // Registering a schema that is already registered returns its existing ID.
+        let path = match self.auto_register {
+            true => format!("/subjects/{}/versions", encode_subject(&subject)),
+            false => format!("/subjects/{}", encode_subject(&subject)),
+        };
+        let request = RegisterRequest {
+            schema: &self.schema.schema,
+            schema_type: self.schema.schema_type,
+        };
+        let response: RegisterResponse = self.registry.post(&path, &request).await?;
+
+        let header = confluent::write_header(response.id, self.schema.message_indexes.as_deref());
+        self.headers.lock().unwrap().insert(subject, header.clone());
+        Ok(header)
+    }
+}
+
+fn encode_subject(subject: &str) -> String {
+    utf8_percent_encode(subject, NON_ALPHANUMERIC).to_string()
+}
+
+/// Protobuf schemas fetched with `format=serialized` are base64-encoded file descriptors.
+fn decode_protobuf_file(schema: &str) -> Result<Bytes, SchemaRegistryError> {
+    BASE64_STANDARD
+        .decode(schema)
+        .map(Bytes::from)
+        .map_err(|error| SchemaRegistryError::InvalidSchema {
+            source: error.into(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{convert::Infallible, path::PathBuf};
+
+    use bytes::{BufMut, BytesMut};
+    use futures::StreamExt;
+    use http::{Method, Response};
+    use tokio_util::codec::FramedRead;
+    use vector_lib::codecs::encoding::{
+        AvroSerializerOptions, ProtobufSerializerConfig, ProtobufSerializerOptions,
+    };
+
+    use super::*;
+    use crate::test_util::http::spawn_blackhole_http_server;
+
+    const AVRO_SCHEMA: &str = r#"{ "type": "record", "name": "Log", "namespace": "com.example",
+        "fields": [{ "name": "message", "type": "string" }] }"#;
+
+    fn protobuf_options() -> ProtobufSerializerOptions {
+        ProtobufSerializerOptions {
+            desc_file: PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("lib/codecs/tests/data/protobuf/protos/test_protobuf.desc"),
+            message_type: "test_protobuf.Person".to_string(),
+        }
+    }
+
+    fn json_response(status: StatusCode, body: serde_json::Value) -> Response<Body> {
+        Response::builder()
+            .status(status)
+            .header(CONTENT_TYPE, CONTENT_TYPE_SCHEMA_REGISTRY)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    /// A schema registry with an Avro schema registered under `logs-value` with ID 1, and a
+    /// Protobuf schema with ID 2.
+    async fn mock_registry(request: Request<Body>) -> Result<Response<Body>, Infallible> {
+        let protobuf_file = ProtobufMessageSchema::new(&protobuf_options())
+            .unwrap()
+            .file;
+        let response = match (request.method(), request.uri().path()) {
+            (&Method::GET, "/schemas/ids/1") => {
+                json_response(StatusCode::OK, serde_json::json!({ "schema": AVRO_SCHEMA }))
+            }
+            (&Method::GET, "/schemas/ids/2") => json_response(
+                StatusCode::OK,
+                serde_json::json!({
+                    "schemaType": "PROTOBUF",
+                    "schema": BASE64_STANDARD.encode(protobuf_file),
+                }),
+            ),
+            (&Method::POST, "/subjects/logs-value" | "/subjects/logs-value/versions") => {
+                json_response(StatusCode::OK, serde_json::json!({ "id": 1 }))
+            }
+            (&Method::POST, "/subjects/metrics-value/versions") => {
+                json_response(StatusCode::OK, serde_json::json!({ "id": 3 }))
+            }
+            (&Method::POST, "/subjects/test_protobuf.Person/versions") => {
+                json_response(StatusCode::OK, serde_json::json!({ "id": 2 }))
+            }
+            _ => json_response(
+                StatusCode::NOT_FOUND,
+                serde_json::json!({ "error_code": 40401, "message": "Subject not found." }),
+            ),
+        };
+        Ok(response)
+    }
+
+    async fn registry_config() -> SchemaRegistryConfig {
+        SchemaRegistryConfig {
+            url: spawn_blackhole_http_server(mock_registry).await.to_string(),
+            auth: None,
+            tls: None,
+        }
+    }
+
+    async fn decode(registry: &SchemaRegistry, message: Bytes) -> Vec<crate::event::Event> {
+        let (decoder, message) = registry
+            .decoder(message, LogNamespace::Vector)
+            .await
+            .unwrap();
+        let (events, _) = FramedRead::new(message.as_ref(), decoder)
+            .next()
+            .await
+            .unwrap()
+            .unwrap();
+        events.into_vec()
+    }
+
+    #[tokio::test]
+    async fn decodes_avro_messages() {
+        let registry = registry_config()
+            .await
+            .build(&ProxyConfig::default())
+            .unwrap();
+
+        let mut message = BytesMut::from(&confluent::write_header(1, None)[..]);
+        message.put_slice(b"\x06abc");
+        let events = decode(&registry, message.freeze()).await;
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["message"], "abc".into());
+        assert!(registry.schemas.lock().unwrap().contains_key(&1));
+    }
+
+    #[tokio::test]
+    async fn decodes_protobuf_messages() {
+        let registry = registry_config()
+            .await
+            .build(&ProxyConfig::default())
+            .unwrap();
+
+        let mut message = BytesMut::from(&confluent::write_header(2, Some(&[0]))[..]);
+        // `Person { name: "someone", id: 1 }`
+        message.put_slice(b"\x0a\x07someone\x10\x01");
+        let events = decode(&registry, message.freeze()).await;
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["name"], "someone".into());
+        assert_eq!(events[0].as_log()["id"], 1.into());
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_schemas() {
+        let registry = registry_config()
+            .await
+            .build(&ProxyConfig::default())
+            .unwrap();
+
+        let message = confluent::write_header(42, None);
+        let error = registry
+            .decoder(message, LogNamespace::Vector)
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error,
+            SchemaRegistryError::ResponseStatus {
+                status: StatusCode::NOT_FOUND,
+                ..
+            }
+        ));
+
+        let error = registry
+            .decoder(
+                Bytes::from_static(b"not in the wire format"),
+                LogNamespace::Vector,
+            )
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(error, SchemaRegistryError::InvalidMessage { .. }));
+    }
+
+    #[test]
+    fn retries_registry_failures() {
+        let status = |status| SchemaRegistryError::ResponseStatus {
+            status,
+            body: String::new(),
+        };
+        assert!(status(StatusCode::NOT_FOUND).is_permanent());
+        assert!(!status(StatusCode::SERVICE_UNAVAILABLE).is_permanent());
+        assert!(!status(StatusCode::UNAUTHORIZED).is_permanent());
+        assert!(SchemaRegistryError::UnsupportedSchema { reason: "test" }.is_permanent());
+    }
+
+    #[tokio::test]
+    async fn resolves_headers_per_subject() {
+        let avro = SerializerConfig::Avro {
+            avro: AvroSerializerOptions {
+                schema: AVRO_SCHEMA.to_string(),
+            },
+        };
+        let encoder = SchemaRegistrySinkConfig {
+            registry: registry_config().await,
+            subject_name_strategy: SubjectNameStrategy::TopicName,
+            auto_register: true,
+        }
+        .build(&avro, &ProxyConfig::default())
+        .unwrap();
+
+        assert_eq!(
+            encoder.header("logs").await.unwrap(),
+            confluent::write_header(1, None)
+        );
+        assert_eq!(
+            encoder.header("metrics").await.unwrap(),
+            confluent::write_header(3, None)
+        );
+    }
+
+    #[tokio::test]
+    async fn requires_registered_schema_without_auto_register() {
+        let avro = SerializerConfig::Avro {
+            avro: AvroSerializerOptions {
+                schema: AVRO_SCHEMA.to_string(),
+            },
+        };
+        let encoder = SchemaRegistrySinkConfig {
+            registry: registry_config().await,
+            subject_name_strategy: SubjectNameStrategy::TopicName,
+            auto_register: false,
+        }
+        .build(&avro, &ProxyConfig::default())
+        .unwrap();
+
+        assert_eq!(
+            encoder.header("logs").await.unwrap(),
+            confluent::write_header(1, None)
+        );
+        assert!(encoder.header("metrics").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn names_subjects_after_protobuf_message_types() {
+        let protobuf = SerializerConfig::Protobuf(ProtobufSerializerConfig {
+            protobuf: protobuf_options(),
+        });
+        let encoder = SchemaRegistrySinkConfig {
+            registry: registry_config().await,
+            subject_name_strategy: SubjectNameStrategy::RecordName,
+            auto_register: true,
+        }
+        .build(&protobuf, &ProxyConfig::default())
+        .unwrap();
+
+        assert_eq!(
+            encoder.header("logs").await.unwrap(),
+            confluent::write_header(2, Some(&[0]))
+        );
+    }
+
+    #[test]
+    fn rejects_other_encodings() {
+        let config = SchemaRegistrySinkConfig {
+            registry: SchemaRegistryConfig {
+                url: "http://localhost:8081".to_string(),
+                auth: None,
+                tls: None,
+            },
+            subject_name_strategy: SubjectNameStrategy::TopicName,
+            auto_register: true,
+        };
+        assert!(config
+            .build(
+                &SerializerConfig::Json(Default::default()),
+                &ProxyConfig::default()
+            )
+            .is_err());
+    }
+}
@@ -61,7 +61,7 @@ impl HttpResourceConfig {
         input_rx: mpsc::Receiver<TestEvent>,
         task_coordinator: &TaskCoordinator<Configuring>,
         runner_metrics: &Arc<Mutex<RunnerMetrics>>,
-    ) {
+    ) -> vector_lib::Result<()> {
         match direction {
             // The source will pull data from us.
             ResourceDirection::Pull => {
@@ -92,7 +92,7 @@ fn spawn_input_http_server(
     mut input_rx: mpsc::Receiver<TestEvent>,
     task_coordinator: &TaskCoordinator<Configuring>,
     runner_metrics: &Arc<Mutex<RunnerMetrics>>,
-) {
+) -> vector_lib::Result<()> {
     // This HTTP server will poll the input receiver for input events and buffer them. When a
     // request comes in on the right path/method, one buffered input event will be sent back. If no
     // buffered events are available when the request arrives, an empty response (204 No Content) is
@@ -100,7 +100,7 @@ fn spawn_input_http_server(
     let outstanding_events = Arc::new(Mutex::new(VecDeque::new()));
 
     // First, we'll build and spawn our HTTP server.
-    let encoder = codec.into_encoder();
+    let encoder = codec.into_encoder()?;
     let sendable_events = Arc::clone(&outstanding_events);
 
     let (resource_notifier, http_server_shutdown_tx) = spawn_http_server(
@@ -189,6 +189,8 @@ fn spawn_input_http_server(
 
         info!("HTTP server external input resource completed.");
     });
+
+    Ok(())
 }
 
 /// Spawns an HTTP client that pushes events to a source which is accepting events over HTTP.
@@ -198,12 +200,12 @@ fn spawn_input_http_client(
     mut input_rx: mpsc::Receiver<TestEvent>,
     task_coordinator: &TaskCoordinator<Configuring>,
     runner_metrics: &Arc<Mutex<RunnerMetrics>>,
-) {
+) -> vector_lib::Result<()> {
     // Spin up an HTTP client that will push the input data to the source on a
     // request-per-input-item basis. This runs serially and has no parallelism.
     let started = task_coordinator.track_started();
     let completed = task_coordinator.track_completed();
-    let mut encoder = codec.into_encoder();
+    let mut encoder = codec.into_encoder()?;
     let runner_metrics = Arc::clone(runner_metrics);
 
     tokio::spawn(async move {
@@ -276,6 +278,8 @@ fn spawn_input_http_client(
 
         info!("HTTP client external input resource completed.");
     });
+
+    Ok(())
 }
 
 /// Anything that the output side HTTP external resource needs
@@ -6,14 +6,15 @@ use std::sync::Arc;
 use tokio::sync::{mpsc, Mutex};
 use vector_lib::{
     codecs::{
-        decoding::{self, DeserializerConfig},
+        decoding::{self, CsvDeserializerOptions, DeserializerConfig},
         encoding::{
-            self, Framer, FramingConfig, JsonSerializerConfig, SerializerConfig,
-            TextSerializerConfig,
+            self, format::CsvSerializerOptions, CsvSerializerConfig, Framer, FramingConfig,
+            JsonSerializerConfig, SerializerConfig, TextSerializerConfig,
         },
         BytesEncoder,
     },
     config::LogNamespace,
+    lookup::{lookup_v2::ConfigTargetPath, owned_value_path, OwnedTargetPath},
 };
 use vector_lib::{config::DataType, event::Event};
 
@@ -75,7 +76,7 @@ impl ResourceCodec {
     ///
     /// The encoder is generated as an inverse to the input codec: if a decoding configuration was
     /// given, we generate an encoder that satisfies that decoding configuration, and vice versa.
-    pub fn into_encoder(&self) -> Encoder<encoding::Framer> {
+    pub fn into_encoder(&self) -> vector_lib::Result<Encoder<encoding::Framer>> {
         let (framer, serializer) = match self {
             Self::Encoding(config) => (
                 Framer::Bytes(BytesEncoder),
@@ -95,11 +96,11 @@ impl ResourceCodec {
             }
             Self::Decoding(config) => (
                 decoder_framing_to_encoding_framer(config.framing()),
-                deserializer_config_to_serializer(config.config()),
+                deserializer_config_to_serializer(config.config())?,
             ),
         };
 
-        Encoder::<encoding::Framer>::new(framer, serializer)
+        Ok(Encoder::<encoding::Framer>::new(framer, serializer))
     }
 
     /// Gets a decoder for this codec.
@@ -145,13 +146,17 @@ impl From<DecodingConfig> for ResourceCodec {
     }
 }
 
-fn deserializer_config_to_serializer(config: &DeserializerConfig) -> encoding::Serializer {
+fn deserializer_config_to_serializer(
+    config: &DeserializerConfig,
+) -> vector_lib::Result<encoding::Serializer> {
     let serializer_config = match config {
         // TODO: This isn't necessarily a one-to-one conversion, at least not in the future when
         // "bytes" can be a top-level field and we aren't implicitly decoding everything into the
         // `message` field... but it's close enough for now.
         DeserializerConfig::Bytes => SerializerConfig::Text(TextSerializerConfig::default()),
         DeserializerConfig::Json { .. } => SerializerConfig::Json(JsonSerializerConfig::default()),
+        DeserializerConfig::Logfmt(_) => SerializerConfig::Logfmt,
+        DeserializerConfig::Csv(config) => csv_serializer_config(&config.csv)?,
         DeserializerConfig::Protobuf(config) => {
             SerializerConfig::Protobuf(vector_lib::codecs::encoding::ProtobufSerializerConfig {
                 protobuf: vector_lib::codecs::encoding::ProtobufSerializerOptions {
@@ -174,9 +179,41 @@ fn deserializer_config_to_serializer(config: &DeserializerConfig) -> encoding::S
         DeserializerConfig::Vrl { .. } => unimplemented!(),
     };
 
-    serializer_config
+    Ok(serializer_config
         .build()
-        .expect("building serializer should never fail")
+        .expect("building serializer should never fail"))
+}
+
+/// Builds a CSV serializer writing the columns the CSV deserializer expects, in order.
+fn csv_serializer_config(options: &CsvDeserializerOptions) -> vector_lib::Result<SerializerConfig> {
+    // The serializer encodes each event on its own, so it can't write the header row.
+    if options.has_header || options.columns.is_empty() {
+        return Err("Encoding CSV requires the columns to be configured, without a header.".into());
+    }
+    // The serializer's quote character isn't configurable.
+    if options.quote != b'"' {
+        return Err("Encoding CSV only supports the default quote character.".into());
+    }
+    // The deserializer inserts each column as a top-level field, rather than parsing its name as
+    // a path.
+    let fields = options
+        .columns
+        .iter()
+        .map(|column| {
+            let name = column.name.as_str();
+            ConfigTargetPath(OwnedTargetPath::event(owned_value_path!(name)))
+        })
+        .collect();
+
+    Ok(SerializerConfig::Csv(CsvSerializerConfig::new(
+        CsvSerializerOptions {
+            delimiter: options.delimiter,
+            double_quote: options.double_quote,
+            escape: options.escape,
+            fields,
+            ..Default::default()
+        },
+    )))
 }
 
 fn decoder_framing_to_encoding_framer(framing: &decoding::FramingConfig) -> encoding::Framer {
@@ -195,9 +232,7 @@ fn decoder_framing_to_encoding_framer(framing: &decoding::FramingConfig) -> enco
             })
         }
         decoding::FramingConfig::NewlineDelimited(_) => encoding::FramingConfig::NewlineDelimited,
-        // TODO: There's no equivalent octet counting framer for encoding... although
-        // there's no particular reason that would make it hard to write.
-        decoding::FramingConfig::OctetCounting(_) => todo!(),
+        decoding::FramingConfig::OctetCounting(_) => encoding::FramingConfig::OctetCounting,
     };
 
     framing_config.build()
@@ -211,9 +246,11 @@ fn serializer_config_to_deserializer(
         SerializerConfig::Csv { .. } => todo!(),
         SerializerConfig::Gelf => DeserializerConfig::Gelf(Default::default()),
         SerializerConfig::Json(_) => DeserializerConfig::Json(Default::default()),
-        SerializerConfig::Logfmt => todo!(),
+        SerializerConfig::Logfmt => DeserializerConfig::Logfmt(Default::default()),
         SerializerConfig::Native => DeserializerConfig::Native,
         SerializerConfig::NativeJson => DeserializerConfig::NativeJson(Default::default()),
+        #[cfg(feature = "codecs-parquet")]
+        SerializerConfig::Parquet { .. } => return Err("Decoding Parquet is not supported.".into()),
         SerializerConfig::Protobuf(config) => {
             DeserializerConfig::Protobuf(vector_lib::codecs::decoding::ProtobufDeserializerConfig {
                 protobuf: vector_lib::codecs::decoding::ProtobufDeserializerOptions {
@@ -223,6 +260,12 @@ fn serializer_config_to_deserializer(
             })
         }
         SerializerConfig::RawMessage | SerializerConfig::Text(_) => DeserializerConfig::Bytes,
+        #[cfg(feature = "codecs-syslog")]
+        SerializerConfig::Syslog(_) => DeserializerConfig::Syslog(Default::default()),
+        #[cfg(not(feature = "codecs-syslog"))]
+        SerializerConfig::Syslog(_) => {
+            return Err("Decoding syslog requires the `codecs-syslog` feature.".into())
+        }
     };
 
     deserializer_config.build()
@@ -247,6 +290,9 @@ fn encoder_framing_to_decoding_framer(framing: encoding::FramingConfig) -> decod
         encoding::FramingConfig::NewlineDelimited => {
             decoding::FramingConfig::NewlineDelimited(Default::default())
         }
+        encoding::FramingConfig::OctetCounting => {
+            decoding::FramingConfig::OctetCounting(Default::default())
+        }
     };
 
     framing_config.build()
@@ -338,7 +384,7 @@ impl ExternalResource {
         input_rx: mpsc::Receiver<TestEvent>,
         task_coordinator: &TaskCoordinator<Configuring>,
         runner_metrics: &Arc<Mutex<RunnerMetrics>>,
-    ) {
+    ) -> vector_lib::Result<()> {
         match self.definition {
             ResourceDefinition::Http(http_config) => http_config.spawn_as_input(
                 self.direction,
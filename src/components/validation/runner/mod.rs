@@ -462,7 +462,10 @@ fn build_external_resource(
         .as_ref()
         .map(|resource| resource.codec.clone());
 
-    let maybe_encoder = resource_codec.as_ref().map(|codec| codec.into_encoder());
+    let maybe_encoder = resource_codec
+        .as_ref()
+        .map(|codec| codec.into_encoder())
+        .transpose()?;
 
     match component_type {
         ComponentType::Source => {
@@ -473,7 +476,7 @@ fn build_external_resource(
             let (tx, rx) = mpsc::channel(1024);
             let resource =
                 maybe_external_resource.expect("a source must always have an external resource");
-            resource.spawn_as_input(rx, input_task_coordinator, runner_metrics);
+            resource.spawn_as_input(rx, input_task_coordinator, runner_metrics)?;
 
             Ok((
                 RunnerInput::External(tx),
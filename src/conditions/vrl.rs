@@ -1,7 +1,7 @@
 use vector_lib::configurable::configurable_component;
 use vector_lib::{compile_vrl, emit, TimeZone};
 use vrl::compiler::runtime::{Runtime, RuntimeResult, Terminate};
-use vrl::compiler::{CompilationResult, CompileConfig, Program, TypeState, VrlRuntime};
+use vrl::compiler::{CompilationResult, CompileConfig, Function, Program, TypeState, VrlRuntime};
 use vrl::diagnostic::Formatter;
 use vrl::value::Value;
 
@@ -48,6 +48,17 @@ impl ConditionalConfig for VrlConfig {
             .chain(vector_vrl_functions::all())
             .collect::<Vec<_>>();
 
+        self.build_with_functions(&functions, enrichment_tables)
+    }
+}
+
+impl VrlConfig {
+    /// Builds the condition, only allowing its source to call the given functions.
+    pub(crate) fn build_with_functions(
+        &self,
+        functions: &[Box<dyn Function>],
+        enrichment_tables: &vector_lib::enrichment::TableRegistry,
+    ) -> crate::Result<Condition> {
         let state = TypeState::default();
 
         let mut config = CompileConfig::default();
@@ -58,7 +69,7 @@ impl ConditionalConfig for VrlConfig {
             program,
             warnings,
             config: _,
-        } = compile_vrl(&self.source, &functions, &state, config).map_err(|diagnostics| {
+        } = compile_vrl(&self.source, functions, &state, config).map_err(|diagnostics| {
             Formatter::new(&self.source, diagnostics)
                 .colored()
                 .to_string()
@@ -2,10 +2,11 @@ use std::net::{Ipv4Addr, SocketAddr};
 
 use url::Url;
 use vector_lib::configurable::configurable_component;
+use vector_lib::sensitive_string::SensitiveString;
 
 /// API options.
 #[configurable_component]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[serde(default, deny_unknown_fields)]
 pub struct Options {
     /// Whether or not the API endpoint is available.
@@ -23,6 +24,15 @@ pub struct Options {
     /// Whether or not the GraphQL endpoint is enabled
     #[serde(default = "default_graphql", skip_serializing_if = "is_true")]
     pub graphql: bool,
+
+    /// A token that clients must provide to change the state of Vector through the API.
+    ///
+    /// GraphQL mutations such as reloading the configuration or pausing a source are only allowed
+    /// for requests with an `Authorization: Bearer <token>` header, so they are disabled unless a
+    /// token is set. Queries and subscriptions are not affected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[configurable(metadata(docs::examples = "${VECTOR_API_TOKEN}"))]
+    pub token: Option<SensitiveString>,
 }
 
 impl Default for Options {
@@ -32,6 +42,7 @@ impl Default for Options {
             playground: default_playground(),
             address: default_address(),
             graphql: default_graphql(),
+            token: None,
         }
     }
 }
@@ -89,11 +100,20 @@ impl Options {
             }
         };
 
+        // Try to merge token
+        let token = match (self.token.clone(), other.token) {
+            (Some(a), Some(b)) if a != b => {
+                return Err("Conflicting `api` token.".to_owned());
+            }
+            (a, b) => a.or(b),
+        };
+
         let options = Options {
             address,
             enabled: self.enabled | other.enabled,
             playground: self.playground & other.playground,
             graphql: self.graphql & other.graphql,
+            token,
         };
 
         *self = options;
@@ -108,6 +128,7 @@ fn bool_merge() {
         address: None,
         playground: false,
         graphql: false,
+        token: None,
     };
 
     a.merge(Options::default()).unwrap();
@@ -118,7 +139,8 @@ fn bool_merge() {
             enabled: true,
             address: default_address(),
             playground: false,
-            graphql: false
+            graphql: false,
+            token: None,
         }
     );
 }
@@ -131,6 +153,7 @@ fn bind_merge() {
         address: Some(address),
         playground: true,
         graphql: true,
+        token: None,
     };
 
     a.merge(Options::default()).unwrap();
@@ -142,6 +165,7 @@ fn bind_merge() {
             address: Some(address),
             playground: true,
             graphql: true,
+            token: None,
         }
     );
 }
@@ -160,3 +184,21 @@ fn bind_conflict() {
 
     assert!(a.merge(b).is_err());
 }
+
+#[test]
+fn token_conflict() {
+    let mut a = Options {
+        token: Some("a".to_owned().into()),
+        ..Options::default()
+    };
+
+    a.merge(Options::default()).unwrap();
+    assert_eq!(a.token, Some("a".to_owned().into()));
+
+    let b = Options {
+        token: Some("b".to_owned().into()),
+        ..Options::default()
+    };
+
+    assert!(a.merge(b).is_err());
+}
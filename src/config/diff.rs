@@ -4,7 +4,7 @@ use indexmap::IndexMap;
 
 use super::{ComponentKey, Config};
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ConfigDiff {
     pub sources: Difference,
     pub transforms: Difference,
@@ -54,7 +54,7 @@ impl ConfigDiff {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Difference {
     pub to_remove: HashSet<ComponentKey>,
     pub to_change: HashSet<ComponentKey>,
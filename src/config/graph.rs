@@ -3,7 +3,7 @@ use std::collections::{HashMap, HashSet, VecDeque};
 
 use super::{
     schema, ComponentKey, DataType, OutputId, SinkOuter, SourceOuter, SourceOutput, TransformOuter,
-    TransformOutput,
+    TransformOutput, DROPPED_OUTPUT,
 };
 
 #[derive(Debug, Clone)]
@@ -17,6 +17,7 @@ pub enum Node {
     },
     Sink {
         ty: DataType,
+        reroute_dropped: bool,
     },
 }
 
@@ -90,6 +91,7 @@ impl Graph {
                 id.clone(),
                 Node::Sink {
                     ty: config.inner.input().data_type(),
+                    reroute_dropped: config.reroute_dropped,
                 },
             );
         }
@@ -156,7 +158,7 @@ impl Graph {
         match self.nodes[key] {
             Node::Source { .. } => panic!("no inputs on sources"),
             Node::Transform { in_ty, .. } => in_ty,
-            Node::Sink { ty } => ty,
+            Node::Sink { ty, .. } => ty,
         }
     }
 
@@ -164,8 +166,7 @@ impl Graph {
     ///
     /// # Panics
     ///
-    /// Will panic if the given id is not present in the graph or identifies a sink, which can't
-    /// have inputs.
+    /// Will panic if the given id is not present in the graph or identifies a missing output.
     fn get_output_type(&self, id: &OutputId) -> DataType {
         match &self.nodes[&id.component] {
             Node::Source { outputs } => outputs
@@ -178,7 +179,11 @@ impl Graph {
                 .find(|output| output.port == id.port)
                 .map(|output| output.ty)
                 .expect("output didn't exist"),
-            Node::Sink { .. } => panic!("no outputs on sinks"),
+            Node::Sink {
+                ty,
+                reroute_dropped: true,
+            } if id.port.as_deref() == Some(DROPPED_OUTPUT) => *ty,
+            Node::Sink { .. } => panic!("output didn't exist"),
         }
     }
 
@@ -263,7 +268,12 @@ impl Graph {
         self.nodes
             .iter()
             .flat_map(|(key, node)| match node {
-                Node::Sink { .. } => vec![],
+                Node::Sink {
+                    reroute_dropped, ..
+                } => reroute_dropped
+                    .then(|| OutputId::from((key, DROPPED_OUTPUT.to_string())))
+                    .into_iter()
+                    .collect(),
                 Node::Source { outputs } => outputs
                     .iter()
                     .map(|output| OutputId {
@@ -355,7 +365,7 @@ impl Graph {
             .into_iter()
             .filter(|path| {
                 if let Some(key) = path.last() {
-                    matches!(self.nodes.get(key), Some(Node::Sink { ty: _ }))
+                    matches!(self.nodes.get(key), Some(Node::Sink { .. }))
                 } else {
                     false
                 }
@@ -429,7 +439,13 @@ mod test {
         fn add_sink(&mut self, id: &str, ty: DataType, inputs: Vec<&str>) {
             let id = ComponentKey::from(id);
             let inputs = clean_inputs(inputs);
-            self.nodes.insert(id.clone(), Node::Sink { ty });
+            self.nodes.insert(
+                id.clone(),
+                Node::Sink {
+                    ty,
+                    reroute_dropped: false,
+                },
+            );
             for from in inputs {
                 self.edges.push(Edge {
                     from,
@@ -632,6 +648,88 @@ mod test {
         );
     }
 
+    #[test]
+    fn allows_sink_dropped_outputs() {
+        let mut graph = Graph::default();
+        graph.add_source("log_source", DataType::Log);
+        graph.add_sink("log_sink", DataType::Log, vec!["log_source"]);
+        graph.add_sink("plain_sink", DataType::Log, vec!["log_source"]);
+        match graph.nodes.get_mut(&ComponentKey::from("log_sink")) {
+            Some(Node::Sink {
+                reroute_dropped, ..
+            }) => *reroute_dropped = true,
+            _ => panic!("invalid sink"),
+        }
+        graph.add_sink("dead_letter_sink", DataType::Log, vec![]);
+        graph.add_sink("bad_sink", DataType::Log, vec![]);
+
+        assert_eq!(
+            Ok(()),
+            graph.test_add_input("dead_letter_sink", "log_sink.dropped")
+        );
+
+        let expected =
+            "Input \"plain_sink.dropped\" for sink \"bad_sink\" doesn't match any components."
+                .to_string();
+        assert_eq!(
+            Err(expected),
+            graph.test_add_input("bad_sink", "plain_sink.dropped")
+        );
+
+        assert_eq!(Ok(()), graph.typecheck());
+        assert_eq!(Ok(()), graph.check_for_cycles());
+    }
+
+    #[test]
+    fn forwards_definitions_to_sink_dropped_outputs() {
+        use vector_lib::lookup::owned_value_path;
+        use vrl::value::Kind;
+
+        use crate::{
+            config::ConfigBuilder,
+            test_util::mock::{basic_sink, basic_source},
+            topology::schema,
+        };
+
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", basic_source().1);
+        builder.add_sink("out", &["in"], basic_sink(1).1);
+        builder.add_sink("dead_letter", &["out.dropped"], basic_sink(1).1);
+        builder
+            .sinks
+            .get_mut(&ComponentKey::from("out"))
+            .unwrap()
+            .reroute_dropped = true;
+        let config = builder.build().unwrap();
+
+        let dropped = OutputId::from((&ComponentKey::from("out"), DROPPED_OUTPUT.to_string()));
+        let definitions = schema::input_definitions(
+            &[dropped.clone()],
+            &config,
+            Default::default(),
+            &mut HashMap::new(),
+        )
+        .unwrap();
+        let possible = schema::possible_definitions(
+            &[dropped.clone()],
+            &config,
+            Default::default(),
+            &mut HashMap::new(),
+        )
+        .unwrap();
+        assert_eq!(definitions, possible);
+
+        assert_eq!(definitions.len(), 1);
+        let (output, definition) = &definitions[0];
+        assert_eq!(output, &dropped);
+        assert_eq!(
+            definition
+                .event_kind()
+                .at_path(&owned_value_path!("metadata", "dropped", "reason")),
+            Kind::bytes()
+        );
+    }
+
     #[test]
     fn disallows_ambiguous_inputs() {
         let mut graph = Graph::default();
@@ -239,6 +239,15 @@ pub fn load_secret_backends_from_paths(
     loader_from_paths(SecretBackendLoader::new(), config_paths)
 }
 
+/// Collects the paths that the secret backends configured in `ConfigPaths` ask to be watched.
+///
+/// Errors are ignored, as they are reported when the configuration itself is loaded.
+pub fn watched_secret_paths(config_paths: &[ConfigPath]) -> Vec<PathBuf> {
+    load_secret_backends_from_paths(config_paths)
+        .map(|loader| loader.watched_paths())
+        .unwrap_or_default()
+}
+
 pub fn load_from_str(input: &str, format: Format) -> Result<Config, Vec<String>> {
     let builder = load_from_inputs(std::iter::once((input.as_bytes(), format)))?;
     let (config, build_warnings) = builder.build_with_warnings()?;
@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
     io::Read,
+    path::PathBuf,
 };
 
 use futures::TryFutureExt;
@@ -85,6 +86,13 @@ impl SecretBackendLoader {
     pub(crate) fn has_secrets_to_retrieve(&self) -> bool {
         !self.secret_keys.is_empty()
     }
+
+    pub(crate) fn watched_paths(&self) -> Vec<PathBuf> {
+        self.backends
+            .values()
+            .flat_map(SecretBackend::watched_paths)
+            .collect()
+    }
 }
 
 impl Process for SecretBackendLoader {
@@ -49,12 +49,14 @@ pub use enrichment_table::{EnrichmentTableConfig, EnrichmentTableOuter};
 pub use format::{Format, FormatHint};
 pub use loading::{
     load, load_builder_from_paths, load_from_paths, load_from_paths_with_provider_and_secrets,
-    load_from_str, load_source_from_paths, merge_path_lists, process_paths, COLLECTOR,
-    CONFIG_PATHS,
+    load_from_str, load_source_from_paths, merge_path_lists, process_paths, watched_secret_paths,
+    COLLECTOR, CONFIG_PATHS,
 };
 pub use provider::ProviderConfig;
 pub use secret::SecretBackend;
-pub use sink::{BoxedSink, SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter};
+pub use sink::{
+    BoxedSink, SinkConfig, SinkContext, SinkHealthcheckOptions, SinkOuter, DROPPED_OUTPUT,
+};
 pub use source::{BoxedSource, SourceConfig, SourceContext, SourceOuter};
 pub use transform::{
     get_transform_output_ids, BoxedTransform, TransformConfig, TransformContext, TransformOuter,
@@ -143,6 +145,10 @@ impl Config {
         self.sinks.get(id)
     }
 
+    pub(crate) fn remove_sink(&mut self, id: &ComponentKey) -> Option<SinkOuter<OutputId>> {
+        self.sinks.shift_remove(id)
+    }
+
     pub fn inputs_for_node(&self, id: &ComponentKey) -> Option<&[OutputId]> {
         self.transforms
             .get(id)
@@ -190,6 +196,15 @@ impl Config {
                     .map(|input| (sink.clone(), input.clone()))
                     .collect();
                 self.propagate_acks_rec(inputs);
+            } else if let Some(upstream_sink) = self.sinks.get(component) {
+                // Events rerouted from the `dropped` output of a sink carry the finalizers of the
+                // events that sink received.
+                let inputs = upstream_sink
+                    .inputs
+                    .iter()
+                    .map(|input| (sink.clone(), input.clone()))
+                    .collect();
+                self.propagate_acks_rec(inputs);
             }
         }
     }
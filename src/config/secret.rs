@@ -1,4 +1,7 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
 
 use enum_dispatch::enum_dispatch;
 use vector_lib::configurable::NamedComponent;
@@ -13,4 +16,9 @@ pub trait SecretBackend: NamedComponent + core::fmt::Debug + Send + Sync {
         secret_keys: HashSet<String>,
         signal_rx: &mut signal::SignalRx,
     ) -> crate::Result<HashMap<String, String>>;
+
+    /// Paths that, when watching the configuration for changes, trigger a reload when modified.
+    fn watched_paths(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
 }
@@ -15,12 +15,15 @@ use vector_lib::{
     sink::VectorSink,
 };
 
-use super::{dot_graph::GraphConfig, schema, ComponentKey, ProxyConfig, Resource};
+use super::{dot_graph::GraphConfig, schema, ComponentKey, OutputId, ProxyConfig, Resource};
 use crate::extra_context::ExtraContext;
 use crate::sinks::{util::UriSerde, Healthcheck};
 
 pub type BoxedSink = Box<dyn SinkConfig>;
 
+/// The name of the output that sinks reroute rejected events to.
+pub const DROPPED_OUTPUT: &str = "dropped";
+
 impl Configurable for BoxedSink {
     fn referenceable_name() -> Option<&'static str> {
         Some("vector::sinks::Sinks")
@@ -79,6 +82,31 @@ where
     #[serde(default, skip_serializing_if = "vector_lib::serde::is_default")]
     proxy: ProxyConfig,
 
+    /// Reroutes events rejected by the sink to a named output instead of dropping them.
+    ///
+    /// When a sink permanently fails to deliver events, such as when a request is rejected with a
+    /// non-retriable error or an individual item of a bulk request fails, the events are normally
+    /// marked as rejected and dropped.
+    ///
+    /// When enabled, the events that end up rejected are forwarded to a specially-named output,
+    /// `dropped`, which can be used as an input by other components, for example, to write them to
+    /// a file or an object store for later replay. The events are annotated with additional fields
+    /// describing why they were dropped, including the error reported by the sink, when available.
+    ///
+    /// While the `dropped` output has consumers, a copy of each event is kept until the sink has
+    /// finished processing it. The sink is held back when too many events are awaiting their
+    /// status.
+    ///
+    /// Events are rerouted at the granularity the sink reports rejections at. For example, when
+    /// some of the items of an `elasticsearch` bulk request fail, all the events of that request
+    /// are rerouted.
+    ///
+    /// Outputs of sinks are not matched by wildcards in `inputs`, so they must be referenced
+    /// explicitly, for example, `my_sink.dropped`.
+    #[serde(default, skip_serializing_if = "vector_lib::serde::is_default")]
+    #[configurable(metadata(docs::advanced))]
+    pub reroute_dropped: bool,
+
     #[serde(flatten)]
     #[configurable(metadata(docs::hidden))]
     pub inner: BoxedSink,
@@ -100,6 +128,7 @@ where
             healthcheck_uri: None,
             inner: inner.into(),
             proxy: Default::default(),
+            reroute_dropped: false,
             graph: Default::default(),
         }
     }
@@ -137,6 +166,12 @@ where
         &self.proxy
     }
 
+    /// Gets the ID of the output that rejected events are rerouted to, if enabled.
+    pub fn dropped_output(&self, id: &ComponentKey) -> Option<OutputId> {
+        self.reroute_dropped
+            .then(|| OutputId::from((id, DROPPED_OUTPUT.to_string())))
+    }
+
     pub(super) fn map_inputs<U>(self, f: impl Fn(&T) -> U) -> SinkOuter<U>
     where
         U: Configurable + Serialize,
@@ -157,6 +192,7 @@ where
             healthcheck: self.healthcheck,
             healthcheck_uri: self.healthcheck_uri,
             proxy: self.proxy,
+            reroute_dropped: self.reroute_dropped,
             graph: self.graph,
         }
     }
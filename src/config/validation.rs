@@ -349,7 +349,13 @@ pub fn warnings(config: &Config) -> Vec<String> {
         .collect::<Vec<_>>()
     });
 
-    for (input_type, id) in transform_ids.chain(source_ids) {
+    let sink_ids = config
+        .sinks
+        .iter()
+        .flat_map(|(key, sink)| sink.dropped_output(key))
+        .map(|output| ("sink", output));
+
+    for (input_type, id) in transform_ids.chain(source_ids).chain(sink_ids) {
         if !config
             .transforms
             .iter()
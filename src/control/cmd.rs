@@ -0,0 +1,73 @@
+use vector_lib::api_client::{gql::ControlMutationExt, Client};
+
+use super::Command;
+
+/// CLI command func for issuing control mutations to a local/remote Vector API server.
+pub(crate) async fn cmd(opts: &super::Opts) -> exitcode::ExitCode {
+    let url = opts.url();
+    let client = Client::new(url.clone()).with_token(opts.token.clone());
+
+    let (errors, done) = match &opts.command {
+        Command::Reload => (
+            client.reload_config().await.map(|response| response.errors),
+            "Configuration reloaded.".to_string(),
+        ),
+        Command::PauseSource { component_id } => (
+            client
+                .pause_source(component_id.clone())
+                .await
+                .map(|response| response.errors),
+            format!("Source {component_id:?} paused."),
+        ),
+        Command::ResumeSource { component_id } => (
+            client
+                .resume_source(component_id.clone())
+                .await
+                .map(|response| response.errors),
+            format!("Source {component_id:?} resumed."),
+        ),
+        Command::SetLogFilter { filter } => (
+            client
+                .set_log_filter(filter.clone())
+                .await
+                .map(|response| response.errors),
+            format!("Log filter set to {filter:?}."),
+        ),
+        Command::DrainSink { component_id } => (
+            client
+                .drain_sink(component_id.clone())
+                .await
+                .map(|response| response.errors),
+            format!("Sink {component_id:?} drained."),
+        ),
+    };
+
+    #[allow(clippy::print_stdout, clippy::print_stderr)]
+    match errors {
+        Ok(None) => {
+            println!("{done}");
+            exitcode::OK
+        }
+        Ok(Some(errors)) => {
+            for error in errors {
+                eprintln!("{}", error.message);
+            }
+            exitcode::SOFTWARE
+        }
+        Err(error) => {
+            eprintln!(
+                indoc::indoc! {"
+                Vector API server isn't reachable ({}): {:#}
+
+                Have you enabled the API?
+
+                To enable the API, add the following to your Vector config file:
+
+                [api]
+                    enabled = true"},
+                url, error
+            );
+            exitcode::UNAVAILABLE
+        }
+    }
+}
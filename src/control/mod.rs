@@ -0,0 +1,66 @@
+//! Control subcommand
+mod cmd;
+
+use clap::{Parser, Subcommand};
+pub(crate) use cmd::cmd;
+use url::Url;
+
+use crate::config::api::default_graphql_url;
+
+/// Control options
+#[derive(Parser, Debug, Clone)]
+#[command(rename_all = "kebab-case")]
+pub struct Opts {
+    /// GraphQL API server endpoint
+    #[arg(short, long, global = true)]
+    url: Option<Url>,
+
+    /// API token, which must match the `api.token` option of the API server
+    #[arg(short, long, env = "VECTOR_API_TOKEN", global = true)]
+    token: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+impl Opts {
+    /// Use the provided URL as the Vector GraphQL API server, or default to the local port
+    /// provided by the API config.
+    pub fn url(&self) -> Url {
+        self.url.clone().unwrap_or_else(default_graphql_url)
+    }
+}
+
+/// Control commands
+#[derive(Subcommand, Debug, Clone)]
+#[command(rename_all = "kebab-case")]
+enum Command {
+    /// Reload the configuration from disk
+    Reload,
+
+    /// Pause a source, holding back the events it sends to downstream components
+    PauseSource {
+        /// ID of the source to pause
+        component_id: String,
+    },
+
+    /// Resume a paused source
+    ResumeSource {
+        /// ID of the source to resume
+        component_id: String,
+    },
+
+    /// Replace the filter applied to internal logs, using the same format as `VECTOR_LOG`, e.g.
+    /// `vector=debug`
+    SetLogFilter {
+        /// Logging filter targets
+        filter: String,
+    },
+
+    /// Disconnect a sink from its inputs and wait for it to process its buffered events. The sink
+    /// is started again by the next configuration reload
+    DrainSink {
+        /// ID of the sink to drain
+        component_id: String,
+    },
+}
@@ -34,6 +34,7 @@ use vector_lib::sensitive_string::SensitiveString;
 use crate::{
     config::ProxyConfig,
     internal_events::{http_client, HttpServerRequestReceived, HttpServerResponseSent},
+    oauth2::{OAuth2Config, OAuth2Error},
     tls::{tls_connector_builder, MaybeTlsSettings, TlsError},
 };
 
@@ -56,6 +57,8 @@ pub enum HttpError {
     CallRequest { source: hyper::Error },
     #[snafu(display("Failed to build HTTP request: {}", source))]
     BuildRequest { source: http::Error },
+    #[snafu(display("Failed to obtain OAuth2 access token: {}", source))]
+    Authorize { source: OAuth2Error },
 }
 
 impl HttpError {
@@ -64,7 +67,8 @@ impl HttpError {
             HttpError::BuildRequest { .. } | HttpError::MakeProxyConnector { .. } => false,
             HttpError::CallRequest { .. }
             | HttpError::BuildTlsConnector { .. }
-            | HttpError::MakeHttpsConnector { .. } => true,
+            | HttpError::MakeHttpsConnector { .. }
+            | HttpError::Authorize { .. } => true,
         }
     }
 }
@@ -299,6 +303,15 @@ pub enum Auth {
         /// The bearer authentication token.
         token: SensitiveString,
     },
+
+    /// OAuth2 authentication.
+    ///
+    /// An access token is obtained from the authorization server with the client credentials or
+    /// the JWT bearer grant, and passed as a bearer token. The token is refreshed ahead of its
+    /// expiry, and requests rejected with a `401 Unauthorized` response are sent once more with
+    /// a new token.
+    #[serde(rename = "oauth2")]
+    OAuth2(OAuth2Config),
 }
 
 pub trait MaybeAuth: Sized {
@@ -317,10 +330,16 @@ impl MaybeAuth for Option<Auth> {
 
 impl Auth {
     pub fn apply<B>(&self, req: &mut Request<B>) {
-        self.apply_headers_map(req.headers_mut())
+        match self {
+            Auth::OAuth2(config) => config.apply(req),
+            _ => self.apply_headers_map(req.headers_mut()),
+        }
     }
 
     pub fn apply_builder(&self, mut builder: Builder) -> Builder {
+        if let Auth::OAuth2(config) = self {
+            return config.apply_builder(builder);
+        }
         if let Some(map) = builder.headers_mut() {
             self.apply_headers_map(map)
         }
@@ -337,6 +356,7 @@ impl Auth {
                 Ok(auth) => map.typed_insert(auth),
                 Err(error) => error!(message = "Invalid bearer token.", token = %token, %error),
             },
+            Auth::OAuth2(config) => config.apply_headers_map(map),
         }
     }
 }
@@ -1,4 +1,9 @@
-use vector_lib::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+use std::path::Path;
+
+use metrics::{counter, gauge};
+use vector_lib::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, InternalEvent, INTENTIONAL,
+};
 
 #[derive(Debug)]
 pub struct DedupeEventsDropped {
@@ -13,3 +18,54 @@ impl InternalEvent for DedupeEventsDropped {
         });
     }
 }
+
+#[derive(Debug)]
+pub struct DedupeCacheLookup {
+    pub hit: bool,
+}
+
+impl InternalEvent for DedupeCacheLookup {
+    fn emit(self) {
+        if self.hit {
+            counter!("dedupe_cache_hits_total").increment(1);
+        } else {
+            counter!("dedupe_cache_misses_total").increment(1);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DedupeCacheSize {
+    pub size: usize,
+}
+
+impl InternalEvent for DedupeCacheSize {
+    fn emit(self) {
+        gauge!("dedupe_cache_events").set(self.size as f64);
+    }
+}
+
+#[derive(Debug)]
+pub struct DedupeCacheIoError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl<'a> InternalEvent for DedupeCacheIoError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Unable to access the persisted deduplication cache.",
+            error = %self.error,
+            path = ?self.path,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
@@ -184,3 +184,28 @@ impl<'a> InternalEvent for HttpInternalError<'a> {
         .increment(1);
     }
 }
+
+#[derive(Debug)]
+pub struct HttpResponseProgramError<'a> {
+    pub error: &'a str,
+}
+
+impl<'a> InternalEvent for HttpResponseProgramError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed computing the response to a request.",
+            error = %self.error,
+            error_code = "response_program_failed",
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "response_program_failed",
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
@@ -95,6 +95,24 @@ impl<'a> InternalEvent for GotHttpWarning<'a> {
     }
 }
 
+#[derive(Debug)]
+pub struct OAuth2TokenRefreshError<'a> {
+    pub error: &'a crate::oauth2::OAuth2Error,
+}
+
+impl<'a> InternalEvent for OAuth2TokenRefreshError<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Failed to refresh OAuth2 access token.",
+            error = %self.error,
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!("http_client_errors_total", "error_kind" => self.error.to_string()).increment(1);
+    }
+}
+
 /// Newtype placeholder to provide a formatter for the request and response body.
 struct FormatBody<'a, B>(&'a B);
 
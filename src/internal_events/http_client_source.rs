@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use metrics::counter;
 use vector_lib::internal_event::InternalEvent;
 use vector_lib::{
@@ -87,3 +89,54 @@ impl InternalEvent for HttpClientHttpError {
         .increment(1);
     }
 }
+
+#[derive(Debug)]
+pub struct HttpClientProgramError<'a> {
+    pub error: &'a str,
+    pub url: String,
+}
+
+impl<'a> InternalEvent for HttpClientProgramError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed running a VRL program on an HTTP response.",
+            url = %self.url,
+            error = %self.error,
+            error_type = error_type::SCRIPT_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "url" => self.url,
+            "error_type" => error_type::SCRIPT_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpClientCheckpointError<'a> {
+    pub error: std::io::Error,
+    pub path: &'a Path,
+}
+
+impl<'a> InternalEvent for HttpClientCheckpointError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Unable to access the persisted cursor.",
+            error = %self.error,
+            path = ?self.path,
+            error_type = error_type::IO_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::IO_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
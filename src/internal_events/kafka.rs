@@ -1,7 +1,9 @@
+use std::time::Duration;
+
 use metrics::{counter, gauge};
 use vector_lib::internal_event::InternalEvent;
 use vector_lib::{
-    internal_event::{error_stage, error_type},
+    internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL},
     json_size::JsonSize,
 };
 use vrl::path::OwnedTargetPath;
@@ -90,6 +92,33 @@ impl InternalEvent for KafkaOffsetUpdateError {
     }
 }
 
+#[derive(Debug)]
+pub struct KafkaTransactionError<'a> {
+    pub error: &'a rdkafka::error::KafkaError,
+    pub attempt: usize,
+}
+
+impl InternalEvent for KafkaTransactionError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Kafka transaction failed.",
+            error = %self.error,
+            attempt = self.attempt,
+            error_code = "kafka_transaction",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "kafka_transaction",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        )
+        .increment(1);
+    }
+}
+
 #[derive(Debug)]
 pub struct KafkaReadError {
     pub error: rdkafka::error::KafkaError,
@@ -174,3 +203,80 @@ impl InternalEvent for KafkaHeaderExtractionError<'_> {
         .increment(1);
     }
 }
+
+pub struct KafkaSchemaRegistryDecodeError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for KafkaSchemaRegistryDecodeError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to resolve the schema of message. Dropping message.",
+            error = %self.error,
+            error_code = "schema_registry",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "schema_registry",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+pub struct KafkaSchemaRegistryRequestError<E> {
+    pub error: E,
+    pub retry_in: Duration,
+}
+
+impl<E: std::fmt::Display> InternalEvent for KafkaSchemaRegistryRequestError<E> {
+    fn emit(self) {
+        error!(
+            message = "Failed to fetch the schema of message. Retrying.",
+            error = %self.error,
+            retry_in_ms = self.retry_in.as_millis() as u64,
+            error_code = "schema_registry",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "schema_registry",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+pub struct KafkaSchemaRegistryEncodeError<E> {
+    pub error: E,
+}
+
+impl<E: std::fmt::Display> InternalEvent for KafkaSchemaRegistryEncodeError<E> {
+    fn emit(self) {
+        let reason = "Failed to resolve the schema of event.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_code = "schema_registry",
+            error_type = error_type::ENCODER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "schema_registry",
+            "error_type" => error_type::ENCODER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
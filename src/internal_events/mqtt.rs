@@ -1,7 +1,7 @@
 use std::fmt::Debug;
 
 use metrics::counter;
-use rumqttc::ConnectionError;
+use rumqttc::{ClientError, ConnectionError};
 use vector_lib::internal_event::InternalEvent;
 use vector_lib::internal_event::{error_stage, error_type};
 
@@ -33,3 +33,61 @@ impl InternalEvent for MqttConnectionError {
         Some("MqttConnectionError")
     }
 }
+
+#[derive(Debug)]
+pub struct MqttReceiveError {
+    pub error: ConnectionError,
+}
+
+impl InternalEvent for MqttReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Failed to receive message from MQTT broker.",
+            error = %self.error,
+            error_code = "mqtt_connection_error",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "mqtt_connection_error",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("MqttReceiveError")
+    }
+}
+
+#[derive(Debug)]
+pub struct MqttClientError {
+    pub error: ClientError,
+}
+
+impl InternalEvent for MqttClientError {
+    fn emit(self) {
+        error!(
+            message = "Failed to send request to MQTT broker.",
+            error = %self.error,
+            error_code = "mqtt_client_error",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "mqtt_client_error",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("MqttClientError")
+    }
+}
@@ -0,0 +1,52 @@
+use metrics::counter;
+use vector_lib::internal_event::{error_stage, error_type, InternalEvent};
+
+#[derive(Debug)]
+pub struct NatsJetStreamReadError {
+    pub error: async_nats::Error,
+}
+
+impl InternalEvent for NatsJetStreamReadError {
+    fn emit(self) {
+        error!(
+            message = "Failed to read a message from JetStream.",
+            error = %self.error,
+            error_code = "reading_message",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "reading_message",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
+
+#[derive(Debug)]
+pub struct NatsJetStreamAckError {
+    pub error: async_nats::Error,
+}
+
+impl InternalEvent for NatsJetStreamAckError {
+    fn emit(self) {
+        error!(
+            message = "Failed to acknowledge a JetStream message.",
+            error = %self.error,
+            error_code = "acknowledging_message",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "acknowledging_message",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+}
@@ -0,0 +1,40 @@
+use metrics::counter;
+use vector_lib::internal_event::{error_stage, error_type};
+use vector_lib::internal_event::{ComponentEventsDropped, InternalEvent, UNINTENTIONAL};
+
+#[derive(Debug)]
+pub struct OpentelemetryPartialSuccessError<'a> {
+    pub rejected: u64,
+    pub error_message: &'a str,
+    pub signal: &'static str,
+}
+
+impl InternalEvent for OpentelemetryPartialSuccessError<'_> {
+    fn emit(self) {
+        let reason = "OTLP endpoint rejected part of the request.";
+
+        error!(
+            message = reason,
+            rejected = self.rejected,
+            error = self.error_message,
+            signal = self.signal,
+            error_code = "partial_success",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+
+        counter!(
+            "component_errors_total",
+            "error_code" => "partial_success",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        )
+        .increment(1);
+
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.rejected as usize,
+            reason,
+        });
+    }
+}
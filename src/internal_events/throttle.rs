@@ -1,5 +1,7 @@
 use metrics::counter;
-use vector_lib::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+use vector_lib::internal_event::{
+    error_stage, error_type, ComponentEventsDropped, InternalEvent, INTENTIONAL,
+};
 
 #[derive(Debug)]
 pub(crate) struct ThrottleEventDiscarded {
@@ -31,3 +33,28 @@ impl InternalEvent for ThrottleEventDiscarded {
         })
     }
 }
+
+#[derive(Debug)]
+pub(crate) struct ThrottleLimitParseError<'a> {
+    pub field: &'a str,
+    pub value: &'a str,
+}
+
+impl InternalEvent for ThrottleLimitParseError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Rendered limit is not a number, the event is not limited by it.",
+            field = self.field,
+            value = self.value,
+            error_type = error_type::CONVERSION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_type" => error_type::CONVERSION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        )
+        .increment(1);
+    }
+}
@@ -1,5 +1,6 @@
 use std::error::Error;
 use std::fmt::Debug;
+use std::net::SocketAddr;
 
 use metrics::counter;
 use vector_lib::internal_event::InternalEvent;
@@ -91,3 +92,58 @@ impl InternalEvent for WsConnectionError {
         Some("WsConnectionError")
     }
 }
+
+#[derive(Debug)]
+pub struct WsReceiveError {
+    pub error: tokio_tungstenite::tungstenite::Error,
+}
+
+impl InternalEvent for WsReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Failed to receive WebSocket message.",
+            error = %self.error,
+            error_code = "ws_receive_error",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total",
+            "error_code" => "ws_receive_error",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        )
+        .increment(1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsReceiveError")
+    }
+}
+
+#[derive(Debug)]
+pub struct WsSlowClient {
+    pub peer_addr: SocketAddr,
+    pub disconnected: bool,
+}
+
+impl InternalEvent for WsSlowClient {
+    fn emit(self) {
+        warn!(
+            message = "Client is not keeping up with the events sent to it.",
+            peer_addr = %self.peer_addr,
+            disconnected = self.disconnected,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "websocket_slow_clients_total",
+            "action" => if self.disconnected { "disconnect" } else { "drop_events" },
+        )
+        .increment(1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsSlowClient")
+    }
+}
@@ -0,0 +1,15 @@
+use vector_lib::internal_event::{ComponentEventsDropped, InternalEvent, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct WindowEventsDropped {
+    pub count: usize,
+}
+
+impl InternalEvent for WindowEventsDropped {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "Events have been dropped outside of a flushed window.",
+        });
+    }
+}
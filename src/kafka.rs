@@ -1,10 +1,20 @@
 #![allow(missing_docs)]
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
 
-use rdkafka::{consumer::ConsumerContext, ClientConfig, ClientContext, Statistics};
+use once_cell::sync::Lazy;
+use rdkafka::{
+    consumer::{ConsumerContext, ConsumerGroupMetadata},
+    ClientConfig, ClientContext, Statistics,
+};
 use snafu::Snafu;
 use tracing::Span;
+use vector_lib::config::ComponentKey;
 use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::OwnedTargetPath;
 use vector_lib::sensitive_string::SensitiveString;
 
 use crate::{
@@ -185,3 +195,81 @@ impl ClientContext for KafkaStatisticsContext {
 }
 
 impl ConsumerContext for KafkaStatisticsContext {}
+
+/// The consumer groups of the running `kafka` sources, by component.
+static CONSUMER_GROUPS: Lazy<Mutex<HashMap<ComponentKey, Arc<KafkaConsumerGroup>>>> =
+    Lazy::new(Default::default);
+
+/// The consumer group of a `kafka` source, which lets transactional `kafka` sinks commit the
+/// offsets of the messages the source consumed within their transactions.
+pub(crate) struct KafkaConsumerGroup {
+    group_metadata: Box<dyn Fn() -> Option<ConsumerGroupMetadata> + Send + Sync>,
+
+    /// Where the source puts the topic of each message in its events.
+    pub(crate) topic: Option<OwnedTargetPath>,
+
+    /// Where the source puts the partition of each message in its events.
+    pub(crate) partition: Option<OwnedTargetPath>,
+
+    /// Where the source puts the offset of each message in its events.
+    pub(crate) offset: Option<OwnedTargetPath>,
+}
+
+impl KafkaConsumerGroup {
+    pub(crate) fn new(
+        group_metadata: impl Fn() -> Option<ConsumerGroupMetadata> + Send + Sync + 'static,
+        topic: Option<OwnedTargetPath>,
+        partition: Option<OwnedTargetPath>,
+        offset: Option<OwnedTargetPath>,
+    ) -> Self {
+        Self {
+            group_metadata: Box::new(group_metadata),
+            topic,
+            partition,
+            offset,
+        }
+    }
+
+    /// The current metadata of the consumer group, if the consumer is still a member of it.
+    pub(crate) fn group_metadata(&self) -> Option<ConsumerGroupMetadata> {
+        (self.group_metadata)()
+    }
+
+    /// Registers the consumer group of a source, until the returned registration is dropped.
+    pub(crate) fn register(self, key: ComponentKey) -> KafkaConsumerGroupRegistration {
+        let group = Arc::new(self);
+        CONSUMER_GROUPS
+            .lock()
+            .expect("poisoned lock")
+            .insert(key.clone(), Arc::clone(&group));
+        KafkaConsumerGroupRegistration { key, group }
+    }
+
+    /// Gets the consumer group of the given source, if it is a running `kafka` source.
+    pub(crate) fn get(key: &ComponentKey) -> Option<Arc<Self>> {
+        CONSUMER_GROUPS
+            .lock()
+            .expect("poisoned lock")
+            .get(key)
+            .cloned()
+    }
+}
+
+/// Unregisters the consumer group of a source when dropped.
+pub(crate) struct KafkaConsumerGroupRegistration {
+    key: ComponentKey,
+    group: Arc<KafkaConsumerGroup>,
+}
+
+impl Drop for KafkaConsumerGroupRegistration {
+    fn drop(&mut self) {
+        let mut groups = CONSUMER_GROUPS.lock().expect("poisoned lock");
+        // The source may have been replaced by a reload in the meantime.
+        if groups
+            .get(&self.key)
+            .is_some_and(|group| Arc::ptr_eq(group, &self.group))
+        {
+            groups.remove(&self.key);
+        }
+    }
+}
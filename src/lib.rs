@@ -70,6 +70,9 @@ pub mod aws;
 #[allow(unreachable_pub)]
 pub mod codecs;
 pub mod common;
+#[cfg(feature = "api-client")]
+#[allow(unreachable_pub)]
+pub mod control;
 mod convert_config;
 pub mod encoding_transcode;
 pub mod enrichment_tables;
@@ -86,9 +89,12 @@ pub mod kafka;
 pub mod kubernetes;
 pub mod line_agg;
 pub mod list;
+#[cfg(any(feature = "sources-mqtt", feature = "sinks-mqtt"))]
+pub(crate) mod mqtt;
 #[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub(crate) mod nats;
 pub mod net;
+pub mod oauth2;
 #[allow(unreachable_pub)]
 pub(crate) mod proto;
 pub mod providers;
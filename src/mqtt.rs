@@ -0,0 +1,31 @@
+//! Configuration shared by the `mqtt` source and sink.
+use rumqttc::QoS;
+use vector_lib::configurable::configurable_component;
+
+/// Supported Quality of Service types for MQTT.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+#[allow(clippy::enum_variant_names)]
+pub enum MqttQoS {
+    /// AtLeastOnce.
+    #[derivative(Default)]
+    AtLeastOnce,
+
+    /// AtMostOnce.
+    AtMostOnce,
+
+    /// ExactlyOnce.
+    ExactlyOnce,
+}
+
+impl From<MqttQoS> for QoS {
+    fn from(value: MqttQoS) -> Self {
+        match value {
+            MqttQoS::AtLeastOnce => QoS::AtLeastOnce,
+            MqttQoS::AtMostOnce => QoS::AtMostOnce,
+            MqttQoS::ExactlyOnce => QoS::ExactlyOnce,
+        }
+    }
+}
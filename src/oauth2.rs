@@ -0,0 +1,735 @@
+//! OAuth2 authentication of HTTP requests.
+//!
+//! Access tokens are requested from the token endpoint of the authorization server, with either
+//! the client credentials or the JWT bearer grant. They are cached, and shared by all the
+//! components configured with the same settings. A token is refreshed in the background when it
+//! is about to expire, and replaced right away when the server rejects it.
+
+use std::{
+    future::Future,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, RwLock,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use headers::{Authorization, HeaderMapExt};
+use http::{
+    header::{ACCEPT, CONTENT_TYPE},
+    HeaderMap, Request, Response, StatusCode,
+};
+use hyper::Body;
+use once_cell::sync::Lazy;
+use openssl::{hash::MessageDigest, pkey::PKey, sign::Signer};
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+use tokio::{runtime::Handle, time::Instant};
+use vector_lib::configurable::configurable_component;
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::{
+    config::ProxyConfig,
+    http::{AuthorizeSnafu, HttpClient, HttpError},
+    internal_events::http_client::OAuth2TokenRefreshError,
+};
+
+const JWT_BEARER_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:jwt-bearer";
+
+/// The lifetime of the assertions of the JWT bearer grant.
+const ASSERTION_LIFETIME_SECS: u64 = 300;
+
+/// The lifetime assumed for tokens whose response doesn't tell when they expire.
+const DEFAULT_TOKEN_LIFETIME_SECS: u64 = 3600;
+
+const fn default_refresh_before_expiry_secs() -> u64 {
+    60
+}
+
+/// The token caches, shared by the components using the same configuration.
+///
+/// A cache is dropped once nothing else references it and its token expired, so that the caches
+/// of the configurations no longer in use don't pile up across reloads.
+static CACHES: Lazy<Mutex<Vec<Arc<TokenCache>>>> = Lazy::new(Default::default);
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum OAuth2Error {
+    #[snafu(display("The client_credentials grant requires a client secret"))]
+    MissingClientSecret,
+    #[snafu(display("Failed to read private key {:?}: {}", path, source))]
+    ReadKey {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("Invalid private key: {}", source))]
+    InvalidKey { source: openssl::error::ErrorStack },
+    #[snafu(display("Failed to sign JWT assertion: {}", source))]
+    SignAssertion { source: openssl::error::ErrorStack },
+    #[snafu(display("Failed to build token request: {}", source))]
+    BuildTokenRequest { source: http::Error },
+    #[snafu(display("Failed to build HTTP client: {}", source))]
+    BuildHttpClient { source: HttpError },
+    #[snafu(display("Failed to request token: {}", source))]
+    RequestToken { source: HttpError },
+    #[snafu(display("Failed to read token response: {}", source))]
+    ReadTokenResponse { source: hyper::Error },
+    #[snafu(display("Token request failed with status {}: {}", status, body))]
+    TokenRequestFailed { status: StatusCode, body: String },
+    #[snafu(display("Invalid token response: {}", source))]
+    ParseTokenResponse { source: serde_json::Error },
+}
+
+/// OAuth2 authentication.
+///
+/// Access tokens are obtained from the token endpoint of the authorization server, and sent as
+/// bearer tokens. They are refreshed ahead of their expiry, and replaced when they are rejected.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct OAuth2Config {
+    /// The URL of the token endpoint of the authorization server.
+    #[configurable(metadata(docs::examples = "https://auth.example.com/oauth2/token"))]
+    pub token_endpoint: String,
+
+    /// The client identifier.
+    #[configurable(metadata(docs::examples = "${OAUTH2_CLIENT_ID}"))]
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub client_id: String,
+
+    /// The client secret, sent to the token endpoint with basic authentication.
+    ///
+    /// Required by the `client_credentials` grant. Without it, the client identifier is sent in
+    /// the body of the token requests instead.
+    #[configurable(metadata(docs::examples = "${OAUTH2_CLIENT_SECRET}"))]
+    pub client_secret: Option<SensitiveString>,
+
+    /// The scopes requested for the access tokens.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "logs:write"))]
+    pub scopes: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub grant: OAuth2Grant,
+
+    /// How long before their expiry the access tokens are refreshed.
+    #[serde(default = "default_refresh_before_expiry_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub refresh_before_expiry_secs: u64,
+}
+
+/// The grant used to obtain access tokens.
+#[configurable_component]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "type")]
+#[configurable(metadata(docs::enum_tag_description = "The type of grant."))]
+pub enum OAuth2Grant {
+    /// The [client credentials grant][client_credentials], authenticated with the client
+    /// identifier and secret.
+    ///
+    /// [client_credentials]: https://datatracker.ietf.org/doc/html/rfc6749#section-4.4
+    #[default]
+    ClientCredentials,
+
+    /// The [JWT bearer grant][jwt_bearer], authenticated with a JWT assertion signed by the
+    /// private key of the client.
+    ///
+    /// The assertions are signed with `RS256`, and expire after five minutes.
+    ///
+    /// [jwt_bearer]: https://datatracker.ietf.org/doc/html/rfc7523#section-2.1
+    JwtBearer {
+        /// The path to the PEM-encoded RSA private key signing the assertions.
+        #[configurable(metadata(docs::examples = "/etc/vector/oauth2.key"))]
+        key_file: PathBuf,
+
+        /// The identifier of the key, sent in the `kid` header of the assertions.
+        #[configurable(metadata(docs::examples = "b4c1a2"))]
+        key_id: Option<String>,
+
+        /// The subject of the assertions.
+        ///
+        /// Defaults to the client identifier.
+        #[configurable(metadata(docs::examples = "service-account@example.com"))]
+        subject: Option<String>,
+
+        /// The audience of the assertions.
+        ///
+        /// Defaults to the token endpoint.
+        #[configurable(metadata(docs::examples = "https://auth.example.com"))]
+        audience: Option<String>,
+    },
+}
+
+impl OAuth2Config {
+    /// Authorizes the request with the cached access token.
+    ///
+    /// When no valid token is cached yet, the request is marked so that [`authorize`] waits for
+    /// one before it is sent.
+    pub(crate) fn apply<B>(&self, request: &mut Request<B>) {
+        let authorization = self.authorization(request.headers_mut());
+        request.extensions_mut().insert(authorization);
+    }
+
+    pub(crate) fn apply_builder(
+        &self,
+        mut builder: http::request::Builder,
+    ) -> http::request::Builder {
+        if let Some(headers) = builder.headers_mut() {
+            let authorization = self.authorization(headers);
+            builder = builder.extension(authorization);
+        }
+        builder
+    }
+
+    pub(crate) fn apply_headers_map(&self, headers: &mut HeaderMap) {
+        self.authorization(headers);
+    }
+
+    fn authorization(&self, headers: &mut HeaderMap) -> OAuth2Authorization {
+        let cache = TokenCache::shared(self);
+        let token = cache.cached();
+        if let Some(token) = &token {
+            insert_token(headers, token);
+        }
+        OAuth2Authorization { cache, token }
+    }
+}
+
+/// The access token a request is authorized with.
+#[derive(Clone)]
+pub struct OAuth2Authorization {
+    cache: Arc<TokenCache>,
+    token: Option<String>,
+}
+
+impl OAuth2Authorization {
+    /// Replaces the token rejected by the server, returning the new one.
+    pub async fn refresh(&self) -> Result<String, OAuth2Error> {
+        self.cache.replace(self.token.as_deref()).await
+    }
+
+    /// Authorizes the request again, with a token replacing the one rejected by the server.
+    pub async fn reauthorize<B>(&self, request: &mut Request<B>) -> Result<(), OAuth2Error> {
+        let token = self.refresh().await?;
+        insert_token(request.headers_mut(), &token);
+        request.extensions_mut().insert(OAuth2Authorization {
+            cache: Arc::clone(&self.cache),
+            token: Some(token),
+        });
+        Ok(())
+    }
+}
+
+/// Waits for an access token when the request was authorized with OAuth2 before one was cached.
+///
+/// Returns the authorization of the request, if it uses OAuth2.
+pub async fn authorize<B>(
+    request: &mut Request<B>,
+) -> Result<Option<OAuth2Authorization>, OAuth2Error> {
+    let Some(authorization) = request.extensions().get::<OAuth2Authorization>().cloned() else {
+        return Ok(None);
+    };
+    if authorization.token.is_some() {
+        return Ok(Some(authorization));
+    }
+
+    let token = authorization.cache.token().await?;
+    insert_token(request.headers_mut(), &token);
+    let authorization = OAuth2Authorization {
+        cache: authorization.cache,
+        token: Some(token),
+    };
+    request.extensions_mut().insert(authorization.clone());
+    Ok(Some(authorization))
+}
+
+/// Sends a request authorized with [`crate::http::Auth`].
+///
+/// When the server rejects the OAuth2 access token of the request, the token is replaced and
+/// the request is sent once more.
+pub async fn send_authorized<B, R, E, F, Fut>(
+    mut request: Request<B>,
+    mut send: F,
+) -> Result<Response<R>, E>
+where
+    B: Clone,
+    E: From<HttpError>,
+    F: FnMut(Request<B>) -> Fut,
+    Fut: Future<Output = Result<Response<R>, E>>,
+{
+    let authorization = authorize(&mut request).await.context(AuthorizeSnafu)?;
+    let Some(authorization) = authorization else {
+        return send(request).await;
+    };
+
+    let mut retry = copy_request(&request);
+    let response = send(request).await?;
+    if response.status() != StatusCode::UNAUTHORIZED {
+        return Ok(response);
+    }
+
+    debug!(message = "OAuth2 access token rejected, sending the request with a new one.");
+    authorization
+        .reauthorize(&mut retry)
+        .await
+        .context(AuthorizeSnafu)?;
+    send(retry).await
+}
+
+fn copy_request<B: Clone>(request: &Request<B>) -> Request<B> {
+    let mut copy = Request::new(request.body().clone());
+    *copy.method_mut() = request.method().clone();
+    *copy.uri_mut() = request.uri().clone();
+    *copy.version_mut() = request.version();
+    *copy.headers_mut() = request.headers().clone();
+    copy
+}
+
+fn insert_token(headers: &mut HeaderMap, token: &str) {
+    match Authorization::bearer(token) {
+        Ok(auth) => headers.typed_insert(auth),
+        Err(error) => error!(message = "Invalid OAuth2 access token.", %error),
+    }
+}
+
+#[derive(Clone, Debug)]
+struct AccessToken {
+    value: String,
+    expires_at: Instant,
+}
+
+/// The access token obtained for a configuration.
+struct TokenCache {
+    config: OAuth2Config,
+    token: RwLock<Option<AccessToken>>,
+    /// Held while requesting a token, so that concurrent requests wait for the same token.
+    requesting: tokio::sync::Mutex<()>,
+    refreshing: AtomicBool,
+    /// The runtime refreshing the token in the background, if the cache was built on one.
+    handle: Option<Handle>,
+}
+
+impl TokenCache {
+    /// Returns the cache of the tokens obtained with this configuration.
+    fn shared(config: &OAuth2Config) -> Arc<Self> {
+        let mut caches = CACHES.lock().expect("poisoned lock");
+        caches.retain(|cache| Arc::strong_count(cache) > 1 || cache.valid().is_some());
+        if let Some(cache) = caches.iter().find(|cache| &cache.config == config) {
+            return Arc::clone(cache);
+        }
+
+        let cache = Arc::new(Self {
+            config: config.clone(),
+            token: RwLock::new(None),
+            requesting: tokio::sync::Mutex::new(()),
+            refreshing: AtomicBool::new(false),
+            handle: Handle::try_current().ok(),
+        });
+        caches.push(Arc::clone(&cache));
+        cache
+    }
+
+    fn refresh_before_expiry(&self) -> Duration {
+        Duration::from_secs(self.config.refresh_before_expiry_secs)
+    }
+
+    /// Returns the cached token while it's valid.
+    ///
+    /// The token is refreshed in the background when it's missing or about to expire. Without a
+    /// runtime to refresh it on, requests wait for a new token in [`authorize`] instead.
+    fn cached(self: &Arc<Self>) -> Option<String> {
+        let token = self.valid();
+        let expiring = token.as_ref().map_or(true, |token| {
+            token.expires_at <= Instant::now() + self.refresh_before_expiry()
+        });
+        if let Some(handle) = self.handle.as_ref().filter(|_| expiring) {
+            if !self.refreshing.swap(true, Ordering::AcqRel) {
+                self.spawn_refresh(handle);
+            }
+        }
+        token.map(|token| token.value)
+    }
+
+    fn spawn_refresh(self: &Arc<Self>, handle: &Handle) {
+        let cache = Arc::clone(self);
+        handle.spawn(async move {
+            if let Err(error) = cache.refresh().await {
+                emit!(OAuth2TokenRefreshError { error: &error });
+            }
+            cache.refreshing.store(false, Ordering::Release);
+        });
+    }
+
+    fn valid(&self) -> Option<AccessToken> {
+        self.token
+            .read()
+            .expect("poisoned lock")
+            .clone()
+            .filter(|token| token.expires_at > Instant::now())
+    }
+
+    /// Returns a valid token, requesting one when none is cached.
+    async fn token(&self) -> Result<String, OAuth2Error> {
+        let _requesting = self.requesting.lock().await;
+        match self.valid() {
+            Some(token) => Ok(token.value),
+            None => self.request().await,
+        }
+    }
+
+    /// Requests a new token, unless it was refreshed while waiting for another request.
+    async fn refresh(&self) -> Result<String, OAuth2Error> {
+        let _requesting = self.requesting.lock().await;
+        match self.valid() {
+            Some(token) if token.expires_at > Instant::now() + self.refresh_before_expiry() => {
+                Ok(token.value)
+            }
+            _ => self.request().await,
+        }
+    }
+
+    /// Requests a new token, unless the rejected one was already replaced.
+    async fn replace(&self, rejected: Option<&str>) -> Result<String, OAuth2Error> {
+        let _requesting = self.requesting.lock().await;
+        match self.valid() {
+            Some(token) if Some(token.value.as_str()) != rejected => Ok(token.value),
+            _ => self.request().await,
+        }
+    }
+
+    async fn request(&self) -> Result<String, OAuth2Error> {
+        debug!(
+            message = "Requesting OAuth2 access token.",
+            token_endpoint = %self.config.token_endpoint,
+            client_id = %self.config.client_id,
+        );
+        let token = request_token(&self.config).await?;
+        let value = token.value.clone();
+        *self.token.write().expect("poisoned lock") = Some(token);
+        Ok(value)
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: Option<u64>,
+}
+
+async fn request_token(config: &OAuth2Config) -> Result<AccessToken, OAuth2Error> {
+    let mut form = url::form_urlencoded::Serializer::new(String::new());
+    match &config.grant {
+        OAuth2Grant::ClientCredentials => {
+            if config.client_secret.is_none() {
+                return Err(OAuth2Error::MissingClientSecret);
+            }
+            form.append_pair("grant_type", "client_credentials");
+        }
+        OAuth2Grant::JwtBearer { .. } => {
+            form.append_pair("grant_type", JWT_BEARER_GRANT_TYPE);
+            form.append_pair("assertion", &sign_assertion(config)?);
+        }
+    }
+    if !config.scopes.is_empty() {
+        form.append_pair("scope", &config.scopes.join(" "));
+    }
+    if config.client_secret.is_none() {
+        form.append_pair("client_id", &config.client_id);
+    }
+
+    let mut request = Request::post(&config.token_endpoint)
+        .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .header(ACCEPT, "application/json")
+        .body(Body::from(form.finish()))
+        .context(BuildTokenRequestSnafu)?;
+    if let Some(secret) = &config.client_secret {
+        let auth = Authorization::basic(&config.client_id, secret.inner());
+        request.headers_mut().typed_insert(auth);
+    }
+
+    let requested_at = Instant::now();
+    let proxy = ProxyConfig::from_env();
+    let response = HttpClient::new(None, &proxy)
+        .context(BuildHttpClientSnafu)?
+        .send(request)
+        .await
+        .context(RequestTokenSnafu)?;
+
+    let (parts, body) = response.into_parts();
+    let body = hyper::body::to_bytes(body)
+        .await
+        .context(ReadTokenResponseSnafu)?;
+    if !parts.status.is_success() {
+        return Err(OAuth2Error::TokenRequestFailed {
+            status: parts.status,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        });
+    }
+
+    let response: TokenResponse = serde_json::from_slice(&body).context(ParseTokenResponseSnafu)?;
+    let lifetime = response.expires_in.unwrap_or(DEFAULT_TOKEN_LIFETIME_SECS);
+    Ok(AccessToken {
+        value: response.access_token,
+        expires_at: requested_at + Duration::from_secs(lifetime),
+    })
+}
+
+/// Builds the JWT assertion of the JWT bearer grant, signed with `RS256`.
+fn sign_assertion(config: &OAuth2Config) -> Result<String, OAuth2Error> {
+    let OAuth2Grant::JwtBearer {
+        key_file,
+        key_id,
+        subject,
+        audience,
+    } = &config.grant
+    else {
+        unreachable!("assertions are only signed for the JWT bearer grant");
+    };
+
+    let pem = std::fs::read(key_file).context(ReadKeySnafu { path: key_file })?;
+    let key = PKey::private_key_from_pem(&pem).context(InvalidKeySnafu)?;
+
+    let mut header = serde_json::json!({ "alg": "RS256", "typ": "JWT" });
+    if let Some(key_id) = key_id {
+        header["kid"] = key_id.as_str().into();
+    }
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let claims = serde_json::json!({
+        "iss": config.client_id,
+        "sub": subject.as_ref().unwrap_or(&config.client_id),
+        "aud": audience.as_ref().unwrap_or(&config.token_endpoint),
+        "iat": now,
+        "exp": now + ASSERTION_LIFETIME_SECS,
+        "jti": uuid::Uuid::new_v4().to_string(),
+    });
+
+    let signing_input = format!(
+        "{}.{}",
+        base64_url(header.to_string().as_bytes()),
+        base64_url(claims.to_string().as_bytes())
+    );
+    let mut signer = Signer::new(MessageDigest::sha256(), &key).context(SignAssertionSnafu)?;
+    signer
+        .update(signing_input.as_bytes())
+        .context(SignAssertionSnafu)?;
+    let signature = signer.sign_to_vec().context(SignAssertionSnafu)?;
+
+    Ok(format!("{}.{}", signing_input, base64_url(&signature)))
+}
+
+/// Encodes data with the unpadded URL-safe base64 alphabet of JWTs.
+fn base64_url(data: &[u8]) -> String {
+    openssl::base64::encode_block(data)
+        .trim_end_matches('=')
+        .replace('+', "-")
+        .replace('/', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use bytes::Bytes;
+    use http::HeaderValue;
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Server,
+    };
+    use openssl::{rsa::Rsa, sign::Verifier};
+
+    use super::*;
+    use crate::{http::Auth, test_util::next_addr};
+
+    fn config(token_endpoint: String) -> OAuth2Config {
+        OAuth2Config {
+            token_endpoint,
+            client_id: "vector".into(),
+            client_secret: Some("secret".to_string().into()),
+            scopes: vec!["logs:write".into(), "metrics:write".into()],
+            grant: OAuth2Grant::ClientCredentials,
+            refresh_before_expiry_secs: default_refresh_before_expiry_secs(),
+        }
+    }
+
+    /// Starts a token endpoint issuing `token-1`, `token-2`, etc., returning its URL and the
+    /// number of tokens issued.
+    fn token_endpoint() -> (String, Arc<AtomicUsize>) {
+        let addr = next_addr();
+        let issued = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&issued);
+        let make_service = make_service_fn(move |_| {
+            let counter = Arc::clone(&counter);
+            async move {
+                Ok::<_, hyper::Error>(service_fn(move |request: Request<Body>| {
+                    let counter = Arc::clone(&counter);
+                    async move {
+                        let authorization = request.headers().get("authorization").cloned();
+                        let body = hyper::body::to_bytes(request.into_body()).await?;
+                        assert_eq!(
+                            body,
+                            "grant_type=client_credentials&scope=logs%3Awrite+metrics%3Awrite"
+                        );
+                        assert_eq!(authorization.unwrap(), "Basic dmVjdG9yOnNlY3JldA==");
+
+                        let issued = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                        let body = format!(
+                            r#"{{"access_token":"token-{}","token_type":"Bearer","expires_in":3600}}"#,
+                            issued
+                        );
+                        Ok::<_, hyper::Error>(Response::new(Body::from(body)))
+                    }
+                }))
+            }
+        });
+        tokio::spawn(Server::bind(&addr).serve(make_service));
+        (format!("http://{}/token", addr), issued)
+    }
+
+    #[test]
+    fn parses_config() {
+        let auth: Auth = toml::from_str(
+            r#"
+            strategy = "oauth2"
+            token_endpoint = "https://auth.example.com/oauth2/token"
+            client_id = "vector"
+            grant.type = "jwt_bearer"
+            grant.key_file = "/etc/vector/oauth2.key"
+            "#,
+        )
+        .unwrap();
+
+        let Auth::OAuth2(config) = auth else {
+            panic!("expected OAuth2 authentication");
+        };
+        assert_eq!(config.client_secret, None);
+        assert_eq!(config.refresh_before_expiry_secs, 60);
+        assert!(matches!(config.grant, OAuth2Grant::JwtBearer { .. }));
+    }
+
+    #[tokio::test]
+    async fn authorizes_requests_with_shared_token() {
+        let (endpoint, issued) = token_endpoint();
+        let auth = Auth::OAuth2(config(endpoint));
+
+        let mut request = Request::get("http://example.com").body(()).unwrap();
+        auth.apply(&mut request);
+        authorize(&mut request).await.unwrap();
+        assert_eq!(request.headers()["authorization"], "Bearer token-1");
+
+        let mut request = Request::get("http://example.com").body(()).unwrap();
+        auth.clone().apply(&mut request);
+        assert_eq!(request.headers()["authorization"], "Bearer token-1");
+        assert_eq!(issued.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn replaces_rejected_token() {
+        let (endpoint, issued) = token_endpoint();
+        let auth = Auth::OAuth2(config(endpoint));
+
+        let mut request = Request::post("http://example.com")
+            .body(Bytes::from("payload"))
+            .unwrap();
+        auth.apply(&mut request);
+
+        let mut sent = Vec::new();
+        let response = send_authorized(request, |request: Request<Bytes>| {
+            let token = request.headers()["authorization"].clone();
+            let status = if sent.is_empty() {
+                StatusCode::UNAUTHORIZED
+            } else {
+                StatusCode::OK
+            };
+            sent.push((token, request.into_body()));
+            let response = Response::builder().status(status).body(()).unwrap();
+            async move { Ok::<_, HttpError>(response) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            sent,
+            vec![
+                (
+                    HeaderValue::from_static("Bearer token-1"),
+                    Bytes::from("payload")
+                ),
+                (
+                    HeaderValue::from_static("Bearer token-2"),
+                    Bytes::from("payload")
+                ),
+            ]
+        );
+        assert_eq!(issued.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn drops_unused_caches() {
+        let unused = config("http://127.0.0.1:9/unused".into());
+        let cache = TokenCache::shared(&unused);
+        assert!(Arc::ptr_eq(&cache, &TokenCache::shared(&unused)));
+        // Without a runtime, the token isn't refreshed in the background.
+        assert_eq!(cache.cached(), None);
+        assert!(!cache.refreshing.load(Ordering::Acquire));
+
+        drop(cache);
+        TokenCache::shared(&config("http://127.0.0.1:9/other".into()));
+        let caches = CACHES.lock().unwrap();
+        assert!(!caches.iter().any(|cache| cache.config == unused));
+    }
+
+    #[test]
+    fn signs_assertions() {
+        let rsa = Rsa::generate(2048).unwrap();
+        let key_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(key_file.path(), rsa.private_key_to_pem().unwrap()).unwrap();
+
+        let mut config = config("https://auth.example.com/token".into());
+        config.grant = OAuth2Grant::JwtBearer {
+            key_file: key_file.path().into(),
+            key_id: Some("key-1".into()),
+            subject: None,
+            audience: None,
+        };
+        let assertion = sign_assertion(&config).unwrap();
+
+        let parts = assertion.split('.').collect::<Vec<_>>();
+        let [header, claims, signature] = parts[..] else {
+            panic!("expected three parts in {assertion}");
+        };
+        let decode = |part: &str| {
+            let mut part = part.replace('-', "+").replace('_', "/");
+            while part.len() % 4 != 0 {
+                part.push('=');
+            }
+            openssl::base64::decode_block(&part).unwrap()
+        };
+
+        let header: serde_json::Value = serde_json::from_slice(&decode(header)).unwrap();
+        assert_eq!(
+            header,
+            serde_json::json!({ "alg": "RS256", "typ": "JWT", "kid": "key-1" })
+        );
+        let claims: serde_json::Value = serde_json::from_slice(&decode(claims)).unwrap();
+        assert_eq!(claims["iss"], "vector");
+        assert_eq!(claims["sub"], "vector");
+        assert_eq!(claims["aud"], "https://auth.example.com/token");
+        assert_eq!(
+            claims["exp"].as_u64().unwrap() - claims["iat"].as_u64().unwrap(),
+            ASSERTION_LIFETIME_SECS
+        );
+
+        let public_key = PKey::public_key_from_pem(&rsa.public_key_to_pem().unwrap()).unwrap();
+        let mut verifier = Verifier::new(MessageDigest::sha256(), &public_key).unwrap();
+        verifier
+            .update(format!("{header}.{claims}", header = parts[0], claims = parts[1]).as_bytes())
+            .unwrap();
+        assert!(verifier.verify(&decode(signature)).unwrap());
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use vector_lib::configurable::{component::GenerateConfig, configurable_component};
+
+use crate::{config::SecretBackend, signal};
+
+/// Configuration for the `directory` secrets backend.
+///
+/// Each secret is read from the file named after its key, such as the files of a Kubernetes secret
+/// mounted as a volume. Files are read again each time the configuration is reloaded.
+#[configurable_component(secrets("directory"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct DirectoryBackend {
+    /// The path of the directory to read secrets from.
+    #[configurable(metadata(docs::examples = "/var/run/secrets/vector"))]
+    pub path: PathBuf,
+
+    /// Whether to remove the trailing newline from the contents of each file.
+    #[serde(default = "crate::serde::default_true")]
+    pub remove_trailing_newline: bool,
+
+    /// Whether changes to the files in the directory trigger a configuration reload.
+    ///
+    /// This only has an effect when Vector watches its configuration for changes, and is useful
+    /// to pick up rotated secrets without restarting Vector.
+    #[serde(default)]
+    pub watch: bool,
+}
+
+impl GenerateConfig for DirectoryBackend {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(DirectoryBackend {
+            path: PathBuf::from("/path/to/secrets"),
+            remove_trailing_newline: true,
+            watch: false,
+        })
+        .unwrap()
+    }
+}
+
+impl SecretBackend for DirectoryBackend {
+    async fn retrieve(
+        &mut self,
+        secret_keys: HashSet<String>,
+        _: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let mut secrets = HashMap::new();
+        for k in secret_keys.into_iter() {
+            // Keys can't contain slashes, but `.` and `..` would still escape the directory.
+            if k.chars().all(|c| c == '.') {
+                return Err(format!("secret key '{}' is not a valid file name", k).into());
+            }
+            let path = self.path.join(&k);
+            let mut secret = tokio::fs::read_to_string(&path).await.map_err(|error| {
+                format!(
+                    "secret for key '{}' was not retrieved from {:?}: {}",
+                    k, path, error
+                )
+            })?;
+            if self.remove_trailing_newline {
+                let len = secret.trim_end_matches(['\r', '\n']).len();
+                secret.truncate(len);
+            }
+            if secret.is_empty() {
+                return Err(format!("secret for key '{}' was empty", k).into());
+            }
+            secrets.insert(k, secret);
+        }
+        Ok(secrets)
+    }
+
+    fn watched_paths(&self) -> Vec<PathBuf> {
+        if self.watch {
+            vec![self.path.clone()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tokio::sync::broadcast;
+
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    async fn retrieve(
+        backend: &mut DirectoryBackend,
+        keys: &[&str],
+    ) -> crate::Result<HashMap<String, String>> {
+        let (_tx, mut rx) = broadcast::channel(1);
+        backend
+            .retrieve(keys.iter().map(|k| k.to_string()).collect(), &mut rx)
+            .await
+    }
+
+    fn backend(remove_trailing_newline: bool) -> DirectoryBackend {
+        let path = temp_dir();
+        fs::create_dir(&path).unwrap();
+        DirectoryBackend {
+            path,
+            remove_trailing_newline,
+            watch: false,
+        }
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<DirectoryBackend>();
+    }
+
+    #[tokio::test]
+    async fn reads_one_file_per_secret() {
+        let mut backend = backend(true);
+        fs::write(backend.path.join("username"), "admin\n").unwrap();
+        fs::write(backend.path.join("db.password"), "hunter2\r\n").unwrap();
+
+        let secrets = retrieve(&mut backend, &["username", "db.password"])
+            .await
+            .unwrap();
+        assert_eq!(secrets["username"], "admin");
+        assert_eq!(secrets["db.password"], "hunter2");
+
+        fs::write(backend.path.join("username"), "root\n").unwrap();
+        let secrets = retrieve(&mut backend, &["username"]).await.unwrap();
+        assert_eq!(secrets["username"], "root");
+    }
+
+    #[tokio::test]
+    async fn keeps_trailing_newline() {
+        let mut backend = backend(false);
+        fs::write(backend.path.join("certificate"), "-----END-----\n").unwrap();
+
+        let secrets = retrieve(&mut backend, &["certificate"]).await.unwrap();
+        assert_eq!(secrets["certificate"], "-----END-----\n");
+    }
+
+    #[tokio::test]
+    async fn fails_on_missing_empty_or_invalid_secret() {
+        let mut backend = backend(true);
+        fs::write(backend.path.join("empty"), "\n").unwrap();
+
+        assert!(retrieve(&mut backend, &["missing"]).await.is_err());
+        assert!(retrieve(&mut backend, &["empty"]).await.is_err());
+        assert!(retrieve(&mut backend, &[".."]).await.is_err());
+    }
+}
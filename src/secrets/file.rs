@@ -0,0 +1,164 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use vector_lib::configurable::{component::GenerateConfig, configurable_component};
+
+use crate::{
+    config::{format, Format, SecretBackend},
+    signal,
+};
+
+/// Configuration for the `file` secrets backend.
+///
+/// The file contains a map of secret keys to their values, in JSON or YAML. It is read again each
+/// time the configuration is reloaded.
+#[configurable_component(secrets("file"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FileBackend {
+    /// The path of the file to read secrets from.
+    ///
+    /// The file is parsed as JSON if its extension is `.json`, and as YAML otherwise.
+    #[configurable(metadata(docs::examples = "/var/run/secrets/vector/secrets.json"))]
+    pub path: PathBuf,
+
+    /// Whether changes to the file trigger a configuration reload.
+    ///
+    /// This only has an effect when Vector watches its configuration for changes, and is useful
+    /// to pick up rotated secrets without restarting Vector.
+    #[serde(default)]
+    pub watch: bool,
+}
+
+impl GenerateConfig for FileBackend {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(FileBackend {
+            path: PathBuf::from("/path/to/secrets.json"),
+            watch: false,
+        })
+        .unwrap()
+    }
+}
+
+impl SecretBackend for FileBackend {
+    async fn retrieve(
+        &mut self,
+        secret_keys: HashSet<String>,
+        _: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let contents = tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(|error| format!("unable to read {:?}: {}", self.path, error))?;
+        let format = match Format::from_path(&self.path) {
+            Ok(Format::Json) => Format::Json,
+            _ => Format::Yaml,
+        };
+        let mut output = format::deserialize::<HashMap<String, String>>(&contents, format)
+            .map_err(|errors| format!("unable to parse {:?}: {}", self.path, errors.join(", ")))?;
+
+        let mut secrets = HashMap::new();
+        for k in secret_keys.into_iter() {
+            match output.remove(&k) {
+                Some(secret) if secret.is_empty() => {
+                    return Err(format!("secret for key '{}' was empty", k).into());
+                }
+                Some(secret) => {
+                    secrets.insert(k, secret);
+                }
+                None => {
+                    return Err(format!("secret for key '{}' was not retrieved", k).into());
+                }
+            }
+        }
+        Ok(secrets)
+    }
+
+    fn watched_paths(&self) -> Vec<PathBuf> {
+        if self.watch {
+            vec![self.path.clone()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tokio::sync::broadcast;
+
+    use super::*;
+    use crate::test_util::temp_file;
+
+    async fn retrieve(
+        backend: &mut FileBackend,
+        keys: &[&str],
+    ) -> crate::Result<HashMap<String, String>> {
+        let (_tx, mut rx) = broadcast::channel(1);
+        backend
+            .retrieve(keys.iter().map(|k| k.to_string()).collect(), &mut rx)
+            .await
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<FileBackend>();
+    }
+
+    #[tokio::test]
+    async fn reads_json_and_yaml() {
+        let json = temp_file().with_extension("json");
+        fs::write(&json, r#"{"username": "admin", "password": "hunter2"}"#).unwrap();
+        let mut backend = FileBackend {
+            path: json,
+            watch: false,
+        };
+        let secrets = retrieve(&mut backend, &["username", "password"])
+            .await
+            .unwrap();
+        assert_eq!(secrets["username"], "admin");
+        assert_eq!(secrets["password"], "hunter2");
+
+        let yaml = temp_file().with_extension("yaml");
+        fs::write(&yaml, "db.password: swordfish\n").unwrap();
+        let mut backend = FileBackend {
+            path: yaml,
+            watch: true,
+        };
+        let secrets = retrieve(&mut backend, &["db.password"]).await.unwrap();
+        assert_eq!(secrets["db.password"], "swordfish");
+        assert_eq!(backend.watched_paths(), vec![backend.path.clone()]);
+    }
+
+    #[tokio::test]
+    async fn reads_rotated_secrets() {
+        let path = temp_file().with_extension("json");
+        fs::write(&path, r#"{"token": "old"}"#).unwrap();
+        let mut backend = FileBackend {
+            path: path.clone(),
+            watch: false,
+        };
+        assert_eq!(
+            retrieve(&mut backend, &["token"]).await.unwrap()["token"],
+            "old"
+        );
+
+        fs::write(&path, r#"{"token": "new"}"#).unwrap();
+        assert_eq!(
+            retrieve(&mut backend, &["token"]).await.unwrap()["token"],
+            "new"
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_on_missing_or_empty_secret() {
+        let path = temp_file().with_extension("json");
+        fs::write(&path, r#"{"empty": ""}"#).unwrap();
+        let mut backend = FileBackend { path, watch: false };
+        assert!(retrieve(&mut backend, &["missing"]).await.is_err());
+        assert!(retrieve(&mut backend, &["empty"]).await.is_err());
+    }
+}
@@ -8,8 +8,12 @@ use crate::{config::SecretBackend, signal};
 
 #[cfg(feature = "secrets-aws-secrets-manager")]
 mod aws_secrets_manager;
+mod directory;
 mod exec;
+mod file;
 mod test;
+#[cfg(feature = "secrets-vault")]
+mod vault;
 
 /// Configurable secret backends in Vector.
 #[allow(clippy::large_enum_variant)]
@@ -18,13 +22,23 @@ mod test;
 #[enum_dispatch(SecretBackend)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum SecretBackends {
+    /// Directory.
+    Directory(directory::DirectoryBackend),
+
     /// Exec.
     Exec(exec::ExecBackend),
 
+    /// File.
+    File(file::FileBackend),
+
     /// AWS Secrets Manager.
     #[cfg(feature = "secrets-aws-secrets-manager")]
     AwsSecretsManager(aws_secrets_manager::AwsSecretsManagerBackend),
 
+    /// HashiCorp Vault.
+    #[cfg(feature = "secrets-vault")]
+    Vault(vault::VaultBackend),
+
     /// Test.
     #[configurable(metadata(docs::hidden))]
     Test(test::TestBackend),
@@ -34,9 +48,13 @@ pub enum SecretBackends {
 impl NamedComponent for SecretBackends {
     fn get_component_name(&self) -> &'static str {
         match self {
+            Self::Directory(config) => config.get_component_name(),
             Self::Exec(config) => config.get_component_name(),
+            Self::File(config) => config.get_component_name(),
             #[cfg(feature = "secrets-aws-secrets-manager")]
             Self::AwsSecretsManager(config) => config.get_component_name(),
+            #[cfg(feature = "secrets-vault")]
+            Self::Vault(config) => config.get_component_name(),
             Self::Test(config) => config.get_component_name(),
         }
     }
@@ -0,0 +1,453 @@
+use std::collections::{HashMap, HashSet};
+
+use bytes::Bytes;
+use http::{Method, Request};
+use hyper::Body;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tokio::time::{timeout, Duration};
+use vector_lib::configurable::{component::GenerateConfig, configurable_component};
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::{
+    config::{ProxyConfig, SecretBackend},
+    http::HttpClient,
+    signal,
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// Configuration for the `vault` secrets backend.
+///
+/// Secrets are referenced as `SECRET[<backend>.<path>.<field>]`, where the dots in `<path>` stand
+/// for the slashes separating the segments of the secret's path within the KV secrets engine. For
+/// example, `SECRET[vault.app.db.password]` resolves the `password` field of the secret at
+/// `app/db`.
+#[configurable_component(secrets("vault"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct VaultBackend {
+    /// The address of the Vault server.
+    #[configurable(metadata(docs::examples = "https://vault.example.com:8200"))]
+    pub endpoint: String,
+
+    /// The path at which the KV secrets engine is mounted.
+    #[serde(default = "default_mount")]
+    pub mount: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub kv_version: KvVersion,
+
+    /// The namespace to read secrets from.
+    ///
+    /// Namespaces are only available with Vault Enterprise and HCP Vault.
+    #[configurable(metadata(docs::examples = "admin/team"))]
+    pub namespace: Option<String>,
+
+    #[configurable(derived)]
+    pub auth: VaultAuth,
+
+    /// The timeout, in seconds, for each request to Vault.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout: u64,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub proxy: ProxyConfig,
+}
+
+/// The version of the KV secrets engine.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum KvVersion {
+    /// Version 1 of the KV secrets engine, which isn't versioned.
+    V1,
+
+    /// Version 2 of the KV secrets engine, which keeps versions of each secret.
+    #[default]
+    V2,
+}
+
+/// Configuration of the authentication strategy for Vault.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "strategy")]
+#[configurable(metadata(docs::enum_tag_description = "The authentication strategy to use."))]
+pub enum VaultAuth {
+    /// Authenticate with a Vault token.
+    Token {
+        /// The Vault token.
+        #[configurable(metadata(docs::examples = "${VAULT_TOKEN}"))]
+        token: SensitiveString,
+    },
+
+    /// Authenticate with the AppRole auth method.
+    #[serde(rename = "approle")]
+    AppRole {
+        /// The role ID of the AppRole.
+        role_id: String,
+
+        /// The secret ID of the AppRole.
+        #[configurable(metadata(docs::examples = "${VAULT_SECRET_ID}"))]
+        secret_id: SensitiveString,
+
+        /// The path at which the AppRole auth method is mounted.
+        #[serde(default = "default_approle_mount")]
+        mount: String,
+    },
+}
+
+fn default_mount() -> String {
+    "secret".to_owned()
+}
+
+fn default_approle_mount() -> String {
+    "approle".to_owned()
+}
+
+const fn default_timeout_secs() -> u64 {
+    5
+}
+
+impl GenerateConfig for VaultBackend {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(VaultBackend {
+            endpoint: String::from("https://vault.example.com:8200"),
+            mount: default_mount(),
+            kv_version: KvVersion::V2,
+            namespace: None,
+            auth: VaultAuth::Token {
+                token: String::from("${VAULT_TOKEN}").into(),
+            },
+            timeout: default_timeout_secs(),
+            tls: None,
+            proxy: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[derive(Deserialize)]
+struct ErrorResponse {
+    errors: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct LoginResponse {
+    auth: LoginAuth,
+}
+
+#[derive(Deserialize)]
+struct LoginAuth {
+    client_token: String,
+}
+
+#[derive(Deserialize)]
+struct SecretResponse<T> {
+    data: T,
+}
+
+#[derive(Deserialize)]
+struct KvV2Data {
+    data: Map<String, Value>,
+}
+
+impl SecretBackend for VaultBackend {
+    async fn retrieve(
+        &mut self,
+        secret_keys: HashSet<String>,
+        _: &mut signal::SignalRx,
+    ) -> crate::Result<HashMap<String, String>> {
+        let tls_settings = TlsSettings::from_options(&self.tls)?;
+        let proxy = ProxyConfig::from_env().merge(&self.proxy);
+        let client = HttpClient::<Body>::new(tls_settings, &proxy)?;
+
+        let token = self.login(&client).await?;
+
+        // Secret keys referring to the same secret are resolved with a single read.
+        let mut paths = HashMap::<String, Vec<(String, String)>>::new();
+        for key in secret_keys {
+            let (path, field) = key.rsplit_once('.').ok_or_else(|| {
+                format!(
+                    "secret key '{}' must be of the form '<path>.<field>' for the vault backend",
+                    key
+                )
+            })?;
+            paths
+                .entry(path.replace('.', "/"))
+                .or_default()
+                .push((field.to_owned(), key));
+        }
+
+        let mut secrets = HashMap::new();
+        for (path, fields) in paths {
+            let data = self.read(&client, &token, &path).await?;
+            for (field, key) in fields {
+                let value = match data.get(&field) {
+                    Some(Value::String(value)) => value.clone(),
+                    Some(Value::Null) | None => {
+                        return Err(format!(
+                            "field '{}' of secret '{}' does not exist",
+                            field, path
+                        )
+                        .into());
+                    }
+                    Some(value) => value.to_string(),
+                };
+                if value.is_empty() {
+                    return Err(format!("field '{}' of secret '{}' was empty", field, path).into());
+                }
+                secrets.insert(key, value);
+            }
+        }
+        Ok(secrets)
+    }
+}
+
+impl VaultBackend {
+    fn request(&self, method: Method, path: &str) -> http::request::Builder {
+        let uri = format!(
+            "{}/v1/{}",
+            self.endpoint.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        );
+        let builder = Request::builder().method(method).uri(uri);
+        match &self.namespace {
+            Some(namespace) => builder.header("X-Vault-Namespace", namespace),
+            None => builder,
+        }
+    }
+
+    async fn send(&self, client: &HttpClient, request: Request<Body>) -> crate::Result<Bytes> {
+        let uri = request.uri().to_string();
+        let response = timeout(Duration::from_secs(self.timeout), async {
+            let response = client.send(request).await?;
+            let (parts, body) = response.into_parts();
+            let body = hyper::body::to_bytes(body).await?;
+            Ok::<_, crate::Error>((parts.status, body))
+        })
+        .await
+        .map_err(|_| format!("request to '{}' timed out", uri))??;
+
+        let (status, body) = response;
+        if !status.is_success() {
+            let errors = serde_json::from_slice::<ErrorResponse>(&body)
+                .map(|response| response.errors.join(", "))
+                .unwrap_or_else(|_| String::from_utf8_lossy(&body).into_owned());
+            return Err(format!("request to '{}' failed with {}: {}", uri, status, errors).into());
+        }
+        Ok(body)
+    }
+
+    /// Returns the token to authenticate the requests reading secrets with.
+    async fn login(&self, client: &HttpClient) -> crate::Result<String> {
+        match &self.auth {
+            VaultAuth::Token { token } => Ok(token.inner().to_owned()),
+            VaultAuth::AppRole {
+                role_id,
+                secret_id,
+                mount,
+            } => {
+                let body = serde_json::json!({
+                    "role_id": role_id,
+                    "secret_id": secret_id.inner(),
+                });
+                let request = self
+                    .request(
+                        Method::POST,
+                        &format!("auth/{}/login", mount.trim_matches('/')),
+                    )
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&body)?))?;
+                let body = self.send(client, request).await?;
+                let response = serde_json::from_slice::<LoginResponse>(&body)?;
+                Ok(response.auth.client_token)
+            }
+        }
+    }
+
+    /// Reads the fields of the secret at the given path of the KV secrets engine.
+    async fn read(
+        &self,
+        client: &HttpClient,
+        token: &str,
+        path: &str,
+    ) -> crate::Result<Map<String, Value>> {
+        let mount = self.mount.trim_matches('/');
+        let path = match self.kv_version {
+            KvVersion::V1 => format!("{}/{}", mount, path),
+            KvVersion::V2 => format!("{}/data/{}", mount, path),
+        };
+        let request = self
+            .request(Method::GET, &path)
+            .header("X-Vault-Token", token)
+            .body(Body::empty())?;
+        let body = self.send(client, request).await?;
+
+        Ok(match self.kv_version {
+            KvVersion::V1 => {
+                serde_json::from_slice::<SecretResponse<Map<String, Value>>>(&body)?.data
+            }
+            KvVersion::V2 => {
+                serde_json::from_slice::<SecretResponse<KvV2Data>>(&body)?
+                    .data
+                    .data
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tokio::sync::broadcast;
+    use wiremock::{
+        matchers::{body_json, header, method, path},
+        Mock, MockServer, ResponseTemplate,
+    };
+
+    use super::*;
+
+    fn backend(server: &MockServer, kv_version: KvVersion, auth: VaultAuth) -> VaultBackend {
+        VaultBackend {
+            endpoint: server.uri(),
+            mount: default_mount(),
+            kv_version,
+            namespace: None,
+            auth,
+            timeout: default_timeout_secs(),
+            tls: None,
+            proxy: Default::default(),
+        }
+    }
+
+    async fn retrieve(
+        backend: &mut VaultBackend,
+        keys: &[&str],
+    ) -> crate::Result<HashMap<String, String>> {
+        let (_tx, mut signal_rx) = broadcast::channel(1);
+        backend
+            .retrieve(
+                keys.iter().map(|key| key.to_string()).collect(),
+                &mut signal_rx,
+            )
+            .await
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<VaultBackend>();
+    }
+
+    #[tokio::test]
+    async fn reads_kv_v2_secrets_with_token() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/secret/data/app/db"))
+            .and(header("X-Vault-Token", "token"))
+            .and(header("X-Vault-Namespace", "team"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": {
+                    "data": { "username": "vector", "port": 5432 },
+                    "metadata": { "version": 3 }
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut backend = backend(
+            &server,
+            KvVersion::V2,
+            VaultAuth::Token {
+                token: String::from("token").into(),
+            },
+        );
+        backend.namespace = Some("team".to_owned());
+
+        let secrets = retrieve(&mut backend, &["app.db.username", "app.db.port"])
+            .await
+            .unwrap();
+
+        assert_eq!(secrets["app.db.username"], "vector");
+        assert_eq!(secrets["app.db.port"], "5432");
+    }
+
+    #[tokio::test]
+    async fn reads_kv_v1_secrets_with_approle() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/auth/approle/login"))
+            .and(body_json(
+                json!({ "role_id": "role", "secret_id": "secret" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "auth": { "client_token": "client-token" }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/kv/password"))
+            .and(header("X-Vault-Token", "client-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "value": "hunter2" }
+            })))
+            .mount(&server)
+            .await;
+
+        let mut backend = backend(
+            &server,
+            KvVersion::V1,
+            VaultAuth::AppRole {
+                role_id: "role".to_owned(),
+                secret_id: String::from("secret").into(),
+                mount: default_approle_mount(),
+            },
+        );
+        backend.mount = "kv".to_owned();
+
+        let secrets = retrieve(&mut backend, &["password.value"]).await.unwrap();
+
+        assert_eq!(secrets["password.value"], "hunter2");
+    }
+
+    #[tokio::test]
+    async fn fails_on_missing_field_or_error_response() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/secret/data/app"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": { "data": { "username": "vector" } }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/secret/data/denied"))
+            .respond_with(
+                ResponseTemplate::new(403)
+                    .set_body_json(json!({ "errors": ["permission denied"] })),
+            )
+            .mount(&server)
+            .await;
+
+        let mut backend = backend(
+            &server,
+            KvVersion::V2,
+            VaultAuth::Token {
+                token: String::from("token").into(),
+            },
+        );
+
+        let error = retrieve(&mut backend, &["app.password"]).await.unwrap_err();
+        assert!(error.to_string().contains("does not exist"));
+
+        let error = retrieve(&mut backend, &["denied.password"])
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("permission denied"));
+    }
+}
@@ -20,7 +20,7 @@ where
     S: Service<AppsignalRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+    S::Error: std::fmt::Debug + std::fmt::Display + Into<crate::Error> + Send,
 {
     pub(super) async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let service = ServiceBuilder::new().service(self.service);
@@ -65,7 +65,7 @@ where
     S: Service<AppsignalRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+    S::Error: std::fmt::Debug + std::fmt::Display + Into<crate::Error> + Send,
 {
     async fn run(
         self: Box<Self>,
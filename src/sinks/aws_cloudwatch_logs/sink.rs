@@ -30,7 +30,7 @@ where
     S: Service<BatchCloudwatchRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: fmt::Debug + Into<crate::Error> + Send,
+    S::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
 {
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let mut request_builder = self.request_builder;
@@ -106,7 +106,7 @@ where
     S: Service<BatchCloudwatchRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: fmt::Debug + Into<crate::Error> + Send,
+    S::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
 {
     async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         self.run_inner(input).await
@@ -1,4 +1,8 @@
-use std::{borrow::Cow, fmt::Debug, marker::PhantomData};
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Display},
+    marker::PhantomData,
+};
 
 use rand::random;
 use vector_lib::lookup::lookup_v2::ConfigValuePath;
@@ -38,7 +42,7 @@ where
     S: Service<BatchKinesisRequest<R>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: Debug + Into<crate::Error> + Send,
+    S::Error: Debug + Display + Into<crate::Error> + Send,
     R: Record + Send + Sync + Unpin + Clone + 'static,
 {
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
@@ -84,7 +88,7 @@ where
     S: Service<BatchKinesisRequest<R>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: Debug + Into<crate::Error> + Send,
+    S::Error: Debug + Display + Into<crate::Error> + Send,
     R: Record + Send + Sync + Unpin + Clone + 'static,
 {
     async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
@@ -22,8 +22,8 @@ use crate::{
             sink::S3Sink,
         },
         util::{
-            timezone_to_offset, BatchConfig, BulkSizeBasedDefaultBatchSettings, Compression,
-            ServiceBuilderExt, TowerRequestConfig,
+            encoding::object_extension, timezone_to_offset, BatchConfig,
+            BulkSizeBasedDefaultBatchSettings, Compression, ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck,
     },
@@ -90,7 +90,8 @@ pub struct S3SinkConfig {
 
     /// The filename extension to use in the object key.
     ///
-    /// This overrides setting the extension based on the configured `compression`.
+    /// This overrides setting the extension based on the configured `compression`, or `parquet`
+    /// when using the `parquet` codec.
     #[configurable(metadata(docs::examples = "json"))]
     pub filename_extension: Option<String>,
 
@@ -227,13 +228,24 @@ impl S3SinkConfig {
         let partitioner = S3KeyPartitioner::new(key_prefix, ssekms_key_id);
 
         let transformer = self.encoding.transformer();
-        let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
+        let (framer, serializer) = self.encoding.build_batched(SinkType::MessageBased)?;
+        let format_extension = object_extension(&serializer, self.compression)?;
         let encoder = Encoder::<Framer>::new(framer, serializer);
 
+        let mut api_options = self.options.clone();
+        if format_extension.is_some() {
+            api_options
+                .content_type
+                .get_or_insert_with(|| encoder.content_type().to_owned());
+        }
+
         let request_options = S3RequestOptions {
             bucket: self.bucket.clone(),
-            api_options: self.options.clone(),
-            filename_extension: self.filename_extension.clone(),
+            api_options,
+            filename_extension: self
+                .filename_extension
+                .clone()
+                .or_else(|| format_extension.map(Into::into)),
             filename_time_format: self.filename_time_format.clone(),
             filename_append_uuid: self.filename_append_uuid,
             encoder: (transformer, encoder),
@@ -16,8 +16,8 @@ use crate::{
             self, config::AzureBlobRetryLogic, service::AzureBlobService, sink::AzureBlobSink,
         },
         util::{
-            partitioner::KeyPartitioner, BatchConfig, BulkSizeBasedDefaultBatchSettings,
-            Compression, ServiceBuilderExt, TowerRequestConfig,
+            encoding::object_extension, partitioner::KeyPartitioner, BatchConfig,
+            BulkSizeBasedDefaultBatchSettings, Compression, ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
@@ -228,13 +228,15 @@ impl AzureBlobSinkConfig {
             .unwrap_or(DEFAULT_FILENAME_APPEND_UUID);
 
         let transformer = self.encoding.transformer();
-        let (framer, serializer) = self.encoding.build(SinkType::MessageBased)?;
+        let (framer, serializer) = self.encoding.build_batched(SinkType::MessageBased)?;
+        let blob_extension = object_extension(&serializer, self.compression)?;
         let encoder = Encoder::<Framer>::new(framer, serializer);
 
         let request_options = AzureBlobRequestOptions {
             container_name: self.container_name.clone(),
             blob_time_format,
             blob_append_uuid,
+            blob_extension,
             encoder: (transformer, encoder),
             compression: self.compression,
         };
@@ -22,6 +22,7 @@ pub struct AzureBlobRequestOptions {
     pub container_name: String,
     pub blob_time_format: String,
     pub blob_append_uuid: bool,
+    pub blob_extension: Option<&'static str>,
     pub encoder: (Transformer, Encoder<Framer>),
     pub compression: Compression,
 }
@@ -74,7 +75,9 @@ impl RequestBuilder<(String, Vec<Event>)> for AzureBlobRequestOptions {
                 .unwrap_or_else(|| formatted_ts.to_string())
         };
 
-        let extension = self.compression.extension();
+        let extension = self
+            .blob_extension
+            .unwrap_or_else(|| self.compression.extension());
         azure_metadata.partition_key = format!(
             "{}{}.{}",
             azure_metadata.partition_key, blob_name, extension
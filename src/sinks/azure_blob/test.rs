@@ -59,6 +59,7 @@ fn azure_blob_build_request_without_compression() {
         container_name,
         blob_time_format,
         blob_append_uuid,
+        blob_extension: None,
         encoder: (
             Default::default(),
             Encoder::<Framer>::new(
@@ -107,6 +108,7 @@ fn azure_blob_build_request_with_compression() {
         container_name,
         blob_time_format,
         blob_append_uuid,
+        blob_extension: None,
         encoder: (
             Default::default(),
             Encoder::<Framer>::new(
@@ -155,6 +157,7 @@ fn azure_blob_build_request_with_time_format() {
         container_name,
         blob_time_format,
         blob_append_uuid,
+        blob_extension: None,
         encoder: (
             Default::default(),
             Encoder::<Framer>::new(
@@ -206,6 +209,7 @@ fn azure_blob_build_request_with_uuid() {
         container_name,
         blob_time_format,
         blob_append_uuid,
+        blob_extension: None,
         encoder: (
             Default::default(),
             Encoder::<Framer>::new(
@@ -30,7 +30,7 @@ where
     Svc: Service<RB::Request> + Send + 'static,
     Svc::Future: Send + 'static,
     Svc::Response: DriverResponse + Send + 'static,
-    Svc::Error: fmt::Debug + Into<crate::Error> + Send,
+    Svc::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
     RB: RequestBuilder<(String, Vec<Event>)> + Send + Sync + 'static,
     RB::Error: fmt::Display + Send,
     RB::Request: Finalizable + MetaDescriptive + Send,
@@ -72,7 +72,7 @@ where
     Svc: Service<RB::Request> + Send + 'static,
     Svc::Future: Send + 'static,
     Svc::Response: DriverResponse + Send + 'static,
-    Svc::Error: fmt::Debug + Into<crate::Error> + Send,
+    Svc::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
     RB: RequestBuilder<(String, Vec<Event>)> + Send + Sync + 'static,
     RB::Error: fmt::Display + Send,
     RB::Request: Finalizable + MetaDescriptive + Send,
@@ -1,4 +1,7 @@
-use std::{fmt::Debug, io};
+use std::{
+    fmt::{Debug, Display},
+    io,
+};
 
 use bytes::Bytes;
 use vector_lib::codecs::{encoding::Framer, CharacterDelimitedEncoder, JsonSerializerConfig};
@@ -20,7 +23,7 @@ where
     S: Service<AzureMonitorLogsRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: Debug + Into<crate::Error> + Send,
+    S::Error: Debug + Display + Into<crate::Error> + Send,
 {
     pub fn new(
         batch_settings: BatcherSettings,
@@ -68,7 +71,7 @@ where
     S: Service<AzureMonitorLogsRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: Debug + Into<crate::Error> + Send,
+    S::Error: Debug + Display + Into<crate::Error> + Send,
 {
     async fn run(
         self: Box<Self>,
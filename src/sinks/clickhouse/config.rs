@@ -7,11 +7,13 @@ use super::{
 };
 use crate::{
     http::{Auth, HttpClient, MaybeAuth},
+    oauth2,
     sinks::{
         prelude::*,
         util::{http::HttpService, RealtimeSizeBasedDefaultBatchSettings, UriSerde},
     },
 };
+use bytes::Bytes;
 use http::{Request, StatusCode, Uri};
 use hyper::Body;
 use std::fmt;
@@ -199,13 +201,14 @@ fn get_healthcheck_uri(endpoint: &Uri) -> String {
 
 async fn healthcheck(client: HttpClient, endpoint: Uri, auth: Option<Auth>) -> crate::Result<()> {
     let uri = get_healthcheck_uri(&endpoint);
-    let mut request = Request::get(uri).body(Body::empty()).unwrap();
+    let mut request = Request::get(uri).body(Bytes::new()).unwrap();
 
     if let Some(auth) = auth {
         auth.apply(&mut request);
     }
 
-    let response = client.send(request).await?;
+    let response =
+        oauth2::send_authorized(request, |request| client.send(request.map(Body::from))).await?;
 
     match response.status() {
         StatusCode::OK => Ok(()),
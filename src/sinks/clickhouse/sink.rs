@@ -17,7 +17,7 @@ where
     S: Service<HttpRequest<PartitionKey>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+    S::Error: std::fmt::Debug + std::fmt::Display + Into<crate::Error> + Send,
 {
     pub const fn new(
         batch_settings: BatcherSettings,
@@ -71,7 +71,7 @@ where
     S: Service<HttpRequest<PartitionKey>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+    S::Error: std::fmt::Debug + std::fmt::Display + Into<crate::Error> + Send,
 {
     async fn run(
         self: Box<Self>,
@@ -19,7 +19,7 @@ where
     S: Service<DatadogEventsRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: fmt::Debug + Into<crate::Error> + Send,
+    S::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
 {
     async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         input
@@ -86,7 +86,7 @@ where
     S: Service<DatadogEventsRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: fmt::Debug + Into<crate::Error> + Send,
+    S::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
 {
     async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         self.run(input).await
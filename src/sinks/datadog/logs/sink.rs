@@ -1,4 +1,9 @@
-use std::{collections::VecDeque, fmt::Debug, io, sync::Arc};
+use std::{
+    collections::VecDeque,
+    fmt::{Debug, Display},
+    io,
+    sync::Arc,
+};
 
 use itertools::Itertools;
 use snafu::Snafu;
@@ -329,7 +334,7 @@ where
     S: Service<LogApiRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: Debug + Into<crate::Error> + Send,
+    S::Error: Debug + Display + Into<crate::Error> + Send,
 {
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let default_api_key = Arc::clone(&self.default_api_key);
@@ -379,7 +384,7 @@ where
     S: Service<LogApiRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: Debug + Into<crate::Error> + Send,
+    S::Error: Debug + Display + Into<crate::Error> + Send,
 {
     async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         self.run_inner(input).await
@@ -17,16 +17,18 @@ impl MetricNormalize for DatadogMetricsNormalizer {
             // system sending an incremental gauge update is kind of doing it wrong, but alas.
             MetricValue::Counter { .. } => state.make_incremental(metric),
             MetricValue::Gauge { .. } => state.make_absolute(metric),
-            // We convert distributions and aggregated histograms to sketches internally. We can't
+            // We convert distributions and aggregated or exponential histograms to sketches internally. We can't
             // send absolute sketches to Datadog, though, so we incrementalize them first.
             MetricValue::Distribution { .. } => state
                 .make_incremental(metric)
                 .filter(|metric| !metric.value().is_empty())
                 .and_then(|metric| AgentDDSketch::transform_to_sketch(metric).ok()),
-            MetricValue::AggregatedHistogram { .. } => state
-                .make_incremental(metric)
-                .filter(|metric| !metric.value().is_empty())
-                .and_then(|metric| AgentDDSketch::transform_to_sketch(metric).ok()),
+            MetricValue::AggregatedHistogram { .. } | MetricValue::ExponentialHistogram { .. } => {
+                state
+                    .make_incremental(metric)
+                    .filter(|metric| !metric.value().is_empty())
+                    .and_then(|metric| AgentDDSketch::transform_to_sketch(metric).ok())
+            }
             // Sketches cannot be subtracted from one another, so we treat them as implicitly
             // incremental, and just update the metric type.
             MetricValue::Sketch { .. } => Some(metric.into_incremental()),
@@ -46,6 +46,7 @@ impl Partitioner for DatadogMetricsTypePartitioner {
             MetricValue::Set { .. } => DatadogMetricsEndpoint::series(),
             MetricValue::Distribution { .. } => DatadogMetricsEndpoint::Sketches,
             MetricValue::AggregatedHistogram { .. } => DatadogMetricsEndpoint::Sketches,
+            MetricValue::ExponentialHistogram { .. } => DatadogMetricsEndpoint::Sketches,
             // NOTE: AggregatedSummary will be split into counters and gauges during normalization
             MetricValue::AggregatedSummary { .. } => DatadogMetricsEndpoint::series(),
             MetricValue::Sketch { .. } => DatadogMetricsEndpoint::Sketches,
@@ -64,7 +65,7 @@ pub(crate) struct DatadogMetricsSink<S> {
 impl<S> DatadogMetricsSink<S>
 where
     S: Service<DatadogMetricsRequest> + Send,
-    S::Error: fmt::Debug + Send + 'static,
+    S::Error: fmt::Debug + fmt::Display + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse,
 {
@@ -153,7 +154,7 @@ where
 impl<S> StreamSink<Event> for DatadogMetricsSink<S>
 where
     S: Service<DatadogMetricsRequest> + Send,
-    S::Error: fmt::Debug + Send + 'static,
+    S::Error: fmt::Debug + fmt::Display + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse,
 {
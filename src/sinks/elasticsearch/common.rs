@@ -14,6 +14,7 @@ use super::{
 };
 use crate::{
     http::{HttpClient, MaybeAuth},
+    oauth2,
     sinks::{
         elasticsearch::{
             ElasticsearchAuthConfig, ElasticsearchCommonMode, ElasticsearchConfig, ParseError,
@@ -69,6 +70,11 @@ impl ElasticsearchCommon {
                 let auth = auth.choose_one(&uri.auth)?.unwrap();
                 Some(Auth::Basic(auth))
             }
+            Some(ElasticsearchAuthConfig::OAuth2(oauth2)) => {
+                let auth = Some(crate::http::Auth::OAuth2(oauth2.clone()));
+                let auth = auth.choose_one(&uri.auth)?.unwrap();
+                Some(Auth::Basic(auth))
+            }
             #[cfg(feature = "aws-core")]
             Some(ElasticsearchAuthConfig::Aws(aws)) => {
                 let region = config
@@ -343,8 +349,9 @@ async fn get(
         }
     }
 
-    client
-        .send(request.map(hyper::Body::from))
-        .await
-        .map_err(Into::into)
+    oauth2::send_authorized(request, |request| {
+        client.send(request.map(hyper::Body::from))
+    })
+    .await
+    .map_err(Into::into)
 }
@@ -49,6 +49,10 @@ pub enum ElasticsearchAuthConfig {
         password: SensitiveString,
     },
 
+    /// OAuth2 authentication.
+    #[serde(rename = "oauth2")]
+    OAuth2(crate::oauth2::OAuth2Config),
+
     #[cfg(feature = "aws-core")]
     /// Amazon OpenSearch Service-specific authentication.
     Aws(crate::aws::AwsAuthentication),
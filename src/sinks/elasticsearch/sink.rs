@@ -60,7 +60,7 @@ where
     S: Service<ElasticsearchRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: fmt::Debug + Into<crate::Error> + Send,
+    S::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
 {
     pub async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let mode = self.mode;
@@ -168,7 +168,7 @@ where
     S: Service<ElasticsearchRequest> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: fmt::Debug + Into<crate::Error> + Send,
+    S::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
 {
     async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         self.run_inner(input).await
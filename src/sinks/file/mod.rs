@@ -16,30 +16,35 @@ use tokio::{
 };
 use tokio_util::codec::Encoder as _;
 use vector_lib::codecs::{
-    encoding::{Framer, FramingConfig},
+    encoding::{Framer, FramingConfig, ParquetWriter, Serializer},
     TextSerializerConfig,
 };
 use vector_lib::configurable::configurable_component;
 use vector_lib::{
     internal_event::{CountByteSize, EventsSent, InternalEventHandle as _, Output, Registered},
+    json_size::JsonSize,
     EstimatedJsonEncodedSizeOf, TimeZone,
 };
 
 use crate::{
     codecs::{Encoder, EncodingConfigWithFraming, SinkType, Transformer},
     config::{AcknowledgementsConfig, DataType, GenerateConfig, Input, SinkConfig, SinkContext},
-    event::{Event, EventStatus, Finalizable},
+    event::{Event, EventFinalizers, EventStatus, Finalizable},
     expiring_hash_map::ExpiringHashMap,
     internal_events::{
-        FileBytesSent, FileInternalMetricsConfig, FileIoError, FileOpen, TemplateRenderingError,
+        EncoderSerializeError, FileBytesSent, FileInternalMetricsConfig, FileIoError, FileOpen,
+        TemplateRenderingError,
     },
     sinks::util::{timezone_to_offset, StreamSink},
     template::Template,
 };
 
 mod bytes_path;
+mod rotation;
 
 use bytes_path::BytesPath;
+use rotation::FileStats;
+pub use rotation::{RetentionConfig, RotationConfig, RotationNaming};
 
 /// Configuration for the `file` sink.
 #[serde_as]
@@ -74,6 +79,10 @@ pub struct FileSinkConfig {
     #[serde(default, skip_serializing_if = "crate::serde::is_default")]
     pub compression: Compression,
 
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub rotation: RotationConfig,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -98,6 +107,7 @@ impl GenerateConfig for FileSinkConfig {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Default::default(),
+            rotation: Default::default(),
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: Default::default(),
@@ -136,15 +146,41 @@ enum OutFile {
     Regular(File),
     Gzip(GzipEncoder<File>),
     Zstd(ZstdEncoder<File>),
+    Parquet {
+        file: File,
+        // Taken when the file is closed, as writing the footer consumes the writer.
+        writer: Option<ParquetWriter>,
+    },
 }
 
 impl OutFile {
-    fn new(file: File, compression: Compression) -> Self {
-        match compression {
+    async fn new(
+        file: File,
+        compression: Compression,
+        parquet_writer: Option<ParquetWriter>,
+    ) -> Result<Self, std::io::Error> {
+        if let Some(writer) = parquet_writer {
+            // A Parquet file ends with its footer, so rows can't be appended to an existing file.
+            // Such files are rotated out of the way before being opened again, when rotation is
+            // configured.
+            if file.metadata().await?.len() > 0 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    "Parquet files can't be appended to, and the file is not empty. Configure \
+                     `rotation` to rotate it instead.",
+                ));
+            }
+            return Ok(OutFile::Parquet {
+                file,
+                writer: Some(writer),
+            });
+        }
+
+        Ok(match compression {
             Compression::None => OutFile::Regular(file),
             Compression::Gzip => OutFile::Gzip(GzipEncoder::new(file)),
             Compression::Zstd => OutFile::Zstd(ZstdEncoder::new(file)),
-        }
+        })
     }
 
     async fn sync_all(&mut self) -> Result<(), std::io::Error> {
@@ -152,14 +188,27 @@ impl OutFile {
             OutFile::Regular(file) => file.sync_all().await,
             OutFile::Gzip(gzip) => gzip.get_mut().sync_all().await,
             OutFile::Zstd(zstd) => zstd.get_mut().sync_all().await,
+            OutFile::Parquet { file, .. } => file.sync_all().await,
         }
     }
 
-    async fn shutdown(&mut self) -> Result<(), std::io::Error> {
+    /// Shuts the file down, returning the number of bytes that were left to write to it.
+    async fn shutdown(&mut self) -> Result<usize, std::io::Error> {
         match self {
-            OutFile::Regular(file) => file.shutdown().await,
-            OutFile::Gzip(gzip) => gzip.shutdown().await,
-            OutFile::Zstd(zstd) => zstd.shutdown().await,
+            OutFile::Regular(file) => file.shutdown().await.map(|()| 0),
+            OutFile::Gzip(gzip) => gzip.shutdown().await.map(|()| 0),
+            OutFile::Zstd(zstd) => zstd.shutdown().await.map(|()| 0),
+            OutFile::Parquet { file, writer } => {
+                let mut byte_size = 0;
+                if let Some(writer) = writer.take() {
+                    let footer = writer.finish().map_err(|error| {
+                        std::io::Error::new(std::io::ErrorKind::InvalidData, error)
+                    })?;
+                    file.write_all(&footer).await?;
+                    byte_size = footer.len();
+                }
+                file.shutdown().await.map(|()| byte_size)
+            }
         }
     }
 
@@ -168,14 +217,122 @@ impl OutFile {
             OutFile::Regular(file) => file.write_all(src).await,
             OutFile::Gzip(gzip) => gzip.write_all(src).await,
             OutFile::Zstd(zstd) => zstd.write_all(src).await,
+            OutFile::Parquet { file, .. } => file.write_all(src).await,
+        }
+    }
+
+    /// Writes an event as a row of a Parquet file, returning the number of bytes of completed
+    /// row groups written to the file.
+    ///
+    /// Events that don't fit the column schema are reported as encoding errors.
+    async fn write_row(&mut self, event: &Event) -> Result<usize, WriteError> {
+        let OutFile::Parquet {
+            writer: Some(writer),
+            ..
+        } = self
+        else {
+            unreachable!("rows are only written to open Parquet files");
+        };
+
+        let error = match writer.write(std::slice::from_ref(event)) {
+            Ok(rejected) => rejected.into_iter().next().map(|rejected| rejected.error),
+            Err(error) => Some(error),
+        };
+        if let Some(error) = error {
+            emit!(EncoderSerializeError { error: &error });
+            return Err(WriteError::Encoding);
+        }
+
+        let bytes = writer.take_bytes();
+        self.write_all(&bytes).await?;
+        Ok(bytes.len())
+    }
+
+    /// Whether rows are buffered in memory, rather than written to the file yet.
+    ///
+    /// Only Parquet files buffer rows, until their row group is complete.
+    fn has_buffered_rows(&self) -> bool {
+        match self {
+            OutFile::Parquet {
+                writer: Some(writer),
+                ..
+            } => writer.buffered_rows() > 0,
+            _ => false,
+        }
+    }
+
+    /// The estimated size of the rows buffered in memory.
+    fn buffered_bytes(&self) -> usize {
+        match self {
+            OutFile::Parquet {
+                writer: Some(writer),
+                ..
+            } => writer.buffered_size(),
+            _ => 0,
         }
     }
 
     /// Shutdowns by flushing data, writing headers, and syncing all of that
     /// data and metadata to the filesystem.
-    async fn close(&mut self) -> Result<(), std::io::Error> {
-        self.shutdown().await?;
-        self.sync_all().await
+    ///
+    /// Returns the number of bytes that were left to write to the file.
+    async fn close(&mut self) -> Result<usize, std::io::Error> {
+        let byte_size = self.shutdown().await?;
+        self.sync_all().await.map(|()| byte_size)
+    }
+}
+
+/// Why an event couldn't be written to a file.
+enum WriteError {
+    /// The event couldn't be encoded, which was already reported.
+    Encoding,
+    Io(std::io::Error),
+}
+
+impl From<std::io::Error> for WriteError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+struct OpenFile {
+    file: OutFile,
+    stats: FileStats,
+    pending: PendingEvents,
+}
+
+/// The events written to a file that are not acknowledged yet.
+///
+/// Events are acknowledged once they are written to the file. The rows of a Parquet file are
+/// buffered in memory until their row group is complete, so they are only acknowledged once
+/// the row group is written, or the file is closed.
+#[derive(Default)]
+struct PendingEvents {
+    finalizers: EventFinalizers,
+    count: usize,
+    byte_size: JsonSize,
+}
+
+impl PendingEvents {
+    fn push(&mut self, finalizers: EventFinalizers, byte_size: JsonSize) {
+        self.finalizers.merge(finalizers);
+        self.count += 1;
+        self.byte_size += byte_size;
+    }
+
+    fn deliver(&mut self, events_sent: &Registered<EventsSent>) {
+        let pending = std::mem::take(self);
+        if pending.count > 0 {
+            events_sent.emit(CountByteSize(pending.count, pending.byte_size));
+        }
+        pending.finalizers.update_status(EventStatus::Delivered);
+    }
+
+    /// Fails the pending events, returning how many there were.
+    fn fail(&mut self) -> usize {
+        let pending = std::mem::take(self);
+        pending.finalizers.update_status(EventStatus::Errored);
+        pending.count
     }
 }
 
@@ -207,8 +364,9 @@ pub struct FileSink {
     transformer: Transformer,
     encoder: Encoder<Framer>,
     idle_timeout: Duration,
-    files: ExpiringHashMap<Bytes, OutFile>,
+    files: ExpiringHashMap<Bytes, OpenFile>,
     compression: Compression,
+    rotation: RotationConfig,
     events_sent: Registered<EventsSent>,
     include_file_metric_tag: bool,
 }
@@ -216,7 +374,18 @@ pub struct FileSink {
 impl FileSink {
     pub fn new(config: &FileSinkConfig, cx: SinkContext) -> crate::Result<Self> {
         let transformer = config.encoding.transformer();
-        let (framer, serializer) = config.encoding.build(SinkType::StreamBased)?;
+        let (framer, serializer) = config.encoding.build_batched(SinkType::StreamBased)?;
+        if matches!(serializer, Serializer::Parquet(_)) && config.compression != Compression::None {
+            return Err(
+                "The `parquet` codec can't be combined with compression. Set \
+                 `compression = \"none\"` and use `encoding.parquet.compression` to compress the \
+                 file's contents instead."
+                    .into(),
+            );
+        }
+        if config.rotation.in_progress_suffix.as_deref() == Some("") {
+            return Err("`rotation.in_progress_suffix` can't be empty.".into());
+        }
         let encoder = Encoder::<Framer>::new(framer, serializer);
 
         let offset = config
@@ -231,6 +400,7 @@ impl FileSink {
             idle_timeout: config.idle_timeout,
             files: ExpiringHashMap::default(),
             compression: config.compression,
+            rotation: config.rotation.clone(),
             events_sent: register!(EventsSent::from(Output(None))),
             include_file_metric_tag: config.internal_metrics.include_file_tag,
         })
@@ -273,17 +443,15 @@ impl FileSink {
                             // Close all the open files.
                             debug!(message = "Closing all the open files.");
                             for (path, file) in self.files.iter_mut() {
-                                if let Err(error) = file.close().await {
-                                    emit!(FileIoError {
-                                        error,
-                                        code: "failed_closing_file",
-                                        message: "Failed to close file.",
-                                        path,
-                                        dropped_events: 0,
-                                    });
-                                } else{
-                                    trace!(message = "Successfully closed file.", path = ?path);
-                                }
+                                close_file(
+                                    &self.rotation,
+                                    path,
+                                    file,
+                                    false,
+                                    &self.events_sent,
+                                    self.include_file_metric_tag,
+                                )
+                                .await;
                             }
 
                             emit!(FileOpen {
@@ -302,16 +470,16 @@ impl FileSink {
                         Some((mut expired_file, path)) => {
                             // We got an expired file. All we really want is to
                             // flush and close it.
-                            if let Err(error) = expired_file.close().await {
-                                emit!(FileIoError {
-                                    error,
-                                    code: "failed_closing_file",
-                                    message: "Failed to close file.",
-                                    path: &path,
-                                    dropped_events: 0,
-                                });
-                            }
-                            drop(expired_file); // ignore close error
+                            close_file(
+                                &self.rotation,
+                                &path,
+                                &mut expired_file,
+                                false,
+                                &self.events_sent,
+                                self.include_file_metric_tag,
+                            )
+                            .await;
+                            drop(expired_file);
                             emit!(FileOpen {
                                 count: self.files.len()
                             });
@@ -345,8 +513,23 @@ impl FileSink {
             file
         } else {
             trace!(message = "Opening new file.", ?path);
-            let file = match open_file(BytesPath::new(path.clone())).await {
-                Ok(file) => file,
+            let parquet_writer = match self.encoder.serializer() {
+                Serializer::Parquet(serializer) => Some(serializer.writer()),
+                _ => None,
+            };
+            let active_path = self.rotation.active_path(&path);
+            if parquet_writer.is_some()
+                && self.rotation.is_enabled()
+                && is_non_empty(&active_path).await
+            {
+                // A Parquet file ends with its footer, so rows can't be appended to it. The file
+                // is rotated instead, and the events are written to a new one. Without rotation,
+                // the file is left as it is and opening it fails.
+                self.rotation.rotate(&path).await;
+            }
+            let stats = FileStats::open(BytesPath::new(active_path.clone()).as_ref()).await;
+            let outfile = match open_outfile(&active_path, self.compression, parquet_writer).await {
+                Ok(outfile) => outfile,
                 Err(error) => {
                     // We couldn't open the file for this event.
                     // Maybe other events will work though! Just log
@@ -363,9 +546,12 @@ impl FileSink {
                 }
             };
 
-            let outfile = OutFile::new(file, self.compression);
-
-            self.files.insert_at(path.clone(), outfile, next_deadline);
+            let file = OpenFile {
+                file: outfile,
+                stats,
+                pending: PendingEvents::default(),
+            };
+            self.files.insert_at(path.clone(), file, next_deadline);
             emit!(FileOpen {
                 count: self.files.len()
             });
@@ -375,28 +561,94 @@ impl FileSink {
         trace!(message = "Writing an event to file.", path = ?path);
         let event_size = event.estimated_json_encoded_size_of();
         let finalizers = event.take_finalizers();
-        match write_event_to_file(file, event, &self.transformer, &mut self.encoder).await {
+        match write_event_to_file(&mut file.file, event, &self.transformer, &mut self.encoder).await
+        {
             Ok(byte_size) => {
-                finalizers.update_status(EventStatus::Delivered);
-                self.events_sent.emit(CountByteSize(1, event_size));
+                file.stats.bytes += byte_size as u64;
+                file.stats.buffered_bytes = file.file.buffered_bytes() as u64;
+                file.stats.events += 1;
+                file.pending.push(finalizers, event_size);
+                if !file.file.has_buffered_rows() {
+                    file.pending.deliver(&self.events_sent);
+                }
                 emit!(FileBytesSent {
                     byte_size,
                     file: String::from_utf8_lossy(&path),
                     include_file_metric_tag: self.include_file_metric_tag,
                 });
             }
-            Err(error) => {
+            Err(WriteError::Encoding) => finalizers.update_status(EventStatus::Rejected),
+            Err(WriteError::Io(error)) => {
                 finalizers.update_status(EventStatus::Errored);
+                // The rows buffered for the row group that failed to be written are lost as well.
+                let dropped_events = 1 + file.pending.fail();
                 emit!(FileIoError {
                     code: "failed_writing_file",
                     message: "Failed to write the file.",
                     error,
                     path: &path,
-                    dropped_events: 1,
+                    dropped_events,
+                });
+            }
+        }
+
+        if self.rotation.is_due(&file.stats) {
+            if let Some((mut file, _)) = self.files.remove(&path) {
+                close_file(
+                    &self.rotation,
+                    &path,
+                    &mut file,
+                    true,
+                    &self.events_sent,
+                    self.include_file_metric_tag,
+                )
+                .await;
+                emit!(FileOpen {
+                    count: self.files.len()
+                });
+            }
+        }
+    }
+}
+
+/// Closes a file, rotating it if it's due or if files are always rotated once complete.
+///
+/// The events that were pending until the file is complete are acknowledged.
+async fn close_file(
+    rotation: &RotationConfig,
+    path: &Bytes,
+    file: &mut OpenFile,
+    due: bool,
+    events_sent: &Registered<EventsSent>,
+    include_file_metric_tag: bool,
+) {
+    match file.file.close().await {
+        Ok(byte_size) => {
+            trace!(message = "Successfully closed file.", path = ?path);
+            if byte_size > 0 {
+                emit!(FileBytesSent {
+                    byte_size,
+                    file: String::from_utf8_lossy(path),
+                    include_file_metric_tag,
                 });
             }
+            file.pending.deliver(events_sent);
+        }
+        Err(error) => {
+            let dropped_events = file.pending.fail();
+            emit!(FileIoError {
+                error,
+                code: "failed_closing_file",
+                message: "Failed to close file.",
+                path,
+                dropped_events,
+            });
         }
     }
+
+    if due || rotation.rotates_on_close() {
+        rotation.rotate(path).await;
+    }
 }
 
 async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
@@ -415,18 +667,38 @@ async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
         .await
 }
 
+async fn is_non_empty(path: &Bytes) -> bool {
+    fs::metadata(BytesPath::new(path.clone()))
+        .await
+        .is_ok_and(|metadata| metadata.len() > 0)
+}
+
+async fn open_outfile(
+    path: &Bytes,
+    compression: Compression,
+    parquet_writer: Option<ParquetWriter>,
+) -> std::io::Result<OutFile> {
+    let file = open_file(BytesPath::new(path.clone())).await?;
+    OutFile::new(file, compression, parquet_writer).await
+}
+
 async fn write_event_to_file(
     file: &mut OutFile,
     mut event: Event,
     transformer: &Transformer,
     encoder: &mut Encoder<Framer>,
-) -> Result<usize, std::io::Error> {
+) -> Result<usize, WriteError> {
     transformer.transform(&mut event);
+    if let OutFile::Parquet { .. } = file {
+        return file.write_row(&event).await;
+    }
+
     let mut buffer = BytesMut::new();
     encoder
         .encode(event, &mut buffer)
         .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
-    file.write_all(&buffer).await.map(|()| buffer.len())
+    file.write_all(&buffer).await?;
+    Ok(buffer.len())
 }
 
 #[async_trait]
@@ -445,7 +717,10 @@ mod tests {
 
     use futures::{stream, SinkExt};
     use similar_asserts::assert_eq;
-    use vector_lib::{event::LogEvent, sink::VectorSink};
+    use vector_lib::{
+        event::{BatchNotifier, BatchStatus, LogEvent},
+        sink::VectorSink,
+    };
 
     use super::*;
     use crate::{
@@ -471,6 +746,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: Default::default(),
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -497,6 +773,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::Gzip,
+            rotation: Default::default(),
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -523,6 +800,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::Zstd,
+            rotation: Default::default(),
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -540,6 +818,113 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn single_partition_parquet() {
+        let template = temp_file();
+
+        let config: FileSinkConfig = toml::from_str(&format!(
+            r#"
+            path = "{}"
+            encoding.codec = "parquet"
+            encoding.parquet.schema = [{{ name = "message", type = "string" }}]
+            "#,
+            template.display()
+        ))
+        .unwrap();
+
+        let (input, _) = random_lines_with_stream(100, 64, None);
+
+        run_assert_log_sink(config, input).await;
+
+        // A complete Parquet file starts and ends with the magic bytes.
+        let output = std::fs::read(template).unwrap();
+        assert!(output.starts_with(b"PAR1"));
+        assert!(output.ends_with(b"PAR1"));
+    }
+
+    #[tokio::test]
+    async fn reopening_parquet() {
+        let directory = temp_dir();
+        let path = directory.join("vector.parquet");
+
+        let config: FileSinkConfig = toml::from_str(&format!(
+            r#"
+            path = "{}"
+            encoding.codec = "parquet"
+            encoding.parquet.schema = [{{ name = "message", type = "string" }}]
+            rotation.max_events = 1000
+            "#,
+            path.display()
+        ))
+        .unwrap();
+
+        for _ in 0..2 {
+            let (input, _) = random_lines_with_stream(10, 64, None);
+            run_assert_log_sink(config.clone(), input).await;
+        }
+
+        // The complete file is rotated rather than appended to.
+        let mut names = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["vector.1.parquet", "vector.parquet"]);
+        for name in names {
+            let output = std::fs::read(directory.join(name)).unwrap();
+            assert!(output.starts_with(b"PAR1"));
+            assert!(output.ends_with(b"PAR1"));
+        }
+    }
+
+    #[tokio::test]
+    async fn reopening_parquet_without_rotation() {
+        let directory = temp_dir();
+        let path = directory.join("vector.parquet");
+
+        let config: FileSinkConfig = toml::from_str(&format!(
+            r#"
+            path = "{}"
+            encoding.codec = "parquet"
+            encoding.parquet.schema = [{{ name = "message", type = "string" }}]
+            "#,
+            path.display()
+        ))
+        .unwrap();
+
+        let (input, _) = random_lines_with_stream(10, 64, None);
+        run_assert_log_sink(config.clone(), input).await;
+        let output = std::fs::read(&path).unwrap();
+
+        // The complete file is neither appended to nor rotated, and the events are rejected.
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let (_, events) = random_lines_with_stream(10, 64, Some(batch));
+        let sink = FileSink::new(&config, SinkContext::default()).unwrap();
+        VectorSink::from_event_streamsink(sink)
+            .run(Box::pin(events))
+            .await
+            .unwrap();
+        assert_eq!(receiver.await, BatchStatus::Errored);
+
+        assert_eq!(std::fs::read_dir(&directory).unwrap().count(), 1);
+        assert_eq!(std::fs::read(&path).unwrap(), output);
+    }
+
+    #[test]
+    fn parquet_rejects_compression() {
+        let config: FileSinkConfig = toml::from_str(
+            r#"
+            path = "/tmp/vector.parquet"
+            compression = "gzip"
+            encoding.codec = "parquet"
+            encoding.parquet.schema = [{ name = "message", type = "string" }]
+            "#,
+        )
+        .unwrap();
+
+        assert!(FileSink::new(&config, SinkContext::default()).is_err());
+    }
+
     #[tokio::test]
     async fn many_partitions() {
         let directory = temp_dir();
@@ -554,6 +939,7 @@ mod tests {
             idle_timeout: default_idle_timeout(),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: Default::default(),
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -636,6 +1022,7 @@ mod tests {
             idle_timeout: Duration::from_secs(1),
             encoding: (None::<FramingConfig>, TextSerializerConfig::default()).into(),
             compression: Compression::None,
+            rotation: Default::default(),
             acknowledgements: Default::default(),
             timezone: Default::default(),
             internal_metrics: FileInternalMetricsConfig {
@@ -683,6 +1070,45 @@ mod tests {
         sink_handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn rotation() {
+        let directory = temp_dir();
+        let path = directory.join("vector.log");
+
+        let config: FileSinkConfig = toml::from_str(&format!(
+            r#"
+            path = "{}"
+            encoding.codec = "text"
+            rotation.max_events = 10
+            rotation.in_progress_suffix = ".inprogress"
+            rotation.retention.max_files = 3
+            "#,
+            path.display()
+        ))
+        .unwrap();
+
+        let (input, _) = random_lines_with_stream(100, 45, None);
+
+        run_assert_log_sink(config, input.clone()).await;
+
+        // The last file is rotated when the sink stops, and only the three most recent files
+        // are kept.
+        let mut names = std::fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, ["vector.3.log", "vector.4.log", "vector.5.log"]);
+        assert_eq!(
+            lines_from_file(directory.join("vector.3.log")),
+            input[20..30].to_vec()
+        );
+        assert_eq!(
+            lines_from_file(directory.join("vector.5.log")),
+            input[40..].to_vec()
+        );
+    }
+
     async fn run_assert_log_sink(config: FileSinkConfig, events: Vec<String>) {
         run_assert_sink(
             config,
@@ -0,0 +1,500 @@
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use bytes::{Bytes, BytesMut};
+use chrono::{NaiveDateTime, Utc};
+use serde_with::serde_as;
+use tokio::fs;
+use vector_lib::configurable::configurable_component;
+
+use super::bytes_path::BytesPath;
+use crate::internal_events::FileIoError;
+
+/// The format of the timestamps of files rotated with the `timestamp` naming.
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%3fZ";
+
+/// Rotation configuration.
+///
+/// Once a file reaches one of the configured limits, it's closed and renamed, and the events
+/// that follow are written to a new file at the original path.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RotationConfig {
+    /// The number of bytes written to a file after which it's rotated.
+    ///
+    /// The bytes are counted before compression, starting from the size of the file when it's
+    /// opened, so that a file written to before Vector restarted is rotated in time.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 104857600))]
+    pub max_bytes: Option<NonZeroU64>,
+
+    /// The number of events written to a file after which it's rotated.
+    ///
+    /// The events are counted from when the file is opened.
+    #[configurable(metadata(docs::type_unit = "events"))]
+    #[configurable(metadata(docs::examples = 100000))]
+    pub max_events: Option<NonZeroU64>,
+
+    /// The age of a file after which it's rotated.
+    ///
+    /// The age is counted from when the file was created, or last modified where the creation
+    /// time isn't available, and is checked whenever an event is written to the file.
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    #[serde(default, rename = "max_age_secs")]
+    #[configurable(metadata(docs::examples = 3600))]
+    #[configurable(metadata(docs::human_name = "Maximum Age"))]
+    pub max_age: Option<Duration>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub naming: RotationNaming,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub retention: RetentionConfig,
+
+    /// A suffix added to the path of the files while they are being written to.
+    ///
+    /// When set, a file is only renamed to its rotated name once it's complete: when it reaches
+    /// one of the limits, is idle for `idle_timeout_secs`, or when Vector stops. This lets other
+    /// tools pick up the rotated files without reading partially written ones.
+    #[configurable(metadata(docs::examples = ".inprogress"))]
+    pub in_progress_suffix: Option<String>,
+}
+
+/// How rotated files are named.
+///
+/// The identifier of a rotated file is inserted before the extensions of its path, so
+/// `/var/log/app.log.gz` is rotated to files like `/var/log/app.1.log.gz`.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationNaming {
+    /// Rotated files are numbered, starting at 1, with the most recent file having the highest
+    /// number.
+    #[default]
+    Numbered,
+
+    /// Rotated files are named after the UTC time of their rotation, such as
+    /// `20240102T030405678Z`.
+    Timestamp,
+}
+
+/// Retention configuration.
+///
+/// Once a file is rotated, the oldest rotated files of its path are deleted until all of the
+/// configured limits are respected. The file currently being written to is never deleted.
+#[configurable_component]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct RetentionConfig {
+    /// The number of rotated files to keep for each path.
+    #[configurable(metadata(docs::examples = 10))]
+    pub max_files: Option<NonZeroUsize>,
+
+    /// The total size of the rotated files to keep for each path.
+    #[configurable(metadata(docs::type_unit = "bytes"))]
+    #[configurable(metadata(docs::examples = 1073741824))]
+    pub max_total_bytes: Option<NonZeroU64>,
+}
+
+impl RotationConfig {
+    /// Whether files are rotated at all.
+    pub(super) const fn is_enabled(&self) -> bool {
+        self.max_bytes.is_some()
+            || self.max_events.is_some()
+            || self.max_age.is_some()
+            || self.rotates_on_close()
+    }
+
+    /// Whether files are rotated when they are closed, rather than reopened later on.
+    pub(super) const fn rotates_on_close(&self) -> bool {
+        self.in_progress_suffix.is_some()
+    }
+
+    /// The path written to while the file at the given path is open.
+    pub(super) fn active_path(&self, path: &Bytes) -> Bytes {
+        match &self.in_progress_suffix {
+            Some(suffix) => {
+                let mut active = BytesMut::from(&path[..]);
+                active.extend_from_slice(suffix.as_bytes());
+                active.freeze()
+            }
+            None => path.clone(),
+        }
+    }
+
+    /// Whether a file has reached any of the limits.
+    pub(super) fn is_due(&self, stats: &FileStats) -> bool {
+        self.max_bytes
+            .is_some_and(|max| stats.bytes + stats.buffered_bytes >= max.get())
+            || self.max_events.is_some_and(|max| stats.events >= max.get())
+            || self
+                .max_age
+                .is_some_and(|max| stats.opened_at.elapsed() >= max)
+    }
+}
+
+/// What has been written to an open file.
+#[derive(Debug)]
+pub(super) struct FileStats {
+    /// When the file was created, according to its metadata.
+    pub(super) opened_at: Instant,
+    /// The size of the file when it was opened, plus what has been written to it since.
+    pub(super) bytes: u64,
+    /// The estimated size of what is buffered in memory, and not written to the file yet.
+    pub(super) buffered_bytes: u64,
+    pub(super) events: u64,
+}
+
+impl FileStats {
+    /// Creates the stats of a file that is being opened, accounting for what was written to it
+    /// before, if it already exists.
+    pub(super) async fn open(path: &Path) -> Self {
+        let metadata = fs::metadata(path).await.ok();
+        let age = metadata
+            .as_ref()
+            .and_then(|metadata| metadata.created().or_else(|_| metadata.modified()).ok())
+            .and_then(|created| created.elapsed().ok())
+            .unwrap_or_default();
+        let now = Instant::now();
+
+        Self {
+            opened_at: now.checked_sub(age).unwrap_or(now),
+            bytes: metadata.map_or(0, |metadata| metadata.len()),
+            buffered_bytes: 0,
+            events: 0,
+        }
+    }
+}
+
+/// A file that was rotated.
+#[derive(Debug)]
+pub(super) struct RotatedFile {
+    pub(super) path: PathBuf,
+    id: RotatedId,
+    len: u64,
+}
+
+#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum RotatedId {
+    Number(u64),
+    Timestamp(NaiveDateTime),
+}
+
+/// The rotated files of a path.
+#[derive(Debug)]
+pub(super) struct RotatedFiles {
+    directory: PathBuf,
+    stem: String,
+    extensions: String,
+    naming: RotationNaming,
+}
+
+impl RotatedFiles {
+    pub(super) fn new(path: &Path, naming: RotationNaming) -> Self {
+        let directory = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        // The leading dot of hidden files isn't an extension.
+        let (stem, extensions) = match name.get(1..).and_then(|rest| rest.find('.')) {
+            Some(index) => name.split_at(index + 1),
+            None => (name.as_str(), ""),
+        };
+
+        Self {
+            directory,
+            stem: stem.to_owned(),
+            extensions: extensions.to_owned(),
+            naming,
+        }
+    }
+
+    fn path(&self, id: &str) -> PathBuf {
+        self.directory
+            .join(format!("{}.{}{}", self.stem, id, self.extensions))
+    }
+
+    fn parse_id(&self, name: &str) -> Option<RotatedId> {
+        let id = name
+            .strip_prefix(self.stem.as_str())?
+            .strip_prefix('.')?
+            .strip_suffix(self.extensions.as_str())?;
+        match self.naming {
+            RotationNaming::Numbered => {
+                if !id.bytes().all(|byte| byte.is_ascii_digit()) {
+                    return None;
+                }
+                id.parse().ok().map(RotatedId::Number)
+            }
+            RotationNaming::Timestamp => NaiveDateTime::parse_from_str(id, TIMESTAMP_FORMAT)
+                .ok()
+                .map(RotatedId::Timestamp),
+        }
+    }
+
+    /// Lists the rotated files, from the oldest to the most recent one.
+    pub(super) async fn list(&self) -> std::io::Result<Vec<RotatedFile>> {
+        let mut files = Vec::new();
+        let mut entries = fs::read_dir(&self.directory).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let Some(id) = self.parse_id(&entry.file_name().to_string_lossy()) else {
+                continue;
+            };
+            let metadata = entry.metadata().await?;
+            if metadata.is_file() {
+                files.push(RotatedFile {
+                    path: entry.path(),
+                    id,
+                    len: metadata.len(),
+                });
+            }
+        }
+        files.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(files)
+    }
+
+    fn next_number(files: &[RotatedFile]) -> u64 {
+        match files.last().map(|file| &file.id) {
+            Some(RotatedId::Number(number)) => number + 1,
+            _ => 1,
+        }
+    }
+
+    /// The path to rotate the next file to, given the files rotated so far.
+    pub(super) fn next_path(&self, files: &[RotatedFile]) -> PathBuf {
+        match self.naming {
+            RotationNaming::Numbered => self.path(&Self::next_number(files).to_string()),
+            RotationNaming::Timestamp => {
+                self.path(&Utc::now().format(TIMESTAMP_FORMAT).to_string())
+            }
+        }
+    }
+
+    /// The path to rotate the next file to, skipping the paths that are already taken, as
+    /// renaming the file would overwrite them.
+    pub(super) async fn next_free_path(&self, files: &[RotatedFile]) -> std::io::Result<PathBuf> {
+        let mut number = Self::next_number(files);
+        let mut path = self.next_path(files);
+        while fs::try_exists(&path).await? {
+            path = match self.naming {
+                RotationNaming::Numbered => {
+                    number += 1;
+                    self.path(&number.to_string())
+                }
+                // Timestamps have a millisecond resolution, so files rotated within the same
+                // millisecond would otherwise share their name.
+                RotationNaming::Timestamp => {
+                    tokio::time::sleep(Duration::from_millis(1)).await;
+                    self.next_path(files)
+                }
+            };
+        }
+        Ok(path)
+    }
+}
+
+impl RotationConfig {
+    /// Renames the closed file at the given path to its rotated name, then deletes the rotated
+    /// files that exceed the retention limits.
+    pub(super) async fn rotate(&self, path: &Bytes) {
+        let rotated = RotatedFiles::new(BytesPath::new(path.clone()).as_ref(), self.naming);
+
+        let rotated_path = match rotated.list().await {
+            Ok(files) => rotated.next_free_path(&files).await,
+            Err(error) => Err(error),
+        };
+        let rotated_path = match rotated_path {
+            Ok(rotated_path) => rotated_path,
+            Err(error) => {
+                emit!(FileIoError {
+                    error,
+                    code: "failed_rotating_file",
+                    message: "Failed to list the rotated files.",
+                    path,
+                    dropped_events: 0,
+                });
+                return;
+            }
+        };
+        if let Err(error) = fs::rename(BytesPath::new(self.active_path(path)), &rotated_path).await
+        {
+            emit!(FileIoError {
+                error,
+                code: "failed_rotating_file",
+                message: "Failed to rename the file.",
+                path,
+                dropped_events: 0,
+            });
+            return;
+        }
+        debug!(message = "Rotated file.", ?path, rotated_path = %rotated_path.display());
+
+        if self.retention == RetentionConfig::default() {
+            return;
+        }
+        let files = match rotated.list().await {
+            Ok(files) => files,
+            Err(error) => {
+                emit!(FileIoError {
+                    error,
+                    code: "failed_rotating_file",
+                    message: "Failed to list the rotated files.",
+                    path,
+                    dropped_events: 0,
+                });
+                return;
+            }
+        };
+        for file in self.retention.expired(&files) {
+            if let Err(error) = fs::remove_file(&file.path).await {
+                emit!(FileIoError {
+                    error,
+                    code: "failed_deleting_file",
+                    message: "Failed to delete a rotated file.",
+                    path: &file.path,
+                    dropped_events: 0,
+                });
+            } else {
+                debug!(message = "Deleted rotated file.", path = %file.path.display());
+            }
+        }
+    }
+}
+
+impl RetentionConfig {
+    /// The rotated files to delete, given all of them from the oldest to the most recent one.
+    pub(super) fn expired<'a>(&self, files: &'a [RotatedFile]) -> &'a [RotatedFile] {
+        let mut count = files.len();
+        let mut total_bytes = files.iter().map(|file| file.len).sum::<u64>();
+
+        let mut expired = 0;
+        for file in files {
+            let over_count = self.max_files.is_some_and(|max| count > max.get());
+            let over_size = self
+                .max_total_bytes
+                .is_some_and(|max| total_bytes > max.get());
+            if !over_count && !over_size {
+                break;
+            }
+            count -= 1;
+            total_bytes -= file.len;
+            expired += 1;
+        }
+        &files[..expired]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    fn file(name: &str, len: u64, rotated: &RotatedFiles) -> RotatedFile {
+        RotatedFile {
+            path: PathBuf::from(name),
+            id: rotated.parse_id(name).unwrap(),
+            len,
+        }
+    }
+
+    #[test]
+    fn names_rotated_files() {
+        let rotated = RotatedFiles::new(Path::new("/var/log/app.log.gz"), RotationNaming::Numbered);
+        assert_eq!(
+            rotated.next_path(&[]),
+            PathBuf::from("/var/log/app.1.log.gz")
+        );
+
+        let files = [
+            file("app.2.log.gz", 0, &rotated),
+            file("app.10.log.gz", 0, &rotated),
+        ];
+        assert_eq!(
+            rotated.next_path(&files),
+            PathBuf::from("/var/log/app.11.log.gz")
+        );
+
+        assert!(rotated.parse_id("app.log.gz").is_none());
+        assert!(rotated.parse_id("app.log.gz.inprogress").is_none());
+        assert!(rotated.parse_id("app.x.log.gz").is_none());
+        assert!(rotated.parse_id("other.1.log.gz").is_none());
+
+        let hidden = RotatedFiles::new(Path::new(".app"), RotationNaming::Numbered);
+        assert_eq!(hidden.next_path(&[]), PathBuf::from("./.app.1"));
+    }
+
+    #[test]
+    fn parses_timestamps() {
+        let rotated = RotatedFiles::new(Path::new("/var/log/app.log"), RotationNaming::Timestamp);
+        let next = rotated.next_path(&[]);
+        let name = next.file_name().unwrap().to_string_lossy();
+        assert!(rotated.parse_id(&name).is_some());
+        assert!(rotated.parse_id("app.1.log").is_none());
+    }
+
+    #[test]
+    fn expires_oldest_files() {
+        let rotated = RotatedFiles::new(Path::new("app.log"), RotationNaming::Numbered);
+        let files = [
+            file("app.1.log", 100, &rotated),
+            file("app.2.log", 100, &rotated),
+            file("app.3.log", 100, &rotated),
+        ];
+
+        let by_count = RetentionConfig {
+            max_files: NonZeroUsize::new(2),
+            max_total_bytes: None,
+        };
+        assert_eq!(by_count.expired(&files).len(), 1);
+
+        let by_size = RetentionConfig {
+            max_files: None,
+            max_total_bytes: NonZeroU64::new(150),
+        };
+        assert_eq!(by_size.expired(&files).len(), 2);
+
+        assert!(RetentionConfig::default().expired(&files).is_empty());
+    }
+
+    #[tokio::test]
+    async fn skips_taken_paths() {
+        let directory = temp_dir();
+        std::fs::create_dir_all(directory.join("app.1.log")).unwrap();
+        let rotated = RotatedFiles::new(&directory.join("app.log"), RotationNaming::Numbered);
+
+        // Directories aren't rotated files, but they can't be overwritten either.
+        let files = rotated.list().await.unwrap();
+        assert!(files.is_empty());
+        assert_eq!(
+            rotated.next_free_path(&files).await.unwrap(),
+            directory.join("app.2.log")
+        );
+
+        let rotated = RotatedFiles::new(&directory.join("app.log"), RotationNaming::Timestamp);
+        let taken = rotated.next_path(&[]);
+        std::fs::write(&taken, "").unwrap();
+        assert_ne!(rotated.next_free_path(&[]).await.unwrap(), taken);
+    }
+
+    #[tokio::test]
+    async fn counts_existing_bytes() {
+        let directory = temp_dir();
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("app.log");
+        assert_eq!(FileStats::open(&path).await.bytes, 0);
+
+        std::fs::write(&path, "0123456789").unwrap();
+        assert_eq!(FileStats::open(&path).await.bytes, 10);
+    }
+}
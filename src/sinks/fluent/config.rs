@@ -0,0 +1,202 @@
+use vector_lib::sensitive_string::SensitiveString;
+
+use crate::{
+    sinks::{
+        prelude::*,
+        util::{service::TowerRequestConfigDefaults, SinkBuildError},
+    },
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+use super::{
+    request_builder::FluentRequestBuilder,
+    service::{FluentConnector, FluentRetryLogic, FluentService},
+    sink::FluentSink,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct FluentTowerRequestConfigDefaults;
+
+impl TowerRequestConfigDefaults for FluentTowerRequestConfigDefaults {
+    const CONCURRENCY: Concurrency = Concurrency::None;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FluentDefaultBatchSettings;
+
+impl SinkBatchSettings for FluentDefaultBatchSettings {
+    const MAX_EVENTS: Option<usize> = Some(1000);
+    const MAX_BYTES: Option<usize> = Some(1_000_000);
+    const TIMEOUT_SECS: f64 = 1.0;
+}
+
+/// Compression of the forwarded entries.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum FluentCompression {
+    /// The entries are sent uncompressed, in `PackedForward` mode.
+    #[default]
+    None,
+
+    /// The entries are compressed with gzip, in `CompressedPackedForward` mode.
+    Gzip,
+}
+
+/// Shared key authentication with the server.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FluentSecurityConfig {
+    /// The hostname of this client, sent to the server during the handshake.
+    ///
+    /// Defaults to the hostname of the machine.
+    #[configurable(metadata(docs::examples = "vector.example.com"))]
+    pub self_hostname: Option<String>,
+
+    /// The key shared with the server, used to authenticate both the client and the server.
+    #[configurable(metadata(docs::examples = "${FLUENT_SHARED_KEY}"))]
+    pub shared_key: SensitiveString,
+
+    /// The username to authenticate with, when the server requires user authentication.
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub username: Option<String>,
+
+    /// The password to authenticate with, when the server requires user authentication.
+    #[configurable(metadata(docs::examples = "${FLUENT_PASSWORD}"))]
+    pub password: Option<SensitiveString>,
+}
+
+/// Configuration for the `fluent` sink.
+#[configurable_component(sink(
+    "fluent",
+    "Forward logs to Fluentd or Fluent Bit with the Forward protocol."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct FluentSinkConfig {
+    /// The address of the server to connect to.
+    ///
+    /// Both IP address and hostname are accepted formats.
+    ///
+    /// The address _must_ include a port.
+    #[configurable(metadata(docs::examples = "127.0.0.1:24224"))]
+    #[configurable(metadata(docs::examples = "fluentd.example.com:24224"))]
+    pub address: String,
+
+    /// The tag of the forwarded entries.
+    ///
+    /// Events are grouped by their rendered tag, and each group is sent in its own message.
+    #[configurable(metadata(docs::examples = "vector"))]
+    #[configurable(metadata(docs::examples = "app.{{ application_id }}"))]
+    pub tag: Template,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub compression: FluentCompression,
+
+    #[configurable(derived)]
+    pub security: Option<FluentSecurityConfig>,
+
+    #[configurable(derived)]
+    pub keepalive: Option<TcpKeepaliveConfig>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, skip_serializing_if = "crate::serde::is_default")]
+    pub encoding: Transformer,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<FluentDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig<FluentTowerRequestConfigDefaults>,
+
+    /// Controls how acknowledgements are handled for this sink.
+    ///
+    /// When enabled, each message carries a `chunk` option, and is only acknowledged once the
+    /// server replies with the same chunk.
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for FluentSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "127.0.0.1:24224"
+            tag = "vector""#,
+        )
+        .unwrap()
+    }
+}
+
+impl FluentSinkConfig {
+    fn build_connector(&self) -> crate::Result<FluentConnector> {
+        let uri = self.address.parse::<http::Uri>()?;
+        let host = uri.host().ok_or(SinkBuildError::MissingHost)?.to_string();
+        let port = uri.port_u16().ok_or(SinkBuildError::MissingPort)?;
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let security = self
+            .security
+            .clone()
+            .map(|security| {
+                let self_hostname = match security.self_hostname {
+                    Some(hostname) => hostname,
+                    None => crate::get_hostname()?,
+                };
+                Ok::<_, crate::Error>((self_hostname, security))
+            })
+            .transpose()?;
+
+        Ok(FluentConnector::new(
+            host,
+            port,
+            self.keepalive,
+            tls,
+            security,
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "fluent")]
+impl SinkConfig for FluentSinkConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let connector = self.build_connector()?;
+        let healthcheck = connector.clone().healthcheck().boxed();
+
+        let batcher_settings = self.batch.validate()?.into_batcher_settings()?;
+        let acknowledgements = self
+            .acknowledgements
+            .merge_default(&cx.globals.acknowledgements)
+            .enabled();
+        let request_builder =
+            FluentRequestBuilder::new(self.encoding.clone(), self.compression, acknowledgements);
+
+        let service = ServiceBuilder::new()
+            .settings(self.request.into_settings(), FluentRetryLogic)
+            .service(FluentService::new(connector));
+
+        let sink = FluentSink::new(self.tag.clone(), batcher_settings, request_builder, service);
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
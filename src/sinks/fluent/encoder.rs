@@ -0,0 +1,134 @@
+//! Encoding for the `fluent` sink.
+
+use std::io;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use vector_lib::config::telemetry;
+
+use crate::sinks::{prelude::*, util::encoding::Encoder as SinkEncoder};
+
+/// The extension type of `EventTime` values in the Forward protocol.
+const EVENT_TIME_EXT_TYPE: i8 = 0;
+
+/// Encodes events as the entries of a `PackedForward` message, each entry being an array of the
+/// event time and the record.
+#[derive(Clone, Debug)]
+pub(super) struct FluentEncoder {
+    pub(super) transformer: Transformer,
+}
+
+impl FluentEncoder {
+    fn encode_event(&self, event: Event) -> rmpv::Value {
+        let mut log = event.into_log();
+        let timestamp = match log.remove_timestamp() {
+            Some(Value::Timestamp(timestamp)) => timestamp,
+            _ => Utc::now(),
+        };
+        let (record, _) = log.into_parts();
+
+        rmpv::Value::Array(vec![event_time(timestamp), to_msgpack(record)])
+    }
+}
+
+impl SinkEncoder<Vec<Event>> for FluentEncoder {
+    fn encode_input(
+        &self,
+        events: Vec<Event>,
+        writer: &mut dyn io::Write,
+    ) -> io::Result<(usize, GroupedCountByteSize)> {
+        let mut byte_size = telemetry().create_request_count_byte_size();
+        let n_events = events.len();
+        let mut body = Vec::new();
+
+        for mut event in events {
+            self.transformer.transform(&mut event);
+            byte_size.add_event(&event, event.estimated_json_encoded_size_of());
+            rmpv::encode::write_value(&mut body, &self.encode_event(event))
+                .expect("writing to a Vec can't fail");
+        }
+
+        write_all(writer, n_events, &body).map(|()| (body.len(), byte_size))
+    }
+}
+
+/// Converts a timestamp to an `EventTime`, which keeps its nanoseconds.
+fn event_time(timestamp: DateTime<Utc>) -> rmpv::Value {
+    let mut data = Vec::with_capacity(8);
+    data.extend_from_slice(&(timestamp.timestamp() as u32).to_be_bytes());
+    data.extend_from_slice(&timestamp.timestamp_subsec_nanos().to_be_bytes());
+    rmpv::Value::Ext(EVENT_TIME_EXT_TYPE, data)
+}
+
+pub(super) fn to_msgpack(value: Value) -> rmpv::Value {
+    match value {
+        Value::Bytes(bytes) => match String::from_utf8(bytes.to_vec()) {
+            Ok(string) => rmpv::Value::String(string.into()),
+            Err(_) => rmpv::Value::Binary(bytes.to_vec()),
+        },
+        Value::Regex(regex) => rmpv::Value::String(regex.as_str().into()),
+        Value::Integer(integer) => rmpv::Value::from(integer),
+        Value::Float(float) => rmpv::Value::F64(float.into_inner()),
+        Value::Boolean(boolean) => rmpv::Value::Boolean(boolean),
+        Value::Timestamp(timestamp) => rmpv::Value::String(
+            timestamp
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                .into(),
+        ),
+        Value::Object(object) => rmpv::Value::Map(
+            object
+                .into_iter()
+                .map(|(key, value)| (rmpv::Value::String(key.as_str().into()), to_msgpack(value)))
+                .collect(),
+        ),
+        Value::Array(array) => rmpv::Value::Array(array.into_iter().map(to_msgpack).collect()),
+        Value::Null => rmpv::Value::Nil,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vrl::btreemap;
+
+    use super::*;
+
+    #[test]
+    fn encodes_values() {
+        let value = Value::from(btreemap! {
+            "message" => "hello",
+            "count" => 3,
+            "ratio" => 0.5,
+            "ok" => true,
+            "nested" => btreemap! { "list" => vec![Value::Null] },
+        });
+
+        assert_eq!(
+            to_msgpack(value),
+            rmpv::Value::Map(vec![
+                ("count".into(), 3.into()),
+                ("message".into(), "hello".into()),
+                (
+                    "nested".into(),
+                    rmpv::Value::Map(vec![(
+                        "list".into(),
+                        rmpv::Value::Array(vec![rmpv::Value::Nil])
+                    )])
+                ),
+                ("ok".into(), true.into()),
+                ("ratio".into(), rmpv::Value::F64(0.5)),
+            ])
+        );
+    }
+
+    #[test]
+    fn encodes_event_time() {
+        let timestamp = DateTime::from_timestamp(1_700_000_000, 123).unwrap();
+        let rmpv::Value::Ext(ext_type, data) = event_time(timestamp) else {
+            panic!("expected an extension value");
+        };
+        assert_eq!(ext_type, EVENT_TIME_EXT_TYPE);
+        assert_eq!(
+            data,
+            [1_700_000_000u32.to_be_bytes(), 123u32.to_be_bytes()].concat()
+        );
+    }
+}
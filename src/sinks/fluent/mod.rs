@@ -0,0 +1,40 @@
+//! `fluent` sink.
+//!
+//! Forwards logs to [Fluentd](https://www.fluentd.org/) and
+//! [Fluent Bit](https://fluentbit.io/) with the [Forward protocol][forward].
+//!
+//! [forward]: https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1
+mod config;
+mod encoder;
+mod request_builder;
+mod service;
+mod sink;
+
+#[cfg(test)]
+mod tests;
+
+use snafu::Snafu;
+
+use crate::{dns, tls::TlsError};
+
+pub use config::FluentSinkConfig;
+
+#[derive(Debug, Snafu)]
+pub(super) enum FluentError {
+    #[snafu(display("Connect error: {}", source))]
+    Connect { source: TlsError },
+    #[snafu(display("Unable to resolve DNS: {}", source))]
+    Dns { source: dns::DnsError },
+    #[snafu(display("No addresses returned."))]
+    NoAddresses,
+    #[snafu(display("I/O error: {}", source))]
+    Io { source: std::io::Error },
+    #[snafu(display("Connection closed by the server."))]
+    ConnectionClosed,
+    #[snafu(display("Invalid response from the server: {}", source))]
+    Decode { source: rmpv::decode::Error },
+    #[snafu(display("Unexpected response from the server: {}", value))]
+    UnexpectedResponse { value: rmpv::Value },
+    #[snafu(display("Authentication failed: {}", reason))]
+    AuthenticationFailed { reason: String },
+}
@@ -0,0 +1,162 @@
+//! `RequestBuilder` implementation for the `fluent` sink.
+
+use std::io;
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use bytes::Bytes;
+
+use crate::sinks::prelude::*;
+
+use super::{config::FluentCompression, encoder::FluentEncoder};
+
+/// A `PackedForward` or `CompressedPackedForward` message.
+#[derive(Clone)]
+pub(super) struct FluentRequest {
+    pub(super) message: Bytes,
+    /// The chunk that the server acknowledges the message with, when acknowledgements are enabled.
+    pub(super) chunk: Option<String>,
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+}
+
+impl Finalizable for FluentRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for FluentRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct FluentRequestBuilder {
+    encoder: FluentEncoder,
+    compression: FluentCompression,
+    acknowledgements: bool,
+}
+
+impl FluentRequestBuilder {
+    pub(super) const fn new(
+        transformer: Transformer,
+        compression: FluentCompression,
+        acknowledgements: bool,
+    ) -> Self {
+        Self {
+            encoder: FluentEncoder { transformer },
+            compression,
+            acknowledgements,
+        }
+    }
+}
+
+impl RequestBuilder<(String, Vec<Event>)> for FluentRequestBuilder {
+    type Metadata = (String, usize, EventFinalizers);
+    type Events = Vec<Event>;
+    type Encoder = FluentEncoder;
+    type Payload = Bytes;
+    type Request = FluentRequest;
+    type Error = io::Error;
+
+    fn compression(&self) -> Compression {
+        match self.compression {
+            FluentCompression::None => Compression::None,
+            FluentCompression::Gzip => Compression::gzip_default(),
+        }
+    }
+
+    fn encoder(&self) -> &Self::Encoder {
+        &self.encoder
+    }
+
+    fn split_input(
+        &self,
+        input: (String, Vec<Event>),
+    ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
+        let (tag, mut events) = input;
+        let finalizers = events.take_finalizers();
+        let builder = RequestMetadataBuilder::from_events(&events);
+        ((tag, events.len(), finalizers), builder, events)
+    }
+
+    fn build_request(
+        &self,
+        metadata: Self::Metadata,
+        request_metadata: RequestMetadata,
+        payload: EncodeResult<Self::Payload>,
+    ) -> Self::Request {
+        let (tag, size, finalizers) = metadata;
+        let chunk = self
+            .acknowledgements
+            .then(|| BASE64_STANDARD.encode(rand::random::<[u8; 16]>()));
+
+        let mut options = vec![("size".into(), size.into())];
+        if let Some(chunk) = &chunk {
+            options.push(("chunk".into(), chunk.as_str().into()));
+        }
+        if self.compression == FluentCompression::Gzip {
+            options.push(("compressed".into(), "gzip".into()));
+        }
+
+        let message = rmpv::Value::Array(vec![
+            tag.into(),
+            rmpv::Value::Binary(payload.into_payload().to_vec()),
+            rmpv::Value::Map(options),
+        ]);
+        let mut buffer = Vec::new();
+        rmpv::encode::write_value(&mut buffer, &message).expect("writing to a Vec can't fail");
+
+        FluentRequest {
+            message: buffer.into(),
+            chunk,
+            finalizers,
+            metadata: request_metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(compression: FluentCompression, acknowledgements: bool) -> rmpv::Value {
+        let builder =
+            FluentRequestBuilder::new(Transformer::default(), compression, acknowledgements);
+        let events = vec![Event::Log(LogEvent::from("hello"))];
+        let (metadata, request_metadata_builder, events) =
+            builder.split_input(("app".to_string(), events));
+        let payload = builder.encode_events(events).unwrap();
+        let request_metadata = request_metadata_builder.build(&payload);
+        let request = builder.build_request(metadata, request_metadata, payload);
+
+        let message = rmpv::decode::read_value(&mut &request.message[..]).unwrap();
+        assert_eq!(
+            message[2]["chunk"].as_str(),
+            request.chunk.as_deref(),
+            "the chunk option matches the acknowledged chunk"
+        );
+        message
+    }
+
+    #[test]
+    fn builds_packed_forward() {
+        let message = build(FluentCompression::None, false);
+        assert_eq!(message[0].as_str(), Some("app"));
+        assert!(message[1].is_bin());
+        assert_eq!(message[2]["size"].as_u64(), Some(1));
+        assert!(message[2]["chunk"].is_nil());
+        assert!(message[2]["compressed"].is_nil());
+    }
+
+    #[test]
+    fn builds_compressed_packed_forward_with_chunk() {
+        let message = build(FluentCompression::Gzip, true);
+        assert_eq!(message[2]["compressed"].as_str(), Some("gzip"));
+        assert!(message[2]["chunk"].is_str());
+    }
+}
@@ -0,0 +1,326 @@
+use std::{
+    io::Cursor,
+    net::SocketAddr,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::{Buf, BytesMut};
+use openssl::sha::Sha512;
+use snafu::ResultExt;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+    sync::Mutex,
+};
+
+use crate::{
+    dns,
+    internal_events::{TcpSocketConnectionEstablished, TcpSocketOutgoingConnectionError},
+    sinks::prelude::*,
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsSettings, MaybeTlsStream},
+};
+
+use super::{
+    config::FluentSecurityConfig, request_builder::FluentRequest, ConnectSnafu, DnsSnafu,
+    FluentError, IoSnafu,
+};
+
+/// Connects to the server, and authenticates with it when a shared key is configured.
+#[derive(Clone)]
+pub(super) struct FluentConnector {
+    host: String,
+    port: u16,
+    keepalive: Option<TcpKeepaliveConfig>,
+    tls: MaybeTlsSettings,
+    /// The hostname of this client and the security settings.
+    security: Option<(String, FluentSecurityConfig)>,
+}
+
+impl FluentConnector {
+    pub(super) const fn new(
+        host: String,
+        port: u16,
+        keepalive: Option<TcpKeepaliveConfig>,
+        tls: MaybeTlsSettings,
+        security: Option<(String, FluentSecurityConfig)>,
+    ) -> Self {
+        Self {
+            host,
+            port,
+            keepalive,
+            tls,
+            security,
+        }
+    }
+
+    pub(super) async fn healthcheck(self) -> crate::Result<()> {
+        self.connect().await.map(|_| ()).map_err(Into::into)
+    }
+
+    async fn connect(&self) -> Result<FluentConnection, FluentError> {
+        let result = self.connect_inner().await;
+        if let Err(error) = &result {
+            emit!(TcpSocketOutgoingConnectionError { error });
+        }
+        result
+    }
+
+    async fn connect_inner(&self) -> Result<FluentConnection, FluentError> {
+        let ip = dns::Resolver
+            .lookup_ip(self.host.clone())
+            .await
+            .context(DnsSnafu)?
+            .next()
+            .ok_or(FluentError::NoAddresses)?;
+
+        let addr = SocketAddr::new(ip, self.port);
+        let mut stream = self
+            .tls
+            .connect(&self.host, &addr)
+            .await
+            .context(ConnectSnafu)?;
+        if let Some(keepalive) = self.keepalive {
+            if let Err(error) = stream.set_keepalive(keepalive) {
+                warn!(message = "Failed configuring TCP keepalive.", %error);
+            }
+        }
+        emit!(TcpSocketConnectionEstablished {
+            peer_addr: Some(addr),
+        });
+
+        let mut connection = FluentConnection {
+            stream,
+            buffer: BytesMut::new(),
+        };
+        if let Some((hostname, security)) = &self.security {
+            connection.handshake(hostname, security).await?;
+        }
+        Ok(connection)
+    }
+}
+
+/// An established connection to the server.
+struct FluentConnection {
+    stream: MaybeTlsStream<TcpStream>,
+    buffer: BytesMut,
+}
+
+impl FluentConnection {
+    async fn send(&mut self, message: &[u8]) -> Result<(), FluentError> {
+        self.stream.write_all(message).await.context(IoSnafu)?;
+        self.stream.flush().await.context(IoSnafu)
+    }
+
+    async fn send_value(&mut self, value: &rmpv::Value) -> Result<(), FluentError> {
+        let mut buffer = Vec::new();
+        rmpv::encode::write_value(&mut buffer, value).expect("writing to a Vec can't fail");
+        self.send(&buffer).await
+    }
+
+    /// Reads the next value sent by the server.
+    async fn receive(&mut self) -> Result<rmpv::Value, FluentError> {
+        loop {
+            let mut cursor = Cursor::new(&self.buffer[..]);
+            match rmpv::decode::read_value(&mut cursor) {
+                Ok(value) => {
+                    let position = cursor.position() as usize;
+                    self.buffer.advance(position);
+                    return Ok(value);
+                }
+                Err(
+                    rmpv::decode::Error::InvalidMarkerRead(error)
+                    | rmpv::decode::Error::InvalidDataRead(error),
+                ) if error.kind() == std::io::ErrorKind::UnexpectedEof => {}
+                Err(source) => return Err(FluentError::Decode { source }),
+            }
+
+            let read = self
+                .stream
+                .read_buf(&mut self.buffer)
+                .await
+                .context(IoSnafu)?;
+            if read == 0 {
+                return Err(FluentError::ConnectionClosed);
+            }
+        }
+    }
+
+    /// Waits for the server to acknowledge the message sent with the given chunk.
+    async fn receive_ack(&mut self, chunk: &str) -> Result<(), FluentError> {
+        let response = self.receive().await?;
+        if response["ack"].as_str() == Some(chunk) {
+            Ok(())
+        } else {
+            Err(FluentError::UnexpectedResponse { value: response })
+        }
+    }
+
+    /// Performs the handshake of the Forward protocol, which authenticates the client and the
+    /// server with their shared key.
+    async fn handshake(
+        &mut self,
+        hostname: &str,
+        security: &FluentSecurityConfig,
+    ) -> Result<(), FluentError> {
+        let helo = self.receive().await?;
+        let (nonce, auth_salt) = match (helo[0].as_str(), helo[1]["nonce"].as_slice()) {
+            (Some("HELO"), Some(nonce)) => (
+                nonce.to_vec(),
+                helo[1]["auth"].as_slice().unwrap_or_default().to_vec(),
+            ),
+            _ => {
+                return Err(FluentError::UnexpectedResponse {
+                    value: helo.clone(),
+                })
+            }
+        };
+
+        let shared_key = security.shared_key.inner().as_bytes();
+        let salt = hex::encode(rand::random::<[u8; 16]>());
+        let digest = |hostname: &str| {
+            sha512_hex(&[salt.as_bytes(), hostname.as_bytes(), &nonce, shared_key])
+        };
+
+        // User authentication is only performed when the server sends a salt for it.
+        let (username, password_digest) = match (&security.username, &security.password) {
+            (Some(username), Some(password)) if !auth_salt.is_empty() => (
+                username.as_str(),
+                sha512_hex(&[&auth_salt, username.as_bytes(), password.inner().as_bytes()]),
+            ),
+            _ => ("", String::new()),
+        };
+
+        self.send_value(&rmpv::Value::Array(vec![
+            "PING".into(),
+            hostname.into(),
+            salt.as_str().into(),
+            digest(hostname).into(),
+            username.into(),
+            password_digest.into(),
+        ]))
+        .await?;
+
+        let pong = self.receive().await?;
+        match (
+            pong[0].as_str(),
+            pong[1].as_bool(),
+            pong[3].as_str(),
+            pong[4].as_str(),
+        ) {
+            (Some("PONG"), Some(true), Some(server_hostname), Some(server_digest))
+                if server_digest == digest(server_hostname) =>
+            {
+                Ok(())
+            }
+            (Some("PONG"), Some(true), _, _) => Err(FluentError::AuthenticationFailed {
+                reason: "the server is not authenticated by the shared key".to_string(),
+            }),
+            (Some("PONG"), Some(false), _, _) => Err(FluentError::AuthenticationFailed {
+                reason: pong[2].as_str().unwrap_or_default().to_string(),
+            }),
+            _ => Err(FluentError::UnexpectedResponse {
+                value: pong.clone(),
+            }),
+        }
+    }
+}
+
+pub(super) fn sha512_hex(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hex::encode(hasher.finish())
+}
+
+/// Sends messages over a single connection, which is reopened after errors.
+#[derive(Clone)]
+pub(super) struct FluentService {
+    connector: FluentConnector,
+    connection: Arc<Mutex<Option<FluentConnection>>>,
+}
+
+impl FluentService {
+    pub(super) fn new(connector: FluentConnector) -> Self {
+        Self {
+            connector,
+            connection: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl Service<FluentRequest> for FluentService {
+    type Response = FluentResponse;
+    type Error = FluentError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, request: FluentRequest) -> Self::Future {
+        let connector = self.connector.clone();
+        let connection = Arc::clone(&self.connection);
+
+        Box::pin(async move {
+            let mut connection = connection.lock().await;
+            // The connection is only put back once the message went through, so that errors
+            // reconnect on the next attempt.
+            let mut current = match connection.take() {
+                Some(current) => current,
+                None => connector.connect().await?,
+            };
+            current.send(&request.message).await?;
+            if let Some(chunk) = &request.chunk {
+                current.receive_ack(chunk).await?;
+            }
+            *connection = Some(current);
+
+            Ok(FluentResponse {
+                byte_size: request.message.len(),
+                events_byte_size: request
+                    .metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+            })
+        })
+    }
+}
+
+pub(super) struct FluentResponse {
+    events_byte_size: GroupedCountByteSize,
+    byte_size: usize,
+}
+
+impl DriverResponse for FluentResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.byte_size)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub(super) struct FluentRetryLogic;
+
+impl RetryLogic for FluentRetryLogic {
+    type Error = FluentError;
+    type Response = FluentResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        // The server rejected the credentials or the message, sending it again won't help.
+        !matches!(
+            error,
+            FluentError::AuthenticationFailed { .. } | FluentError::UnexpectedResponse { .. }
+        )
+    }
+}
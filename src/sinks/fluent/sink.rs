@@ -0,0 +1,72 @@
+use crate::sinks::{prelude::*, util::partitioner::KeyPartitioner};
+
+use super::request_builder::{FluentRequest, FluentRequestBuilder};
+
+pub(super) struct FluentSink<S> {
+    tag: Template,
+    batcher_settings: BatcherSettings,
+    request_builder: FluentRequestBuilder,
+    service: S,
+}
+
+impl<S> FluentSink<S>
+where
+    S: Service<FluentRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + std::fmt::Display + Into<crate::Error> + Send,
+{
+    pub(super) const fn new(
+        tag: Template,
+        batcher_settings: BatcherSettings,
+        request_builder: FluentRequestBuilder,
+        service: S,
+    ) -> Self {
+        Self {
+            tag,
+            batcher_settings,
+            request_builder,
+            service,
+        }
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batcher_settings = self.batcher_settings;
+
+        input
+            .batched_partitioned(KeyPartitioner::new(self.tag), || {
+                batcher_settings.as_byte_size_config()
+            })
+            .filter_map(|(tag, batch)| async move { tag.map(move |tag| (tag, batch)) })
+            .request_builder(
+                default_request_builder_concurrency_limit(),
+                self.request_builder,
+            )
+            .filter_map(|request| async {
+                match request {
+                    Err(error) => {
+                        emit!(SinkRequestBuildError { error });
+                        None
+                    }
+                    Ok(request) => Some(request),
+                }
+            })
+            .into_driver(self.service)
+            .protocol("tcp")
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for FluentSink<S>
+where
+    S: Service<FluentRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: std::fmt::Debug + std::fmt::Display + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,312 @@
+use std::{
+    io::{Cursor, Read},
+    net::SocketAddr,
+};
+
+use bytes::{Buf, BytesMut};
+use flate2::read::MultiGzDecoder;
+use futures::stream;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+use vector_lib::event::{BatchNotifier, BatchStatus, BatchStatusReceiver};
+
+use super::{config::FluentSinkConfig, service::sha512_hex};
+use crate::{
+    sinks::prelude::*,
+    test_util::{
+        components::{
+            run_and_assert_sink_compliance, run_and_assert_sink_error, COMPONENT_ERROR_TAGS,
+            SINK_TAGS,
+        },
+        next_addr, trace_init,
+    },
+};
+
+const NONCE: &[u8] = b"server nonce";
+const SERVER_HOSTNAME: &str = "fluentd";
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<FluentSinkConfig>();
+}
+
+fn config(addr: SocketAddr, options: &str) -> FluentSinkConfig {
+    toml::from_str(&format!(
+        r#"
+        address = "{addr}"
+        tag = "app.{{{{ app }}}}"
+        {options}
+        "#
+    ))
+    .unwrap()
+}
+
+async fn build_sink(config: FluentSinkConfig) -> VectorSink {
+    let (sink, _healthcheck) = config.build(SinkContext::default()).await.unwrap();
+    sink
+}
+
+fn events(apps: &[&str]) -> (Vec<Event>, BatchStatusReceiver) {
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let events = apps
+        .iter()
+        .enumerate()
+        .map(|(index, app)| {
+            let mut log = LogEvent::from(format!("message {index}")).with_batch_notifier(&batch);
+            log.insert("app", *app);
+            Event::Log(log)
+        })
+        .collect();
+    (events, receiver)
+}
+
+async fn read_value(stream: &mut TcpStream, buffer: &mut BytesMut) -> Option<rmpv::Value> {
+    loop {
+        let mut cursor = Cursor::new(&buffer[..]);
+        if let Ok(value) = rmpv::decode::read_value(&mut cursor) {
+            let position = cursor.position() as usize;
+            buffer.advance(position);
+            return Some(value);
+        }
+        if stream.read_buf(buffer).await.unwrap() == 0 {
+            return None;
+        }
+    }
+}
+
+async fn write_value(stream: &mut TcpStream, value: rmpv::Value) {
+    let mut buffer = Vec::new();
+    rmpv::encode::write_value(&mut buffer, &value).unwrap();
+    stream.write_all(&buffer).await.unwrap();
+}
+
+/// Performs the server side of the handshake, returning whether the client is authenticated.
+async fn handshake(stream: &mut TcpStream, buffer: &mut BytesMut, shared_key: &str) -> bool {
+    write_value(
+        stream,
+        rmpv::Value::Array(vec![
+            "HELO".into(),
+            rmpv::Value::Map(vec![
+                ("nonce".into(), rmpv::Value::Binary(NONCE.to_vec())),
+                ("auth".into(), rmpv::Value::Binary(Vec::new())),
+                ("keepalive".into(), true.into()),
+            ]),
+        ]),
+    )
+    .await;
+
+    let ping = read_value(stream, buffer).await.unwrap();
+    assert_eq!(ping[0].as_str(), Some("PING"));
+    assert_eq!(ping[1].as_str(), Some("vector"));
+    let salt = ping[2].as_str().unwrap().as_bytes().to_vec();
+    let authenticated = ping[3].as_str()
+        == Some(&sha512_hex(&[
+            &salt,
+            b"vector",
+            NONCE,
+            shared_key.as_bytes(),
+        ]));
+
+    write_value(
+        stream,
+        rmpv::Value::Array(vec![
+            "PONG".into(),
+            authenticated.into(),
+            if authenticated {
+                ""
+            } else {
+                "shared_key mismatch"
+            }
+            .into(),
+            SERVER_HOSTNAME.into(),
+            sha512_hex(&[
+                &salt,
+                SERVER_HOSTNAME.as_bytes(),
+                NONCE,
+                shared_key.as_bytes(),
+            ])
+            .into(),
+        ]),
+    )
+    .await;
+    authenticated
+}
+
+/// Spawns a server that accepts a single connection and returns the messages that it received.
+async fn spawn_server(
+    shared_key: Option<&'static str>,
+    ack: bool,
+) -> (SocketAddr, JoinHandle<Vec<rmpv::Value>>) {
+    let addr = next_addr();
+    let listener = TcpListener::bind(addr).await.unwrap();
+
+    let server = tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buffer = BytesMut::new();
+        if let Some(shared_key) = shared_key {
+            if !handshake(&mut stream, &mut buffer, shared_key).await {
+                return Vec::new();
+            }
+        }
+
+        let mut messages = Vec::new();
+        while let Some(message) = read_value(&mut stream, &mut buffer).await {
+            if let Some(chunk) = message[2]["chunk"].as_str() {
+                let response = match ack {
+                    true => vec![("ack".into(), chunk.into())],
+                    false => Vec::new(),
+                };
+                write_value(&mut stream, rmpv::Value::Map(response)).await;
+            }
+            messages.push(message);
+        }
+        messages
+    });
+
+    (addr, server)
+}
+
+/// Returns the messages of the entries of a forwarded message.
+fn entry_messages(message: &rmpv::Value) -> Vec<String> {
+    let mut entries = message[1].as_slice().unwrap().to_vec();
+    if message[2]["compressed"].as_str() == Some("gzip") {
+        let mut decompressed = Vec::new();
+        MultiGzDecoder::new(&entries[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        entries = decompressed;
+    }
+
+    let mut cursor = Cursor::new(&entries[..]);
+    let mut messages = Vec::new();
+    while (cursor.position() as usize) < entries.len() {
+        let entry = rmpv::decode::read_value(&mut cursor).unwrap();
+        assert!(matches!(&entry[0], rmpv::Value::Ext(0, time) if time.len() == 8));
+        messages.push(entry[1]["message"].as_str().unwrap().to_string());
+    }
+    messages
+}
+
+#[tokio::test]
+async fn forwards_events_by_tag() {
+    trace_init();
+
+    let (addr, server) = spawn_server(None, false).await;
+    let sink = build_sink(config(addr, "")).await;
+    let (events, receiver) = events(&["a", "b", "a"]);
+
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+    assert_eq!(receiver.await, BatchStatus::Delivered);
+
+    let mut messages = server
+        .await
+        .unwrap()
+        .iter()
+        .map(|message| {
+            assert_eq!(
+                message[2]["size"].as_u64(),
+                Some(entry_messages(message).len() as u64)
+            );
+            assert!(message[2]["chunk"].is_nil());
+            (
+                message[0].as_str().unwrap().to_string(),
+                entry_messages(message),
+            )
+        })
+        .collect::<Vec<_>>();
+    messages.sort();
+    assert_eq!(
+        messages,
+        vec![
+            (
+                "app.a".to_string(),
+                vec!["message 0".to_string(), "message 2".to_string()]
+            ),
+            ("app.b".to_string(), vec!["message 1".to_string()]),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn forwards_compressed_events_with_acks() {
+    trace_init();
+
+    let (addr, server) = spawn_server(None, true).await;
+    let sink = build_sink(config(
+        addr,
+        r#"
+        compression = "gzip"
+        acknowledgements = true
+        "#,
+    ))
+    .await;
+    let (events, receiver) = events(&["a", "a"]);
+
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+    assert_eq!(receiver.await, BatchStatus::Delivered);
+
+    let messages = server.await.unwrap();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0][2]["chunk"].is_str());
+    assert_eq!(
+        entry_messages(&messages[0]),
+        vec!["message 0".to_string(), "message 1".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn rejects_unacknowledged_events() {
+    trace_init();
+
+    let (addr, server) = spawn_server(None, false).await;
+    let sink = build_sink(config(addr, "acknowledgements = true")).await;
+    let (events, receiver) = events(&["a"]);
+
+    run_and_assert_sink_error(sink, stream::iter(events), &COMPONENT_ERROR_TAGS).await;
+    assert_eq!(receiver.await, BatchStatus::Rejected);
+    assert_eq!(server.await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn authenticates_with_shared_key() {
+    trace_init();
+
+    let (addr, server) = spawn_server(Some("secret"), true).await;
+    let sink = build_sink(config(
+        addr,
+        r#"
+        acknowledgements = true
+        security.self_hostname = "vector"
+        security.shared_key = "secret"
+        "#,
+    ))
+    .await;
+    let (events, receiver) = events(&["a"]);
+
+    run_and_assert_sink_compliance(sink, stream::iter(events), &SINK_TAGS).await;
+    assert_eq!(receiver.await, BatchStatus::Delivered);
+    assert_eq!(server.await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn fails_with_wrong_shared_key() {
+    trace_init();
+
+    let (addr, server) = spawn_server(Some("secret"), true).await;
+    let sink = build_sink(config(
+        addr,
+        r#"
+        security.self_hostname = "vector"
+        security.shared_key = "wrong"
+        "#,
+    ))
+    .await;
+    let (events, receiver) = events(&["a"]);
+
+    run_and_assert_sink_error(sink, stream::iter(events), &COMPONENT_ERROR_TAGS).await;
+    assert_eq!(receiver.await, BatchStatus::Rejected);
+    assert!(server.await.unwrap().is_empty());
+}
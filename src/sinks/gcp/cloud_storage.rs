@@ -32,9 +32,9 @@ use crate::{
             sink::GcsSink,
         },
         util::{
-            batch::BatchConfig, partitioner::KeyPartitioner, request_builder::EncodeResult,
-            timezone_to_offset, BulkSizeBasedDefaultBatchSettings, Compression, RequestBuilder,
-            ServiceBuilderExt, TowerRequestConfig,
+            batch::BatchConfig, encoding::object_extension, partitioner::KeyPartitioner,
+            request_builder::EncodeResult, timezone_to_offset, BulkSizeBasedDefaultBatchSettings,
+            Compression, RequestBuilder, ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck, VectorSink,
     },
@@ -140,7 +140,8 @@ pub struct GcsSinkConfig {
 
     /// The filename extension to use in the object key.
     ///
-    /// If not specified, the extension is determined by the compression scheme used.
+    /// If not specified, the extension is determined by the compression scheme used, or is
+    /// `parquet` when using the `parquet` codec.
     #[configurable(metadata(docs::advanced))]
     filename_extension: Option<String>,
 
@@ -371,7 +372,8 @@ impl RequestBuilder<(String, Vec<Event>)> for RequestSettings {
 impl RequestSettings {
     fn new(config: &GcsSinkConfig, cx: SinkContext) -> crate::Result<Self> {
         let transformer = config.encoding.transformer();
-        let (framer, serializer) = config.encoding.build(SinkType::MessageBased)?;
+        let (framer, serializer) = config.encoding.build_batched(SinkType::MessageBased)?;
+        let format_extension = object_extension(&serializer, config.compression)?;
         let encoder = Encoder::<Framer>::new(framer, serializer);
         let acl = config
             .acl
@@ -396,6 +398,7 @@ impl RequestSettings {
         let extension = config
             .filename_extension
             .clone()
+            .or_else(|| format_extension.map(Into::into))
             .unwrap_or_else(|| config.compression.extension().into());
         let time_format = config.filename_time_format.clone();
         let append_uuid = config.filename_append_uuid;
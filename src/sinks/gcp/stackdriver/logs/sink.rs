@@ -18,7 +18,7 @@ where
     S: Service<HttpRequest<()>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+    S::Error: std::fmt::Debug + std::fmt::Display + Into<crate::Error> + Send,
 {
     /// Creates a new `StackdriverLogsSink`.
     pub(super) const fn new(
@@ -66,7 +66,7 @@ where
     S: Service<HttpRequest<()>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+    S::Error: std::fmt::Debug + std::fmt::Display + Into<crate::Error> + Send,
 {
     async fn run(
         self: Box<Self>,
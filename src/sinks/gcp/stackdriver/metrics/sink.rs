@@ -35,7 +35,7 @@ where
     S: Service<HttpRequest<()>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+    S::Error: std::fmt::Debug + std::fmt::Display + Into<crate::Error> + Send,
 {
     /// Creates a new `StackdriverMetricsSink`.
     pub(super) const fn new(
@@ -92,7 +92,7 @@ where
     S: Service<HttpRequest<()>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+    S::Error: std::fmt::Debug + std::fmt::Display + Into<crate::Error> + Send,
 {
     async fn run(
         self: Box<Self>,
@@ -33,7 +33,7 @@ where
     Svc: Service<RB::Request> + Send + 'static,
     Svc::Future: Send + 'static,
     Svc::Response: DriverResponse + Send + 'static,
-    Svc::Error: fmt::Debug + Into<crate::Error> + Send,
+    Svc::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
     RB: RequestBuilder<(String, Vec<Event>)> + Send + Sync + 'static,
     RB::Error: fmt::Display + Send,
     RB::Request: Finalizable + MetaDescriptive + Send,
@@ -74,7 +74,7 @@ where
     Svc: Service<RB::Request> + Send + 'static,
     Svc::Future: Send + 'static,
     Svc::Response: DriverResponse + Send + 'static,
-    Svc::Error: fmt::Debug + Into<crate::Error> + Send,
+    Svc::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
     RB: RequestBuilder<(String, Vec<Event>)> + Send + Sync + 'static,
     RB::Error: fmt::Display + Send,
     RB::Request: Finalizable + MetaDescriptive + Send,
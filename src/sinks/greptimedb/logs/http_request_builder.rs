@@ -2,6 +2,7 @@ use crate::{
     codecs::{Encoder, Transformer},
     event::{Event, EventFinalizers, Finalizable},
     http::{Auth, HttpClient, HttpError},
+    oauth2,
     sinks::{
         prelude::*,
         util::http::{HttpRequest, HttpResponse, HttpRetryLogic, HttpServiceRequestBuilder},
@@ -215,13 +216,14 @@ pub(super) async fn http_healthcheck(
     auth: Option<Auth>,
 ) -> crate::Result<()> {
     let uri = format!("{endpoint}/health");
-    let mut request = Request::get(uri).body(Body::empty())?;
+    let mut request = Request::get(uri).body(Bytes::new())?;
 
     if let Some(auth) = auth {
         auth.apply(&mut request);
     }
 
-    let response = client.send(request).await?;
+    let response =
+        oauth2::send_authorized(request, |request| client.send(request.map(Body::from))).await?;
 
     match response.status() {
         StatusCode::OK => Ok(()),
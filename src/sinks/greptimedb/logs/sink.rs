@@ -26,7 +26,7 @@ where
     S: Service<HttpRequest<PartitionKey>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+    S::Error: std::fmt::Debug + std::fmt::Display + Into<crate::Error> + Send,
 {
     pub const fn new(
         batcher_settings: BatcherSettings,
@@ -80,7 +80,7 @@ where
     S: Service<HttpRequest<PartitionKey>> + Send + 'static,
     S::Future: Send + 'static,
     S::Response: DriverResponse + Send + 'static,
-    S::Error: std::fmt::Debug + Into<crate::Error> + Send,
+    S::Error: std::fmt::Debug + std::fmt::Display + Into<crate::Error> + Send,
 {
     async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         self.run_inner(input).await
@@ -36,6 +36,7 @@ impl GreptimeDBBatchSizer {
                 MetricValue::Distribution { .. } => F64_BYTE_SIZE * (DISTRIBUTION_QUANTILES.len() + DISTRIBUTION_STAT_FIELD_COUNT),
                 MetricValue::AggregatedHistogram { buckets, .. }  => F64_BYTE_SIZE * (buckets.len() + SUMMARY_STAT_FIELD_COUNT),
                 MetricValue::AggregatedSummary { quantiles, .. } => F64_BYTE_SIZE * (quantiles.len() + SUMMARY_STAT_FIELD_COUNT),
+                MetricValue::ExponentialHistogram { histogram } => F64_BYTE_SIZE * (histogram.positive.counts.len() + histogram.negative.counts.len() + 1 + SUMMARY_STAT_FIELD_COUNT),
                 MetricValue::Sketch { .. } => F64_BYTE_SIZE * (DISTRIBUTION_QUANTILES.len() + DISTRIBUTION_STAT_FIELD_COUNT),
             }
     }
@@ -125,6 +125,11 @@ pub fn metric_to_insert_request(
             encode_f64_value("count", *count as f64, &mut schema, &mut columns);
             encode_f64_value("sum", *sum, &mut schema, &mut columns);
         }
+        MetricValue::ExponentialHistogram { histogram } => {
+            encode_histogram(&histogram.to_buckets(), &mut schema, &mut columns);
+            encode_f64_value("count", histogram.count as f64, &mut schema, &mut columns);
+            encode_f64_value("sum", histogram.sum, &mut schema, &mut columns);
+        }
         MetricValue::Sketch { sketch } => {
             let MetricSketch::AgentDDSketch(sketch) = sketch;
             encode_sketch(sketch, &mut schema, &mut columns);
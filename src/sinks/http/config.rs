@@ -1,5 +1,6 @@
 //! Configuration for the `http` sink.
 
+use bytes::Bytes;
 use http::{header::AUTHORIZATION, HeaderName, HeaderValue, Method, Request, StatusCode};
 use hyper::Body;
 use indexmap::IndexMap;
@@ -11,6 +12,7 @@ use vector_lib::codecs::{
 use crate::{
     codecs::{EncodingConfigWithFraming, SinkType},
     http::{Auth, HttpClient, MaybeAuth},
+    oauth2,
     sinks::{
         prelude::*,
         util::{
@@ -175,13 +177,14 @@ impl GenerateConfig for HttpSinkConfig {
 async fn healthcheck(uri: UriSerde, auth: Option<Auth>, client: HttpClient) -> crate::Result<()> {
     let auth = auth.choose_one(&uri.auth)?;
     let uri = uri.with_default_parts();
-    let mut request = Request::head(&uri.uri).body(Body::empty()).unwrap();
+    let mut request = Request::head(&uri.uri).body(Bytes::new()).unwrap();
 
     if let Some(auth) = auth {
         auth.apply(&mut request);
     }
 
-    let response = client.send(request).await?;
+    let response =
+        oauth2::send_authorized(request, |request| client.send(request.map(Body::from))).await?;
 
     match response.status() {
         StatusCode::OK => Ok(()),
@@ -5,7 +5,7 @@ use futures::{future::BoxFuture, stream, SinkExt};
 use tower::Service;
 use vector_lib::configurable::configurable_component;
 use vector_lib::{
-    event::metric::{MetricSketch, MetricTags, Quantile},
+    event::metric::{Bucket, MetricSketch, MetricTags, Quantile},
     ByteSizeOf, EstimatedJsonEncodedSizeOf,
 };
 
@@ -306,6 +306,21 @@ fn encode_events(
     output
 }
 
+fn encode_buckets(buckets: &[Bucket], count: u64, sum: f64) -> HashMap<KeyString, Field> {
+    let mut fields: HashMap<KeyString, Field> = buckets
+        .iter()
+        .map(|sample| {
+            (
+                format!("bucket_{}", sample.upper_limit).into(),
+                Field::UnsignedInt(sample.count),
+            )
+        })
+        .collect();
+    fields.insert("count".into(), Field::UnsignedInt(count));
+    fields.insert("sum".into(), Field::Float(sum));
+    fields
+}
+
 fn get_type_and_fields(
     value: &MetricValue,
     quantiles: &[f64],
@@ -318,21 +333,15 @@ fn get_type_and_fields(
             buckets,
             count,
             sum,
-        } => {
-            let mut fields: HashMap<KeyString, Field> = buckets
-                .iter()
-                .map(|sample| {
-                    (
-                        format!("bucket_{}", sample.upper_limit).into(),
-                        Field::UnsignedInt(sample.count),
-                    )
-                })
-                .collect();
-            fields.insert("count".into(), Field::UnsignedInt(*count));
-            fields.insert("sum".into(), Field::Float(*sum));
-
-            ("histogram", Some(fields))
-        }
+        } => ("histogram", Some(encode_buckets(buckets, *count, *sum))),
+        MetricValue::ExponentialHistogram { histogram } => (
+            "histogram",
+            Some(encode_buckets(
+                &histogram.to_buckets(),
+                histogram.count,
+                histogram.sum,
+            )),
+        ),
         MetricValue::AggregatedSummary {
             quantiles,
             count,
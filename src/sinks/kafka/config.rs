@@ -1,6 +1,7 @@
-use std::{collections::HashMap, time::Duration};
+use std::{collections::HashMap, num::NonZeroUsize, time::Duration};
 
 use futures::FutureExt;
+use once_cell::sync::Lazy;
 use rdkafka::ClientConfig;
 use serde_with::serde_as;
 use vector_lib::codecs::JsonSerializerConfig;
@@ -9,6 +10,7 @@ use vector_lib::lookup::lookup_v2::ConfigTargetPath;
 use vrl::value::Kind;
 
 use crate::{
+    common::schema_registry::SchemaRegistrySinkConfig,
     kafka::{KafkaAuthConfig, KafkaCompression},
     serde::json::to_string,
     sinks::{
@@ -17,6 +19,9 @@ use crate::{
     },
 };
 
+/// The acknowledgements of transactional sinks, which are always enabled.
+static TRANSACTION_ACKNOWLEDGEMENTS: Lazy<AcknowledgementsConfig> = Lazy::new(|| true.into());
+
 /// Configuration for the `kafka` sink.
 #[serde_as]
 #[configurable_component(sink(
@@ -117,6 +122,19 @@ pub struct KafkaSinkConfig {
     #[configurable(metadata(docs::examples = "headers"))]
     pub headers_key: Option<ConfigTargetPath>,
 
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    pub transaction: Option<KafkaTransactionConfig>,
+
+    /// Writes messages in the [Confluent wire format][wire_format], prefixed with the ID of the
+    /// schema of the encoding in a schema registry.
+    ///
+    /// Requires the `avro` or `protobuf` encoding. The schema is looked up, or registered, once
+    /// per subject.
+    ///
+    /// [wire_format]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+    pub schema_registry: Option<SchemaRegistrySinkConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -126,6 +144,67 @@ pub struct KafkaSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// Transactional delivery configuration.
+///
+/// When enabled, events are written in batches, each within a single transaction, so that
+/// consumers reading with the `read_committed` isolation level see either all or none of the events
+/// of a batch. Events coming from a `kafka` source also have the offsets of their messages
+/// committed within the same transaction, which provides exactly-once delivery from Kafka to Kafka.
+///
+/// Transactions require end-to-end acknowledgements, which are enabled for the sink. Otherwise,
+/// the source would commit the offsets of its messages on its own, before they are delivered.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaTransactionConfig {
+    /// The transactional ID of the producer.
+    ///
+    /// It must be unique to this sink among the producers writing to the cluster, and stay the
+    /// same across restarts so that the transactions left open by a previous instance are
+    /// aborted.
+    #[configurable(metadata(docs::examples = "vector-kafka-sink-1"))]
+    pub transactional_id: String,
+
+    /// The maximum number of events written within a single transaction.
+    #[serde(default = "default_transaction_max_events")]
+    pub max_events: NonZeroUsize,
+
+    /// The maximum number of bytes written within a single transaction.
+    ///
+    /// This is based on the size of the encoded events.
+    #[serde(default = "default_transaction_max_bytes")]
+    pub max_bytes: NonZeroUsize,
+
+    /// The maximum time, in milliseconds, to wait for more events before committing a transaction.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[serde(default = "default_transaction_commit_interval_ms")]
+    #[configurable(metadata(docs::human_name = "Commit Interval"))]
+    pub commit_interval_ms: Duration,
+
+    /// The timeout, in milliseconds, for initializing, committing and aborting transactions.
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[serde(default = "default_transaction_timeout_ms")]
+    #[configurable(metadata(docs::human_name = "Transaction Operation Timeout"))]
+    pub timeout_ms: Duration,
+}
+
+fn default_transaction_max_events() -> NonZeroUsize {
+    NonZeroUsize::new(1000).expect("static non-zero number")
+}
+
+fn default_transaction_max_bytes() -> NonZeroUsize {
+    NonZeroUsize::new(10_000_000).expect("static non-zero number")
+}
+
+const fn default_transaction_commit_interval_ms() -> Duration {
+    Duration::from_millis(1000)
+}
+
+const fn default_transaction_timeout_ms() -> Duration {
+    Duration::from_millis(30000)
+}
+
 const fn default_socket_timeout_ms() -> Duration {
     Duration::from_millis(60000) // default in librdkafka
 }
@@ -155,6 +234,10 @@ impl KafkaSinkConfig {
 
         self.auth.apply(&mut client_config)?;
 
+        if let Some(transaction) = &self.transaction {
+            client_config.set("transactional.id", &transaction.transactional_id);
+        }
+
         // All batch options are producer only.
         client_config
             .set("compression.codec", &to_string(self.compression))
@@ -247,6 +330,8 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            transaction: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -256,8 +341,11 @@ impl GenerateConfig for KafkaSinkConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "kafka")]
 impl SinkConfig for KafkaSinkConfig {
-    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let sink = KafkaSink::new(self.clone())?;
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        if self.transaction.is_some() && self.acknowledgements == false.into() {
+            return Err("`transaction` requires `acknowledgements` to be enabled.".into());
+        }
+        let sink = KafkaSink::new(self.clone(), cx.proxy())?;
         let hc = healthcheck(self.clone()).boxed();
         Ok((VectorSink::from_event_streamsink(sink), hc))
     }
@@ -270,7 +358,11 @@ impl SinkConfig for KafkaSinkConfig {
     }
 
     fn acknowledgements(&self) -> &AcknowledgementsConfig {
-        &self.acknowledgements
+        if self.transaction.is_some() {
+            &TRANSACTION_ACKNOWLEDGEMENTS
+        } else {
+            &self.acknowledgements
+        }
     }
 }
 
@@ -282,4 +374,36 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    fn transactional_config(acknowledgements: &str) -> KafkaSinkConfig {
+        toml::from_str(&format!(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "logs"
+            encoding.codec = "json"
+            transaction.transactional_id = "vector-1"
+            {acknowledgements}
+            "#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn transactions_enable_acknowledgements() {
+        let config = transactional_config("");
+        assert!(config
+            .acknowledgements()
+            .merge_default(&false.into())
+            .enabled());
+    }
+
+    #[tokio::test]
+    async fn transactions_reject_disabled_acknowledgements() {
+        let config = transactional_config("acknowledgements.enabled = false");
+        let error = config.build(SinkContext::default()).await.err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "`transaction` requires `acknowledgements` to be enabled."
+        );
+    }
 }
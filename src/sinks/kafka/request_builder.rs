@@ -1,11 +1,14 @@
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use rdkafka::message::{Header, OwnedHeaders};
 use vector_lib::lookup::OwnedTargetPath;
 
 use crate::{
     internal_events::KafkaHeaderExtractionError,
     sinks::{
-        kafka::service::{KafkaRequest, KafkaRequestMetadata},
+        kafka::{
+            service::{KafkaRequest, KafkaRequestMetadata},
+            transaction::SourceOffset,
+        },
         prelude::*,
     },
 };
@@ -14,10 +17,14 @@ pub struct KafkaRequestBuilder {
     pub key_field: Option<OwnedTargetPath>,
     pub headers_key: Option<OwnedTargetPath>,
     pub encoder: (Transformer, Encoder<()>),
+    /// Whether to track the `kafka` source messages that events were consumed from.
+    pub source_offsets: bool,
 }
 
-impl RequestBuilder<(String, Event)> for KafkaRequestBuilder {
-    type Metadata = KafkaRequestMetadata;
+/// Builds requests from the topic of an event, the schema registry prefix of its message if any,
+/// and the event.
+impl RequestBuilder<(String, Option<Bytes>, Event)> for KafkaRequestBuilder {
+    type Metadata = (KafkaRequestMetadata, Option<Bytes>);
     type Events = Event;
     type Encoder = (Transformer, Encoder<()>);
     type Payload = Bytes;
@@ -34,9 +41,9 @@ impl RequestBuilder<(String, Event)> for KafkaRequestBuilder {
 
     fn split_input(
         &self,
-        input: (String, Event),
+        input: (String, Option<Bytes>, Event),
     ) -> (Self::Metadata, RequestMetadataBuilder, Self::Events) {
-        let (topic, mut event) = input;
+        let (topic, header, mut event) = input;
         let builder = RequestMetadataBuilder::from_event(&event);
 
         let metadata = KafkaRequestMetadata {
@@ -45,19 +52,34 @@ impl RequestBuilder<(String, Event)> for KafkaRequestBuilder {
             timestamp_millis: get_timestamp_millis(&event),
             headers: get_headers(&event, self.headers_key.as_ref()),
             topic,
+            source_offset: self
+                .source_offsets
+                .then(|| SourceOffset::from_event(&event))
+                .flatten(),
         };
 
-        (metadata, builder, event)
+        ((metadata, header), builder, event)
     }
 
     fn build_request(
         &self,
-        metadata: Self::Metadata,
+        (metadata, header): Self::Metadata,
         request_metadata: RequestMetadata,
         payload: EncodeResult<Self::Payload>,
     ) -> Self::Request {
+        let body = match header {
+            None => payload.into_payload(),
+            Some(header) => {
+                let payload = payload.into_payload();
+                let mut body = BytesMut::with_capacity(header.len() + payload.len());
+                body.extend_from_slice(&header);
+                body.extend_from_slice(&payload);
+                body.freeze()
+            }
+        };
+
         KafkaRequest {
-            body: payload.into_payload(),
+            body,
             metadata,
             request_metadata,
         }
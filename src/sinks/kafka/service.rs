@@ -16,6 +16,7 @@ use rdkafka::{
 };
 use vector_lib::config;
 
+use super::transaction::SourceOffset;
 use crate::{kafka::KafkaStatisticsContext, sinks::prelude::*};
 
 pub struct KafkaRequest {
@@ -30,12 +31,13 @@ pub struct KafkaRequestMetadata {
     pub timestamp_millis: Option<i64>,
     pub headers: Option<OwnedHeaders>,
     pub topic: String,
+    pub source_offset: Option<SourceOffset>,
 }
 
 pub struct KafkaResponse {
-    event_byte_size: GroupedCountByteSize,
-    raw_byte_size: usize,
-    event_status: EventStatus,
+    pub(super) event_byte_size: GroupedCountByteSize,
+    pub(super) raw_byte_size: usize,
+    pub(super) event_status: EventStatus,
 }
 
 impl DriverResponse for KafkaResponse {
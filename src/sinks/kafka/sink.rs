@@ -10,8 +10,15 @@ use vrl::path::OwnedTargetPath;
 
 use super::config::KafkaSinkConfig;
 use crate::{
+    common::schema_registry::SchemaRegistryEncoder,
+    config::ProxyConfig,
+    internal_events::KafkaSchemaRegistryEncodeError,
     kafka::KafkaStatisticsContext,
-    sinks::kafka::{request_builder::KafkaRequestBuilder, service::KafkaService},
+    sinks::kafka::{
+        request_builder::KafkaRequestBuilder,
+        service::{KafkaRequest, KafkaService},
+        transaction::{KafkaTransactionRequest, KafkaTransactionService},
+    },
     sinks::prelude::*,
 };
 
@@ -29,6 +36,8 @@ pub struct KafkaSink {
     topic: Template,
     key_field: Option<OwnedTargetPath>,
     headers_key: Option<OwnedTargetPath>,
+    transaction: Option<(KafkaTransactionService, BatcherSettings)>,
+    schema_registry: Option<SchemaRegistryEncoder>,
 }
 
 pub(crate) fn create_producer(
@@ -44,12 +53,27 @@ pub(crate) fn create_producer(
 }
 
 impl KafkaSink {
-    pub(crate) fn new(config: KafkaSinkConfig) -> crate::Result<Self> {
+    pub(crate) fn new(config: KafkaSinkConfig, proxy: &ProxyConfig) -> crate::Result<Self> {
         let producer_config = config.to_rdkafka()?;
         let producer = create_producer(producer_config)?;
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
+        let schema_registry = config
+            .schema_registry
+            .as_ref()
+            .map(|schema_registry| schema_registry.build(config.encoding.config(), proxy))
+            .transpose()?;
+        let transaction = config.transaction.map(|transaction| {
+            (
+                KafkaTransactionService::new(producer.clone(), transaction.timeout_ms),
+                BatcherSettings::new(
+                    transaction.commit_interval_ms,
+                    transaction.max_bytes,
+                    transaction.max_events,
+                ),
+            )
+        });
 
         Ok(KafkaSink {
             headers_key: config.headers_key.map(|key| key.0),
@@ -58,6 +82,8 @@ impl KafkaSink {
             service: KafkaService::new(producer),
             topic: config.topic,
             key_field: config.key_field.map(|key| key.0),
+            transaction,
+            schema_registry,
         })
     }
 
@@ -66,9 +92,11 @@ impl KafkaSink {
             key_field: self.key_field,
             headers_key: self.headers_key,
             encoder: (self.transformer, self.encoder),
+            source_offsets: self.transaction.is_some(),
         };
+        let schema_registry = self.schema_registry.as_ref();
 
-        input
+        let requests = input
             .filter_map(|event| {
                 // Compute the topic.
                 future::ready(
@@ -85,6 +113,20 @@ impl KafkaSink {
                         .map(|topic| (topic, event)),
                 )
             })
+            .filter_map(|(topic, event)| async move {
+                // Resolve the schema registry prefix of the messages written to the topic.
+                let header = match schema_registry {
+                    None => None,
+                    Some(schema_registry) => match schema_registry.header(&topic).await {
+                        Ok(header) => Some(header),
+                        Err(error) => {
+                            emit!(KafkaSchemaRegistryEncodeError { error });
+                            return None;
+                        }
+                    },
+                };
+                Some((topic, header, event))
+            })
             .request_builder(default_request_builder_concurrency_limit(), request_builder)
             .filter_map(|request| async {
                 match request {
@@ -94,11 +136,36 @@ impl KafkaSink {
                     }
                     Ok(req) => Some(req),
                 }
-            })
-            .into_driver(self.service)
-            .protocol("kafka")
-            .run()
-            .await
+            });
+
+        match self.transaction {
+            None => {
+                requests
+                    .into_driver(self.service)
+                    .protocol("kafka")
+                    .run()
+                    .await
+            }
+            Some((service, batch_settings)) => {
+                if let Err(error) = service.init().await {
+                    error!(message = "Failed to initialize Kafka transactions.", %error);
+                    return Err(());
+                }
+
+                // Transactions can't overlap, so they are written one at a time.
+                let service = ServiceBuilder::new().concurrency_limit(1).service(service);
+                requests
+                    .batched(
+                        batch_settings
+                            .as_item_size_config(|request: &KafkaRequest| request.body.len()),
+                    )
+                    .map(KafkaTransactionRequest::from)
+                    .into_driver(service)
+                    .protocol("kafka")
+                    .run()
+                    .await
+            }
+        }
     }
 }
 
@@ -3,26 +3,34 @@
 #[cfg(feature = "kafka-integration-tests")]
 #[cfg(test)]
 mod integration_test {
-    use std::{collections::HashMap, future::ready, thread, time::Duration};
+    use std::{
+        collections::HashMap, future::ready, num::NonZeroUsize, sync::Arc, thread, time::Duration,
+    };
 
     use bytes::Bytes;
     use futures::StreamExt;
     use rdkafka::{
         consumer::{BaseConsumer, Consumer},
         message::Headers,
+        producer::{FutureProducer, FutureRecord},
         Message, Offset, TopicPartitionList,
     };
     use vector_lib::codecs::TextSerializerConfig;
-    use vector_lib::lookup::lookup_v2::ConfigTargetPath;
+    use vector_lib::lookup::{lookup_v2::ConfigTargetPath, owned_value_path, OwnedTargetPath};
     use vector_lib::{
-        config::{init_telemetry, Tags, Telemetry},
+        config::{init_telemetry, ComponentKey, Tags, Telemetry},
         event::{BatchNotifier, BatchStatus},
     };
 
-    use super::super::{config::KafkaSinkConfig, sink::KafkaSink, *};
+    use super::super::{
+        config::{KafkaSinkConfig, KafkaTransactionConfig},
+        sink::KafkaSink,
+        *,
+    };
     use crate::{
+        config::ProxyConfig,
         event::{ObjectMap, Value},
-        kafka::{KafkaAuthConfig, KafkaCompression, KafkaSaslConfig},
+        kafka::{KafkaAuthConfig, KafkaCompression, KafkaConsumerGroup, KafkaSaslConfig},
         sinks::prelude::*,
         test_util::{
             components::{
@@ -61,6 +69,8 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            transaction: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config).await.unwrap();
@@ -85,6 +95,8 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: None,
+            transaction: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         };
         self::sink::healthcheck(config).await.unwrap();
@@ -184,11 +196,13 @@ mod integration_test {
             batch,
             librdkafka_options,
             headers_key: None,
+            transaction: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         };
         config.clone().to_rdkafka()?;
         self::sink::healthcheck(config.clone()).await?;
-        KafkaSink::new(config)
+        KafkaSink::new(config, &ProxyConfig::default())
     }
 
     #[tokio::test]
@@ -332,6 +346,8 @@ mod integration_test {
             message_timeout_ms: Duration::from_millis(300000),
             librdkafka_options: HashMap::new(),
             headers_key: Some(headers_key.clone()),
+            transaction: None,
+            schema_registry: None,
             acknowledgements: Default::default(),
         };
         let topic = format!("{}-{}", topic, chrono::Utc::now().format("%Y%m%d"));
@@ -358,7 +374,7 @@ mod integration_test {
 
         if test_telemetry_tags {
             assert_data_volume_sink_compliance(&DATA_VOLUME_SINK_TAGS, async move {
-                let sink = KafkaSink::new(config).unwrap();
+                let sink = KafkaSink::new(config, &ProxyConfig::default()).unwrap();
                 let sink = VectorSink::from_event_streamsink(sink);
                 sink.run(input_events).await
             })
@@ -366,7 +382,7 @@ mod integration_test {
             .expect("Running sink failed");
         } else {
             assert_sink_compliance(&SINK_TAGS, async move {
-                let sink = KafkaSink::new(config).unwrap();
+                let sink = KafkaSink::new(config, &ProxyConfig::default()).unwrap();
                 let sink = VectorSink::from_event_streamsink(sink);
                 sink.run(input_events).await
             })
@@ -431,4 +447,158 @@ mod integration_test {
         assert_eq!(out.len(), input.len());
         assert_eq!(out, input);
     }
+
+    fn transactional_config(topic: &str) -> KafkaSinkConfig {
+        KafkaSinkConfig {
+            bootstrap_servers: kafka_address(9091),
+            topic: Template::try_from(topic).unwrap(),
+            healthcheck_topic: None,
+            key_field: None,
+            encoding: TextSerializerConfig::default().into(),
+            batch: BatchConfig::default(),
+            compression: KafkaCompression::None,
+            auth: KafkaAuthConfig::default(),
+            socket_timeout_ms: Duration::from_millis(60000),
+            message_timeout_ms: Duration::from_millis(300000),
+            librdkafka_options: HashMap::new(),
+            headers_key: None,
+            transaction: Some(KafkaTransactionConfig {
+                transactional_id: format!("vector-{}", random_string(10)),
+                max_events: NonZeroUsize::new(100).unwrap(),
+                max_bytes: NonZeroUsize::new(10_000_000).unwrap(),
+                commit_interval_ms: Duration::from_millis(100),
+                timeout_ms: Duration::from_secs(30),
+            }),
+            schema_registry: None,
+            acknowledgements: Default::default(),
+        }
+    }
+
+    fn consumer(group_id: &str) -> BaseConsumer {
+        let mut client_config = rdkafka::ClientConfig::new();
+        client_config.set("bootstrap.servers", kafka_address(9091));
+        client_config.set("group.id", group_id);
+        client_config.set("enable.auto.commit", "false");
+        client_config.set("auto.offset.reset", "earliest");
+        client_config.set("isolation.level", "read_committed");
+        client_config.create().unwrap()
+    }
+
+    /// Reads back the committed messages of the topic.
+    fn read_committed(topic: &str, count: usize) -> Vec<String> {
+        let consumer = consumer(&random_string(10));
+        consumer.subscribe(&[topic]).unwrap();
+
+        let mut failures = 0;
+        let mut out = Vec::new();
+        while failures < 100 && out.len() < count {
+            match consumer.poll(Duration::from_secs(3)) {
+                Some(Ok(msg)) => {
+                    let s: &str = msg.payload_view().unwrap().unwrap();
+                    out.push(s.to_owned());
+                }
+                _ => failures += 1,
+            }
+        }
+        out
+    }
+
+    #[tokio::test]
+    async fn kafka_transactions_happy_path() {
+        crate::test_util::trace_init();
+
+        let topic = format!("test-{}", random_string(10));
+        let config = transactional_config(&topic);
+
+        let num_events = 1000;
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let (input, events) = random_lines_with_stream(100, num_events, Some(batch));
+
+        assert_sink_compliance(&SINK_TAGS, async move {
+            let sink = KafkaSink::new(config, &ProxyConfig::default()).unwrap();
+            let sink = VectorSink::from_event_streamsink(sink);
+            sink.run(events).await
+        })
+        .await
+        .expect("Running sink failed");
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+        assert_eq!(read_committed(&topic, num_events), input);
+    }
+
+    #[tokio::test]
+    async fn kafka_transactions_commit_source_offsets() {
+        crate::test_util::trace_init();
+
+        let input_topic = format!("test-input-{}", random_string(10));
+        let output_topic = format!("test-{}", random_string(10));
+        let group_id = format!("test-group-{}", random_string(10));
+        let num_events = 10;
+
+        let producer: FutureProducer = rdkafka::ClientConfig::new()
+            .set("bootstrap.servers", kafka_address(9091))
+            .create()
+            .unwrap();
+        for i in 0..num_events {
+            let payload = format!("message {i}");
+            producer
+                .send(
+                    FutureRecord::<(), _>::to(&input_topic).payload(&payload),
+                    Duration::from_secs(3),
+                )
+                .await
+                .unwrap();
+        }
+
+        // This consumer stands in for a `kafka` source, whose offsets are committed by the sink.
+        let consumer = Arc::new(consumer(&group_id));
+        consumer.subscribe(&[&input_topic]).unwrap();
+        wait_for(|| {
+            _ = consumer.poll(Duration::from_millis(100));
+            ready(consumer.assignment().unwrap().count() > 0)
+        })
+        .await;
+        let _registration = KafkaConsumerGroup::new(
+            {
+                let consumer = Arc::clone(&consumer);
+                move || consumer.group_metadata()
+            },
+            Some(OwnedTargetPath::event(owned_value_path!("topic"))),
+            Some(OwnedTargetPath::event(owned_value_path!("partition"))),
+            Some(OwnedTargetPath::event(owned_value_path!("offset"))),
+        )
+        .register(ComponentKey::from("in"));
+
+        let (input, events): (Vec<_>, Vec<_>) = (0..num_events)
+            .map(|i| {
+                let message = format!("message {i}");
+                let mut log = LogEvent::from(message.as_str());
+                log.insert("topic", input_topic.clone());
+                log.insert("partition", 0);
+                log.insert("offset", i as i64);
+                let mut event = Event::Log(log);
+                event
+                    .metadata_mut()
+                    .set_source_id(Arc::new(ComponentKey::from("in")));
+                (message, event)
+            })
+            .unzip();
+
+        let sink =
+            KafkaSink::new(transactional_config(&output_topic), &ProxyConfig::default()).unwrap();
+        VectorSink::from_event_streamsink(sink)
+            .run(futures::stream::iter(events).map(Into::into))
+            .await
+            .expect("Running sink failed");
+
+        assert_eq!(read_committed(&output_topic, num_events), input);
+
+        let committed = consumer.committed(Duration::from_secs(3)).unwrap();
+        assert_eq!(
+            committed
+                .find_partition(&input_topic, 0)
+                .map(|partition| partition.offset()),
+            Some(Offset::Offset(num_events as i64))
+        );
+    }
 }
@@ -0,0 +1,264 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use rdkafka::{
+    error::KafkaError,
+    producer::{FutureProducer, FutureRecord, Producer},
+    util::Timeout,
+    Offset, TopicPartitionList,
+};
+use vector_lib::config::ComponentKey;
+
+use crate::{
+    internal_events::KafkaTransactionError,
+    kafka::{KafkaConsumerGroup, KafkaStatisticsContext},
+    sinks::{
+        kafka::service::{KafkaRequest, KafkaResponse},
+        prelude::*,
+    },
+};
+
+/// The number of times a transaction is attempted before its events are rejected.
+const MAX_ATTEMPTS: usize = 5;
+
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The position of the message that an event was consumed from by a `kafka` source.
+pub struct SourceOffset {
+    source: Arc<ComponentKey>,
+    topic: String,
+    partition: i32,
+    offset: i64,
+}
+
+impl SourceOffset {
+    /// Gets the position of the message that the event was consumed from, if it comes from a
+    /// running `kafka` source.
+    pub(super) fn from_event(event: &Event) -> Option<Self> {
+        let source = event.metadata().source_id()?;
+        let group = KafkaConsumerGroup::get(source)?;
+        let log = event.maybe_as_log()?;
+
+        Some(Self {
+            topic: log.get(group.topic.as_ref()?)?.as_str()?.into_owned(),
+            partition: log
+                .get(group.partition.as_ref()?)?
+                .as_integer()?
+                .try_into()
+                .ok()?,
+            offset: log.get(group.offset.as_ref()?)?.as_integer()?,
+            source: Arc::clone(source),
+        })
+    }
+}
+
+/// A batch of records written within a single transaction.
+pub struct KafkaTransactionRequest {
+    requests: Vec<KafkaRequest>,
+
+    /// The offsets to commit for each partition consumed by each source, which are those of the
+    /// next messages to consume.
+    offsets: HashMap<Arc<ComponentKey>, HashMap<(String, i32), i64>>,
+
+    finalizers: EventFinalizers,
+    request_metadata: RequestMetadata,
+}
+
+impl From<Vec<KafkaRequest>> for KafkaTransactionRequest {
+    fn from(mut requests: Vec<KafkaRequest>) -> Self {
+        let mut finalizers = EventFinalizers::default();
+        let mut offsets = HashMap::<_, HashMap<_, _>>::new();
+        for request in &mut requests {
+            finalizers.merge(request.take_finalizers());
+            if let Some(position) = request.metadata.source_offset.take() {
+                let offset = offsets
+                    .entry(position.source)
+                    .or_default()
+                    .entry((position.topic, position.partition))
+                    .or_insert(0);
+                *offset = (*offset).max(position.offset + 1);
+            }
+        }
+        let request_metadata = RequestMetadata::from_batch(
+            requests
+                .iter_mut()
+                .map(|request| std::mem::take(&mut request.request_metadata)),
+        );
+
+        Self {
+            requests,
+            offsets,
+            finalizers,
+            request_metadata,
+        }
+    }
+}
+
+impl Finalizable for KafkaTransactionRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for KafkaTransactionRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.request_metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.request_metadata
+    }
+}
+
+/// Writes each batch of records within a transaction, along with the offsets of the messages they
+/// were consumed from.
+///
+/// Transactions can't overlap, so this service must not be called concurrently.
+#[derive(Clone)]
+pub struct KafkaTransactionService {
+    kafka_producer: FutureProducer<KafkaStatisticsContext>,
+
+    /// The timeout of the transactional operations.
+    timeout: Duration,
+}
+
+impl KafkaTransactionService {
+    pub(crate) const fn new(
+        kafka_producer: FutureProducer<KafkaStatisticsContext>,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            kafka_producer,
+            timeout,
+        }
+    }
+
+    /// Initializes transactions on the producer, which fences off any previous producer using the
+    /// same transactional ID and aborts the transactions it left open.
+    pub(crate) async fn init(&self) -> Result<(), KafkaError> {
+        self.run_blocking(|producer, timeout| producer.init_transactions(timeout))
+            .await
+    }
+
+    /// Runs one of the transactional operations of the producer, which block until they complete.
+    async fn run_blocking<F>(&self, operation: F) -> Result<(), KafkaError>
+    where
+        F: FnOnce(&FutureProducer<KafkaStatisticsContext>, Duration) -> Result<(), KafkaError>
+            + Send
+            + 'static,
+    {
+        let producer = self.kafka_producer.clone();
+        let timeout = self.timeout;
+        tokio::task::spawn_blocking(move || operation(&producer, timeout))
+            .await
+            .expect("transactional operation panicked")
+    }
+
+    async fn run_transaction(&self, request: &KafkaTransactionRequest) -> Result<(), KafkaError> {
+        self.kafka_producer.begin_transaction()?;
+
+        let deliveries = request
+            .requests
+            .iter()
+            .map(|request| self.kafka_producer.send(record(request), Timeout::Never));
+        future::try_join_all(deliveries)
+            .await
+            .map_err(|(error, _)| error)?;
+
+        for (source, offsets) in &request.offsets {
+            // Without the consumer group, which is gone if the source stopped, the source commits
+            // the offsets itself once the events are acknowledged.
+            let Some(group_metadata) =
+                KafkaConsumerGroup::get(source).and_then(|group| group.group_metadata())
+            else {
+                continue;
+            };
+            let mut tpl = TopicPartitionList::new();
+            for ((topic, partition), offset) in offsets {
+                tpl.add_partition_offset(topic, *partition, Offset::Offset(*offset))?;
+            }
+            self.run_blocking(move |producer, timeout| {
+                producer.send_offsets_to_transaction(&tpl, &group_metadata, timeout)
+            })
+            .await?;
+        }
+
+        self.run_blocking(|producer, timeout| producer.commit_transaction(timeout))
+            .await
+    }
+}
+
+fn record(request: &KafkaRequest) -> FutureRecord<'_, [u8], [u8]> {
+    let mut record = FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
+    if let Some(key) = &request.metadata.key {
+        record = record.key(&key[..]);
+    }
+    if let Some(timestamp) = request.metadata.timestamp_millis {
+        record = record.timestamp(timestamp);
+    }
+    if let Some(headers) = &request.metadata.headers {
+        record = record.headers(headers.clone());
+    }
+    record
+}
+
+impl Service<KafkaTransactionRequest> for KafkaTransactionService {
+    type Response = KafkaResponse;
+    type Error = KafkaError;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, mut request: KafkaTransactionRequest) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            let raw_byte_size = request
+                .requests
+                .iter()
+                .map(|request| {
+                    request.body.len() + request.metadata.key.as_ref().map_or(0, |key| key.len())
+                })
+                .sum();
+
+            let mut attempt = 1;
+            loop {
+                let error = match this.run_transaction(&request).await {
+                    Ok(()) => {
+                        return Ok(KafkaResponse {
+                            event_byte_size: std::mem::take(&mut request.request_metadata)
+                                .into_events_estimated_json_encoded_byte_size(),
+                            raw_byte_size,
+                            event_status: EventStatus::Delivered,
+                        })
+                    }
+                    Err(error) => error,
+                };
+
+                emit!(KafkaTransactionError {
+                    error: &error,
+                    attempt,
+                });
+                // A fatal error means the producer was fenced off by another one using the same
+                // transactional ID, or can't be used anymore for another reason.
+                if matches!(&error, KafkaError::Transaction(error) if error.is_fatal()) {
+                    return Err(error);
+                }
+                this.run_blocking(|producer, timeout| producer.abort_transaction(timeout))
+                    .await?;
+                if attempt == MAX_ATTEMPTS {
+                    return Err(error);
+                }
+
+                attempt += 1;
+                tokio::time::sleep(RETRY_BACKOFF).await;
+            }
+        })
+    }
+}
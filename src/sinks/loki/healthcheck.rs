@@ -1,5 +1,5 @@
 use super::config::LokiConfig;
-use crate::http::HttpClient;
+use crate::{http::HttpClient, oauth2};
 
 async fn fetch_status(
     endpoint: &str,
@@ -9,14 +9,15 @@ async fn fetch_status(
     let endpoint = config.endpoint.append_path(endpoint)?;
 
     let mut req = http::Request::get(endpoint.uri)
-        .body(hyper::Body::empty())
+        .body(bytes::Bytes::new())
         .expect("Building request never fails.");
 
     if let Some(auth) = &config.auth {
         auth.apply(&mut req);
     }
 
-    Ok(client.send(req).await?.status())
+    let response = oauth2::send_authorized(req, |req| client.send(req.map(hyper::Body::from)));
+    Ok(response.await?.status())
 }
 
 pub async fn healthcheck(config: LokiConfig, client: HttpClient) -> crate::Result<()> {
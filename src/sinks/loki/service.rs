@@ -7,6 +7,7 @@ use tracing::Instrument;
 
 use crate::{
     http::{Auth, HttpClient},
+    oauth2,
     sinks::{prelude::*, util::UriSerde},
 };
 
@@ -127,17 +128,18 @@ impl Service<LokiRequest> for LokiService {
             req = req.header("Content-Encoding", ce);
         }
 
-        let body = hyper::Body::from(request.payload);
-        let mut req = req.body(body).unwrap();
+        let mut req = req.body(request.payload).unwrap();
 
         if let Some(auth) = &self.endpoint.auth {
             auth.apply(&mut req);
         }
 
-        let mut client = self.client.clone();
+        let client = self.client.clone();
 
         Box::pin(async move {
-            match client.call(req).in_current_span().await {
+            let response =
+                oauth2::send_authorized(req, |req| client.send(req.map(hyper::Body::from)));
+            match response.in_current_span().await {
                 Ok(response) => {
                     let status = response.status();
 
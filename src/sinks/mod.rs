@@ -49,6 +49,8 @@ pub mod datadog;
 pub mod elasticsearch;
 #[cfg(feature = "sinks-file")]
 pub mod file;
+#[cfg(feature = "sinks-fluent")]
+pub mod fluent;
 #[cfg(feature = "sinks-gcp")]
 pub mod gcp;
 #[cfg(feature = "sinks-gcp-chronicle")]
@@ -79,6 +81,8 @@ pub mod nats;
 pub mod new_relic;
 #[cfg(feature = "sinks-webhdfs")]
 pub mod opendal_common;
+#[cfg(feature = "sinks-opentelemetry")]
+pub mod opentelemetry;
 #[cfg(feature = "sinks-papertrail")]
 pub mod papertrail;
 #[cfg(feature = "sinks-prometheus")]
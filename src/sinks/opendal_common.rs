@@ -54,7 +54,7 @@ where
     Svc: Service<OpenDalRequest> + Send + 'static,
     Svc::Future: Send + 'static,
     Svc::Response: DriverResponse + Send + 'static,
-    Svc::Error: fmt::Debug + Into<crate::Error> + Send,
+    Svc::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
 {
     async fn run(
         self: Box<Self>,
@@ -69,7 +69,7 @@ where
     Svc: Service<OpenDalRequest> + Send + 'static,
     Svc::Future: Send + 'static,
     Svc::Response: DriverResponse + Send + 'static,
-    Svc::Error: fmt::Debug + Into<crate::Error> + Send,
+    Svc::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
 {
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let partitioner = self.partitioner;
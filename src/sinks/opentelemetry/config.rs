@@ -0,0 +1,228 @@
+use futures::{future, FutureExt};
+use http::{HeaderName, HeaderValue, Uri};
+use indexmap::IndexMap;
+use vector_lib::configurable::configurable_component;
+
+use super::{
+    service::{OpentelemetryResponse, OpentelemetryService},
+    sink::OpentelemetrySink,
+    OpentelemetrySinkError,
+};
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::{Auth, HttpClient},
+    sinks::{
+        util::{
+            http::{validate_headers, RequestConfig},
+            retries::RetryLogic,
+            uri::with_default_scheme,
+            BatchConfig, RealtimeSizeBasedDefaultBatchSettings, ServiceBuilderExt,
+        },
+        Healthcheck, VectorSink,
+    },
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+/// Transport protocol used to deliver OTLP data.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OpentelemetryProtocol {
+    /// OTLP over gRPC.
+    ///
+    /// Requests are sent to the `Export` method of the logs, metrics, and trace services.
+    #[default]
+    Grpc,
+
+    /// OTLP over HTTP with binary Protobuf payloads.
+    ///
+    /// Requests are sent to the `/v1/logs`, `/v1/metrics`, and `/v1/traces` paths of the endpoint.
+    Http,
+}
+
+/// Configuration for the `opentelemetry` sink.
+#[configurable_component(sink(
+    "opentelemetry",
+    "Deliver logs, metrics, and traces to an OpenTelemetry (OTLP) endpoint."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    /// The OTLP endpoint to send data to.
+    ///
+    /// For the `grpc` protocol this is the address of the collector, typically on port 4317.
+    /// For the `http` protocol this is the base URL that the signal paths (for example `/v1/logs`)
+    /// are appended to, typically on port 4318.
+    #[configurable(metadata(docs::examples = "http://localhost:4317"))]
+    #[configurable(metadata(docs::examples = "https://otel-collector.example.com:4318"))]
+    pub endpoint: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub protocol: OpentelemetryProtocol,
+
+    /// Whether or not to compress requests.
+    ///
+    /// If set to `true`, requests are compressed with [`gzip`][gzip_docs].
+    ///
+    /// [gzip_docs]: https://www.gzip.org/
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    pub compression: bool,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: RequestConfig,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::is_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "http://localhost:4317"
+            protocol = "grpc""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SinkConfig for OpentelemetryConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let endpoint = with_default_scheme(&self.endpoint, tls.is_tls())?;
+        let headers = validate_headers(&self.request.headers)?;
+
+        let service = match self.protocol {
+            OpentelemetryProtocol::Grpc => {
+                let headers = self.grpc_headers(headers)?;
+                let client = crate::http::build_proxy_connector(tls, cx.proxy())
+                    .map(|proxy| hyper::Client::builder().http2_only(true).build(proxy))?;
+                OpentelemetryService::grpc(client, endpoint, headers, self.compression)
+            }
+            OpentelemetryProtocol::Http => {
+                let client = HttpClient::new(tls, cx.proxy())?;
+                OpentelemetryService::http(
+                    client,
+                    endpoint,
+                    headers,
+                    self.auth.clone(),
+                    self.compression,
+                )
+            }
+        };
+
+        let request_settings = self.request.tower.into_settings();
+        let batch_settings = self.batch.into_batcher_settings()?;
+
+        let service = tower::ServiceBuilder::new()
+            .settings(request_settings, OpentelemetryRetryLogic)
+            .service(service);
+
+        let sink = OpentelemetrySink {
+            batch_settings,
+            service,
+        };
+
+        // OTLP has no dedicated health endpoint that is guaranteed to be exposed by every
+        // receiver, so the sink relies on request errors to surface connectivity issues.
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl OpentelemetryConfig {
+    /// Adds the configured authentication to the headers sent with every gRPC request.
+    ///
+    /// gRPC requests are issued by the tonic clients and can't be resent with a refreshed access
+    /// token, so OAuth2 authentication is only supported with the `http` protocol.
+    fn grpc_headers(
+        &self,
+        mut headers: IndexMap<HeaderName, HeaderValue>,
+    ) -> crate::Result<IndexMap<HeaderName, HeaderValue>> {
+        if let Some(auth) = &self.auth {
+            if let Auth::OAuth2(_) = auth {
+                return Err(
+                    "OAuth2 authentication is not supported with the `grpc` protocol.".into(),
+                );
+            }
+            let mut map = http::HeaderMap::new();
+            auth.apply_headers_map(&mut map);
+            headers.extend(
+                map.into_iter()
+                    .filter_map(|(name, value)| name.map(|name| (name, value))),
+            );
+        }
+        Ok(headers)
+    }
+}
+
+/// Returns the URI that requests for the given signal path are sent to when using the `http`
+/// protocol.
+pub(super) fn signal_uri(endpoint: &Uri, signal_path: &str) -> crate::Result<Uri> {
+    let base = endpoint.to_string();
+    format!("{}{}", base.trim_end_matches('/'), signal_path)
+        .parse::<Uri>()
+        .map_err(Into::into)
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct OpentelemetryRetryLogic;
+
+impl RetryLogic for OpentelemetryRetryLogic {
+    type Error = OpentelemetrySinkError;
+    type Response = OpentelemetryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        use tonic::Code::*;
+
+        match error {
+            // Retryable codes as listed in the OTLP specification.
+            //
+            // <https://opentelemetry.io/docs/specs/otlp/#failures>
+            OpentelemetrySinkError::Grpc { source } => matches!(
+                source.code(),
+                Cancelled
+                    | DeadlineExceeded
+                    | Aborted
+                    | OutOfRange
+                    | Unavailable
+                    | DataLoss
+                    | ResourceExhausted
+            ),
+            OpentelemetrySinkError::Http { source } => source.is_retriable(),
+            OpentelemetrySinkError::HttpStatus { status, .. } => {
+                matches!(status.as_u16(), 429 | 502 | 503 | 504)
+            }
+            OpentelemetrySinkError::Body { .. } => true,
+            OpentelemetrySinkError::DecodeResponse { .. } => false,
+        }
+    }
+}
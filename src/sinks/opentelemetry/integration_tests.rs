@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use futures::stream;
+use vector_lib::event::{BatchNotifier, BatchStatus};
+
+use super::{OpentelemetryConfig, OpentelemetryProtocol};
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{Event, LogEvent, Metric, MetricKind, MetricValue, TraceEvent},
+    test_util::{
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+        retry_until,
+    },
+};
+
+fn otel_health_url() -> String {
+    std::env::var("OTEL_HEALTH_URL").unwrap_or_else(|_| "http://0.0.0.0:13133".to_owned())
+}
+
+fn otel_otlp_http_url() -> String {
+    std::env::var("OTEL_OTLPHTTP_URL").unwrap_or_else(|_| "http://0.0.0.0:9876".to_owned())
+}
+
+fn otel_otlp_grpc_url() -> String {
+    std::env::var("OTEL_OTLPGRPC_URL").unwrap_or_else(|_| "http://0.0.0.0:9877".to_owned())
+}
+
+async fn wait_ready(address: String) {
+    retry_until(
+        || async {
+            reqwest::get(address.clone())
+                .await
+                .map_err(|err| err.to_string())
+                .and_then(|res| {
+                    if res.status().is_success() {
+                        Ok(())
+                    } else {
+                        Err("Not ready yet...".into())
+                    }
+                })
+        },
+        Duration::from_secs(1),
+        Duration::from_secs(30),
+    )
+    .await;
+}
+
+fn test_events(batch: BatchNotifier) -> Vec<Event> {
+    let mut log = LogEvent::from("hello world");
+    log.insert("severity_text", "INFO");
+    log.insert("resources.service.name", "vector");
+
+    let metric = Metric::new(
+        "requests",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 1.0 },
+    );
+
+    let mut trace = TraceEvent::default();
+    trace.insert("name", "span");
+    trace.insert("trace_id", "0123456789abcdef0123456789abcdef");
+    trace.insert("span_id", "0123456789abcdef");
+
+    vec![Event::from(log), Event::from(metric), Event::from(trace)]
+        .into_iter()
+        .map(|event| event.with_batch_notifier(&batch))
+        .collect()
+}
+
+async fn deliver_all_signals(endpoint: String, protocol: OpentelemetryProtocol) {
+    wait_ready(otel_health_url()).await;
+
+    let config = OpentelemetryConfig {
+        endpoint,
+        protocol,
+        compression: true,
+        batch: Default::default(),
+        request: Default::default(),
+        auth: None,
+        tls: None,
+        acknowledgements: Default::default(),
+    };
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let events = test_events(batch);
+
+    run_and_assert_sink_compliance(sink, stream::iter(events), &HTTP_SINK_TAGS).await;
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+}
+
+#[tokio::test]
+async fn delivers_grpc() {
+    deliver_all_signals(otel_otlp_grpc_url(), OpentelemetryProtocol::Grpc).await;
+}
+
+#[tokio::test]
+async fn delivers_http() {
+    deliver_all_signals(otel_otlp_http_url(), OpentelemetryProtocol::Http).await;
+}
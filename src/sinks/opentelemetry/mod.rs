@@ -0,0 +1,37 @@
+//! The `opentelemetry` sink.
+//!
+//! Converts logs, metrics, and traces into their OTLP representation and exports them to an
+//! OpenTelemetry collector or any other OTLP receiver, over either gRPC or HTTP.
+
+use http::StatusCode;
+use snafu::Snafu;
+
+mod config;
+mod service;
+mod sink;
+
+#[cfg(all(test, feature = "opentelemetry-integration-tests"))]
+mod integration_tests;
+#[cfg(test)]
+mod tests;
+
+pub use config::{OpentelemetryConfig, OpentelemetryProtocol};
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub))]
+pub enum OpentelemetrySinkError {
+    #[snafu(display("gRPC request failed: {}", source))]
+    Grpc { source: tonic::Status },
+
+    #[snafu(display("HTTP request failed: {}", source))]
+    Http { source: crate::http::HttpError },
+
+    #[snafu(display("HTTP request failed with status {}: {}", status, body))]
+    HttpStatus { status: StatusCode, body: String },
+
+    #[snafu(display("Failed to read HTTP response body: {}", source))]
+    Body { source: hyper::Error },
+
+    #[snafu(display("Failed to decode OTLP response: {}", source))]
+    DecodeResponse { source: prost::DecodeError },
+}
@@ -0,0 +1,408 @@
+use std::{
+    io::Write,
+    task::{Context, Poll},
+};
+
+use futures::future::BoxFuture;
+use http::{header, HeaderName, HeaderValue, Uri};
+use hyper::client::HttpConnector;
+use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
+use indexmap::IndexMap;
+use prost::Message;
+use snafu::ResultExt;
+use tonic::{body::BoxBody, codec::CompressionEncoding, IntoRequest};
+use tower::Service;
+use vector_lib::opentelemetry::proto::collector::{
+    logs::v1::{
+        logs_service_client::LogsServiceClient, ExportLogsServiceRequest, ExportLogsServiceResponse,
+    },
+    metrics::v1::{
+        metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest,
+        ExportMetricsServiceResponse,
+    },
+    trace::v1::{
+        trace_service_client::TraceServiceClient, ExportTraceServiceRequest,
+        ExportTraceServiceResponse,
+    },
+};
+use vector_lib::request_metadata::{GroupedCountByteSize, MetaDescriptive, RequestMetadata};
+use vector_lib::stream::DriverResponse;
+
+use super::{
+    config::signal_uri, BodySnafu, DecodeResponseSnafu, GrpcSnafu, HttpSnafu,
+    OpentelemetrySinkError,
+};
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    http::{Auth, HttpClient},
+    internal_events::{EndpointBytesSent, OpentelemetryPartialSuccessError},
+    oauth2,
+    sinks::util::{uri, Compression, Compressor},
+};
+
+/// An OTLP export request for one of the three signal types.
+#[derive(Clone, Debug)]
+pub(super) enum OpentelemetryPayload {
+    Logs(ExportLogsServiceRequest),
+    Metrics(ExportMetricsServiceRequest),
+    Traces(ExportTraceServiceRequest),
+}
+
+impl OpentelemetryPayload {
+    pub(super) fn encoded_len(&self) -> usize {
+        match self {
+            Self::Logs(request) => request.encoded_len(),
+            Self::Metrics(request) => request.encoded_len(),
+            Self::Traces(request) => request.encoded_len(),
+        }
+    }
+
+    fn encode_to_vec(&self) -> Vec<u8> {
+        match self {
+            Self::Logs(request) => request.encode_to_vec(),
+            Self::Metrics(request) => request.encode_to_vec(),
+            Self::Traces(request) => request.encode_to_vec(),
+        }
+    }
+
+    const fn signal(&self) -> &'static str {
+        match self {
+            Self::Logs(_) => "logs",
+            Self::Metrics(_) => "metrics",
+            Self::Traces(_) => "traces",
+        }
+    }
+
+    const fn http_path(&self) -> &'static str {
+        match self {
+            Self::Logs(_) => "/v1/logs",
+            Self::Metrics(_) => "/v1/metrics",
+            Self::Traces(_) => "/v1/traces",
+        }
+    }
+
+    /// Decodes an HTTP response body for this kind of request and returns the number of rejected
+    /// items along with the error message, if the request was only partially successful.
+    fn decode_partial_success(
+        &self,
+        body: bytes::Bytes,
+    ) -> Result<Option<(i64, String)>, prost::DecodeError> {
+        Ok(match self {
+            Self::Logs(_) => ExportLogsServiceResponse::decode(body)?
+                .partial_success
+                .map(|partial| (partial.rejected_log_records, partial.error_message)),
+            Self::Metrics(_) => ExportMetricsServiceResponse::decode(body)?
+                .partial_success
+                .map(|partial| (partial.rejected_data_points, partial.error_message)),
+            Self::Traces(_) => ExportTraceServiceResponse::decode(body)?
+                .partial_success
+                .map(|partial| (partial.rejected_spans, partial.error_message)),
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct OpentelemetryRequest {
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+    pub(super) payload: OpentelemetryPayload,
+}
+
+impl Finalizable for OpentelemetryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for OpentelemetryRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub struct OpentelemetryResponse {
+    events_byte_size: GroupedCountByteSize,
+    raw_byte_size: usize,
+}
+
+impl DriverResponse for OpentelemetryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.raw_byte_size)
+    }
+}
+
+type HyperClient = hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>;
+
+/// gRPC clients for the three OTLP services, sharing a single HTTP/2 connection.
+#[derive(Clone, Debug)]
+pub(super) struct GrpcClients {
+    logs: LogsServiceClient<GrpcTransport>,
+    metrics: MetricsServiceClient<GrpcTransport>,
+    traces: TraceServiceClient<GrpcTransport>,
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct HttpTransport {
+    client: HttpClient,
+    endpoint: Uri,
+    headers: IndexMap<HeaderName, HeaderValue>,
+    auth: Option<Auth>,
+    compression: bool,
+}
+
+#[derive(Clone, Debug)]
+enum Transport {
+    Grpc(GrpcClients),
+    Http(HttpTransport),
+}
+
+#[derive(Clone, Debug)]
+pub struct OpentelemetryService {
+    transport: Transport,
+    protocol: String,
+    endpoint: String,
+}
+
+impl OpentelemetryService {
+    pub(super) fn grpc(
+        client: HyperClient,
+        endpoint: Uri,
+        headers: IndexMap<HeaderName, HeaderValue>,
+        compression: bool,
+    ) -> Self {
+        let (protocol, endpoint_tag) = uri::protocol_endpoint(endpoint.clone());
+        let transport = GrpcTransport {
+            uri: endpoint,
+            client,
+            headers,
+        };
+
+        let mut logs = LogsServiceClient::new(transport.clone());
+        let mut metrics = MetricsServiceClient::new(transport.clone());
+        let mut traces = TraceServiceClient::new(transport);
+        if compression {
+            logs = logs.send_compressed(CompressionEncoding::Gzip);
+            metrics = metrics.send_compressed(CompressionEncoding::Gzip);
+            traces = traces.send_compressed(CompressionEncoding::Gzip);
+        }
+
+        Self {
+            transport: Transport::Grpc(GrpcClients {
+                logs,
+                metrics,
+                traces,
+            }),
+            protocol,
+            endpoint: endpoint_tag,
+        }
+    }
+
+    pub(super) fn http(
+        client: HttpClient,
+        endpoint: Uri,
+        headers: IndexMap<HeaderName, HeaderValue>,
+        auth: Option<Auth>,
+        compression: bool,
+    ) -> Self {
+        let (protocol, endpoint_tag) = uri::protocol_endpoint(endpoint.clone());
+        Self {
+            transport: Transport::Http(HttpTransport {
+                client,
+                endpoint,
+                headers,
+                auth,
+                compression,
+            }),
+            protocol,
+            endpoint: endpoint_tag,
+        }
+    }
+}
+
+impl Service<OpentelemetryRequest> for OpentelemetryService {
+    type Response = OpentelemetryResponse;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness of the gRPC clients is checked by the `export()` calls happening inside
+        // `call()`, and the HTTP client is always ready.
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, mut request: OpentelemetryRequest) -> Self::Future {
+        let transport = self.transport.clone();
+        let protocol = self.protocol.clone();
+        let endpoint = self.endpoint.clone();
+        let raw_byte_size = request.payload.encoded_len();
+        let metadata = std::mem::take(request.metadata_mut());
+        let events_byte_size = metadata.into_events_estimated_json_encoded_byte_size();
+
+        Box::pin(async move {
+            let signal = request.payload.signal();
+            let partial_success = match transport {
+                Transport::Grpc(clients) => send_grpc(clients, request.payload).await?,
+                Transport::Http(http) => send_http(http, request.payload).await?,
+            };
+
+            if let Some((rejected, error_message)) = partial_success {
+                if rejected > 0 {
+                    emit!(OpentelemetryPartialSuccessError {
+                        rejected: rejected as u64,
+                        error_message: &error_message,
+                        signal,
+                    });
+                } else if !error_message.is_empty() {
+                    warn!(
+                        message = "OTLP endpoint accepted the request with a warning.",
+                        warning = %error_message,
+                        signal,
+                        internal_log_rate_limit = true,
+                    );
+                }
+            }
+
+            emit!(EndpointBytesSent {
+                byte_size: raw_byte_size,
+                protocol: &protocol,
+                endpoint: &endpoint,
+            });
+
+            Ok(OpentelemetryResponse {
+                events_byte_size,
+                raw_byte_size,
+            })
+        })
+    }
+}
+
+async fn send_grpc(
+    mut clients: GrpcClients,
+    payload: OpentelemetryPayload,
+) -> Result<Option<(i64, String)>, OpentelemetrySinkError> {
+    Ok(match payload {
+        OpentelemetryPayload::Logs(request) => clients
+            .logs
+            .export(request.into_request())
+            .await
+            .context(GrpcSnafu)?
+            .into_inner()
+            .partial_success
+            .map(|partial| (partial.rejected_log_records, partial.error_message)),
+        OpentelemetryPayload::Metrics(request) => clients
+            .metrics
+            .export(request.into_request())
+            .await
+            .context(GrpcSnafu)?
+            .into_inner()
+            .partial_success
+            .map(|partial| (partial.rejected_data_points, partial.error_message)),
+        OpentelemetryPayload::Traces(request) => clients
+            .traces
+            .export(request.into_request())
+            .await
+            .context(GrpcSnafu)?
+            .into_inner()
+            .partial_success
+            .map(|partial| (partial.rejected_spans, partial.error_message)),
+    })
+}
+
+async fn send_http(
+    http: HttpTransport,
+    payload: OpentelemetryPayload,
+) -> crate::Result<Option<(i64, String)>> {
+    let uri = signal_uri(&http.endpoint, payload.http_path())?;
+
+    let mut body = payload.encode_to_vec();
+    let mut builder =
+        http::Request::post(uri).header(header::CONTENT_TYPE, "application/x-protobuf");
+    if http.compression {
+        let compression = Compression::gzip_default();
+        let mut compressor = Compressor::from(compression);
+        compressor.write_all(&body)?;
+        body = compressor.finish()?.to_vec();
+        if let Some(content_encoding) = compression.content_encoding() {
+            builder = builder.header(header::CONTENT_ENCODING, content_encoding);
+        }
+    }
+    for (name, value) in &http.headers {
+        builder = builder.header(name, value);
+    }
+    let mut request = builder.body(bytes::Bytes::from(body))?;
+    if let Some(auth) = &http.auth {
+        auth.apply(&mut request);
+    }
+
+    let response = oauth2::send_authorized(request, |request| {
+        http.client.send(request.map(hyper::Body::from))
+    })
+    .await
+    .context(HttpSnafu)?;
+    let (parts, body) = response.into_parts();
+    let body = hyper::body::to_bytes(body).await.context(BodySnafu)?;
+
+    if !parts.status.is_success() {
+        return Err(OpentelemetrySinkError::HttpStatus {
+            status: parts.status,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        }
+        .into());
+    }
+
+    payload
+        .decode_partial_success(body)
+        .context(DecodeResponseSnafu)
+        .map_err(Into::into)
+}
+
+/// Routes requests issued by the tonic clients to the configured endpoint.
+#[derive(Clone, Debug)]
+pub(super) struct GrpcTransport {
+    uri: Uri,
+    client: HyperClient,
+    headers: IndexMap<HeaderName, HeaderValue>,
+}
+
+impl Service<hyper::Request<BoxBody>> for GrpcTransport {
+    type Response = hyper::Response<hyper::Body>;
+    type Error = hyper::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, mut req: hyper::Request<BoxBody>) -> Self::Future {
+        let uri = Uri::builder()
+            .scheme(self.uri.scheme().unwrap().clone())
+            .authority(self.uri.authority().unwrap().clone())
+            .path_and_query(req.uri().path_and_query().unwrap().clone())
+            .build()
+            .unwrap();
+
+        *req.uri_mut() = uri;
+        for (name, value) in &self.headers {
+            req.headers_mut().insert(name.clone(), value.clone());
+        }
+
+        Box::pin(self.client.request(req))
+    }
+}
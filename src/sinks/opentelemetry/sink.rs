@@ -0,0 +1,110 @@
+use std::{fmt, num::NonZeroUsize};
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_lib::opentelemetry::encode;
+use vector_lib::stream::{BatcherSettings, DriverResponse};
+use vector_lib::{partition::Partitioner, sink::StreamSink};
+
+use super::service::{OpentelemetryPayload, OpentelemetryRequest};
+use crate::{
+    event::{Event, Finalizable},
+    sinks::util::{metadata::RequestMetadataBuilder, SinkBuilderExt},
+};
+
+/// The OTLP signal an event is exported as.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+enum SignalType {
+    Logs,
+    Metrics,
+    Traces,
+}
+
+/// Partitions events by signal type, as each signal is exported with a separate request.
+#[derive(Default)]
+struct SignalPartitioner;
+
+impl Partitioner for SignalPartitioner {
+    type Item = Event;
+    type Key = SignalType;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        match item {
+            Event::Log(_) => SignalType::Logs,
+            Event::Metric(_) => SignalType::Metrics,
+            Event::Trace(_) => SignalType::Traces,
+        }
+    }
+}
+
+pub(super) struct OpentelemetrySink<S> {
+    pub batch_settings: BatcherSettings,
+    pub service: S,
+}
+
+impl<S> OpentelemetrySink<S>
+where
+    S: Service<OpentelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let batch_settings = self.batch_settings;
+
+        input
+            .batched_partitioned(SignalPartitioner, || batch_settings.as_byte_size_config())
+            .map(|(signal, mut events)| {
+                let finalizers = events.take_finalizers();
+                let builder = RequestMetadataBuilder::from_events(&events);
+                let payload = build_payload(signal, events);
+
+                let bytes_len = NonZeroUsize::new(payload.encoded_len())
+                    .expect("payload should never be zero length");
+
+                OpentelemetryRequest {
+                    finalizers,
+                    metadata: builder.with_request_size(bytes_len),
+                    payload,
+                }
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+fn build_payload(signal: SignalType, events: Vec<Event>) -> OpentelemetryPayload {
+    let events = events.into_iter();
+    match signal {
+        SignalType::Logs => OpentelemetryPayload::Logs(
+            events
+                .map(|event| encode::log_into_record(event.into_log()))
+                .collect(),
+        ),
+        SignalType::Metrics => OpentelemetryPayload::Metrics(
+            events
+                .map(|event| encode::metric_into_proto(event.into_metric()))
+                .collect(),
+        ),
+        SignalType::Traces => OpentelemetryPayload::Traces(
+            events
+                .map(|event| encode::trace_into_span(event.into_trace()))
+                .collect(),
+        ),
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for OpentelemetrySink<S>
+where
+    S: Service<OpentelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + fmt::Display + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
@@ -0,0 +1,366 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use futures::{channel::mpsc, StreamExt};
+use http::request::Parts;
+use hyper::Method;
+use prost::Message;
+use vector_lib::config::LogNamespace;
+use vector_lib::event::{BatchNotifier, BatchStatus};
+use vector_lib::opentelemetry::{
+    encode,
+    proto::{
+        collector::{
+            logs::v1::{
+                ExportLogsPartialSuccess, ExportLogsServiceRequest, ExportLogsServiceResponse,
+            },
+            metrics::v1::ExportMetricsServiceRequest,
+        },
+        common::v1::any_value::Value as PBValue,
+        metrics::v1::{metric::Data, AggregationTemporality},
+    },
+};
+
+use super::OpentelemetryConfig;
+use crate::{
+    config::{SinkConfig as _, SinkContext},
+    event::{Event, LogEvent, Metric, MetricKind, MetricTags, MetricValue},
+    sinks::util::test::build_test_server_generic,
+    test_util::{
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+        next_addr, random_lines_with_stream,
+    },
+};
+
+// one byte for the compression flag plus four bytes for the length
+const GRPC_HEADER_SIZE: usize = 5;
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<OpentelemetryConfig>();
+}
+
+#[test]
+fn legacy_log_into_record() {
+    let mut log = LogEvent::from("hello world");
+    log.insert("severity_text", "INFO");
+    log.insert("host", "localhost");
+
+    let (resource, record) = encode::log_into_record(log);
+
+    assert!(resource.is_none());
+    assert_eq!(record.severity_text, "INFO");
+    assert_eq!(
+        record.body.and_then(|body| body.value),
+        Some(PBValue::StringValue("hello world".to_owned()))
+    );
+    assert_eq!(record.attributes.len(), 1);
+    assert_eq!(record.attributes[0].key, "host");
+}
+
+#[test]
+fn logs_round_trip() {
+    let mut log = LogEvent::from("hello world");
+    log.insert("resources.service.name", "vector");
+    log.insert("attributes.key", "value");
+    log.insert("severity_text", "WARN");
+    log.insert("trace_id", "0123456789abcdef0123456789abcdef");
+
+    let request: ExportLogsServiceRequest = std::iter::once(encode::log_into_record(log)).collect();
+    assert_eq!(request.resource_logs.len(), 1);
+
+    let events = request
+        .resource_logs
+        .into_iter()
+        .flat_map(|resource_logs| resource_logs.into_event_iter(LogNamespace::Legacy))
+        .collect::<Vec<_>>();
+    assert_eq!(events.len(), 1);
+
+    let log = events[0].as_log();
+    assert_eq!(log["message"], "hello world".into());
+    assert_eq!(log["severity_text"], "WARN".into());
+    assert_eq!(log["resources.service.name"], "vector".into());
+    assert_eq!(log["attributes.key"], "value".into());
+    assert_eq!(log["trace_id"], "0123456789abcdef0123456789abcdef".into());
+}
+
+#[test]
+fn metrics_grouped_by_resource() {
+    let counter = Metric::new(
+        "requests",
+        MetricKind::Incremental,
+        MetricValue::Counter { value: 3.0 },
+    )
+    .with_namespace(Some("app"))
+    .with_tags(Some(MetricTags::from_iter([
+        ("resource.service.name".to_owned(), "vector".to_owned()),
+        ("path".to_owned(), "/".to_owned()),
+    ])));
+    let gauge = Metric::new(
+        "temperature",
+        MetricKind::Absolute,
+        MetricValue::Gauge { value: 21.5 },
+    );
+
+    let request: ExportMetricsServiceRequest = [counter, gauge]
+        .into_iter()
+        .map(encode::metric_into_proto)
+        .collect();
+    assert_eq!(request.resource_metrics.len(), 2);
+
+    let with_resource = request
+        .resource_metrics
+        .iter()
+        .find(|resource_metrics| resource_metrics.resource.is_some())
+        .unwrap();
+    assert_eq!(
+        with_resource.resource.as_ref().unwrap().attributes[0].key,
+        "service.name"
+    );
+
+    let metric = &with_resource.scope_metrics[0].metrics[0];
+    assert_eq!(metric.name, "app.requests");
+    match metric.data.as_ref().unwrap() {
+        Data::Sum(sum) => {
+            assert!(sum.is_monotonic);
+            assert_eq!(
+                sum.aggregation_temporality,
+                AggregationTemporality::Delta as i32
+            );
+            assert_eq!(sum.data_points[0].attributes.len(), 1);
+            assert_eq!(sum.data_points[0].attributes[0].key, "path");
+        }
+        data => panic!("unexpected metric data: {data:?}"),
+    }
+}
+
+#[tokio::test]
+async fn deliver_logs_http() {
+    let num_lines = 10;
+    let in_addr = next_addr();
+
+    let config = format!(
+        r#"
+        endpoint = "http://{in_addr}"
+        protocol = "http"
+        "#
+    );
+    let config: OpentelemetryConfig = toml::from_str(&config).unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let (rx, trigger, server) = build_test_server_generic(in_addr, move || {
+        hyper::Response::builder()
+            .header("content-type", "application/x-protobuf")
+            .body(hyper::Body::from(
+                ExportLogsServiceResponse::default().encode_to_vec(),
+            ))
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let (input_lines, events) = random_lines_with_stream(8, num_lines, Some(batch));
+
+    run_and_assert_sink_compliance(sink, events, &HTTP_SINK_TAGS).await;
+    drop(trigger);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let output_lines = get_received_logs(rx, 0, |parts| {
+        assert_eq!(Method::POST, parts.method);
+        assert_eq!("/v1/logs", parts.uri.path());
+        assert_eq!(
+            "application/x-protobuf",
+            parts.headers.get("content-type").unwrap().to_str().unwrap()
+        );
+    })
+    .await;
+
+    assert_eq!(input_lines, output_lines);
+}
+
+#[tokio::test]
+async fn deliver_logs_grpc() {
+    let num_lines = 10;
+    let in_addr = next_addr();
+
+    let config = format!(r#"endpoint = "http://{in_addr}""#);
+    let config: OpentelemetryConfig = toml::from_str(&config).unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let (rx, trigger, server) = build_test_server_generic(in_addr, move || {
+        hyper::Response::builder()
+            .header("grpc-status", "0") // OK
+            .header("content-type", "application/grpc")
+            .body(hyper::Body::from(encode_body(
+                ExportLogsServiceResponse::default(),
+            )))
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let (input_lines, events) = random_lines_with_stream(8, num_lines, Some(batch));
+
+    run_and_assert_sink_compliance(sink, events, &HTTP_SINK_TAGS).await;
+    drop(trigger);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let output_lines = get_received_logs(rx, GRPC_HEADER_SIZE, |parts| {
+        assert_eq!(Method::POST, parts.method);
+        assert_eq!(
+            "/opentelemetry.proto.collector.logs.v1.LogsService/Export",
+            parts.uri.path()
+        );
+    })
+    .await;
+
+    assert_eq!(input_lines, output_lines);
+}
+
+#[tokio::test]
+async fn partial_success_is_delivered() {
+    let in_addr = next_addr();
+
+    let config = format!(
+        r#"
+        endpoint = "http://{in_addr}"
+        protocol = "http"
+        "#
+    );
+    let config: OpentelemetryConfig = toml::from_str(&config).unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let (_rx, trigger, server) = build_test_server_generic(in_addr, move || {
+        let response = ExportLogsServiceResponse {
+            partial_success: Some(ExportLogsPartialSuccess {
+                rejected_log_records: 1,
+                error_message: "invalid record".to_owned(),
+            }),
+        };
+        hyper::Response::builder()
+            .body(hyper::Body::from(response.encode_to_vec()))
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let (_, events) = random_lines_with_stream(8, 2, Some(batch));
+
+    sink.run(events).await.expect("Running sink failed");
+    drop(trigger);
+
+    // Partially rejected requests must not be retried, as the receiver already accepted the
+    // remaining items.
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+}
+
+#[tokio::test]
+async fn acknowledges_error() {
+    let in_addr = next_addr();
+
+    let config = format!(r#"endpoint = "http://{in_addr}""#);
+    let config: OpentelemetryConfig = toml::from_str(&config).unwrap();
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let (_rx, trigger, server) = build_test_server_generic(in_addr, move || {
+        hyper::Response::builder()
+            .header("grpc-status", "7") // permission denied
+            .header("content-type", "application/grpc")
+            .body(tonic::body::empty_body())
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let (_, events) = random_lines_with_stream(8, 10, Some(batch));
+
+    sink.run(events).await.expect("Running sink failed");
+    drop(trigger);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+}
+
+#[tokio::test]
+async fn rejects_oauth2_with_grpc() {
+    let config: OpentelemetryConfig = toml::from_str(
+        r#"
+        endpoint = "http://localhost:4317"
+
+        [auth]
+        strategy = "oauth2"
+        token_endpoint = "http://localhost:8080/token"
+        client_id = "vector"
+        client_secret = "secret"
+        "#,
+    )
+    .unwrap();
+
+    let error = config.build(SinkContext::default()).await.err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "OAuth2 authentication is not supported with the `grpc` protocol."
+    );
+}
+
+async fn get_received_logs(
+    rx: mpsc::Receiver<(Parts, Bytes)>,
+    header_size: usize,
+    assert_parts: impl Fn(Parts),
+) -> Vec<String> {
+    rx.map(|(parts, body)| {
+        assert_parts(parts);
+
+        let request = ExportLogsServiceRequest::decode(body.slice(header_size..)).unwrap();
+        request
+            .resource_logs
+            .into_iter()
+            .flat_map(|resource_logs| resource_logs.into_event_iter(LogNamespace::Legacy))
+            .map(|event: Event| {
+                event
+                    .as_log()
+                    .get("message")
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect::<Vec<_>>()
+    })
+    .collect::<Vec<_>>()
+    .await
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+// taken from <https://github.com/hyperium/tonic/blob/5aa8ae1fec27377cd4c2a41d309945d7e38087d0/examples/src/grpc-web/client.rs#L45-L75>
+fn encode_body<T>(msg: T) -> Bytes
+where
+    T: prost::Message,
+{
+    let mut buf = BytesMut::with_capacity(1024);
+
+    // first skip past the header
+    // cannot write it yet since we don't know the size of the
+    // encoded message
+    buf.reserve(GRPC_HEADER_SIZE);
+    unsafe {
+        buf.advance_mut(GRPC_HEADER_SIZE);
+    }
+
+    // write the message
+    msg.encode(&mut buf).unwrap();
+
+    // now we know the size of encoded message and can write the
+    // header
+    let len = buf.len() - GRPC_HEADER_SIZE;
+    {
+        let mut buf = &mut buf[..GRPC_HEADER_SIZE];
+
+        // compression flag, 0 means "no compression"
+        buf.put_u8(0);
+
+        buf.put_u32(len as u32);
+    }
+
+    buf.split_to(len + GRPC_HEADER_SIZE).freeze()
+}
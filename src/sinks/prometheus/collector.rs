@@ -2,8 +2,11 @@ use std::{collections::BTreeMap, fmt::Write as _};
 
 use chrono::Utc;
 use indexmap::map::IndexMap;
-use vector_lib::event::metric::{samples_to_buckets, MetricSketch, MetricTags, Quantile};
-use vector_lib::prometheus::parser::{proto, METRIC_NAME_LABEL};
+use vector_lib::event::metric::{
+    samples_to_buckets, Bucket, ExponentialBuckets, ExponentialHistogram, MetricSketch, MetricTags,
+    Quantile,
+};
+use vector_lib::prometheus::parser::{proto, METRIC_NAME_LABEL, NATIVE_HISTOGRAM_SCHEMAS};
 
 use crate::{
     event::metric::{Metric, MetricKind, MetricValue, StatisticKind},
@@ -29,6 +32,75 @@ pub(super) trait MetricCollector {
 
     fn finish(self) -> Self::Output;
 
+    fn emit_buckets(
+        &mut self,
+        timestamp: Option<i64>,
+        name: &str,
+        buckets: &[Bucket],
+        count: u64,
+        sum: f64,
+        tags: Option<&MetricTags>,
+    ) {
+        let mut bucket_count = 0.0;
+        for bucket in buckets {
+            // Aggregated histograms are cumulative in Prometheus.  This means that the
+            // count of values in a bucket should only go up at the upper limit goes up,
+            // because if you count a value in a specific bucket, by definition, it is
+            // less than the upper limit of the next bucket.
+            //
+            // While most sources should give us buckets that have an "infinity" bucket
+            // -- everything else that didn't fit in the non-infinity-upper-limit buckets
+            // -- we can't be sure, so we calculate that bucket ourselves.  This is why
+            // we make sure to avoid encoding a bucket if its upper limit is already
+            // infinity, so that we don't double report.
+            //
+            // This check will also avoid printing out a bucket whose upper limit is
+            // negative infinity, because that would make no sense.
+            if bucket.upper_limit.is_infinite() {
+                continue;
+            }
+
+            bucket_count += bucket.count as f64;
+            self.emit_value(
+                timestamp,
+                name,
+                "_bucket",
+                bucket_count,
+                tags,
+                Some(("le", bucket.upper_limit.to_string())),
+            );
+        }
+        self.emit_value(
+            timestamp,
+            name,
+            "_bucket",
+            count as f64,
+            tags,
+            Some(("le", "+Inf".to_string())),
+        );
+        self.emit_value(timestamp, name, "_sum", sum, tags, None);
+        self.emit_value(timestamp, name, "_count", count as f64, tags, None);
+    }
+
+    /// Emits an exponential histogram, which is converted into a classic histogram unless the
+    /// output format supports native histograms.
+    fn emit_exponential_histogram(
+        &mut self,
+        timestamp: Option<i64>,
+        name: &str,
+        histogram: &ExponentialHistogram,
+        tags: Option<&MetricTags>,
+    ) {
+        self.emit_buckets(
+            timestamp,
+            name,
+            &histogram.to_buckets(),
+            histogram.count,
+            histogram.sum,
+            tags,
+        );
+    }
+
     fn encode_metric(
         &mut self,
         default_namespace: Option<&str>,
@@ -121,45 +193,10 @@ pub(super) trait MetricCollector {
                     count,
                     sum,
                 } => {
-                    let mut bucket_count = 0.0;
-                    for bucket in buckets {
-                        // Aggregated histograms are cumulative in Prometheus.  This means that the
-                        // count of values in a bucket should only go up at the upper limit goes up,
-                        // because if you count a value in a specific bucket, by definition, it is
-                        // less than the upper limit of the next bucket.
-                        //
-                        // While most sources should give us buckets that have an "infinity" bucket
-                        // -- everything else that didn't fit in the non-infinity-upper-limit buckets
-                        // -- we can't be sure, so we calculate that bucket ourselves.  This is why
-                        // we make sure to avoid encoding a bucket if its upper limit is already
-                        // infinity, so that we don't double report.
-                        //
-                        // This check will also avoid printing out a bucket whose upper limit is
-                        // negative infinity, because that would make no sense.
-                        if bucket.upper_limit.is_infinite() {
-                            continue;
-                        }
-
-                        bucket_count += bucket.count as f64;
-                        self.emit_value(
-                            timestamp,
-                            name,
-                            "_bucket",
-                            bucket_count,
-                            tags,
-                            Some(("le", bucket.upper_limit.to_string())),
-                        );
-                    }
-                    self.emit_value(
-                        timestamp,
-                        name,
-                        "_bucket",
-                        *count as f64,
-                        tags,
-                        Some(("le", "+Inf".to_string())),
-                    );
-                    self.emit_value(timestamp, name, "_sum", *sum, tags, None);
-                    self.emit_value(timestamp, name, "_count", *count as f64, tags, None);
+                    self.emit_buckets(timestamp, name, buckets, *count, *sum, tags);
+                }
+                MetricValue::ExponentialHistogram { histogram } => {
+                    self.emit_exponential_histogram(timestamp, name, histogram, tags);
                 }
                 MetricValue::AggregatedSummary {
                     quantiles,
@@ -318,6 +355,7 @@ type Labels = Vec<proto::Label>;
 
 pub(super) struct TimeSeries {
     buffer: IndexMap<Labels, Vec<proto::Sample>>,
+    histograms: IndexMap<Labels, Vec<proto::Histogram>>,
     metadata: IndexMap<String, proto::MetricMetadata>,
     timestamp: Option<i64>,
 }
@@ -362,6 +400,7 @@ impl MetricCollector for TimeSeries {
     fn new() -> Self {
         Self {
             buffer: Default::default(),
+            histograms: Default::default(),
             metadata: Default::default(),
             timestamp: None,
         }
@@ -396,11 +435,72 @@ impl MetricCollector for TimeSeries {
             .push(proto::Sample { value, timestamp });
     }
 
+    fn emit_exponential_histogram(
+        &mut self,
+        timestamp_millis: Option<i64>,
+        name: &str,
+        histogram: &ExponentialHistogram,
+        tags: Option<&MetricTags>,
+    ) {
+        let mut histogram = histogram.clone();
+        let max_scale = *NATIVE_HISTOGRAM_SCHEMAS.end();
+        if histogram.scale > max_scale {
+            histogram.downscale(histogram.scale.abs_diff(max_scale));
+        }
+        if !NATIVE_HISTOGRAM_SCHEMAS.contains(&histogram.scale) {
+            let buckets = histogram.to_buckets();
+            self.emit_buckets(
+                timestamp_millis,
+                name,
+                &buckets,
+                histogram.count,
+                histogram.sum,
+                tags,
+            );
+            return;
+        }
+
+        let (negative_spans, negative_deltas) = native_histogram_buckets(&histogram.negative);
+        let (positive_spans, positive_deltas) = native_histogram_buckets(&histogram.positive);
+        let timestamp = timestamp_millis.unwrap_or_else(|| self.default_timestamp());
+        self.histograms
+            .entry(Self::make_labels(tags, name, "", None))
+            .or_default()
+            .push(proto::Histogram {
+                count: Some(proto::histogram::Count::CountInt(histogram.count)),
+                sum: histogram.sum,
+                schema: histogram.scale,
+                zero_threshold: histogram.zero_threshold,
+                zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(
+                    histogram.zero_count,
+                )),
+                negative_spans,
+                negative_deltas,
+                positive_spans,
+                positive_deltas,
+                timestamp,
+                ..Default::default()
+            });
+    }
+
     fn finish(self) -> proto::WriteRequest {
         let timeseries = self
             .buffer
             .into_iter()
-            .map(|(labels, samples)| proto::TimeSeries { labels, samples })
+            .map(|(labels, samples)| proto::TimeSeries {
+                labels,
+                samples,
+                histograms: vec![],
+            })
+            .chain(
+                self.histograms
+                    .into_iter()
+                    .map(|(labels, histograms)| proto::TimeSeries {
+                        labels,
+                        samples: vec![],
+                        histograms,
+                    }),
+            )
             .collect::<Vec<_>>();
         let metadata = self
             .metadata
@@ -414,6 +514,32 @@ impl MetricCollector for TimeSeries {
     }
 }
 
+/// Converts exponential buckets into the spans and count deltas of a native histogram.
+///
+/// Native histogram buckets are indexed by their upper limit rather than their lower limit, so
+/// the indexes are shifted by one.
+fn native_histogram_buckets(buckets: &ExponentialBuckets) -> (Vec<proto::BucketSpan>, Vec<i64>) {
+    if buckets.counts.is_empty() {
+        return (vec![], vec![]);
+    }
+    let span = proto::BucketSpan {
+        offset: buckets.offset + 1,
+        length: buckets.counts.len() as u32,
+    };
+    let mut previous = 0;
+    let deltas = buckets
+        .counts
+        .iter()
+        .map(|count| {
+            let count = i64::try_from(*count).unwrap_or(i64::MAX);
+            let delta = count - previous;
+            previous = count;
+            delta
+        })
+        .collect();
+    (vec![span], deltas)
+}
+
 const fn prometheus_metric_type(metric_value: &MetricValue) -> proto::MetricType {
     use proto::MetricType;
     match metric_value {
@@ -427,7 +553,9 @@ const fn prometheus_metric_type(metric_value: &MetricValue) -> proto::MetricType
             statistic: StatisticKind::Summary,
             ..
         } => MetricType::Summary,
-        MetricValue::AggregatedHistogram { .. } => MetricType::Histogram,
+        MetricValue::AggregatedHistogram { .. } | MetricValue::ExponentialHistogram { .. } => {
+            MetricType::Histogram
+        }
         MetricValue::AggregatedSummary { .. } => MetricType::Summary,
         MetricValue::Sketch { .. } => MetricType::Summary,
     }
@@ -490,6 +618,7 @@ mod tests {
                                 value: $svalue,
                                 timestamp: $timestamp,
                             }],
+                            histograms: vec![],
                         },
                     )*
                 ],
@@ -767,6 +896,82 @@ mod tests {
         encode_one::<T>(Some("vector"), &[], &[], &metric)
     }
 
+    #[test]
+    fn encodes_exponential_histogram_text() {
+        assert_eq!(
+            encode_exponential_histogram::<StringCollector>(),
+            indoc! {r#"
+                # HELP vector_latency latency
+                # TYPE vector_latency histogram
+                vector_latency_bucket{le="0"} 1 1612325106789
+                vector_latency_bucket{le="2"} 3 1612325106789
+                vector_latency_bucket{le="4"} 6 1612325106789
+                vector_latency_bucket{le="+Inf"} 6 1612325106789
+                vector_latency_sum 10.5 1612325106789
+                vector_latency_count 6 1612325106789
+            "#}
+        );
+    }
+
+    #[test]
+    fn encodes_exponential_histogram_request() {
+        assert_eq!(
+            encode_exponential_histogram::<TimeSeries>(),
+            proto::WriteRequest {
+                timeseries: vec![proto::TimeSeries {
+                    labels: vec![proto::Label {
+                        name: "__name__".into(),
+                        value: "vector_latency".into(),
+                    }],
+                    samples: vec![],
+                    histograms: vec![proto::Histogram {
+                        count: Some(proto::histogram::Count::CountInt(6)),
+                        sum: 10.5,
+                        schema: 0,
+                        zero_threshold: 0.0,
+                        zero_count: Some(proto::histogram::ZeroCount::ZeroCountInt(1)),
+                        positive_spans: vec![proto::BucketSpan {
+                            offset: 1,
+                            length: 2,
+                        }],
+                        positive_deltas: vec![2, 1],
+                        timestamp: 1612325106789,
+                        ..Default::default()
+                    }],
+                }],
+                metadata: vec![proto::MetricMetadata {
+                    r#type: proto::metric_metadata::MetricType::Histogram as i32,
+                    metric_family_name: "vector_latency".into(),
+                    help: "latency".into(),
+                    unit: "".into(),
+                }],
+            }
+        );
+    }
+
+    fn encode_exponential_histogram<T: MetricCollector>() -> T::Output {
+        let metric = Metric::new(
+            "latency".to_owned(),
+            MetricKind::Absolute,
+            MetricValue::ExponentialHistogram {
+                histogram: ExponentialHistogram {
+                    scale: 0,
+                    zero_count: 1,
+                    zero_threshold: 0.0,
+                    positive: ExponentialBuckets {
+                        offset: 0,
+                        counts: vec![2, 3],
+                    },
+                    negative: ExponentialBuckets::default(),
+                    count: 6,
+                    sum: 10.5,
+                },
+            },
+        )
+        .with_timestamp(Some(timestamp()));
+        encode_one::<T>(Some("vector"), &[], &[], &metric)
+    }
+
     #[test]
     fn encodes_summary_text() {
         assert_eq!(
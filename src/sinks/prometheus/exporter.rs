@@ -56,6 +56,8 @@ const LOCK_FAILED: &str = "Prometheus exporter data lock is poisoned";
 enum BuildError {
     #[snafu(display("Flush period for sets must be greater or equal to {} secs", min))]
     FlushPeriodTooShort { min: u64 },
+    #[snafu(display("OAuth2 authentication can't be used to authenticate scrapes"))]
+    OAuth2NotSupported,
 }
 
 /// Configuration for the `prometheus_exporter` sink.
@@ -200,6 +202,10 @@ impl SinkConfig for PrometheusExporterConfig {
 
         validate_quantiles(&self.quantiles)?;
 
+        if matches!(self.auth, Some(Auth::OAuth2(_))) {
+            return Err(Box::new(BuildError::OAuth2NotSupported));
+        }
+
         let sink = PrometheusExporter::new(self.clone());
         let healthcheck = future::ok(()).boxed();
 
@@ -323,6 +329,8 @@ fn authorized<T: HttpBody>(req: &Request<T>, auth: &Option<Auth>) -> bool {
                 Auth::Bearer { token } => {
                     HeaderValue::from_str(format!("Bearer {}", token.inner()).as_str())
                 }
+                // Rejected when building the sink.
+                Auth::OAuth2(_) => return false,
             };
 
             if let Ok(encoded_credentials) = encoded_credentials {
@@ -1,3 +1,5 @@
+use std::{collections::HashMap, num::NonZeroUsize};
+
 use redis::{aio::ConnectionManager, RedisResult};
 use snafu::prelude::*;
 
@@ -30,6 +32,11 @@ pub enum DataTypeConfig {
     ///
     /// Redis channels function in a pub/sub fashion, allowing many-to-many broadcasting and receiving.
     Channel,
+
+    /// The Redis `stream` type.
+    ///
+    /// Messages are appended to the stream as entries, which can be read by consumer groups.
+    Stream,
 }
 
 /// List-specific options.
@@ -61,6 +68,51 @@ pub enum Method {
     LPush,
 }
 
+/// Stream-specific options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The field of the stream entries that the encoded messages are stored in.
+    #[serde(default = "default_stream_field")]
+    #[derivative(Default(value = "default_stream_field()"))]
+    #[configurable(metadata(docs::examples = "message"))]
+    pub(super) field: String,
+
+    /// Additional fields of the stream entries, rendered from each event.
+    #[serde(default)]
+    #[configurable(metadata(docs::additional_props_description = "A stream entry field."))]
+    #[configurable(metadata(docs::examples = "stream_fields_examples()"))]
+    pub(super) fields: HashMap<String, Template>,
+
+    /// The maximum length of the stream.
+    ///
+    /// When set, the oldest entries are trimmed as new ones are added, with the `MAXLEN` option
+    /// of `XADD`.
+    #[configurable(metadata(docs::examples = 100000))]
+    pub(super) max_len: Option<NonZeroUsize>,
+
+    /// Whether the stream is trimmed approximately.
+    ///
+    /// Approximate trimming is much more efficient, and may keep a few more entries than
+    /// `max_len`.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    pub(super) approximate_trimming: bool,
+}
+
+fn default_stream_field() -> String {
+    "message".to_owned()
+}
+
+fn stream_fields_examples() -> HashMap<String, String> {
+    HashMap::from([
+        ("host".to_owned(), "{{ host }}".to_owned()),
+        ("app".to_owned(), "{{ app }}".to_owned()),
+    ])
+}
+
 #[derive(Clone, Copy, Debug, Default)]
 pub struct RedisDefaultBatchSettings;
 
@@ -86,6 +138,10 @@ pub struct RedisSinkConfig {
     #[serde(alias = "list")]
     pub(super) list_option: Option<ListOption>,
 
+    #[configurable(derived)]
+    #[serde(alias = "stream")]
+    pub(super) stream_option: Option<StreamOption>,
+
     /// The URL of the Redis endpoint to connect to.
     ///
     /// The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
@@ -1,13 +1,15 @@
+use std::{collections::HashMap, num::NonZeroUsize};
+
 use futures::stream;
 use rand::Rng;
-use redis::AsyncCommands;
+use redis::{streams::StreamRangeReply, AsyncCommands};
 use vector_lib::codecs::JsonSerializerConfig;
 use vector_lib::{
     config::{init_telemetry, Tags, Telemetry},
     event::LogEvent,
 };
 
-use super::config::{DataTypeConfig, ListOption, Method, RedisSinkConfig};
+use super::config::{DataTypeConfig, ListOption, Method, RedisSinkConfig, StreamOption};
 use crate::{
     sinks::prelude::*,
     test_util::{
@@ -42,6 +44,7 @@ async fn redis_sink_list_lpush() {
         list_option: Some(ListOption {
             method: Method::LPush,
         }),
+        stream_option: None,
         batch: BatchConfig::default(),
         request: TowerRequestConfig {
             rate_limit_num: u64::MAX,
@@ -106,6 +109,7 @@ async fn redis_sink_list_rpush() {
         list_option: Some(ListOption {
             method: Method::RPush,
         }),
+        stream_option: None,
         batch: BatchConfig::default(),
         request: TowerRequestConfig {
             rate_limit_num: u64::MAX,
@@ -184,6 +188,7 @@ async fn redis_sink_channel() {
         encoding: JsonSerializerConfig::default().into(),
         data_type: DataTypeConfig::Channel,
         list_option: None,
+        stream_option: None,
         batch: BatchConfig::default(),
         request: TowerRequestConfig {
             rate_limit_num: u64::MAX,
@@ -260,6 +265,7 @@ async fn redis_sink_channel_data_volume_tags() {
         encoding: JsonSerializerConfig::default().into(),
         data_type: DataTypeConfig::Channel,
         list_option: None,
+        stream_option: None,
         batch: BatchConfig::default(),
         request: TowerRequestConfig {
             rate_limit_num: u64::MAX,
@@ -290,3 +296,62 @@ async fn redis_sink_channel_data_volume_tags() {
         }
     }
 }
+
+#[tokio::test]
+async fn redis_sink_stream() {
+    trace_init();
+
+    let key = format!("test-{}", random_string(10));
+    debug!("Test key name: {}.", key);
+    let num_events = 100;
+
+    let cnf = RedisSinkConfig {
+        endpoint: redis_server(),
+        key: Template::try_from(key.as_str()).unwrap(),
+        encoding: JsonSerializerConfig::default().into(),
+        data_type: DataTypeConfig::Stream,
+        list_option: None,
+        stream_option: Some(StreamOption {
+            fields: HashMap::from([("app".to_owned(), Template::try_from("{{ app }}").unwrap())]),
+            max_len: NonZeroUsize::new(10),
+            approximate_trimming: false,
+            ..Default::default()
+        }),
+        batch: BatchConfig::default(),
+        request: TowerRequestConfig {
+            rate_limit_num: u64::MAX,
+            ..Default::default()
+        },
+        acknowledgements: Default::default(),
+    };
+
+    let events = (0..num_events)
+        .map(|i| {
+            let mut log = LogEvent::from(i.to_string());
+            log.insert("app", format!("app-{i}"));
+            Event::from(log)
+        })
+        .collect::<Vec<_>>();
+    let input = stream::iter(events.clone().into_iter().map(Into::into));
+
+    let cnf2 = cnf.clone();
+    assert_sink_compliance(&SINK_TAGS, async move {
+        let cx = SinkContext::default();
+        let (sink, _healthcheck) = cnf2.build(cx).await.unwrap();
+        sink.run(input).await
+    })
+    .await
+    .expect("Running sink failed");
+
+    let mut conn = cnf.build_client().await.unwrap();
+
+    // The stream is trimmed to its last entries.
+    let reply: StreamRangeReply = conn.xrange_all(&key).await.unwrap();
+    assert_eq!(reply.ids.len(), 10);
+    for (entry, event) in reply.ids.iter().zip(&events[num_events - 10..]) {
+        let message: String = entry.get("message").unwrap();
+        assert_eq!(message, serde_json::to_string(event.as_log()).unwrap());
+        let app: String = entry.get("app").unwrap();
+        assert_eq!(app, event.as_log()["app"].to_string_lossy());
+    }
+}
@@ -14,7 +14,7 @@ mod tests;
 mod integration_tests;
 
 use bytes::Bytes;
-use redis::RedisError;
+use redis::{streams::StreamMaxlen, RedisError};
 use snafu::Snafu;
 
 use crate::sinks::prelude::*;
@@ -31,7 +31,7 @@ pub(super) enum RedisSinkError {
     SendError { source: RedisError },
 }
 
-#[derive(Clone, Copy, Debug, Derivative)]
+#[derive(Clone, Debug, Derivative)]
 #[derivative(Default)]
 pub enum DataType {
     /// The Redis `list` type.
@@ -44,12 +44,22 @@ pub enum DataType {
     ///
     /// Redis channels function in a pub/sub fashion, allowing many-to-many broadcasting and receiving.
     Channel,
+
+    /// The Redis `stream` type.
+    ///
+    /// Messages are stored in the given field of the entries, and the stream is trimmed to the
+    /// maximum length if any.
+    Stream {
+        field: String,
+        max_len: Option<StreamMaxlen>,
+    },
 }
 
-/// Wrapper for an `Event` that also stored the rendered key.
+/// Wrapper for an `Event` that also stored the rendered key and stream entry fields.
 pub(super) struct RedisEvent {
     event: Event,
     key: String,
+    fields: Vec<(String, String)>,
 }
 
 impl Finalizable for RedisEvent {
@@ -103,6 +113,7 @@ impl MetaDescriptive for RedisRequest {
 pub(super) struct RedisKvEntry {
     key: String,
     value: Bytes,
+    fields: Vec<(String, String)>,
 }
 
 impl EncodedLength for RedisKvEntry {
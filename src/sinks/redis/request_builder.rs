@@ -23,7 +23,11 @@ pub(super) fn encode_event(
 
     let value = bytes.freeze();
 
-    let event = RedisKvEntry { key, value };
+    let event = RedisKvEntry {
+        key,
+        value,
+        fields: Vec::new(),
+    };
     Some(event)
 }
 
@@ -36,7 +40,12 @@ fn encode_events(
     let request = events
         .into_iter()
         .filter_map(|event| {
-            encode_event(event.event, event.key, transformer, encoder, &mut byte_size)
+            encode_event(event.event, event.key, transformer, encoder, &mut byte_size).map(
+                |entry| RedisKvEntry {
+                    fields: event.fields,
+                    ..entry
+                },
+            )
         })
         .collect::<Vec<_>>();
 
@@ -30,8 +30,8 @@ impl Service<RedisRequest> for RedisService {
         let mut pipe = redis::pipe();
 
         for kv in kvs.request {
-            match self.data_type {
-                super::DataType::List(method) => match method {
+            match &self.data_type {
+                super::DataType::List(method) => match *method {
                     Method::LPush => {
                         if count > 1 {
                             pipe.atomic().lpush(kv.key, kv.value.as_ref());
@@ -54,13 +54,38 @@ impl Service<RedisRequest> for RedisService {
                         pipe.publish(kv.key, kv.value.as_ref());
                     }
                 }
+                super::DataType::Stream { field, max_len } => {
+                    if count > 1 {
+                        pipe.atomic();
+                    }
+                    let items = std::iter::once((field.as_str(), kv.value.as_ref()))
+                        .chain(
+                            kv.fields
+                                .iter()
+                                .map(|(name, value)| (name.as_str(), value.as_bytes())),
+                        )
+                        .collect::<Vec<_>>();
+                    match max_len {
+                        Some(max_len) => pipe.xadd_maxlen(kv.key, *max_len, "*", &items),
+                        None => pipe.xadd(kv.key, "*", &items),
+                    };
+                }
             }
         }
 
         let byte_size = kvs.metadata.events_byte_size();
+        let is_stream = matches!(self.data_type, super::DataType::Stream { .. });
 
         Box::pin(async move {
-            match pipe.query_async(&mut conn).await {
+            let result = if is_stream {
+                // Adding an entry to a stream replies with the ID of the entry.
+                pipe.query_async::<_, Vec<String>>(&mut conn)
+                    .await
+                    .map(|ids| vec![true; ids.len()])
+            } else {
+                pipe.query_async(&mut conn).await
+            };
+            match result {
                 Ok(event_status) => Ok(RedisResponse {
                     event_status,
                     events_byte_size: kvs.metadata.into_events_estimated_json_encoded_byte_size(),
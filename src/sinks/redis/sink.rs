@@ -1,6 +1,6 @@
 use std::future;
 
-use redis::{aio::ConnectionManager, RedisError};
+use redis::{aio::ConnectionManager, streams::StreamMaxlen, RedisError};
 
 use crate::sinks::{prelude::*, util::retries::RetryAction};
 
@@ -18,15 +18,31 @@ pub(super) struct RedisSink {
     conn: ConnectionManager,
     data_type: super::DataType,
     key: Template,
+    fields: Vec<(String, Template)>,
     batcher_settings: BatcherSettings,
 }
 
 impl RedisSink {
     pub(super) fn new(config: &RedisSinkConfig, conn: ConnectionManager) -> crate::Result<Self> {
         let method = config.list_option.map(|option| option.method);
+        let stream = config.stream_option.clone().unwrap_or_default();
         let data_type = match config.data_type {
             DataTypeConfig::Channel => super::DataType::Channel,
             DataTypeConfig::List => super::DataType::List(method.unwrap_or_default()),
+            DataTypeConfig::Stream => super::DataType::Stream {
+                field: stream.field,
+                max_len: stream.max_len.map(|max_len| {
+                    if stream.approximate_trimming {
+                        StreamMaxlen::Approx(max_len.get())
+                    } else {
+                        StreamMaxlen::Equals(max_len.get())
+                    }
+                }),
+            },
+        };
+        let fields = match config.data_type {
+            DataTypeConfig::Stream => stream.fields.into_iter().collect(),
+            _ => Vec::new(),
         };
 
         let batcher_settings = config.batch.validate()?.into_batcher_settings()?;
@@ -44,6 +60,7 @@ impl RedisSink {
             conn,
             data_type,
             key,
+            fields,
         })
     }
 
@@ -63,7 +80,25 @@ impl RedisSink {
             })
             .ok()?;
 
-        Some(RedisEvent { event, key })
+        let fields = self
+            .fields
+            .iter()
+            .map(|(name, template)| {
+                template
+                    .render_string(&event)
+                    .map(|value| (name.clone(), value))
+                    .map_err(|error| {
+                        emit!(TemplateRenderingError {
+                            error,
+                            field: Some(name.as_str()),
+                            drop_event: true,
+                        });
+                    })
+            })
+            .collect::<Result<_, _>>()
+            .ok()?;
+
+        Some(RedisEvent { event, key, fields })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
@@ -71,7 +106,7 @@ impl RedisSink {
 
         let service = RedisService {
             conn: self.conn.clone(),
-            data_type: self.data_type,
+            data_type: self.data_type.clone(),
         };
 
         let service = ServiceBuilder::new()
@@ -231,7 +231,7 @@ pub trait SinkBuilderExt: Stream {
         Self: Sized,
         Self::Item: Finalizable,
         Svc: Service<Self::Item>,
-        Svc::Error: fmt::Debug + 'static,
+        Svc::Error: fmt::Debug + fmt::Display + 'static,
         Svc::Future: Send + 'static,
         Svc::Response: DriverResponse,
     {
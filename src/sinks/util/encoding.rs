@@ -4,9 +4,13 @@ use bytes::BytesMut;
 use itertools::{Itertools, Position};
 use tokio_util::codec::Encoder as _;
 use vector_lib::codecs::encoding::Framer;
+#[cfg(feature = "codecs-parquet")]
+use vector_lib::codecs::encoding::Serializer;
 use vector_lib::request_metadata::GroupedCountByteSize;
 use vector_lib::{config::telemetry, EstimatedJsonEncodedSizeOf};
 
+#[cfg(feature = "codecs-parquet")]
+use crate::internal_events::EncoderSerializeError;
 use crate::{codecs::Transformer, event::Event, internal_events::EncoderWriteError};
 
 pub trait Encoder<T> {
@@ -28,6 +32,11 @@ impl Encoder<Vec<Event>> for (Transformer, crate::codecs::Encoder<Framer>) {
         events: Vec<Event>,
         writer: &mut dyn io::Write,
     ) -> io::Result<(usize, GroupedCountByteSize)> {
+        #[cfg(feature = "codecs-parquet")]
+        if let Serializer::Parquet(serializer) = self.1.serializer() {
+            return encode_parquet(&self.0, serializer, events, writer);
+        }
+
         let mut encoder = self.1.clone();
         let mut bytes_written = 0;
         let mut n_events_pending = events.len();
@@ -71,6 +80,65 @@ impl Encoder<Vec<Event>> for (Transformer, crate::codecs::Encoder<Framer>) {
     }
 }
 
+/// Encodes the whole batch as a single Parquet file.
+///
+/// Events that don't fit the column schema are left out of the file and reported as encoding
+/// errors, rather than failing the whole batch.
+#[cfg(feature = "codecs-parquet")]
+fn encode_parquet(
+    transformer: &Transformer,
+    serializer: &vector_lib::codecs::encoding::ParquetSerializer,
+    mut events: Vec<Event>,
+    writer: &mut dyn io::Write,
+) -> io::Result<(usize, GroupedCountByteSize)> {
+    for event in &mut events {
+        transformer.transform(event);
+    }
+
+    let mut bytes = BytesMut::new();
+    let rejected = serializer
+        .encode_batch(&events, &mut bytes)
+        .map_err(|error| {
+            emit!(EncoderSerializeError { error: &error });
+            io::Error::new(io::ErrorKind::InvalidData, error)
+        })?;
+
+    let mut rejected = rejected.into_iter().peekable();
+    let mut byte_size = telemetry().create_request_count_byte_size();
+    for (index, event) in events.iter().enumerate() {
+        match rejected.next_if(|rejected| rejected.index == index) {
+            Some(rejected) => emit!(EncoderSerializeError {
+                error: &rejected.error
+            }),
+            None => byte_size.add_event(event, event.estimated_json_encoded_size_of()),
+        }
+    }
+
+    write_all(writer, events.len(), &bytes)?;
+    Ok((bytes.len(), byte_size))
+}
+
+/// Returns the filename extension implied by the serializer of a sink writing whole objects, if
+/// any.
+///
+/// Parquet files compress their column chunks themselves and must stay readable without first
+/// decompressing the whole object, so they can't be combined with the sink's own compression.
+#[cfg(feature = "codecs-parquet")]
+pub fn object_extension(
+    serializer: &Serializer,
+    compression: super::Compression,
+) -> crate::Result<Option<&'static str>> {
+    match serializer {
+        Serializer::Parquet(_) if compression != super::Compression::None => Err(
+            "The `parquet` codec can't be combined with compression. Set `compression = \"none\"` \
+             and use `encoding.parquet.compression` to compress the file's contents instead."
+                .into(),
+        ),
+        Serializer::Parquet(_) => Ok(Some("parquet")),
+        _ => Ok(None),
+    }
+}
+
 impl Encoder<Event> for (Transformer, crate::codecs::Encoder<()>) {
     fn encode_input(
         &self,
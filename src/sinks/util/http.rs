@@ -33,6 +33,7 @@ use crate::{
     event::Event,
     http::{HttpClient, HttpError},
     internal_events::{EndpointBytesSent, SinkRequestBuildError},
+    oauth2,
     sinks::prelude::*,
 };
 
@@ -406,7 +407,6 @@ where
                 error
             })?;
             let byte_size = request.body().len();
-            let request = request.map(Body::from);
             let (protocol, endpoint) = uri::protocol_endpoint(request.uri().clone());
 
             let mut decompression_service = ServiceBuilder::new()
@@ -416,7 +416,10 @@ where
             // Any errors raised in `http_client.call` results in a `GotHttpWarning` event being emitted
             // in `HttpClient::send`. This does not result in incrementing `component_errors_total` however,
             // because that is incremented by the driver when retries have been exhausted.
-            let response = decompression_service.call(request).await?;
+            let response = oauth2::send_authorized(request, |request| {
+                decompression_service.call(request.map(Body::from))
+            })
+            .await?;
 
             if response.status().is_success() {
                 emit!(EndpointBytesSent {
@@ -185,6 +185,40 @@ pub fn protocol_endpoint(uri: Uri) -> (String, String) {
     )
 }
 
+/// grpc doesn't like an address without a scheme, so we default to http or https if one isn't
+/// specified in the address.
+pub fn with_default_scheme(address: &str, tls: bool) -> crate::Result<Uri> {
+    let uri: Uri = address.parse()?;
+    if uri.scheme().is_none() {
+        // Default the scheme to http or https.
+        let mut parts = uri.into_parts();
+
+        parts.scheme = if tls {
+            Some(
+                "https"
+                    .parse()
+                    .unwrap_or_else(|_| unreachable!("https should be valid")),
+            )
+        } else {
+            Some(
+                "http"
+                    .parse()
+                    .unwrap_or_else(|_| unreachable!("http should be valid")),
+            )
+        };
+
+        if parts.path_and_query.is_none() {
+            parts.path_and_query = Some(
+                "/".parse()
+                    .unwrap_or_else(|_| unreachable!("root should be valid")),
+            );
+        }
+        Ok(Uri::from_parts(parts)?)
+    } else {
+        Ok(uri)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -20,8 +20,8 @@ use crate::{
     proto::vector as proto,
     sinks::{
         util::{
-            retries::RetryLogic, BatchConfig, RealtimeEventBasedDefaultBatchSettings,
-            ServiceBuilderExt, TowerRequestConfig,
+            retries::RetryLogic, uri::with_default_scheme, BatchConfig,
+            RealtimeEventBasedDefaultBatchSettings, ServiceBuilderExt, TowerRequestConfig,
         },
         Healthcheck, VectorSink as VectorSinkType,
     },
@@ -172,40 +172,6 @@ async fn healthcheck(
     }
 }
 
-/// grpc doesn't like an address without a scheme, so we default to http or https if one isn't
-/// specified in the address.
-pub fn with_default_scheme(address: &str, tls: bool) -> crate::Result<Uri> {
-    let uri: Uri = address.parse()?;
-    if uri.scheme().is_none() {
-        // Default the scheme to http or https.
-        let mut parts = uri.into_parts();
-
-        parts.scheme = if tls {
-            Some(
-                "https"
-                    .parse()
-                    .unwrap_or_else(|_| unreachable!("https should be valid")),
-            )
-        } else {
-            Some(
-                "http"
-                    .parse()
-                    .unwrap_or_else(|_| unreachable!("http should be valid")),
-            )
-        };
-
-        if parts.path_and_query.is_none() {
-            parts.path_and_query = Some(
-                "/".parse()
-                    .unwrap_or_else(|_| unreachable!("root should be valid")),
-            );
-        }
-        Ok(Uri::from_parts(parts)?)
-    } else {
-        Ok(uri)
-    }
-}
-
 fn new_client(
     tls_settings: &MaybeTlsSettings,
     proxy_config: &ProxyConfig,
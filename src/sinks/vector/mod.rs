@@ -50,13 +50,12 @@ mod tests {
         event::{BatchNotifier, BatchStatus},
     };
 
-    use super::config::with_default_scheme;
     use super::*;
     use crate::{
         config::{SinkConfig as _, SinkContext},
         event::Event,
         proto::vector as proto,
-        sinks::util::test::build_test_server_generic,
+        sinks::util::{test::build_test_server_generic, uri::with_default_scheme},
         test_util::{
             components::{
                 run_and_assert_data_volume_sink_compliance, run_and_assert_sink_compliance,
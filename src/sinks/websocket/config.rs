@@ -1,15 +1,20 @@
 use std::num::NonZeroU64;
 
+use futures::FutureExt;
 use snafu::ResultExt;
 use vector_lib::codecs::JsonSerializerConfig;
 use vector_lib::configurable::configurable_component;
 
 use crate::{
     codecs::EncodingConfig,
+    common::websocket::{ConnectSnafu, WebSocketConnector, WebSocketError},
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     http::Auth,
     sinks::{
-        websocket::sink::{ConnectSnafu, WebSocketConnector, WebSocketError, WebSocketSink},
+        websocket::{
+            server::{WebSocketServerConfig, WebSocketServerSink},
+            sink::WebSocketSink,
+        },
         Healthcheck, VectorSink,
     },
     tls::{MaybeTlsSettings, TlsEnableableConfig},
@@ -25,8 +30,14 @@ pub struct WebSocketSinkConfig {
     /// The WebSocket URI to connect to.
     ///
     /// This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
-    pub uri: String,
+    ///
+    /// Either this or `server` must be set.
+    pub uri: Option<String>,
+
+    #[configurable(derived)]
+    pub server: Option<WebSocketServerConfig>,
 
+    /// TLS configuration of the connection to the server, or of the listener in server mode.
     #[configurable(derived)]
     pub tls: Option<TlsEnableableConfig>,
 
@@ -61,6 +72,7 @@ pub struct WebSocketSinkConfig {
     )]
     pub acknowledgements: AcknowledgementsConfig,
 
+    /// The credentials sent to the server, or required from clients in server mode.
     #[configurable(derived)]
     pub auth: Option<Auth>,
 }
@@ -68,7 +80,8 @@ pub struct WebSocketSinkConfig {
 impl GenerateConfig for WebSocketSinkConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
-            uri: "ws://127.0.0.1:9000/endpoint".into(),
+            uri: Some("ws://127.0.0.1:9000/endpoint".into()),
+            server: None,
             tls: None,
             encoding: JsonSerializerConfig::default().into(),
             ping_interval: None,
@@ -84,13 +97,27 @@ impl GenerateConfig for WebSocketSinkConfig {
 #[typetag::serde(name = "websocket")]
 impl SinkConfig for WebSocketSinkConfig {
     async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let connector = self.build_connector()?;
-        let ws_sink = WebSocketSink::new(self, connector.clone())?;
-
-        Ok((
-            VectorSink::from_event_streamsink(ws_sink),
-            Box::pin(async move { connector.healthcheck().await }),
-        ))
+        match (&self.uri, &self.server) {
+            (Some(uri), None) => {
+                let connector = self.build_connector(uri)?;
+                let ws_sink = WebSocketSink::new(self, connector.clone())?;
+
+                Ok((
+                    VectorSink::from_event_streamsink(ws_sink),
+                    Box::pin(async move { connector.healthcheck().await }),
+                ))
+            }
+            (None, Some(server)) => {
+                let tls = MaybeTlsSettings::from_config(&self.tls, true).context(ConnectSnafu)?;
+                let ws_sink = WebSocketServerSink::new(self, server.clone(), tls)?;
+
+                Ok((
+                    VectorSink::from_event_streamsink(ws_sink),
+                    futures::future::ok(()).boxed(),
+                ))
+            }
+            _ => Err("Exactly one of `uri` or `server` must be set.".into()),
+        }
     }
 
     fn input(&self) -> Input {
@@ -103,9 +130,9 @@ impl SinkConfig for WebSocketSinkConfig {
 }
 
 impl WebSocketSinkConfig {
-    fn build_connector(&self) -> Result<WebSocketConnector, WebSocketError> {
+    fn build_connector(&self, uri: &str) -> Result<WebSocketConnector, WebSocketError> {
         let tls = MaybeTlsSettings::from_config(&self.tls, false).context(ConnectSnafu)?;
-        WebSocketConnector::new(self.uri.clone(), tls, self.auth.clone())
+        WebSocketConnector::new(uri.to_owned(), tls, self.auth.clone())
     }
 }
 
@@ -1,4 +1,5 @@
 mod config;
+mod server;
 mod sink;
 
 pub use config::WebSocketSinkConfig;
@@ -0,0 +1,705 @@
+use std::{
+    net::SocketAddr,
+    num::{NonZeroU64, NonZeroUsize},
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use futures::{stream::BoxStream, SinkExt, StreamExt};
+use http::{header::AUTHORIZATION, HeaderMap, HeaderValue, StatusCode};
+use openssl::memcmp;
+use stream_cancel::Tripwire;
+use tokio::{
+    net::TcpStream,
+    sync::{
+        mpsc::{self, error::TrySendError},
+        oneshot,
+    },
+};
+use tokio_tungstenite::{
+    accept_hdr_async,
+    tungstenite::{
+        handshake::server::{ErrorResponse, Request, Response},
+        protocol::Message,
+        Error as WsError,
+    },
+    WebSocketStream,
+};
+use tokio_util::codec::Encoder as _;
+use vector_lib::{
+    configurable::configurable_component,
+    emit,
+    internal_event::{
+        ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle as _, Output, Protocol,
+    },
+    EstimatedJsonEncodedSizeOf,
+};
+
+use super::{config::WebSocketSinkConfig, sink::should_encode_as_binary};
+use crate::{
+    codecs::{Encoder, Transformer},
+    common::websocket::{check_received_pong_time, is_closed, PingInterval},
+    conditions::{Condition, VrlConfig},
+    event::{Event, EventStatus, Finalizable},
+    http::Auth,
+    internal_events::{
+        ConnectionOpen, OpenGauge, WsConnectionError, WsConnectionFailedError, WsSlowClient,
+    },
+    sinks::util::StreamSink,
+    tls::{MaybeTlsIncomingStream, MaybeTlsListener, MaybeTlsSettings},
+};
+
+/// The query parameter of the connect request that clients set to their filter.
+const FILTER_PARAMETER: &str = "filter";
+
+/// The VRL functions that filters can call.
+///
+/// Filters are provided by clients, so they can only call functions that inspect the event, and
+/// not the ones that read the environment, the enrichment tables, or the state of Vector.
+const FILTER_FUNCTIONS: &[&str] = &[
+    "array",
+    "bool",
+    "contains",
+    "contains_all",
+    "downcase",
+    "ends_with",
+    "exists",
+    "float",
+    "includes",
+    "int",
+    "is_array",
+    "is_boolean",
+    "is_empty",
+    "is_float",
+    "is_integer",
+    "is_null",
+    "is_nullish",
+    "is_object",
+    "is_string",
+    "is_timestamp",
+    "length",
+    "match",
+    "match_any",
+    "object",
+    "starts_with",
+    "string",
+    "strlen",
+    "timestamp",
+    "upcase",
+];
+
+/// Configuration for the server mode of the `websocket` sink.
+///
+/// In this mode, the sink listens for clients and sends every event to all the connected clients.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketServerConfig {
+    /// The socket address to listen for connections on.
+    #[configurable(metadata(docs::examples = "0.0.0.0:8080"))]
+    pub address: SocketAddr,
+
+    /// The number of messages buffered for each client.
+    ///
+    /// When a client does not read its messages quickly enough to keep its buffer from filling up,
+    /// the `slow_client` policy is applied.
+    #[serde(default = "default_client_buffer_size")]
+    pub client_buffer_size: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub slow_client: SlowClientPolicy,
+}
+
+const fn default_client_buffer_size() -> NonZeroUsize {
+    NonZeroUsize::new(1000).unwrap()
+}
+
+/// What to do with a client whose buffer is full.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SlowClientPolicy {
+    /// Disconnect the client.
+    #[default]
+    Disconnect,
+
+    /// Keep the client connected, but do not send it the events that do not fit in its buffer.
+    DropEvents,
+}
+
+struct Client {
+    peer_addr: SocketAddr,
+    filter: Option<Condition>,
+    tx: mpsc::Sender<Message>,
+    // Disconnects the client once it is removed.
+    _disconnect: oneshot::Sender<()>,
+}
+
+/// The clients connected to the sink.
+#[derive(Clone, Default)]
+struct Clients(Arc<Mutex<Vec<Arc<Client>>>>);
+
+impl Clients {
+    fn add(&self, client: Client) {
+        self.0
+            .lock()
+            .expect("clients lock poisoned")
+            .push(Arc::new(client));
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.lock().expect("clients lock poisoned").is_empty()
+    }
+
+    /// Sends the message encoded from an event to the clients whose filter matches the event,
+    /// returning how many clients it was sent to.
+    ///
+    /// The filters are evaluated without holding the lock, so they do not hold up the clients
+    /// that are connecting in the meantime.
+    fn fan_out(&self, event: &Event, message: &Message, slow_client: SlowClientPolicy) -> usize {
+        let clients = self.0.lock().expect("clients lock poisoned").clone();
+
+        let mut sent = 0;
+        let mut removed = Vec::new();
+        for client in clients {
+            if let Some(filter) = &client.filter {
+                if !filter.check(event.clone()).0 {
+                    continue;
+                }
+            }
+            match client.tx.try_send(message.clone()) {
+                Ok(()) => sent += 1,
+                Err(TrySendError::Full(_)) => {
+                    emit!(WsSlowClient {
+                        peer_addr: client.peer_addr,
+                        disconnected: slow_client == SlowClientPolicy::Disconnect,
+                    });
+                    if slow_client == SlowClientPolicy::Disconnect {
+                        removed.push(client);
+                    }
+                }
+                Err(TrySendError::Closed(_)) => removed.push(client),
+            }
+        }
+
+        if !removed.is_empty() {
+            self.0
+                .lock()
+                .expect("clients lock poisoned")
+                .retain(|client| !removed.iter().any(|removed| Arc::ptr_eq(client, removed)));
+        }
+        sent
+    }
+}
+
+pub struct WebSocketServerSink {
+    config: WebSocketServerConfig,
+    tls: MaybeTlsSettings,
+    authorization: Option<HeaderValue>,
+    transformer: Transformer,
+    encoder: Encoder<()>,
+    encode_as_binary: bool,
+    ping_interval: Option<NonZeroU64>,
+    ping_timeout: Option<NonZeroU64>,
+    clients: Clients,
+}
+
+impl WebSocketServerSink {
+    pub fn new(
+        config: &WebSocketSinkConfig,
+        server: WebSocketServerConfig,
+        tls: MaybeTlsSettings,
+    ) -> crate::Result<Self> {
+        if matches!(config.auth, Some(Auth::OAuth2(_))) {
+            return Err("OAuth2 authentication can't be used to authenticate clients".into());
+        }
+
+        let transformer = config.encoding.transformer();
+        let serializer = config.encoding.build()?;
+        let encoder = Encoder::<()>::new(serializer);
+        let encode_as_binary = should_encode_as_binary(&encoder)?;
+        let authorization = config.auth.as_ref().and_then(|auth| {
+            let mut headers = HeaderMap::new();
+            auth.apply_headers_map(&mut headers);
+            headers.remove(AUTHORIZATION)
+        });
+
+        Ok(Self {
+            config: server,
+            tls,
+            authorization,
+            transformer,
+            encoder,
+            encode_as_binary,
+            ping_interval: config.ping_interval,
+            ping_timeout: config.ping_timeout,
+            clients: Clients::default(),
+        })
+    }
+
+    async fn accept_clients(
+        listener: MaybeTlsListener,
+        clients: Clients,
+        authorization: Option<HeaderValue>,
+        client_buffer_size: usize,
+        ping_interval: Option<NonZeroU64>,
+        ping_timeout: Option<NonZeroU64>,
+        tripwire: Tripwire,
+    ) {
+        let open_gauge = OpenGauge::new();
+        let incoming = listener.accept_stream().take_until(tripwire);
+        futures::pin_mut!(incoming);
+
+        while let Some(stream) = incoming.next().await {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(error) => {
+                    emit!(WsConnectionFailedError {
+                        error: Box::new(error)
+                    });
+                    continue;
+                }
+            };
+
+            let clients = clients.clone();
+            let authorization = authorization.clone();
+            let open_gauge = open_gauge.clone();
+            // The handshake runs in its own task, so slow clients do not hold up the others.
+            tokio::spawn(async move {
+                let peer_addr = stream.peer_addr();
+                let mut filter = None;
+                let callback = |request: &Request, response: Response| {
+                    filter = check_request(request, authorization.as_ref())?;
+                    Ok(response)
+                };
+                let ws_stream = match accept_hdr_async(stream, callback).await {
+                    Ok(ws_stream) => ws_stream,
+                    Err(error) => {
+                        emit!(WsConnectionError { error });
+                        return;
+                    }
+                };
+
+                let (tx, rx) = mpsc::channel(client_buffer_size);
+                let (disconnect_tx, disconnect_rx) = oneshot::channel();
+                clients.add(Client {
+                    peer_addr,
+                    filter,
+                    tx,
+                    _disconnect: disconnect_tx,
+                });
+
+                let _open_token = open_gauge.open(|count| emit!(ConnectionOpen { count }));
+                tokio::select! {
+                    _ = disconnect_rx => {},
+                    _ = serve_client(ws_stream, rx, ping_interval, ping_timeout) => {},
+                }
+            });
+        }
+    }
+}
+
+/// Checks the credentials of a client, and builds the filter it requested, if any.
+fn check_request(
+    request: &Request,
+    authorization: Option<&HeaderValue>,
+) -> Result<Option<Condition>, ErrorResponse> {
+    if let Some(authorization) = authorization {
+        // The credentials are compared in constant time, to not leak them through timing.
+        let authorized = request
+            .headers()
+            .get(AUTHORIZATION)
+            .is_some_and(|provided| {
+                provided.len() == authorization.len()
+                    && memcmp::eq(provided.as_bytes(), authorization.as_bytes())
+            });
+        if !authorized {
+            return Err(error_response(
+                StatusCode::UNAUTHORIZED,
+                "Missing or invalid credentials.".into(),
+            ));
+        }
+    }
+
+    let source = request.uri().query().and_then(|query| {
+        url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == FILTER_PARAMETER)
+            .map(|(_, value)| value.into_owned())
+    });
+    let Some(source) = source else {
+        return Ok(None);
+    };
+
+    // Anonymous clients can't run code in the sink.
+    if authorization.is_none() {
+        return Err(error_response(
+            StatusCode::FORBIDDEN,
+            "Filters require the sink to authenticate clients.".into(),
+        ));
+    }
+
+    let functions = vrl::stdlib::all()
+        .into_iter()
+        .filter(|function| FILTER_FUNCTIONS.contains(&function.identifier()))
+        .collect::<Vec<_>>();
+    VrlConfig {
+        source,
+        runtime: Default::default(),
+    }
+    .build_with_functions(&functions, &Default::default())
+    .map(Some)
+    .map_err(|error| error_response(StatusCode::BAD_REQUEST, format!("Invalid filter: {error}")))
+}
+
+fn error_response(status: StatusCode, message: String) -> ErrorResponse {
+    let mut response = ErrorResponse::new(Some(message));
+    *response.status_mut() = status;
+    response
+}
+
+/// Sends the messages of a client until it disconnects.
+async fn serve_client(
+    ws_stream: WebSocketStream<MaybeTlsIncomingStream<TcpStream>>,
+    mut rx: mpsc::Receiver<Message>,
+    ping_interval: Option<NonZeroU64>,
+    ping_timeout: Option<NonZeroU64>,
+) {
+    const PING: &[u8] = b"PING";
+
+    let (mut ws_sink, mut ws_stream) = ws_stream.split();
+
+    // tokio::time::Interval panics if the period arg is zero. Since the struct members are
+    // using NonZeroU64 that is not something we need to account for.
+    let mut ping_interval = PingInterval::new(ping_interval.map(u64::from));
+    let mut last_pong = Instant::now();
+
+    loop {
+        let result = tokio::select! {
+            message = rx.recv() => match message {
+                Some(message) => ws_sink.send(message).await,
+                None => break,
+            },
+
+            _ = ping_interval.tick() => {
+                match check_received_pong_time(ping_timeout, last_pong) {
+                    Ok(()) => ws_sink.send(Message::Ping(PING.to_vec())).await,
+                    Err(e) => Err(e)
+                }
+            },
+
+            msg = ws_stream.next() => {
+                // Pongs are sent automatically by tungstenite during reading from the stream, and
+                // any other message from the client is ignored.
+                match msg {
+                    Some(Ok(Message::Pong(_))) => {
+                        last_pong = Instant::now();
+                        Ok(())
+                    },
+                    Some(Ok(Message::Close(_))) | None => Err(WsError::ConnectionClosed),
+                    Some(Ok(_)) => Ok(()),
+                    Some(Err(e)) => Err(e)
+                }
+            },
+        };
+
+        if let Err(error) = result {
+            if !is_closed(&error) {
+                emit!(WsConnectionError { error });
+            }
+            return;
+        }
+    }
+
+    _ = ws_sink.close().await;
+}
+
+#[async_trait]
+impl StreamSink<Event> for WebSocketServerSink {
+    async fn run(self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let address = self.config.address;
+        let listener = self.tls.bind(&address).await.map_err(|error| {
+            error!(
+                message = "Failed to start WebSocket server.",
+                %address,
+                %error,
+            )
+        })?;
+        info!(message = "Listening for WebSocket clients.", %address);
+
+        // Clients stop being accepted once the sink stops.
+        let (_trigger, tripwire) = Tripwire::new();
+        tokio::spawn(Self::accept_clients(
+            listener,
+            self.clients.clone(),
+            self.authorization.clone(),
+            self.config.client_buffer_size.get(),
+            self.ping_interval,
+            self.ping_timeout,
+            tripwire,
+        ));
+
+        let bytes_sent = register!(BytesSent::from(Protocol("websocket".into())));
+        let events_sent = register!(EventsSent::from(Output(None)));
+        let encode_as_binary = self.encode_as_binary;
+        let mut encoder = self.encoder.clone();
+
+        while let Some(mut event) = input.next().await {
+            let finalizers = event.take_finalizers();
+
+            // The events are not kept for the clients that connect later.
+            if self.clients.is_empty() {
+                finalizers.update_status(EventStatus::Delivered);
+                continue;
+            }
+
+            self.transformer.transform(&mut event);
+
+            let event_byte_size = event.estimated_json_encoded_size_of();
+
+            let mut bytes = BytesMut::new();
+            if encoder.encode(event.clone(), &mut bytes).is_err() {
+                // Error is handled by `Encoder`.
+                finalizers.update_status(EventStatus::Errored);
+                continue;
+            }
+            finalizers.update_status(EventStatus::Delivered);
+
+            let message = if encode_as_binary {
+                Message::binary(bytes)
+            } else {
+                Message::text(String::from_utf8_lossy(&bytes))
+            };
+            let message_len = message.len();
+
+            let sent = self
+                .clients
+                .fan_out(&event, &message, self.config.slow_client);
+            if sent > 0 {
+                events_sent.emit(CountByteSize(1, event_byte_size));
+                bytes_sent.emit(ByteSize(message_len * sent));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::channel::mpsc::unbounded;
+    use tokio_tungstenite::{
+        connect_async,
+        tungstenite::{client::IntoClientRequest, Error as WsError},
+        MaybeTlsStream,
+    };
+    use vector_lib::codecs::TextSerializerConfig;
+
+    use super::*;
+    use crate::{
+        config::{SinkConfig, SinkContext},
+        event::{EventArray, LogEvent},
+        http::Auth,
+        test_util::{next_addr, trace_init},
+    };
+
+    fn make_config(address: SocketAddr, auth: Option<Auth>) -> WebSocketSinkConfig {
+        WebSocketSinkConfig {
+            uri: None,
+            server: Some(WebSocketServerConfig {
+                address,
+                client_buffer_size: default_client_buffer_size(),
+                slow_client: SlowClientPolicy::Disconnect,
+            }),
+            tls: None,
+            encoding: TextSerializerConfig::default().into(),
+            ping_interval: None,
+            ping_timeout: None,
+            acknowledgements: Default::default(),
+            auth,
+        }
+    }
+
+    async fn connect(
+        address: SocketAddr,
+        query: &str,
+        authorization: Option<&str>,
+    ) -> Result<WebSocketStream<MaybeTlsStream<TcpStream>>, WsError> {
+        // The sink starts listening once it runs.
+        let mut attempts = 0;
+        loop {
+            let mut request = format!("ws://{address}/{query}")
+                .into_client_request()
+                .unwrap();
+            if let Some(authorization) = authorization {
+                request
+                    .headers_mut()
+                    .insert(AUTHORIZATION, authorization.parse().unwrap());
+            }
+
+            match connect_async(request).await {
+                Err(WsError::Io(_)) if attempts < 50 => {
+                    attempts += 1;
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+                result => return result.map(|(ws_stream, _response)| ws_stream),
+            }
+        }
+    }
+
+    async fn next_text(ws_stream: &mut WebSocketStream<MaybeTlsStream<TcpStream>>) -> String {
+        tokio::time::timeout(Duration::from_secs(5), ws_stream.next())
+            .await
+            .expect("Timed out waiting for a message")
+            .unwrap()
+            .unwrap()
+            .into_text()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn fans_out_events_to_filtered_clients() {
+        trace_init();
+
+        let address = next_addr();
+        let auth = Auth::Bearer {
+            token: "OiJIUzI1NiIsInR5cCI6IkpXVCJ".to_string().into(),
+        };
+        let (sink, _healthcheck) = make_config(address, Some(auth))
+            .build(SinkContext::default())
+            .await
+            .unwrap();
+        let (tx, rx) = unbounded();
+        let sink = tokio::spawn(sink.run(rx.map(EventArray::from)));
+
+        let authorization = Some("Bearer OiJIUzI1NiIsInR5cCI6IkpXVCJ");
+        let mut everything = connect(address, "", authorization).await.unwrap();
+        let mut filtered = connect(
+            address,
+            "?filter=starts_with(string!(.message),%20%22b%22)",
+            authorization,
+        )
+        .await
+        .unwrap();
+        // Gives the sink time to register the clients.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        for message in ["a", "b", "c"] {
+            tx.unbounded_send(Event::from(LogEvent::from(message)))
+                .unwrap();
+        }
+
+        assert_eq!(next_text(&mut everything).await, "a");
+        assert_eq!(next_text(&mut everything).await, "b");
+        assert_eq!(next_text(&mut everything).await, "c");
+        assert_eq!(next_text(&mut filtered).await, "b");
+
+        drop(tx);
+        sink.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_requests() {
+        trace_init();
+
+        let address = next_addr();
+        let auth = Auth::Bearer {
+            token: "OiJIUzI1NiIsInR5cCI6IkpXVCJ".to_string().into(),
+        };
+        let (sink, _healthcheck) = make_config(address, Some(auth))
+            .build(SinkContext::default())
+            .await
+            .unwrap();
+        let (_tx, rx) = unbounded::<Event>();
+        tokio::spawn(sink.run(rx.map(EventArray::from)));
+
+        let status = |result: Result<_, WsError>| match result {
+            Err(WsError::Http(response)) => response.status(),
+            _ => panic!("The request should have been rejected"),
+        };
+        assert_eq!(
+            status(connect(address, "", None).await),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(connect(address, "", Some("Bearer wrong")).await),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(
+                connect(
+                    address,
+                    "?filter=.message%20%2B",
+                    Some("Bearer OiJIUzI1NiIsInR5cCI6IkpXVCJ")
+                )
+                .await
+            ),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status(
+                connect(
+                    address,
+                    "?filter=get_env_var!(%22HOME%22)%20%3D%3D%20%22%2Froot%22",
+                    Some("Bearer OiJIUzI1NiIsInR5cCI6IkpXVCJ")
+                )
+                .await
+            ),
+            StatusCode::BAD_REQUEST
+        );
+        assert!(
+            connect(address, "", Some("Bearer OiJIUzI1NiIsInR5cCI6IkpXVCJ"))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_filters_without_auth() {
+        trace_init();
+
+        let address = next_addr();
+        let (sink, _healthcheck) = make_config(address, None)
+            .build(SinkContext::default())
+            .await
+            .unwrap();
+        let (_tx, rx) = unbounded::<Event>();
+        tokio::spawn(sink.run(rx.map(EventArray::from)));
+
+        match connect(address, "?filter=.message%20%3D%3D%20%22b%22", None).await {
+            Err(WsError::Http(response)) => assert_eq!(response.status(), StatusCode::FORBIDDEN),
+            _ => panic!("The request should have been rejected"),
+        }
+        assert!(connect(address, "", None).await.is_ok());
+    }
+
+    #[test]
+    fn applies_slow_client_policy() {
+        let event = Event::from(LogEvent::from("message"));
+        let message = Message::text("message");
+
+        for (slow_client, kept) in [
+            (SlowClientPolicy::Disconnect, false),
+            (SlowClientPolicy::DropEvents, true),
+        ] {
+            let clients = Clients::default();
+            let (tx, _rx) = mpsc::channel(1);
+            let (disconnect_tx, _disconnect_rx) = oneshot::channel();
+            clients.add(Client {
+                peer_addr: next_addr(),
+                filter: None,
+                tx,
+                _disconnect: disconnect_tx,
+            });
+
+            assert_eq!(clients.fan_out(&event, &message, slow_client), 1);
+            // The client's buffer is full.
+            assert_eq!(clients.fan_out(&event, &message, slow_client), 0);
+            assert_eq!(clients.is_empty(), !kept);
+        }
+    }
+}
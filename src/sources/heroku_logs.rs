@@ -480,8 +480,8 @@ mod tests {
     ) -> u16 {
         let len = body.lines().count();
         let mut req = reqwest::Client::new().post(format!("http://{}/events?{}", address, query));
-        if let Some(auth) = auth {
-            req = req.basic_auth(auth.username, Some(auth.password.inner()));
+        if let Some(HttpSourceAuthConfig::Basic { username, password }) = auth {
+            req = req.basic_auth(username, Some(password.inner()));
         }
         req.header("Logplex-Msg-Count", len)
             .header("Logplex-Frame-Id", "frame-foo")
@@ -495,7 +495,7 @@ mod tests {
     }
 
     fn make_auth() -> HttpSourceAuthConfig {
-        HttpSourceAuthConfig {
+        HttpSourceAuthConfig::Basic {
             username: random_string(16),
             password: random_string(16).into(),
         }
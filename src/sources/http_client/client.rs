@@ -1,5 +1,6 @@
 //! Generalized HTTP client source.
 //! Calls an endpoint at an interval, decoding the HTTP responses into events.
+//! The requests can be templated with a cursor, and follow the pages of the responses.
 
 use bytes::{Bytes, BytesMut};
 use chrono::Utc;
@@ -10,10 +11,11 @@ use snafu::ResultExt;
 use std::{collections::HashMap, time::Duration};
 use tokio_util::codec::Decoder as _;
 
+use super::requests::{CursorConfig, PaginationConfig, ScrapeRequests};
 use crate::sources::util::http_client;
 use crate::{
     codecs::{Decoder, DecodingConfig},
-    config::{SourceConfig, SourceContext},
+    config::{SourceAcknowledgementsConfig, SourceConfig, SourceContext},
     http::Auth,
     serde::{default_decoding, default_framing_message_based},
     sources,
@@ -73,6 +75,10 @@ pub struct HttpClientConfig {
     ///
     /// The parameters provided in this option are appended to any parameters
     /// manually provided in the `endpoint` option.
+    ///
+    /// The `{{ cursor }}` and `{{ page_token }}` placeholders of the values are replaced by the
+    /// cursor and the page token of the request. Parameters whose placeholders are replaced by
+    /// nothing are left out.
     #[serde(default)]
     #[configurable(metadata(
         docs::additional_props_description = "A query string parameter and it's value(s)."
@@ -104,6 +110,19 @@ pub struct HttpClientConfig {
     #[serde(default = "default_http_method")]
     pub method: HttpMethod,
 
+    /// The body of the HTTP requests.
+    ///
+    /// The `{{ cursor }}` and `{{ page_token }}` placeholders are replaced by the cursor and the
+    /// page token of the request.
+    #[configurable(metadata(docs::examples = "{\"since\": \"{{ cursor }}\"}"))]
+    pub body: Option<String>,
+
+    #[configurable(derived)]
+    pub pagination: Option<PaginationConfig>,
+
+    #[configurable(derived)]
+    pub cursor: Option<CursorConfig>,
+
     /// TLS configuration.
     #[configurable(derived)]
     pub tls: Option<TlsConfig>,
@@ -161,6 +180,9 @@ impl Default for HttpClientConfig {
             framing: default_framing_message_based(),
             headers: HashMap::new(),
             method: default_http_method(),
+            body: None,
+            pagination: None,
+            cursor: None,
             tls: None,
             auth: None,
             log_namespace: None,
@@ -191,10 +213,44 @@ impl SourceConfig for HttpClientConfig {
 
         let content_type = self.decoding.content_type(&self.framing).to_string();
 
-        // the only specific context needed is the codec decoding
+        let templated = self
+            .query
+            .values()
+            .flatten()
+            .any(|value| value.contains("{{"));
+        let requests = if self.body.is_some()
+            || self.pagination.is_some()
+            || self.cursor.is_some()
+            || templated
+        {
+            let checkpoint_path = match &self.cursor {
+                Some(_) => Some(
+                    cx.globals
+                        .resolve_and_make_data_subdir(None, cx.key.id())?
+                        .join("checkpoint.json"),
+                ),
+                None => None,
+            };
+            Some(ScrapeRequests::new(
+                self.endpoint
+                    .parse::<Uri>()
+                    .context(sources::UriParseSnafu)?,
+                self.query.clone(),
+                self.body.clone(),
+                self.pagination.as_ref(),
+                self.cursor.as_ref(),
+                checkpoint_path,
+                cx.do_acknowledgements(SourceAcknowledgementsConfig::DEFAULT),
+            )?)
+        } else {
+            None
+        };
+
+        // the specific context needed is the codec decoding, and the state of the requests
         let context = HttpClientContext {
             decoder,
             log_namespace,
+            requests,
         };
 
         warn_if_interval_too_low(self.timeout, self.interval);
@@ -209,6 +265,7 @@ impl SourceConfig for HttpClientConfig {
             tls,
             proxy: cx.proxy.clone(),
             shutdown: cx.shutdown,
+            sequential: self.pagination.is_some() || self.cursor.is_some(),
         };
 
         Ok(call(inputs, context, cx.out, self.method).boxed())
@@ -230,8 +287,9 @@ impl SourceConfig for HttpClientConfig {
         )]
     }
 
+    /// Acknowledgements only delay persisting the cursor.
     fn can_acknowledge(&self) -> bool {
-        false
+        self.cursor.is_some()
     }
 }
 
@@ -251,6 +309,7 @@ impl HttpClientConfig {
 pub struct HttpClientContext {
     pub decoder: Decoder,
     pub log_namespace: LogNamespace,
+    pub requests: Option<ScrapeRequests>,
 }
 
 impl HttpClientContext {
@@ -288,21 +347,39 @@ impl HttpClientBuilder for HttpClientContext {
 
 impl http_client::HttpClientContext for HttpClientContext {
     /// Decodes the HTTP response body into events per the decoder configured.
-    fn on_response(&mut self, _url: &Uri, _header: &Parts, body: &Bytes) -> Option<Vec<Event>> {
+    fn on_response(&mut self, url: &Uri, header: &Parts, body: &Bytes) -> Option<Vec<Event>> {
         // get the body into a byte array
         let mut buf = BytesMut::new();
         buf.extend_from_slice(body);
 
         let events = self.decode_events(&mut buf);
 
+        if let Some(requests) = &mut self.requests {
+            requests.on_response(url, header, body, &events);
+        }
+
         Some(events)
     }
 
+    /// Builds the templated and paginated requests, if any.
+    fn request(&mut self, url: &Uri) -> Option<(Uri, Bytes)> {
+        match &mut self.requests {
+            Some(requests) => requests.request(url),
+            None => Some((url.clone(), Bytes::new())),
+        }
+    }
+
+    fn has_next_page(&mut self) -> bool {
+        self.requests
+            .as_mut()
+            .is_some_and(|requests| requests.has_next_page())
+    }
+
     /// Enriches events with source_type, timestamp
     fn enrich_events(&mut self, events: &mut Vec<Event>) {
         let now = Utc::now();
 
-        for event in events {
+        for event in events.iter_mut() {
             match event {
                 Event::Log(ref mut log) => {
                     self.log_namespace.insert_standard_vector_source_metadata(
@@ -326,5 +403,9 @@ impl http_client::HttpClientContext for HttpClientContext {
                 }
             }
         }
+
+        if let Some(requests) = &self.requests {
+            requests.add_batch_notifier(events);
+        }
     }
 }
@@ -59,6 +59,9 @@ async fn invalid_endpoint() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         auth: None,
         tls: None,
         log_namespace: None,
@@ -78,6 +81,9 @@ async fn collected_logs_bytes() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         auth: None,
         tls: None,
         log_namespace: None,
@@ -103,6 +109,9 @@ async fn collected_logs_json() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         auth: None,
         tls: None,
         log_namespace: None,
@@ -128,6 +137,9 @@ async fn collected_metrics_native_json() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         auth: None,
         tls: None,
         log_namespace: None,
@@ -158,6 +170,9 @@ async fn collected_trace_native_json() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         auth: None,
         tls: None,
         log_namespace: None,
@@ -183,6 +198,9 @@ async fn unauthorized_no_auth() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         auth: None,
         tls: None,
         log_namespace: None,
@@ -202,6 +220,9 @@ async fn unauthorized_wrong_auth() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         tls: None,
         auth: Some(Auth::Basic {
             user: "white_rabbit".to_string(),
@@ -224,6 +245,9 @@ async fn authorized() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         tls: None,
         auth: Some(Auth::Basic {
             user: "user".to_string(),
@@ -246,6 +270,9 @@ async fn tls_invalid_ca() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         tls: Some(TlsConfig {
             ca_file: Some("tests/data/http-client/certs/invalid-ca-cert.pem".into()),
             ..Default::default()
@@ -268,6 +295,9 @@ async fn tls_valid() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         tls: Some(TlsConfig {
             ca_file: Some(tls::TEST_PEM_CA_PATH.into()),
             ..Default::default()
@@ -291,6 +321,9 @@ async fn shutdown() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         tls: None,
         auth: None,
         log_namespace: None,
@@ -1,5 +1,7 @@
 #[cfg(feature = "sources-http_client")]
 pub mod client;
+#[cfg(feature = "sources-http_client")]
+mod requests;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,781 @@
+//! Templated, paginated and incremental requests of the `http_client` source.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use bytes::Bytes;
+use http::{header::LINK, response::Parts, HeaderMap, Uri};
+use serde::{Deserialize, Serialize};
+use vector_lib::configurable::configurable_component;
+use vector_lib::{
+    compile_vrl,
+    event::{BatchNotifier, BatchStatus, Event},
+    finalization::AddBatchNotifier,
+    TimeZone,
+};
+use vrl::{
+    compiler::{
+        runtime::Runtime, CompilationResult, CompileConfig, Program, TargetValue, TypeState,
+    },
+    diagnostic::Formatter,
+    value::{ObjectMap, Secrets, Value},
+};
+
+use crate::{
+    internal_events::{HttpClientCheckpointError, HttpClientProgramError},
+    sources::util::http_client::build_url,
+};
+
+/// Pagination of the responses.
+///
+/// The pages of a scrape are requested one after the other, and a scrape only starts once the
+/// previous one has ended.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct PaginationConfig {
+    #[serde(flatten)]
+    #[configurable(derived)]
+    pub strategy: PaginationStrategy,
+
+    /// The maximum number of pages requested by a single scrape.
+    #[serde(default = "default_max_pages")]
+    #[configurable(metadata(docs::examples = 10))]
+    pub max_pages: usize,
+}
+
+const fn default_max_pages() -> usize {
+    100
+}
+
+/// The strategy to find the next page of the results.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+#[configurable(metadata(docs::enum_tag_description = "The pagination strategy to use."))]
+pub enum PaginationStrategy {
+    /// Requests the URL of the `next` link of the `Link` header of the responses.
+    LinkHeader,
+
+    /// Requests the URL that a VRL program computes from each response.
+    ///
+    /// The scrape ends when the program resolves to `null`. Relative URLs are resolved against
+    /// the URL of the response.
+    NextUrl {
+        /// The VRL program.
+        #[configurable(metadata(docs::examples = "parse_json!(.body).links.next"))]
+        source: String,
+    },
+
+    /// Requests the endpoint again, with the `{{ page_token }}` placeholders of the query and the
+    /// body replaced by the token that a VRL program computes from each response.
+    ///
+    /// The scrape ends when the program resolves to `null`.
+    PageToken {
+        /// The VRL program.
+        #[configurable(metadata(docs::examples = "parse_json!(.body).next_page_token"))]
+        source: String,
+    },
+}
+
+/// A cursor that carries the progress of the source from one scrape to the next.
+///
+/// The cursor replaces the `{{ cursor }}` placeholders of the query and the body, and is
+/// persisted in the data directory so that restarts resume from it. When acknowledgements are
+/// enabled, it's persisted once the events of the scrape are delivered. Otherwise, it's persisted
+/// once the scrape ends, and the events not yet delivered when Vector stops aren't requested
+/// again.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CursorConfig {
+    /// The VRL program that computes the cursor from each response.
+    ///
+    /// When the program resolves to `null`, the cursor is left unchanged.
+    #[configurable(metadata(docs::examples = ".events[-1].timestamp ?? .cursor"))]
+    #[configurable(metadata(docs::examples = "parse_json!(.body).last_id"))]
+    pub source: String,
+
+    /// The cursor of the first scrape, used until a cursor is computed.
+    #[configurable(metadata(docs::examples = "1970-01-01T00:00:00Z"))]
+    pub initial: Option<String>,
+}
+
+/// A compiled VRL program, that runs against the responses of the source.
+///
+/// The program runs against an object with the `url`, `status`, `headers`, and `body` of the
+/// response, the `events` decoded from it, and the current `cursor`.
+#[derive(Clone, Debug)]
+struct ResponseProgram {
+    program: Program,
+}
+
+impl ResponseProgram {
+    fn compile(source: &str) -> crate::Result<Self> {
+        let functions = vrl::stdlib::all()
+            .into_iter()
+            .chain(vector_vrl_functions::all())
+            .collect::<Vec<_>>();
+
+        let CompilationResult {
+            program,
+            warnings,
+            config: _,
+        } = compile_vrl(
+            source,
+            &functions,
+            &TypeState::default(),
+            CompileConfig::default(),
+        )
+        .map_err(|diagnostics| Formatter::new(source, diagnostics).colored().to_string())?;
+
+        if !warnings.is_empty() {
+            let warnings = Formatter::new(source, warnings).colored().to_string();
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        Ok(Self { program })
+    }
+
+    /// Runs the program, returning `None` if it fails or resolves to `null`.
+    fn run(&self, response: &Value, url: &Uri) -> Option<Value> {
+        let mut target = TargetValue {
+            value: response.clone(),
+            metadata: Value::Object(ObjectMap::new()),
+            secrets: Secrets::default(),
+        };
+        match Runtime::default().resolve(&mut target, &self.program, &TimeZone::default()) {
+            Ok(Value::Null) => None,
+            Ok(value) => Some(value),
+            Err(error) => {
+                emit!(HttpClientProgramError {
+                    error: &error.to_string(),
+                    url: url.to_string(),
+                });
+                None
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Pagination {
+    LinkHeader,
+    NextUrl(ResponseProgram),
+    PageToken(ResponseProgram),
+}
+
+#[derive(Clone, Debug)]
+enum NextPage {
+    Url(Uri),
+    Token(Value),
+}
+
+/// The cursor shared by the scrapes, along with the file it is persisted to.
+#[derive(Debug)]
+struct CursorState {
+    cursor: Option<Value>,
+    path: Option<PathBuf>,
+    /// The number of scrapes started, and the last one whose cursor was persisted, when the
+    /// cursor is persisted once the events are acknowledged.
+    scrapes: u64,
+    persisted: u64,
+}
+
+#[derive(Deserialize, Serialize)]
+struct Checkpoint {
+    cursor: Value,
+}
+
+/// Builds the requests of the scrapes, and carries the state of a scrape from one request to the
+/// next.
+#[derive(Clone, Debug)]
+pub struct ScrapeRequests {
+    /// The endpoint, without the configured query.
+    endpoint: Uri,
+    query: HashMap<String, Vec<String>>,
+    body: Option<String>,
+    pagination: Option<(Pagination, usize)>,
+    cursor: Option<(ResponseProgram, Arc<Mutex<CursorState>>)>,
+    acknowledgements: bool,
+
+    page: usize,
+    next: Option<NextPage>,
+    /// The cursor of the current scrape, and the one computed by its responses so far.
+    scrape_cursor: Option<Value>,
+    pending_cursor: Option<Value>,
+    /// The batch the events of the scrape are added to, and where the cursor is set once the
+    /// scrape ends, when the cursor is persisted once the events are acknowledged.
+    batch: Option<(BatchNotifier, Arc<Mutex<Option<Value>>>)>,
+}
+
+impl ScrapeRequests {
+    /// Builds the requests of the source, loading the cursor persisted to `checkpoint_path`.
+    ///
+    /// With `acknowledgements`, the cursor of a scrape is only persisted once its events are
+    /// delivered.
+    pub fn new(
+        endpoint: Uri,
+        query: HashMap<String, Vec<String>>,
+        body: Option<String>,
+        pagination: Option<&PaginationConfig>,
+        cursor: Option<&CursorConfig>,
+        checkpoint_path: Option<PathBuf>,
+        acknowledgements: bool,
+    ) -> crate::Result<Self> {
+        let pagination = pagination
+            .map(|config| {
+                let strategy = match &config.strategy {
+                    PaginationStrategy::LinkHeader => Pagination::LinkHeader,
+                    PaginationStrategy::NextUrl { source } => {
+                        Pagination::NextUrl(ResponseProgram::compile(source)?)
+                    }
+                    PaginationStrategy::PageToken { source } => {
+                        Pagination::PageToken(ResponseProgram::compile(source)?)
+                    }
+                };
+                Ok::<_, crate::Error>((strategy, config.max_pages.max(1)))
+            })
+            .transpose()?;
+
+        let cursor = cursor
+            .map(|config| {
+                let program = ResponseProgram::compile(&config.source)?;
+                let persisted = checkpoint_path.as_deref().and_then(|path| {
+                    load_checkpoint(path).unwrap_or_else(|error| {
+                        emit!(HttpClientCheckpointError { error, path });
+                        None
+                    })
+                });
+                let state = CursorState {
+                    cursor: persisted.or_else(|| config.initial.clone().map(Value::from)),
+                    path: checkpoint_path.clone(),
+                    scrapes: 0,
+                    persisted: 0,
+                };
+                Ok::<_, crate::Error>((program, Arc::new(Mutex::new(state))))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            endpoint,
+            query,
+            body,
+            pagination,
+            cursor,
+            acknowledgements,
+            page: 0,
+            next: None,
+            scrape_cursor: None,
+            pending_cursor: None,
+            batch: None,
+        })
+    }
+
+    /// Returns the URL and the body of the next request of the scrape.
+    pub fn request(&mut self, url: &Uri) -> Option<(Uri, Bytes)> {
+        if self.page == 0 {
+            self.scrape_cursor = self
+                .cursor
+                .as_ref()
+                .and_then(|(_, state)| state.lock().expect("poisoned lock").cursor.clone());
+            self.pending_cursor = None;
+            if self.acknowledgements {
+                self.batch = self
+                    .cursor
+                    .as_ref()
+                    .map(|(_, state)| acknowledge_scrape(state));
+            }
+        }
+        self.page += 1;
+
+        let (next_url, page_token) = match self.next.take() {
+            Some(NextPage::Url(url)) => (Some(url), None),
+            Some(NextPage::Token(token)) => (None, Some(token)),
+            None => (None, None),
+        };
+        let render = |template: &str| {
+            render_template(template, self.scrape_cursor.as_ref(), page_token.as_ref())
+        };
+
+        let url = next_url.unwrap_or_else(|| {
+            if !self
+                .query
+                .values()
+                .flatten()
+                .any(|value| is_template(value))
+            {
+                return url.clone();
+            }
+            let query = self
+                .query
+                .iter()
+                .map(|(key, values)| {
+                    let values = values
+                        .iter()
+                        .filter_map(|value| {
+                            let rendered = render(value);
+                            // Templated parameters without a value are left out, such as the page
+                            // token of the first page.
+                            (!is_template(value) || !rendered.is_empty()).then_some(rendered)
+                        })
+                        .collect();
+                    (key.clone(), values)
+                })
+                .collect();
+            build_url(&self.endpoint, &query)
+        });
+        let body = self
+            .body
+            .as_deref()
+            .map(|body| Bytes::from(render(body)))
+            .unwrap_or_default();
+
+        Some((url, body))
+    }
+
+    /// Finds the next page and the cursor from a response.
+    pub fn on_response(&mut self, url: &Uri, header: &Parts, body: &Bytes, events: &[Event]) {
+        let cursor = self.pending_cursor.as_ref().or(self.scrape_cursor.as_ref());
+        let response = response_value(url, header, body, events, cursor);
+
+        self.next = match &self.pagination {
+            Some((pagination, max_pages)) => {
+                let next = match pagination {
+                    Pagination::LinkHeader => next_link(&header.headers)
+                        .and_then(|link| resolve_url(url, &link))
+                        .map(NextPage::Url),
+                    Pagination::NextUrl(program) => program
+                        .run(&response, url)
+                        .and_then(|next| resolve_url(url, &next.to_string_lossy()))
+                        .map(NextPage::Url),
+                    Pagination::PageToken(program) => {
+                        program.run(&response, url).map(NextPage::Token)
+                    }
+                };
+                if next.is_some() && self.page >= *max_pages {
+                    warn!(
+                        message = "Reached the maximum number of pages of a scrape.",
+                        max_pages = *max_pages,
+                        internal_log_rate_limit = true,
+                    );
+                    None
+                } else {
+                    next
+                }
+            }
+            None => None,
+        };
+
+        if let Some((program, _)) = &self.cursor {
+            if let Some(cursor) = program.run(&response, url) {
+                self.pending_cursor = Some(cursor);
+            }
+        }
+    }
+
+    /// Adds the events of a response to the batch of the scrape, if they are acknowledged.
+    pub fn add_batch_notifier(&self, events: &mut [Event]) {
+        if let Some((batch, _)) = &self.batch {
+            for event in events {
+                event.add_batch_notifier(batch.clone());
+            }
+        }
+    }
+
+    /// Returns whether the scrape continues, and saves the cursor of the scrape once it ends.
+    pub fn has_next_page(&mut self) -> bool {
+        if self.next.is_some() {
+            return true;
+        }
+
+        // The batch is released along with the scrape, once its cursor is known.
+        let batch = self.batch.take();
+        if let (Some((_, state)), Some(cursor)) = (&self.cursor, self.pending_cursor.take()) {
+            let mut state = state.lock().expect("poisoned lock");
+            match &batch {
+                Some((_, scrape_cursor)) => {
+                    *scrape_cursor.lock().expect("poisoned lock") = Some(cursor.clone());
+                }
+                None => {
+                    if let Some(path) = &state.path {
+                        if let Err(error) = save_checkpoint(path, &cursor) {
+                            emit!(HttpClientCheckpointError { error, path });
+                        }
+                    }
+                }
+            }
+            state.cursor = Some(cursor);
+        }
+        false
+    }
+}
+
+/// Starts a scrape whose events are acknowledged, returning the batch they are added to and where
+/// its cursor is set once it ends.
+///
+/// The cursor is persisted once the events are delivered, unless the cursor of a later scrape
+/// was persisted first. Scrapes that end early, such as on an HTTP error, don't set their cursor.
+fn acknowledge_scrape(
+    state: &Arc<Mutex<CursorState>>,
+) -> (BatchNotifier, Arc<Mutex<Option<Value>>>) {
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let scrape = {
+        let mut state = state.lock().expect("poisoned lock");
+        state.scrapes += 1;
+        state.scrapes
+    };
+    let scrape_cursor = Arc::new(Mutex::new(None));
+
+    let state = Arc::clone(state);
+    let cursor = Arc::clone(&scrape_cursor);
+    tokio::spawn(async move {
+        if receiver.await != BatchStatus::Delivered {
+            return;
+        }
+        let Some(cursor) = cursor.lock().expect("poisoned lock").take() else {
+            return;
+        };
+        let mut state = state.lock().expect("poisoned lock");
+        if scrape > state.persisted {
+            if let Some(path) = &state.path {
+                if let Err(error) = save_checkpoint(path, &cursor) {
+                    emit!(HttpClientCheckpointError { error, path });
+                }
+            }
+            state.persisted = scrape;
+        }
+    });
+
+    (batch, scrape_cursor)
+}
+
+/// Describes a response to the VRL programs.
+fn response_value(
+    url: &Uri,
+    header: &Parts,
+    body: &Bytes,
+    events: &[Event],
+    cursor: Option<&Value>,
+) -> Value {
+    let headers = header
+        .headers
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.as_str().into(),
+                Value::from(Bytes::copy_from_slice(value.as_bytes())),
+            )
+        })
+        .collect::<ObjectMap>();
+    let events = events
+        .iter()
+        .filter_map(|event| event.maybe_as_log())
+        .map(|log| log.value().clone())
+        .collect::<Vec<_>>();
+
+    Value::Object(ObjectMap::from([
+        ("url".into(), Value::from(url.to_string())),
+        (
+            "status".into(),
+            Value::from(i64::from(header.status.as_u16())),
+        ),
+        ("headers".into(), Value::Object(headers)),
+        ("body".into(), Value::from(body.clone())),
+        ("events".into(), Value::Array(events)),
+        ("cursor".into(), cursor.cloned().unwrap_or(Value::Null)),
+    ]))
+}
+
+fn is_template(template: &str) -> bool {
+    template.contains("{{")
+}
+
+/// Replaces the `{{ cursor }}` and `{{ page_token }}` placeholders of a template, with an empty
+/// string when there is no such value. Other placeholders are kept as is.
+fn render_template(template: &str, cursor: Option<&Value>, page_token: Option<&Value>) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}").map(|end| start + end) else {
+            break;
+        };
+        rendered.push_str(&rest[..start]);
+        let value = match rest[start + 2..end].trim() {
+            "cursor" => cursor,
+            "page_token" => page_token,
+            _ => {
+                rendered.push_str(&rest[start..end + 2]);
+                rest = &rest[end + 2..];
+                continue;
+            }
+        };
+        if let Some(value) = value {
+            rendered.push_str(&value.to_string_lossy());
+        }
+        rest = &rest[end + 2..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Returns the target of the `next` link of the `Link` headers.
+fn next_link(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get_all(LINK)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .find_map(|link| {
+            let mut parts = link.split(';');
+            let target = parts.next()?.trim().strip_prefix('<')?.strip_suffix('>')?;
+            parts
+                .any(|param| match param.split_once('=') {
+                    Some((name, value)) => {
+                        name.trim().eq_ignore_ascii_case("rel")
+                            && value
+                                .trim()
+                                .trim_matches('"')
+                                .split_whitespace()
+                                .any(|rel| rel.eq_ignore_ascii_case("next"))
+                    }
+                    None => false,
+                })
+                .then(|| target.to_owned())
+        })
+}
+
+/// Resolves a URL, which can be relative to the URL of the response.
+fn resolve_url(base: &Uri, target: &str) -> Option<Uri> {
+    let url = url::Url::parse(&base.to_string()).ok()?.join(target).ok()?;
+    url.as_str().parse().ok()
+}
+
+fn load_checkpoint(path: &Path) -> io::Result<Option<Value>> {
+    match fs::read(path) {
+        Ok(contents) => Ok(Some(
+            serde_json::from_slice::<Checkpoint>(&contents)?.cursor,
+        )),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Writes the checkpoint to a temporary file first, so that a crash can't leave a partial
+/// checkpoint behind.
+fn save_checkpoint(path: &Path, cursor: &Value) -> io::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    let checkpoint = Checkpoint {
+        cursor: cursor.clone(),
+    };
+    fs::write(&tmp_path, serde_json::to_vec(&checkpoint)?)?;
+    fs::rename(&tmp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use http::{HeaderValue, Response};
+    use vector_lib::event::{EventStatus, LogEvent};
+
+    use super::*;
+
+    fn parts(link: Option<&'static str>) -> Parts {
+        let mut response = Response::builder().status(200);
+        if let Some(link) = link {
+            response = response.header(LINK, HeaderValue::from_static(link));
+        }
+        response.body(()).unwrap().into_parts().0
+    }
+
+    fn query(url: &Uri) -> Vec<(String, String)> {
+        let mut pairs = url::form_urlencoded::parse(url.query().unwrap_or_default().as_bytes())
+            .into_owned()
+            .collect::<Vec<_>>();
+        pairs.sort();
+        pairs
+    }
+
+    fn pair(key: &str, value: &str) -> (String, String) {
+        (key.to_owned(), value.to_owned())
+    }
+
+    #[test]
+    fn renders_templates() {
+        let cursor = Value::from("2024-01-01T00:00:00Z");
+        assert_eq!(
+            render_template(
+                r#"{"since": "{{ cursor }}", "page": "{{page_token}}", "other": "{{ other }}"}"#,
+                Some(&cursor),
+                None
+            ),
+            r#"{"since": "2024-01-01T00:00:00Z", "page": "", "other": "{{ other }}"}"#
+        );
+        assert_eq!(
+            render_template("{{ cursor", Some(&cursor), None),
+            "{{ cursor"
+        );
+    }
+
+    #[test]
+    fn finds_next_link() {
+        let header = parts(Some(
+            r#"<https://api.example.com/items?page=1>; rel="prev", <https://api.example.com/items?page=3>; rel="next""#,
+        ));
+        assert_eq!(
+            next_link(&header.headers).as_deref(),
+            Some("https://api.example.com/items?page=3")
+        );
+        assert_eq!(next_link(&parts(None).headers), None);
+    }
+
+    #[test]
+    fn resolves_relative_urls() {
+        let base = Uri::from_static("https://api.example.com/v1/items?page=1");
+        assert_eq!(
+            resolve_url(&base, "/v1/items?page=2"),
+            Some(Uri::from_static("https://api.example.com/v1/items?page=2"))
+        );
+        assert_eq!(
+            resolve_url(&base, "https://other.example.com/items"),
+            Some(Uri::from_static("https://other.example.com/items"))
+        );
+    }
+
+    #[test]
+    fn paginates_with_page_tokens_and_saves_cursor() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint.json");
+        let endpoint = Uri::from_static("http://localhost/items");
+        let query = HashMap::from([
+            ("since".to_owned(), vec!["{{ cursor }}".to_owned()]),
+            ("token".to_owned(), vec!["{{ page_token }}".to_owned()]),
+        ]);
+        let pagination = PaginationConfig {
+            strategy: PaginationStrategy::PageToken {
+                source: "parse_json!(.body).next".to_owned(),
+            },
+            max_pages: 10,
+        };
+        let cursor = CursorConfig {
+            source: "parse_json!(.body).last".to_owned(),
+            initial: Some("0".to_owned()),
+        };
+        let mut requests = ScrapeRequests::new(
+            endpoint.clone(),
+            query,
+            None,
+            Some(&pagination),
+            Some(&cursor),
+            Some(path.clone()),
+            false,
+        )
+        .unwrap();
+
+        let (url, _) = requests.request(&endpoint).unwrap();
+        assert_eq!(query(&url), vec![pair("since", "0")]);
+        requests.on_response(
+            &url,
+            &parts(None),
+            &Bytes::from(r#"{"next": "abc", "last": "5"}"#),
+            &[],
+        );
+        assert!(requests.has_next_page());
+        assert!(!path.exists(), "the cursor is saved once the scrape ends");
+
+        let (url, _) = requests.request(&endpoint).unwrap();
+        assert_eq!(query(&url), vec![pair("since", "0"), pair("token", "abc")]);
+        requests.on_response(
+            &url,
+            &parts(None),
+            &Bytes::from(r#"{"next": null, "last": "9"}"#),
+            &[],
+        );
+        assert!(!requests.has_next_page());
+        assert_eq!(load_checkpoint(&path).unwrap(), Some(Value::from("9")));
+
+        // A restarted source resumes from the persisted cursor.
+        let mut requests = ScrapeRequests::new(
+            endpoint.clone(),
+            HashMap::from([("since".to_owned(), vec!["{{ cursor }}".to_owned()])]),
+            None,
+            None,
+            Some(&cursor),
+            Some(path),
+            false,
+        )
+        .unwrap();
+        let (url, _) = requests.request(&endpoint).unwrap();
+        assert_eq!(query(&url), vec![pair("since", "9")]);
+    }
+
+    /// Runs a scrape of a single response, returning its events.
+    fn scrape(requests: &mut ScrapeRequests, endpoint: &Uri, last: &str) -> Vec<Event> {
+        let mut events = vec![Event::Log(LogEvent::from("event"))];
+        let (url, _) = requests.request(endpoint).unwrap();
+        requests.on_response(
+            &url,
+            &parts(None),
+            &Bytes::from(format!(r#"{{"last": "{last}"}}"#)),
+            &events,
+        );
+        requests.add_batch_notifier(&mut events);
+        assert!(!requests.has_next_page());
+        events
+    }
+
+    async fn wait_for_checkpoint(path: &Path) -> Option<Value> {
+        for _ in 0..100 {
+            if path.exists() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        load_checkpoint(path).unwrap()
+    }
+
+    #[tokio::test]
+    async fn saves_cursor_once_acknowledged() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("checkpoint.json");
+        let endpoint = Uri::from_static("http://localhost/items");
+        let cursor = CursorConfig {
+            source: "parse_json!(.body).last".to_owned(),
+            initial: Some("0".to_owned()),
+        };
+        // Each scrape starts from a copy of the requests, as the source does.
+        let requests = ScrapeRequests::new(
+            endpoint.clone(),
+            HashMap::from([("since".to_owned(), vec!["{{ cursor }}".to_owned()])]),
+            None,
+            None,
+            Some(&cursor),
+            Some(path.clone()),
+            true,
+        )
+        .unwrap();
+
+        let events = scrape(&mut requests.clone(), &endpoint, "5");
+        assert!(
+            !path.exists(),
+            "the cursor is saved once the events are delivered"
+        );
+        // The next scrape resumes from the cursor regardless.
+        let (url, _) = requests.clone().request(&endpoint).unwrap();
+        assert_eq!(query(&url), vec![pair("since", "5")]);
+
+        events[0].metadata().update_status(EventStatus::Delivered);
+        drop(events);
+        assert_eq!(wait_for_checkpoint(&path).await, Some(Value::from("5")));
+
+        // The cursor of a scrape whose events weren't delivered isn't saved.
+        let events = scrape(&mut requests.clone(), &endpoint, "9");
+        events[0].metadata().update_status(EventStatus::Errored);
+        drop(events);
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert_eq!(load_checkpoint(&path).unwrap(), Some(Value::from("5")));
+    }
+}
@@ -13,6 +13,7 @@ use vector_lib::codecs::decoding::{
 use vector_lib::codecs::CharacterDelimitedDecoderConfig;
 use vector_lib::event::Event;
 
+use super::requests::{PaginationConfig, PaginationStrategy};
 use super::HttpClientConfig;
 use crate::test_util::{
     components::{run_and_assert_source_compliance, HTTP_PULL_SOURCE_TAGS},
@@ -93,6 +94,9 @@ async fn bytes_decoding() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         tls: None,
         auth: None,
         log_namespace: None,
@@ -122,6 +126,9 @@ async fn json_decoding_newline_delimited() {
         framing: FramingConfig::NewlineDelimited(Default::default()),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         tls: None,
         auth: None,
         log_namespace: None,
@@ -156,6 +163,9 @@ async fn json_decoding_character_delimited() {
         }),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         tls: None,
         auth: None,
         log_namespace: None,
@@ -190,6 +200,9 @@ async fn request_query_applied() {
         framing: default_framing_message_based(),
         headers: HashMap::new(),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         tls: None,
         auth: None,
         log_namespace: None,
@@ -254,6 +267,9 @@ async fn headers_applied() {
             vec!["bazz".to_string(), "bizz".to_string()],
         )]),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         auth: None,
         tls: None,
         log_namespace: None,
@@ -283,9 +299,83 @@ async fn accept_header_override() {
         framing: default_framing_message_based(),
         headers: HashMap::from([("ACCEPT".to_string(), vec!["application/json".to_string()])]),
         method: HttpMethod::Get,
+        body: None,
+        pagination: None,
+        cursor: None,
         auth: None,
         tls: None,
         log_namespace: None,
     })
     .await;
 }
+
+/// The pages linked by the `Link` header of the responses should all be requested.
+#[tokio::test]
+async fn link_header_pagination() {
+    let in_addr = next_addr();
+
+    let dummy_endpoint = warp::path!("endpoint")
+        .and(warp::query::<HashMap<String, String>>())
+        .map(|query: HashMap<String, String>| {
+            let page = query.get("page").cloned().unwrap_or_else(|| "1".to_owned());
+            let mut response = warp::http::Response::builder();
+            if page == "1" {
+                response = response.header("Link", r#"</endpoint?page=2>; rel="next""#);
+            }
+            response
+                .body(format!(r#"{{"page" : "{}"}}"#, page))
+                .unwrap()
+        });
+
+    tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+    wait_for_tcp(in_addr).await;
+
+    let events = run_compliance(HttpClientConfig {
+        endpoint: format!("http://{}/endpoint", in_addr),
+        interval: INTERVAL,
+        timeout: TIMEOUT,
+        decoding: DeserializerConfig::Json(Default::default()),
+        pagination: Some(PaginationConfig {
+            strategy: PaginationStrategy::LinkHeader,
+            max_pages: 10,
+        }),
+        ..Default::default()
+    })
+    .await;
+
+    let pages = events
+        .iter()
+        .map(|event| event.as_log()["page"].to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    assert!(pages.contains(&"1".to_owned()));
+    assert!(pages.contains(&"2".to_owned()));
+}
+
+/// The configured body should be sent with the requests.
+#[tokio::test]
+async fn request_body() {
+    let in_addr = next_addr();
+
+    let dummy_endpoint = warp::path!("endpoint")
+        .and(warp::post())
+        .and(warp::body::bytes())
+        .map(|body: bytes::Bytes| body.to_vec());
+
+    tokio::spawn(warp::serve(dummy_endpoint).run(in_addr));
+    wait_for_tcp(in_addr).await;
+
+    let events = run_compliance(HttpClientConfig {
+        endpoint: format!("http://{}/endpoint", in_addr),
+        interval: INTERVAL,
+        timeout: TIMEOUT,
+        decoding: DeserializerConfig::Json(Default::default()),
+        method: HttpMethod::Post,
+        body: Some(r#"{"data" : "foo"}"#.to_owned()),
+        ..Default::default()
+    })
+    .await;
+
+    for event in events {
+        assert_eq!(event.as_log()["data"], "foo".into());
+    }
+}
@@ -5,8 +5,18 @@ use chrono::Utc;
 use http::StatusCode;
 use http_serde;
 use tokio_util::codec::Decoder as _;
-use vrl::value::{kind::Collection, Kind};
-use warp::http::{HeaderMap, HeaderValue};
+use vrl::{
+    compiler::{
+        runtime::Runtime, CompilationResult, CompileConfig, Program, TargetValue, TypeState,
+    },
+    diagnostic::Formatter,
+    value::{kind::Collection, Kind, ObjectMap, Secrets},
+};
+use warp::{
+    http::{header::CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue},
+    reply::Response,
+    Reply,
+};
 
 use vector_lib::codecs::{
     decoding::{DeserializerConfig, FramingConfig},
@@ -16,8 +26,10 @@ use vector_lib::codecs::{
 use vector_lib::configurable::configurable_component;
 use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path, path};
 use vector_lib::{
+    compile_vrl,
     config::{DataType, LegacyKey, LogNamespace},
     schema::Definition,
+    TimeZone,
 };
 
 use crate::{
@@ -28,9 +40,10 @@ use crate::{
     },
     event::{Event, Value},
     http::KeepaliveConfig,
+    internal_events::HttpResponseProgramError,
     serde::{bool_or_struct, default_decoding},
     sources::util::{
-        http::{add_query_parameters, HttpMethod},
+        http::{add_query_parameters, path_parameters, HttpMethod, HttpRoute, HttpSourceRequest},
         Encoding, ErrorMessage, HttpSource, HttpSourceAuthConfig,
     },
     tls::TlsEnableableConfig,
@@ -121,9 +134,13 @@ pub struct SimpleHttpConfig {
     strict_path: bool,
 
     /// The URL path on which log event POST requests are sent.
+    ///
+    /// Segments written as `{name}` match any segment, and their values are stored in the
+    /// `path_parameters` of the events.
     #[serde(default = "default_path")]
     #[configurable(metadata(docs::examples = "/event/path"))]
     #[configurable(metadata(docs::examples = "/logs"))]
+    #[configurable(metadata(docs::examples = "/hooks/{tenant}"))]
     path: String,
 
     /// The event key in which the requested URL path used to send the request is stored.
@@ -147,6 +164,31 @@ pub struct SimpleHttpConfig {
     #[serde(default = "default_http_response_code")]
     response_code: StatusCode,
 
+    /// A VRL program that computes the response to requests whose events were delivered.
+    ///
+    /// By default, the response is empty, with the `response_code` status.
+    #[configurable(derived)]
+    response: Option<HttpResponseConfig>,
+
+    /// A VRL program that answers handshake requests, such as the verification requests of
+    /// webhooks.
+    ///
+    /// When the program resolves to a response instead of `null`, the request is answered with
+    /// that response and no events are built from it.
+    #[configurable(derived)]
+    handshake: Option<HttpResponseConfig>,
+
+    /// The routes to serve on the address, instead of the single `path`.
+    ///
+    /// The options that a route leaves unset fall back to the top-level options.
+    #[serde(default)]
+    routes: Vec<HttpRouteConfig>,
+
+    /// The event key in which the name of the route that received the request is stored.
+    #[serde(default = "default_route_key")]
+    #[configurable(metadata(docs::examples = "vector_http_route"))]
+    route_key: OptionalValuePath,
+
     #[configurable(derived)]
     tls: Option<TlsEnableableConfig>,
 
@@ -170,14 +212,119 @@ pub struct SimpleHttpConfig {
     keepalive: KeepaliveConfig,
 }
 
+/// A route served by the `http_server` source.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HttpRouteConfig {
+    /// The name of the route, stored in the events received by the route.
+    #[configurable(metadata(docs::examples = "slack"))]
+    name: String,
+
+    /// The URL path of the route.
+    ///
+    /// Segments written as `{name}` match any segment, and their values are stored in the
+    /// `path_parameters` of the events.
+    #[configurable(metadata(docs::examples = "/slack/events"))]
+    #[configurable(metadata(docs::examples = "/hooks/{tenant}"))]
+    path: String,
+
+    /// The HTTP method of the route.
+    method: Option<HttpMethod>,
+
+    /// Whether or not to treat the `path` of the route as an absolute path.
+    strict_path: Option<bool>,
+
+    #[configurable(derived)]
+    auth: Option<HttpSourceAuthConfig>,
+
+    #[configurable(derived)]
+    framing: Option<FramingConfig>,
+
+    #[configurable(derived)]
+    decoding: Option<DeserializerConfig>,
+
+    /// A VRL program that computes the response to requests whose events were delivered.
+    #[configurable(derived)]
+    response: Option<HttpResponseConfig>,
+
+    /// A VRL program that answers handshake requests.
+    #[configurable(derived)]
+    handshake: Option<HttpResponseConfig>,
+}
+
+/// A VRL program that computes the response to a request.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HttpResponseConfig {
+    /// The VRL program.
+    ///
+    /// The program runs against an object that describes the request, with its `route`,
+    /// `method`, `path`, `path_parameters`, `headers`, `query_parameters`, and `body`. It resolves
+    /// to an object whose optional `status`, `headers`, and `body` fields make the response. A
+    /// `body` that is not a string is encoded as JSON.
+    #[configurable(metadata(
+        docs::examples = "{ \"status\": 202, \"body\": { \"route\": .route } }"
+    ))]
+    #[configurable(metadata(
+        docs::examples = "if .query_parameters.validationToken != null { { \"body\": .query_parameters.validationToken } } else { null }"
+    ))]
+    source: String,
+}
+
+impl HttpResponseConfig {
+    fn build(&self) -> crate::Result<ResponseProgram> {
+        let functions = vrl::stdlib::all()
+            .into_iter()
+            .chain(vector_vrl_functions::all())
+            .collect::<Vec<_>>();
+
+        let CompilationResult {
+            program,
+            warnings,
+            config: _,
+        } = compile_vrl(
+            &self.source,
+            &functions,
+            &TypeState::default(),
+            CompileConfig::default(),
+        )
+        .map_err(|diagnostics| {
+            Formatter::new(&self.source, diagnostics)
+                .colored()
+                .to_string()
+        })?;
+
+        if !warnings.is_empty() {
+            let warnings = Formatter::new(&self.source, warnings).colored().to_string();
+            warn!(message = "VRL compilation warning.", %warnings);
+        }
+
+        Ok(ResponseProgram { program })
+    }
+}
+
 impl SimpleHttpConfig {
     /// Builds the `schema::Definition` for this source using the provided `LogNamespace`.
     fn schema_definition(&self, log_namespace: LogNamespace) -> Definition {
-        let mut schema_definition = self
-            .decoding
-            .as_ref()
-            .unwrap_or(&default_decoding())
-            .schema_definition(log_namespace)
+        let decodings = match self.routes.is_empty() {
+            true => vec![self.decoding.as_ref()],
+            false => self
+                .routes
+                .iter()
+                .map(|route| route.decoding.as_ref().or(self.decoding.as_ref()))
+                .collect(),
+        };
+        let mut schema_definition = decodings
+            .into_iter()
+            .map(|decoding| {
+                decoding
+                    .unwrap_or(&default_decoding())
+                    .schema_definition(log_namespace)
+            })
+            .reduce(Definition::merge)
+            .expect("there is at least one decoding")
             .with_source_metadata(
                 SimpleHttpConfig::NAME,
                 self.path_key.path.clone().map(LegacyKey::InsertIfEmpty),
@@ -210,6 +357,29 @@ impl SimpleHttpConfig {
             )
             .with_standard_vector_source_metadata();
 
+        if !self.routes.is_empty() {
+            schema_definition = schema_definition.with_source_metadata(
+                SimpleHttpConfig::NAME,
+                self.route_key.path.clone().map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("route"),
+                Kind::bytes(),
+                None,
+            );
+        }
+
+        let mut paths = std::iter::once(self.path.as_str())
+            .chain(self.routes.iter().map(|route| route.path.as_str()));
+        if paths.any(|path| path.contains('{')) {
+            // for metadata that is added to the events dynamically from the path parameters
+            schema_definition = schema_definition.with_source_metadata(
+                SimpleHttpConfig::NAME,
+                None,
+                &owned_value_path!("path_parameters"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())).or_undefined(),
+                None,
+            );
+        }
+
         // for metadata that is added to the events dynamically from config options
         if log_namespace == LogNamespace::Legacy {
             schema_definition = schema_definition.unknown_fields(Kind::bytes());
@@ -219,7 +389,15 @@ impl SimpleHttpConfig {
     }
 
     fn get_decoding_config(&self) -> crate::Result<DecodingConfig> {
-        if self.encoding.is_some() && (self.framing.is_some() || self.decoding.is_some()) {
+        self.decoding_config(self.framing.as_ref(), self.decoding.as_ref())
+    }
+
+    fn decoding_config(
+        &self,
+        framing: Option<&FramingConfig>,
+        decoding: Option<&DeserializerConfig>,
+    ) -> crate::Result<DecodingConfig> {
+        if self.encoding.is_some() && (framing.is_some() || decoding.is_some()) {
             return Err("Using `encoding` is deprecated and does not have any effect when `decoding` or `framing` is provided. Configure `framing` and `decoding` instead.".into());
         }
 
@@ -243,10 +421,9 @@ impl SimpleHttpConfig {
                 ),
             }
         } else {
-            let decoding = self.decoding.clone().unwrap_or_else(default_decoding);
-            let framing = self
-                .framing
-                .clone()
+            let decoding = decoding.cloned().unwrap_or_else(default_decoding);
+            let framing = framing
+                .cloned()
                 .unwrap_or_else(|| decoding.default_stream_framing());
             (framing, decoding)
         };
@@ -257,6 +434,46 @@ impl SimpleHttpConfig {
             self.log_namespace.unwrap_or(false).into(),
         ))
     }
+
+    /// Builds the source of the given route, or of the single `path` without routes.
+    fn build_source(
+        &self,
+        route: Option<&HttpRouteConfig>,
+        log_namespace: LogNamespace,
+    ) -> crate::Result<SimpleHttpSource> {
+        let decoder = match route {
+            Some(route) => self.decoding_config(
+                route.framing.as_ref().or(self.framing.as_ref()),
+                route.decoding.as_ref().or(self.decoding.as_ref()),
+            )?,
+            None => self.get_decoding_config()?,
+        }
+        .build()?;
+        let response = route
+            .and_then(|route| route.response.as_ref())
+            .or(self.response.as_ref())
+            .map(HttpResponseConfig::build)
+            .transpose()?;
+        let handshake = route
+            .and_then(|route| route.handshake.as_ref())
+            .or(self.handshake.as_ref())
+            .map(HttpResponseConfig::build)
+            .transpose()?;
+
+        Ok(SimpleHttpSource {
+            headers: build_param_matcher(&remove_duplicates(self.headers.clone(), "headers"))?,
+            query_parameters: remove_duplicates(self.query_parameters.clone(), "query_parameters"),
+            path_key: self.path_key.clone(),
+            host_key: self.host_key.clone(),
+            route: route.map(|route| route.name.clone()),
+            route_key: self.route_key.clone(),
+            route_path: route.map_or_else(|| self.path.clone(), |route| route.path.clone()),
+            response,
+            handshake,
+            decoder,
+            log_namespace,
+        })
+    }
 }
 
 impl Default for SimpleHttpConfig {
@@ -273,6 +490,10 @@ impl Default for SimpleHttpConfig {
             host_key: default_host_key(),
             method: default_http_method(),
             response_code: default_http_response_code(),
+            response: None,
+            handshake: None,
+            routes: Vec::new(),
+            route_key: default_route_key(),
             strict_path: true,
             framing: None,
             decoding: Some(default_decoding()),
@@ -301,6 +522,10 @@ fn default_host_key() -> OptionalValuePath {
     OptionalValuePath::none()
 }
 
+fn default_route_key() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!("route"))
+}
+
 const fn default_http_response_code() -> StatusCode {
     StatusCode::OK
 }
@@ -350,17 +575,34 @@ fn build_param_matcher(list: &[String]) -> crate::Result<Vec<HttpConfigParamKind
 #[typetag::serde(name = "http_server")]
 impl SourceConfig for SimpleHttpConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        let decoder = self.get_decoding_config()?.build()?;
         let log_namespace = cx.log_namespace(self.log_namespace);
 
-        let source = SimpleHttpSource {
-            headers: build_param_matcher(&remove_duplicates(self.headers.clone(), "headers"))?,
-            query_parameters: remove_duplicates(self.query_parameters.clone(), "query_parameters"),
-            path_key: self.path_key.clone(),
-            host_key: self.host_key.clone(),
-            decoder,
-            log_namespace,
-        };
+        if !self.routes.is_empty() {
+            let routes = self
+                .routes
+                .iter()
+                .map(|route| {
+                    Ok(HttpRoute {
+                        source: self.build_source(Some(route), log_namespace)?,
+                        path: route.path.clone(),
+                        method: route.method.unwrap_or(self.method),
+                        strict_path: route.strict_path.unwrap_or(self.strict_path),
+                        response_code: self.response_code,
+                        auth: route.auth.clone().or_else(|| self.auth.clone()),
+                    })
+                })
+                .collect::<crate::Result<Vec<_>>>()?;
+            return SimpleHttpSource::run_routes(
+                routes,
+                self.address,
+                &self.tls,
+                cx,
+                self.acknowledgements,
+                self.keepalive.clone(),
+            );
+        }
+
+        let source = self.build_source(None, log_namespace)?;
         source.run(
             self.address,
             self.path.as_str(),
@@ -382,13 +624,16 @@ impl SourceConfig for SimpleHttpConfig {
 
         let schema_definition = self.schema_definition(log_namespace);
 
-        vec![SourceOutput::new_maybe_logs(
-            self.decoding
-                .as_ref()
-                .map(|d| d.output_type())
-                .unwrap_or(DataType::Log),
-            schema_definition,
-        )]
+        let output_type = |decoding: Option<&DeserializerConfig>| {
+            decoding.map(|d| d.output_type()).unwrap_or(DataType::Log)
+        };
+        let output_type = self
+            .routes
+            .iter()
+            .map(|route| output_type(route.decoding.as_ref().or(self.decoding.as_ref())))
+            .fold(output_type(self.decoding.as_ref()), |a, b| a | b);
+
+        vec![SourceOutput::new_maybe_logs(output_type, schema_definition)]
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -406,10 +651,143 @@ struct SimpleHttpSource {
     query_parameters: Vec<String>,
     path_key: OptionalValuePath,
     host_key: OptionalValuePath,
+    /// The name of the route served by this source, if any.
+    route: Option<String>,
+    route_key: OptionalValuePath,
+    /// The path of the route served by this source, used to extract the path parameters.
+    route_path: String,
+    response: Option<ResponseProgram>,
+    handshake: Option<ResponseProgram>,
     decoder: Decoder,
     log_namespace: LogNamespace,
 }
 
+impl SimpleHttpSource {
+    /// Describes the request to the response programs.
+    fn request_value(&self, request: &HttpSourceRequest) -> Value {
+        let headers = request
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.as_str().into(),
+                    Value::from(Bytes::copy_from_slice(value.as_bytes())),
+                )
+            })
+            .collect::<ObjectMap>();
+        let query_parameters = request
+            .query_parameters
+            .iter()
+            .map(|(name, value)| (name.as_str().into(), Value::from(value.as_str())))
+            .collect::<ObjectMap>();
+        let path_parameters = path_parameters(&self.route_path, &request.path)
+            .into_iter()
+            .map(|(name, value)| (name.into(), Value::from(value)))
+            .collect::<ObjectMap>();
+
+        Value::Object(ObjectMap::from([
+            ("route".into(), Value::from(self.route.clone())),
+            ("method".into(), Value::from(request.method.as_str())),
+            ("path".into(), Value::from(request.path.as_str())),
+            ("path_parameters".into(), Value::Object(path_parameters)),
+            ("headers".into(), Value::Object(headers)),
+            ("query_parameters".into(), Value::Object(query_parameters)),
+            ("body".into(), Value::from(request.body.clone())),
+        ]))
+    }
+}
+
+/// A compiled `HttpResponseConfig`.
+#[derive(Clone, Debug)]
+struct ResponseProgram {
+    program: Program,
+}
+
+impl ResponseProgram {
+    /// Runs the program against the request, returning `None` if it fails.
+    fn run(&self, request: Value) -> Option<Value> {
+        let mut target = TargetValue {
+            value: request,
+            metadata: Value::Object(ObjectMap::new()),
+            secrets: Secrets::default(),
+        };
+        match Runtime::default().resolve(&mut target, &self.program, &TimeZone::default()) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                emit!(HttpResponseProgramError {
+                    error: &error.to_string()
+                });
+                None
+            }
+        }
+    }
+}
+
+/// Builds a response from the value resolved by a response program, which is an object with
+/// optional `status`, `headers`, and `body` fields.
+fn response_from_value(value: Value, default_status: StatusCode) -> Result<Response, String> {
+    let Value::Object(mut object) = value else {
+        return Err(format!(
+            "the response must be an object, got {}",
+            value.kind_str()
+        ));
+    };
+
+    let status = match object.remove("status") {
+        None | Some(Value::Null) => default_status,
+        Some(Value::Integer(status)) => u16::try_from(status)
+            .ok()
+            .and_then(|status| StatusCode::from_u16(status).ok())
+            .ok_or_else(|| format!("invalid status code {status}"))?,
+        Some(status) => {
+            return Err(format!(
+                "the status must be an integer, got {}",
+                status.kind_str()
+            ))
+        }
+    };
+
+    let mut headers = HeaderMap::new();
+    let body = match object.remove("body") {
+        None | Some(Value::Null) => Bytes::new(),
+        Some(Value::Bytes(body)) => body,
+        Some(body) => {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+            serde_json::to_vec(&body)
+                .map_err(|error| format!("failed encoding the body: {error}"))?
+                .into()
+        }
+    };
+
+    match object.remove("headers") {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(fields)) => {
+            for (name, value) in fields {
+                let value = match value {
+                    Value::Bytes(bytes) => bytes,
+                    value => value.to_string_lossy().into_owned().into(),
+                };
+                let name = HeaderName::from_bytes(name.as_str().as_bytes())
+                    .map_err(|error| format!("invalid header name {name:?}: {error}"))?;
+                let value = HeaderValue::from_maybe_shared(value)
+                    .map_err(|error| format!("invalid value of header {name}: {error}"))?;
+                headers.insert(name, value);
+            }
+        }
+        Some(headers) => {
+            return Err(format!(
+                "the headers must be an object, got {}",
+                headers.kind_str()
+            ))
+        }
+    }
+
+    let mut response = Response::new(body.into());
+    *response.status_mut() = status;
+    *response.headers_mut() = headers;
+    Ok(response)
+}
+
 impl HttpSource for SimpleHttpSource {
     /// Enriches the log events with metadata for the `request_path` and for each of the headers.
     /// Non-log events are skipped.
@@ -422,6 +800,7 @@ impl HttpSource for SimpleHttpSource {
         source_ip: Option<&SocketAddr>,
     ) {
         let now = Utc::now();
+        let path_parameters = path_parameters(&self.route_path, request_path);
         for event in events.iter_mut() {
             match event {
                 Event::Log(log) => {
@@ -434,6 +813,26 @@ impl HttpSource for SimpleHttpSource {
                         request_path.to_owned(),
                     );
 
+                    if let Some(route) = &self.route {
+                        self.log_namespace.insert_source_metadata(
+                            SimpleHttpConfig::NAME,
+                            log,
+                            self.route_key.path.as_ref().map(LegacyKey::InsertIfEmpty),
+                            path!("route"),
+                            route.as_str(),
+                        );
+                    }
+
+                    for (name, value) in &path_parameters {
+                        self.log_namespace.insert_source_metadata(
+                            SimpleHttpConfig::NAME,
+                            log,
+                            Some(LegacyKey::InsertIfEmpty(path!(name.as_str()))),
+                            path!("path_parameters", name.as_str()),
+                            value.as_str(),
+                        );
+                    }
+
                     for h in &self.headers {
                         match h {
                             // Add each non-wildcard containing header that was specified
@@ -541,6 +940,36 @@ impl HttpSource for SimpleHttpSource {
         Ok(events)
     }
 
+    fn handshake(&self, request: &HttpSourceRequest) -> Option<Response> {
+        let value = self.handshake.as_ref()?.run(self.request_value(request))?;
+        if value.is_null() {
+            return None;
+        }
+        match response_from_value(value, StatusCode::OK) {
+            Ok(response) => Some(response),
+            Err(error) => {
+                emit!(HttpResponseProgramError { error: &error });
+                None
+            }
+        }
+    }
+
+    fn build_response(&self, response_code: StatusCode, request: &HttpSourceRequest) -> Response {
+        let Some(response) = &self.response else {
+            return response_code.into_response();
+        };
+        response
+            .run(self.request_value(request))
+            .and_then(|value| match response_from_value(value, response_code) {
+                Ok(response) => Some(response),
+                Err(error) => {
+                    emit!(HttpResponseProgramError { error: &error });
+                    None
+                }
+            })
+            .unwrap_or_else(|| response_code.into_response())
+    }
+
     fn enable_source_ip(&self) -> bool {
         self.host_key.path.is_some()
     }
@@ -622,6 +1051,10 @@ mod tests {
                 encoding: None,
                 query_parameters,
                 response_code,
+                response: None,
+                handshake: None,
+                routes: Vec::new(),
+                route_key: super::default_route_key(),
                 tls: None,
                 auth: None,
                 strict_path,
@@ -1559,6 +1992,120 @@ mod tests {
         assert_eq!(definitions, Some(expected_definition))
     }
 
+    async fn source_from_toml(config: &str) -> (impl Stream<Item = Event>, SocketAddr) {
+        let (sender, recv) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let address = next_addr();
+        let config: SimpleHttpConfig =
+            toml::from_str(&format!("address = \"{address}\"\n{config}")).unwrap();
+        let context = SourceContext::new_test(sender, None);
+
+        tokio::spawn(async move {
+            config.build(context).await.unwrap().await.unwrap();
+        });
+        wait_for_tcp(address).await;
+        (recv, address)
+    }
+
+    #[tokio::test]
+    async fn http_routes() {
+        components::init_test();
+        let (rx, addr) = source_from_toml(
+            r#"
+            [[routes]]
+            name = "tenants"
+            path = "/tenants/{tenant}"
+
+            [[routes]]
+            name = "health"
+            path = "/health"
+            "#,
+        )
+        .await;
+
+        let events = spawn_collect_n(
+            async move {
+                assert_eq!(200, send_with_path(addr, "one", "/tenants/acme").await);
+                assert_eq!(200, send_with_path(addr, "two", "/health").await);
+                assert_eq!(404, send_with_path(addr, "three", "/other").await);
+            },
+            rx,
+            2,
+        )
+        .await;
+
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "one".into());
+        assert_eq!(log["route"], "tenants".into());
+        assert_eq!(log["tenant"], "acme".into());
+        assert_eq!(log["path"], "/tenants/acme".into());
+
+        let log = events[1].as_log();
+        assert_eq!(log["message"], "two".into());
+        assert_eq!(log["route"], "health".into());
+        assert!(log.get("tenant").is_none());
+    }
+
+    #[tokio::test]
+    async fn http_response_program() {
+        components::init_test();
+        let (rx, addr) = source_from_toml(
+            r#"
+            response.source = '{ "status": 202, "headers": { "x-request-path": .path }, "body": { "length": strlen!(.body) } }'
+            "#,
+        )
+        .await;
+
+        spawn_collect_n(
+            async move {
+                let response = reqwest::Client::new()
+                    .post(format!("http://{}/events", addr))
+                    .body("hello")
+                    .send()
+                    .await
+                    .unwrap();
+                assert_eq!(202, response.status().as_u16());
+                assert_eq!(response.headers()["x-request-path"], "/events");
+                assert_eq!(response.headers()["content-type"], "application/json");
+                assert_eq!(response.text().await.unwrap(), r#"{"length":5}"#);
+            },
+            rx,
+            1,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn http_handshake_program() {
+        components::init_test();
+        let (rx, addr) = source_from_toml(
+            r#"
+            handshake.source = 'if exists(.query_parameters.validationToken) { { "body": .query_parameters.validationToken } } else { null }'
+            "#,
+        )
+        .await;
+
+        let events = spawn_collect_n(
+            async move {
+                let response = reqwest::Client::new()
+                    .post(format!("http://{}/?validationToken=secret", addr))
+                    .body("ignored")
+                    .send()
+                    .await
+                    .unwrap();
+                assert_eq!(200, response.status().as_u16());
+                assert_eq!(response.text().await.unwrap(), "secret");
+
+                assert_eq!(200, send(addr, "hello").await);
+            },
+            rx,
+            1,
+        )
+        .await;
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].as_log()["message"], "hello".into());
+    }
+
     #[test]
     fn validate_remove_duplicates() {
         let mut list = vec![
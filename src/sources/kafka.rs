@@ -41,18 +41,21 @@ use vector_lib::codecs::{
     decoding::{DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
-use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath};
+use vector_lib::lookup::{
+    lookup_v2::OptionalValuePath, owned_value_path, path, OwnedTargetPath, OwnedValuePath,
+};
 
 use vector_lib::configurable::configurable_component;
 use vector_lib::finalizer::OrderedFinalizer;
 use vector_lib::{
-    config::{LegacyKey, LogNamespace},
+    config::{ComponentKey, DataType, LegacyKey, LogNamespace},
     EstimatedJsonEncodedSizeOf,
 };
 use vrl::value::{kind::Collection, Kind, ObjectMap};
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
+    common::schema_registry::{SchemaRegistry, SchemaRegistryConfig},
     config::{
         log_schema, LogSchema, SourceAcknowledgementsConfig, SourceConfig, SourceContext,
         SourceOutput,
@@ -60,7 +63,7 @@ use crate::{
     event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{
         KafkaBytesReceived, KafkaEventsReceived, KafkaOffsetUpdateError, KafkaReadError,
-        StreamClosedError,
+        KafkaSchemaRegistryDecodeError, KafkaSchemaRegistryRequestError, StreamClosedError,
     },
     kafka,
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
@@ -68,6 +71,11 @@ use crate::{
     SourceSender,
 };
 
+/// The delay before fetching a schema again after the registry failed, doubled on each failure.
+const SCHEMA_REGISTRY_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+const SCHEMA_REGISTRY_MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("The drain_timeout_ms ({}) must be less than session_timeout_ms ({})", value, session_timeout_ms.as_millis()))]
@@ -241,6 +249,15 @@ pub struct KafkaSourceConfig {
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
 
+    /// Decodes messages in the [Confluent wire format][wire_format] with the schemas of a schema
+    /// registry.
+    ///
+    /// When set, `framing` and `decoding` are ignored, and each message is decoded with the Avro or
+    /// Protobuf schema whose ID prefixes it. Schemas are cached once fetched.
+    ///
+    /// [wire_format]: https://docs.confluent.io/platform/current/schema-registry/fundamentals/serdes-develop/index.html#wire-format
+    schema_registry: Option<SchemaRegistryConfig>,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -332,6 +349,11 @@ impl SourceConfig for KafkaSourceConfig {
         let decoder =
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
                 .build()?;
+        let schema_registry = self
+            .schema_registry
+            .as_ref()
+            .map(|config| config.build(&cx.proxy))
+            .transpose()?;
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
 
         if let Some(d) = self.drain_timeout_ms {
@@ -348,9 +370,11 @@ impl SourceConfig for KafkaSourceConfig {
 
         Ok(Box::pin(kafka_source(
             self.clone(),
+            cx.key,
             consumer,
             callback_rx,
             decoder,
+            schema_registry,
             cx.out,
             cx.shutdown,
             false,
@@ -362,9 +386,11 @@ impl SourceConfig for KafkaSourceConfig {
         let log_namespace = global_log_namespace.merge(self.log_namespace);
         let keys = self.keys();
 
-        let schema_definition = self
-            .decoding
-            .schema_definition(log_namespace)
+        let schema_definition = match &self.schema_registry {
+            Some(schema_registry) => schema_registry.schema_definition(log_namespace),
+            None => self.decoding.schema_definition(log_namespace),
+        };
+        let schema_definition = schema_definition
             .with_standard_vector_source_metadata()
             .with_source_metadata(
                 Self::NAME,
@@ -409,10 +435,11 @@ impl SourceConfig for KafkaSourceConfig {
                 None,
             );
 
-        vec![SourceOutput::new_maybe_logs(
-            self.decoding.output_type(),
-            schema_definition,
-        )]
+        let output_type = match self.schema_registry {
+            Some(_) => DataType::Log,
+            None => self.decoding.output_type(),
+        };
+        vec![SourceOutput::new_maybe_logs(output_type, schema_definition)]
     }
 
     fn can_acknowledge(&self) -> bool {
@@ -423,9 +450,11 @@ impl SourceConfig for KafkaSourceConfig {
 #[allow(clippy::too_many_arguments)]
 async fn kafka_source(
     config: KafkaSourceConfig,
+    key: ComponentKey,
     consumer: StreamConsumer<KafkaSourceContext>,
     callback_rx: UnboundedReceiver<KafkaCallback>,
     decoder: Decoder,
+    schema_registry: Option<SchemaRegistry>,
     out: SourceSender,
     shutdown: ShutdownSignal,
     eof: bool,
@@ -440,6 +469,9 @@ async fn kafka_source(
         .set(Arc::downgrade(&consumer))
         .expect("Error setting up consumer context.");
 
+    // Lets transactional `kafka` sinks commit the offsets of the consumed messages.
+    let _registration = consumer_group(&config, &consumer, log_namespace).register(key);
+
     // EOF signal allowing the coordination task to tell the kafka client task when all partitions have reached EOF
     let (eof_tx, eof_rx) = eof.then(oneshot::channel::<()>).unzip();
 
@@ -455,8 +487,14 @@ async fn kafka_source(
         let drain_timeout_ms = config
             .drain_timeout_ms
             .map_or(config.session_timeout_ms / 2, Duration::from_millis);
-        let consumer_state =
-            ConsumerStateInner::<Consuming>::new(config, decoder, out, log_namespace, span);
+        let consumer_state = ConsumerStateInner::<Consuming>::new(
+            config,
+            decoder,
+            schema_registry,
+            out,
+            log_namespace,
+            span,
+        );
         tokio::spawn(async move {
             coordinate_kafka_callbacks(
                 consumer,
@@ -483,6 +521,28 @@ async fn kafka_source(
     Ok(())
 }
 
+fn consumer_group(
+    config: &KafkaSourceConfig,
+    consumer: &Arc<StreamConsumer<KafkaSourceContext>>,
+    log_namespace: LogNamespace,
+) -> kafka::KafkaConsumerGroup {
+    let path = |key: &OptionalValuePath, name: &'static str| match log_namespace {
+        LogNamespace::Vector => Some(OwnedTargetPath::metadata(owned_value_path!(
+            KafkaSourceConfig::NAME,
+            name
+        ))),
+        LogNamespace::Legacy => key.path.clone().map(OwnedTargetPath::event),
+    };
+    let consumer = Arc::downgrade(consumer);
+
+    kafka::KafkaConsumerGroup::new(
+        move || consumer.upgrade()?.group_metadata(),
+        path(&config.topic_key, "topic"),
+        path(&config.partition_key, "partition"),
+        path(&config.offset_key, "offset"),
+    )
+}
+
 /// ConsumerStateInner implements a small struct/enum-based state machine.
 ///
 /// With a ConsumerStateInner<Consuming>, the client is able to spawn new tasks
@@ -503,6 +563,7 @@ async fn kafka_source(
 struct ConsumerStateInner<S> {
     config: KafkaSourceConfig,
     decoder: Decoder,
+    schema_registry: Option<SchemaRegistry>,
     out: SourceSender,
     log_namespace: LogNamespace,
     consumer_state: S,
@@ -564,6 +625,7 @@ impl ConsumerStateInner<Consuming> {
     const fn new(
         config: KafkaSourceConfig,
         decoder: Decoder,
+        schema_registry: Option<SchemaRegistry>,
         out: SourceSender,
         log_namespace: LogNamespace,
         span: Span,
@@ -571,6 +633,7 @@ impl ConsumerStateInner<Consuming> {
         Self {
             config,
             decoder,
+            schema_registry,
             out,
             log_namespace,
             consumer_state: Consuming { span },
@@ -592,6 +655,7 @@ impl ConsumerStateInner<Consuming> {
     ) -> (oneshot::Sender<()>, tokio::task::AbortHandle) {
         let keys = self.config.keys();
         let decoder = self.decoder.clone();
+        let schema_registry = self.schema_registry.clone();
         let log_namespace = self.log_namespace;
         let mut out = self.out.clone();
 
@@ -654,7 +718,7 @@ impl ConsumerStateInner<Consuming> {
                                 topic: msg.topic(),
                                 partition: msg.partition(),
                             });
-                            parse_message(msg, decoder.clone(), &keys, &mut out, acknowledgements, &finalizer, log_namespace).await;
+                            parse_message(msg, decoder.clone(), schema_registry.as_ref(), &keys, &mut out, acknowledgements, &finalizer, log_namespace).await;
                         }
                     },
                 )
@@ -677,6 +741,7 @@ impl ConsumerStateInner<Consuming> {
         let draining = ConsumerStateInner {
             config: self.config,
             decoder: self.decoder,
+            schema_registry: self.schema_registry,
             out: self.out,
             log_namespace: self.log_namespace,
             consumer_state: Draining::new(sig, shutdown, self.consumer_state.span),
@@ -727,6 +792,7 @@ impl ConsumerStateInner<Draining> {
                 ConsumerState::Consuming(ConsumerStateInner {
                     config: self.config,
                     decoder: self.decoder,
+                    schema_registry: self.schema_registry,
                     out: self.out,
                     log_namespace: self.log_namespace,
                     consumer_state: Consuming {
@@ -951,57 +1017,97 @@ fn drive_kafka_consumer(
     });
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn parse_message(
     msg: BorrowedMessage<'_>,
     decoder: Decoder,
+    schema_registry: Option<&SchemaRegistry>,
     keys: &'_ Keys,
     out: &mut SourceSender,
     acknowledgements: bool,
     finalizer: &Option<OrderedFinalizer<FinalizerEntry>>,
     log_namespace: LogNamespace,
 ) {
-    if let Some((count, stream)) = parse_stream(&msg, decoder, keys, log_namespace) {
-        let (batch, receiver) = BatchNotifier::new_with_receiver();
-        let mut stream = stream.map(|event| {
-            // All acknowledgements flow through the normal Finalizer stream so
-            // that they can be handled in one place, but are only tied to the
-            // batch when acknowledgements are enabled
-            if acknowledgements {
-                event.with_batch_notifier(&batch)
-            } else {
-                event
-            }
-        });
-        match out.send_event_stream(&mut stream).await {
-            Err(_) => {
-                emit!(StreamClosedError { count });
-            }
-            Ok(_) => {
-                // Drop stream to avoid borrowing `msg`: "[...] borrow might be used
-                // here, when `stream` is dropped and runs the destructor [...]".
-                drop(stream);
-                if let Some(f) = finalizer.as_ref() {
-                    f.add(msg.into(), receiver)
+    let Some(payload) = msg.payload() else {
+        return; // skip messages with empty payload
+    };
+    let payload = Bytes::copy_from_slice(payload);
+
+    // Messages in the Confluent wire format are decoded with the schema that they refer to.
+    let (decoder, payload) = match schema_registry {
+        None => (decoder, payload),
+        Some(schema_registry) => {
+            let mut retry_in = SCHEMA_REGISTRY_RETRY_DELAY;
+            loop {
+                match schema_registry
+                    .decoder(payload.clone(), log_namespace)
+                    .await
+                {
+                    Ok(decoded) => break decoded,
+                    Err(error) if error.is_permanent() => {
+                        emit!(KafkaSchemaRegistryDecodeError { error });
+                        // The message can never be decoded, so its offset is handled as if it
+                        // was delivered, in order with the other messages of the partition.
+                        let (batch, receiver) = BatchNotifier::new_with_receiver();
+                        drop(batch);
+                        if let Some(f) = finalizer.as_ref() {
+                            f.add(msg.into(), receiver)
+                        }
+                        return;
+                    }
+                    Err(error) => {
+                        // The registry may be unavailable for a while, and the messages after
+                        // this one can't be committed before it anyway.
+                        emit!(KafkaSchemaRegistryRequestError { error, retry_in });
+                        tokio::time::sleep(retry_in).await;
+                        retry_in = (retry_in * 2).min(SCHEMA_REGISTRY_MAX_RETRY_DELAY);
+                    }
                 }
             }
         }
+    };
+
+    let (count, stream) = parse_stream(&msg, payload, decoder, keys, log_namespace);
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let mut stream = stream.map(|event| {
+        // All acknowledgements flow through the normal Finalizer stream so
+        // that they can be handled in one place, but are only tied to the
+        // batch when acknowledgements are enabled
+        if acknowledgements {
+            event.with_batch_notifier(&batch)
+        } else {
+            event
+        }
+    });
+    match out.send_event_stream(&mut stream).await {
+        Err(_) => {
+            emit!(StreamClosedError { count });
+        }
+        Ok(_) => {
+            // Drop stream to avoid borrowing `msg`: "[...] borrow might be used
+            // here, when `stream` is dropped and runs the destructor [...]".
+            drop(stream);
+            if let Some(f) = finalizer.as_ref() {
+                f.add(msg.into(), receiver)
+            }
+        }
     }
 }
 
 // Turn the received message into a stream of parsed events.
 fn parse_stream<'a>(
     msg: &BorrowedMessage<'a>,
+    payload: Bytes,
     decoder: Decoder,
     keys: &'a Keys,
     log_namespace: LogNamespace,
-) -> Option<(usize, impl Stream<Item = Event> + 'a)> {
-    let payload = msg.payload()?; // skip messages with empty payload
-
+) -> (usize, impl Stream<Item = Event> + 'a) {
     let rmsg = ReceivedMessage::from(msg);
 
-    let payload = Cursor::new(Bytes::copy_from_slice(payload));
+    let capacity = payload.len();
+    let payload = Cursor::new(payload);
 
-    let mut stream = FramedRead::with_capacity(payload, decoder, msg.payload_len());
+    let mut stream = FramedRead::with_capacity(payload, decoder, capacity);
     let (count, _) = stream.size_hint();
     let stream = stream! {
         while let Some(result) = stream.next().await {
@@ -1029,7 +1135,7 @@ fn parse_stream<'a>(
         }
     }
     .boxed();
-    Some((count, stream))
+    (count, stream)
 }
 
 #[derive(Clone, Debug)]
@@ -1826,9 +1932,11 @@ mod integration_test {
 
         tokio::spawn(kafka_source(
             config,
+            ComponentKey::from("kafka"),
             consumer,
             callback_rx,
             decoder,
+            None,
             out,
             shutdown,
             eof,
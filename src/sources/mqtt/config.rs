@@ -0,0 +1,275 @@
+use std::time::Duration;
+
+use rand::Rng;
+use rumqttc::{MqttOptions, TlsConfiguration, Transport};
+use snafu::{ResultExt, Snafu};
+use vector_lib::codecs::decoding::{DeserializerConfig, FramingConfig};
+use vector_lib::config::{LegacyKey, LogNamespace, SourceAcknowledgementsConfig};
+use vector_lib::configurable::configurable_component;
+use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path};
+use vector_lib::tls::TlsError;
+use vrl::value::Kind;
+
+use super::source::MqttSource;
+use crate::{
+    codecs::DecodingConfig,
+    config::{SourceConfig, SourceContext, SourceOutput},
+    mqtt::MqttQoS,
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+#[derive(Debug, Snafu)]
+pub enum ConfigurationError {
+    #[snafu(display("Client ID is not allowed to be empty."))]
+    EmptyClientId,
+    #[snafu(display("Username and password must be either both provided or both missing."))]
+    InvalidCredentials,
+    #[snafu(display("At least one topic must be provided."))]
+    NoTopics,
+    #[snafu(display("TLS error: {}", source))]
+    Tls { source: TlsError },
+}
+
+/// Configuration for the `mqtt` source.
+#[configurable_component(source("mqtt", "Collect logs from an MQTT broker."))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSourceConfig {
+    /// MQTT server address (The broker’s domain name or IP address).
+    #[configurable(metadata(docs::examples = "mqtt.example.com", docs::examples = "127.0.0.1"))]
+    #[derivative(Default(value = "\"localhost\".into()"))]
+    pub host: String,
+
+    /// TCP port of the MQTT server to connect to.
+    #[serde(default = "default_port")]
+    #[derivative(Default(value = "default_port()"))]
+    pub port: u16,
+
+    /// MQTT username.
+    pub user: Option<String>,
+
+    /// MQTT password.
+    pub password: Option<String>,
+
+    /// MQTT client ID.
+    ///
+    /// The broker only resumes a persistent session for the same client ID, so this must be set
+    /// for messages published while Vector is stopped to be delivered once it is back.
+    pub client_id: Option<String>,
+
+    /// Connection keep-alive interval.
+    #[serde(default = "default_keep_alive")]
+    #[derivative(Default(value = "default_keep_alive()"))]
+    pub keep_alive: u16,
+
+    /// If set to true, the MQTT session is cleaned on login.
+    ///
+    /// Otherwise the broker keeps the subscriptions and the messages published to them while the
+    /// source is disconnected.
+    #[serde(default = "default_clean_session")]
+    #[derivative(Default(value = "default_clean_session()"))]
+    pub clean_session: bool,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    /// MQTT topic filters to subscribe to.
+    ///
+    /// The single-level (`+`) and multi-level (`#`) wildcards are supported.
+    #[configurable(metadata(docs::examples = "sensors/+/temperature"))]
+    #[configurable(metadata(docs::examples = "vector/#"))]
+    pub topics: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_qos")]
+    pub quality_of_service: MqttQoS,
+
+    /// Overrides the name of the log field used to add the topic to each event.
+    ///
+    /// The value is the topic the message was published to, rather than the filter that matched
+    /// it.
+    ///
+    /// By default, `"topic"` is used.
+    #[serde(default = "default_topic_key")]
+    #[derivative(Default(value = "default_topic_key()"))]
+    #[configurable(metadata(docs::examples = "topic"))]
+    pub topic_key: OptionalValuePath,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub decoding: DeserializerConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub acknowledgements: SourceAcknowledgementsConfig,
+}
+
+const fn default_port() -> u16 {
+    1883
+}
+
+const fn default_keep_alive() -> u16 {
+    60
+}
+
+const fn default_clean_session() -> bool {
+    false
+}
+
+const fn default_qos() -> MqttQoS {
+    MqttQoS::AtLeastOnce
+}
+
+fn default_topic_key() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!("topic"))
+}
+
+impl_generate_config_from_default!(MqttSourceConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SourceConfig for MqttSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+        let options = self.build_options(acknowledgements)?;
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        let source = MqttSource {
+            options,
+            topics: self.topics.clone(),
+            quality_of_service: self.quality_of_service,
+            topic_key: self.topic_key.clone(),
+            decoder,
+            log_namespace,
+            acknowledgements,
+        };
+        Ok(Box::pin(source.run(cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                self.topic_key.path.clone().map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("topic"),
+                Kind::bytes(),
+                Some("topic"),
+            );
+
+        vec![SourceOutput::new_maybe_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl MqttSourceConfig {
+    pub(super) fn build_options(
+        &self,
+        acknowledgements: bool,
+    ) -> Result<MqttOptions, ConfigurationError> {
+        if self.topics.is_empty() {
+            return Err(ConfigurationError::NoTopics);
+        }
+
+        let client_id = self.client_id.clone().unwrap_or_else(|| {
+            let hash = rand::thread_rng()
+                .sample_iter(&rand_distr::Alphanumeric)
+                .take(6)
+                .map(char::from)
+                .collect::<String>();
+            format!("vectorSource{hash}")
+        });
+        if client_id.is_empty() {
+            return Err(ConfigurationError::EmptyClientId);
+        }
+
+        let tls = MaybeTlsSettings::from_config(&self.tls, false).context(TlsSnafu)?;
+        let mut options = MqttOptions::new(&client_id, &self.host, self.port);
+        options.set_keep_alive(Duration::from_secs(self.keep_alive.into()));
+        options.set_clean_session(self.clean_session);
+        // Messages are only acknowledged to the broker once their events are delivered.
+        options.set_manual_acks(acknowledgements);
+        match (&self.user, &self.password) {
+            (Some(user), Some(password)) => {
+                options.set_credentials(user, password);
+            }
+            (None, None) => {}
+            _ => return Err(ConfigurationError::InvalidCredentials),
+        }
+        if let Some(tls) = tls.tls() {
+            let ca = tls.authorities_pem().flatten().collect();
+            let client_auth = None;
+            let alpn = Some(vec!["mqtt".into()]);
+            options.set_transport(Transport::Tls(TlsConfiguration::Simple {
+                ca,
+                client_auth,
+                alpn,
+            }));
+        }
+        Ok(options)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSourceConfig>();
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let config = MqttSourceConfig::default();
+        assert!(matches!(
+            config.build_options(false),
+            Err(ConfigurationError::NoTopics)
+        ));
+
+        let config = MqttSourceConfig {
+            topics: vec!["vector/#".into()],
+            user: Some("vector".into()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.build_options(false),
+            Err(ConfigurationError::InvalidCredentials)
+        ));
+
+        let config = MqttSourceConfig {
+            topics: vec!["vector/#".into()],
+            client_id: Some(String::new()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            config.build_options(false),
+            Err(ConfigurationError::EmptyClientId)
+        ));
+    }
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use rumqttc::{AsyncClient, MqttOptions, QoS};
+
+use super::MqttSourceConfig;
+use crate::config::{log_schema, SourceConfig, SourceContext};
+use crate::event::EventStatus;
+use crate::mqtt::MqttQoS;
+use crate::test_util::components::{assert_source_compliance, SOURCE_TAGS};
+use crate::test_util::{collect_n, random_string, trace_init};
+use crate::SourceSender;
+
+fn mqtt_broker_address() -> String {
+    std::env::var("MQTT_BROKER_ADDRESS").unwrap_or_else(|_| "emqx".into())
+}
+
+fn mqtt_broker_port() -> u16 {
+    std::env::var("MQTT_BROKER_PORT")
+        .unwrap_or_else(|_| "1883".into())
+        .parse::<u16>()
+        .expect("Cannot parse as u16")
+}
+
+async fn publish(topic: &str, messages: &[String]) {
+    let options = MqttOptions::new(
+        format!("integration-test-producer-{}", random_string(6)),
+        mqtt_broker_address(),
+        mqtt_broker_port(),
+    );
+    let (client, mut eventloop) = AsyncClient::new(options, 100);
+    for message in messages {
+        client
+            .publish(topic, QoS::AtLeastOnce, false, message.clone())
+            .await
+            .expect("Cannot publish the message");
+    }
+    client.disconnect().await.expect("Cannot disconnect");
+    // The event loop returns an error once the disconnection is sent.
+    while eventloop.poll().await.is_ok() {}
+}
+
+#[tokio::test]
+async fn mqtt_source_happy() {
+    trace_init();
+
+    let prefix = format!("vector-{}", random_string(6));
+    let topic = format!("{prefix}/sensors/temperature");
+    let config = MqttSourceConfig {
+        host: mqtt_broker_address(),
+        port: mqtt_broker_port(),
+        topics: vec![format!("{prefix}/+/temperature")],
+        quality_of_service: MqttQoS::AtLeastOnce,
+        ..Default::default()
+    };
+
+    let messages = (0..10).map(|_| random_string(10)).collect::<Vec<_>>();
+    let events = assert_source_compliance(&SOURCE_TAGS, async {
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .expect("Cannot build the source");
+        tokio::spawn(source);
+        // Gives the source time to subscribe.
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        publish(&topic, &messages).await;
+        collect_n(rx, messages.len()).await
+    })
+    .await;
+
+    assert_eq!(events.len(), messages.len());
+    for event in events {
+        let log = event.as_log();
+        assert!(log.contains(log_schema().message_key().unwrap().to_string().as_str()));
+        assert_eq!(log["topic"], topic.clone().into());
+    }
+}
+
+#[tokio::test]
+async fn mqtt_source_persistent_session() {
+    trace_init();
+
+    let topic = format!("vector-{}", random_string(6));
+    let config = MqttSourceConfig {
+        host: mqtt_broker_address(),
+        port: mqtt_broker_port(),
+        client_id: Some(format!("vector-source-{}", random_string(6))),
+        topics: vec![topic.clone()],
+        quality_of_service: MqttQoS::ExactlyOnce,
+        acknowledgements: true.into(),
+        ..Default::default()
+    };
+
+    // Creates the session on the broker, then goes away.
+    let (tx, _rx) = SourceSender::new_test();
+    let source = config
+        .clone()
+        .build(SourceContext::new_test(tx, None))
+        .await
+        .expect("Cannot build the source");
+    let handle = tokio::spawn(source);
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    handle.abort();
+    _ = handle.await;
+
+    // Messages published in the meantime are delivered once the session is resumed.
+    let messages = (0..10).map(|_| random_string(10)).collect::<Vec<_>>();
+    publish(&topic, &messages).await;
+
+    let events = assert_source_compliance(&SOURCE_TAGS, async move {
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .expect("Cannot build the source");
+        tokio::spawn(source);
+        collect_n(rx, 10).await
+    })
+    .await;
+
+    let received = events
+        .iter()
+        .map(|event| {
+            event.as_log()[log_schema().message_key().unwrap().to_string()]
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(received, messages);
+}
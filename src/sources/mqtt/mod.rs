@@ -0,0 +1,7 @@
+mod config;
+mod source;
+
+#[cfg(all(test, feature = "mqtt-integration-tests"))]
+mod integration_tests;
+
+pub use config::MqttSourceConfig;
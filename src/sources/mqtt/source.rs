@@ -0,0 +1,215 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use futures::{stream::BoxStream, StreamExt};
+use rumqttc::{
+    AsyncClient, Event as MqttEvent, EventLoop, Incoming, MqttOptions, Publish, SubscribeFilter,
+};
+use tokio::sync::mpsc;
+use tokio_util::codec::FramedRead;
+use tracing::Instrument;
+use vector_lib::codecs::StreamDecodingError;
+use vector_lib::config::{LegacyKey, LogNamespace};
+use vector_lib::finalizer::OrderedFinalizer;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+    Registered,
+};
+use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path};
+use vector_lib::EstimatedJsonEncodedSizeOf;
+
+use super::MqttSourceConfig;
+use crate::{
+    codecs::Decoder,
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{MqttClientError, MqttReceiveError, StreamClosedError},
+    mqtt::MqttQoS,
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+pub(super) struct MqttSource {
+    pub(super) options: MqttOptions,
+    pub(super) topics: Vec<String>,
+    pub(super) quality_of_service: MqttQoS,
+    pub(super) topic_key: OptionalValuePath,
+    pub(super) decoder: Decoder,
+    pub(super) log_namespace: LogNamespace,
+    pub(super) acknowledgements: bool,
+}
+
+/// The number of received messages that can be waiting for the source to process them, before
+/// the event loop stops reading from the broker.
+const RECEIVED_MESSAGES: usize = 128;
+
+impl MqttSource {
+    pub(super) async fn run(
+        self,
+        mut out: SourceSender,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let (client, eventloop) = AsyncClient::new(self.options.clone(), 1024);
+        let (finalizer, ack_stream) =
+            OrderedFinalizer::<Publish>::maybe_new(self.acknowledgements, Some(shutdown.clone()));
+        let events_received = register!(EventsReceived);
+        let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+
+        // The event loop keeps the connection to the broker alive, and sends the requests of the
+        // client, including acknowledgements, so it's driven by its own task rather than being
+        // held up while events are sent downstream.
+        let (publish_tx, mut publish_rx) = mpsc::channel(RECEIVED_MESSAGES);
+        tokio::spawn(
+            drive_event_loop(
+                eventloop,
+                client.clone(),
+                self.subscribe_filters(),
+                publish_tx,
+                shutdown.clone(),
+            )
+            .in_current_span(),
+        );
+        if finalizer.is_some() {
+            tokio::spawn(acknowledge(client, ack_stream).in_current_span());
+        }
+
+        loop {
+            let publish = tokio::select! {
+                _ = &mut shutdown => break,
+                publish = publish_rx.recv() => match publish {
+                    Some(publish) => publish,
+                    None => break,
+                },
+            };
+
+            bytes_received.emit(ByteSize(publish.payload.len()));
+            let mut events = self.decode(&publish, &events_received).await;
+            let count = events.len();
+            let receiver = BatchNotifier::maybe_apply_to(finalizer.is_some(), &mut events);
+            if out.send_batch(events).await.is_err() {
+                emit!(StreamClosedError { count });
+                break;
+            }
+            // Otherwise, the event loop acknowledges messages on receipt.
+            if let (Some(finalizer), Some(receiver)) = (&finalizer, receiver) {
+                finalizer.add(publish, receiver);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn subscribe_filters(&self) -> Vec<SubscribeFilter> {
+        self.topics
+            .iter()
+            .map(|topic| SubscribeFilter::new(topic.clone(), self.quality_of_service.into()))
+            .collect()
+    }
+
+    async fn decode(
+        &self,
+        publish: &Publish,
+        events_received: &Registered<EventsReceived>,
+    ) -> Vec<Event> {
+        let mut decoded = Vec::new();
+        let mut stream = FramedRead::new(publish.payload.as_ref(), self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((events, _byte_size)) => {
+                    let count = events.len();
+                    let byte_size = events.estimated_json_encoded_size_of();
+                    events_received.emit(CountByteSize(count, byte_size));
+
+                    let now = Utc::now();
+
+                    decoded.extend(events.into_iter().map(|mut event| {
+                        if let Event::Log(ref mut log) = event {
+                            self.log_namespace.insert_standard_vector_source_metadata(
+                                log,
+                                MqttSourceConfig::NAME,
+                                now,
+                            );
+                            self.log_namespace.insert_source_metadata(
+                                MqttSourceConfig::NAME,
+                                log,
+                                self.topic_key.path.as_ref().map(LegacyKey::InsertIfEmpty),
+                                &owned_value_path!("topic"),
+                                publish.topic.as_str(),
+                            );
+                        }
+                        event
+                    }));
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+        decoded
+    }
+}
+
+/// Polls the event loop, forwarding the messages received from the broker to the source.
+///
+/// Waiting for the source to take the messages holds back reading from the broker.
+async fn drive_event_loop(
+    mut eventloop: EventLoop,
+    client: AsyncClient,
+    filters: Vec<SubscribeFilter>,
+    publish_tx: mpsc::Sender<Publish>,
+    mut shutdown: ShutdownSignal,
+) {
+    loop {
+        let event = tokio::select! {
+            _ = &mut shutdown => break,
+            event = eventloop.poll() => event,
+        };
+        match event {
+            Ok(MqttEvent::Incoming(Incoming::ConnAck(connack))) => {
+                // A resumed session already holds the subscriptions. The request is only sent
+                // once the event loop is polled again, so it can't be waited for here.
+                if !connack.session_present {
+                    let client = client.clone();
+                    let filters = filters.clone();
+                    tokio::spawn(
+                        async move {
+                            if let Err(error) = client.subscribe_many(filters).await {
+                                emit!(MqttClientError { error });
+                            }
+                        }
+                        .in_current_span(),
+                    );
+                }
+            }
+            Ok(MqttEvent::Incoming(Incoming::Publish(publish))) => {
+                if publish_tx.send(publish).await.is_err() {
+                    break;
+                }
+            }
+            Ok(_) => {}
+            Err(error) => {
+                emit!(MqttReceiveError { error });
+                // The next poll reconnects to the broker.
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
+
+/// Acknowledges the messages to the broker once their events are finalized, which is a no-op for
+/// QoS 0 messages.
+async fn acknowledge(
+    client: AsyncClient,
+    mut ack_stream: BoxStream<'static, (BatchStatus, Publish)>,
+) {
+    // MQTT has no negative acknowledgement, so undelivered messages are acknowledged as well to
+    // keep the session from stalling.
+    while let Some((_status, publish)) = ack_stream.next().await {
+        if let Err(error) = client.ack(&publish).await {
+            emit!(MqttClientError { error });
+        }
+    }
+}
@@ -1,15 +1,21 @@
+use std::time::Duration;
+
+use async_nats::jetstream::{self, consumer, AckKind};
 use chrono::Utc;
-use futures::{pin_mut, StreamExt};
+use futures::{pin_mut, stream::BoxStream, StreamExt, TryStreamExt};
+use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
 use vector_lib::codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
 use vector_lib::configurable::configurable_component;
+use vector_lib::finalizer::UnorderedFinalizer;
 use vector_lib::internal_event::{
     ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+    Registered,
 };
 use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path};
 use vector_lib::{
-    config::{LegacyKey, LogNamespace},
+    config::{LegacyKey, LogNamespace, SourceAcknowledgementsConfig},
     EstimatedJsonEncodedSizeOf,
 };
 use vrl::value::Kind;
@@ -17,10 +23,10 @@ use vrl::value::Kind;
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
-    event::Event,
-    internal_events::StreamClosedError,
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{NatsJetStreamAckError, NatsJetStreamReadError, StreamClosedError},
     nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
     tls::TlsEnableableConfig,
     SourceSender,
@@ -34,8 +40,14 @@ enum BuildError {
     Connect { source: async_nats::ConnectError },
     #[snafu(display("NATS Subscribe Error: {}", source))]
     Subscribe { source: async_nats::SubscribeError },
+    #[snafu(display("NATS JetStream Error: {}", source))]
+    JetStream { source: async_nats::Error },
+    #[snafu(display("`jetstream.deliver_subject` must be set for push consumers"))]
+    MissingDeliverSubject,
 }
 
+type JetStreamMessages = BoxStream<'static, Result<jetstream::Message, async_nats::Error>>;
+
 /// Configuration for the `nats` source.
 #[configurable_component(source(
     "nats",
@@ -74,8 +86,13 @@ pub struct NatsSourceConfig {
     subject: String,
 
     /// The NATS queue group to join.
+    ///
+    /// For JetStream push consumers, this is the group that the messages are delivered to.
     queue: Option<String>,
 
+    #[configurable(derived)]
+    jetstream: Option<NatsJetStreamConfig>,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -112,6 +129,107 @@ pub struct NatsSourceConfig {
     #[serde(default = "default_subscription_capacity")]
     #[derivative(Default(value = "default_subscription_capacity()"))]
     subscriber_capacity: usize,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+}
+
+/// Configuration for consuming messages from [JetStream][jetstream].
+///
+/// Instead of subscribing to `subject`, messages are consumed from a durable consumer of a
+/// stream, filtered by `subject`. Messages are acknowledged once they are sent, or, if
+/// acknowledgements are enabled, once their events are delivered by the sinks. Messages
+/// whose events fail to be delivered are redelivered.
+///
+/// [jetstream]: https://docs.nats.io/nats-concepts/jetstream
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NatsJetStreamConfig {
+    /// The name of the stream to consume from.
+    #[configurable(metadata(docs::examples = "EVENTS"))]
+    stream: String,
+
+    /// The name of the durable consumer.
+    ///
+    /// The consumer is created if it doesn't exist yet. The progress of the consumer is kept by
+    /// the server, so messages published while Vector is down are consumed when it restarts.
+    #[configurable(metadata(docs::examples = "vector"))]
+    consumer: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    mode: NatsJetStreamMode,
+
+    /// The subject that messages are pushed to, for push consumers.
+    #[configurable(metadata(docs::examples = "vector.deliver"))]
+    deliver_subject: Option<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    deliver_policy: NatsDeliverPolicy,
+
+    /// The amount of time the server waits for a message to be acknowledged before
+    /// redelivering it.
+    ///
+    /// With acknowledgements enabled, this should be longer than the time the sinks take to
+    /// deliver the events.
+    #[serde(default = "default_ack_wait")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[serde(rename = "ack_wait_secs")]
+    #[configurable(metadata(docs::human_name = "Acknowledgement Wait"))]
+    ack_wait: Duration,
+}
+
+const fn default_ack_wait() -> Duration {
+    Duration::from_secs(30)
+}
+
+/// How messages are delivered by a JetStream consumer.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NatsJetStreamMode {
+    /// Vector fetches batches of messages from the server.
+    #[default]
+    Pull,
+
+    /// The server sends messages to `deliver_subject`.
+    Push,
+}
+
+/// Where a new JetStream consumer starts in the stream.
+///
+/// This only applies when the consumer is created, not when Vector restarts.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NatsDeliverPolicy {
+    /// Start with the first message of the stream.
+    #[default]
+    All,
+
+    /// Start with the last message of the stream.
+    Last,
+
+    /// Start with the messages published after the consumer is created.
+    New,
+
+    /// Start with the last message of each subject of the stream.
+    LastPerSubject,
+}
+
+impl From<NatsDeliverPolicy> for consumer::DeliverPolicy {
+    fn from(policy: NatsDeliverPolicy) -> Self {
+        match policy {
+            NatsDeliverPolicy::All => Self::All,
+            NatsDeliverPolicy::Last => Self::Last,
+            NatsDeliverPolicy::New => Self::New,
+            NatsDeliverPolicy::LastPerSubject => Self::LastPerSubject,
+        }
+    }
 }
 
 fn default_subject_key_field() -> OptionalValuePath {
@@ -139,11 +257,28 @@ impl GenerateConfig for NatsSourceConfig {
 impl SourceConfig for NatsSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
         let log_namespace = cx.log_namespace(self.log_namespace);
-        let (connection, subscription) = create_subscription(self).await?;
         let decoder =
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
                 .build()?;
 
+        if let Some(jetstream) = &self.jetstream {
+            let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+            let (connection, messages) = create_consumer(self, jetstream).await?;
+
+            return Ok(Box::pin(nats_jetstream_source(
+                self.clone(),
+                connection,
+                messages,
+                decoder,
+                log_namespace,
+                acknowledgements,
+                cx.shutdown,
+                cx.out,
+            )));
+        }
+
+        let (connection, subscription) = create_subscription(self).await?;
+
         Ok(Box::pin(nats_source(
             self.clone(),
             connection,
@@ -181,7 +316,7 @@ impl SourceConfig for NatsSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        self.jetstream.is_some()
     }
 }
 
@@ -231,57 +366,157 @@ async fn nats_source(
     let bytes_received = register!(BytesReceived::from(Protocol::TCP));
     while let Some(msg) = stream.next().await {
         bytes_received.emit(ByteSize(msg.payload.len()));
-        let mut stream = FramedRead::new(msg.payload.as_ref(), decoder.clone());
-        while let Some(next) = stream.next().await {
-            match next {
-                Ok((events, _byte_size)) => {
-                    let count = events.len();
-                    let byte_size = events.estimated_json_encoded_size_of();
-                    events_received.emit(CountByteSize(count, byte_size));
-
-                    let now = Utc::now();
-
-                    let events = events.into_iter().map(|mut event| {
-                        if let Event::Log(ref mut log) = event {
-                            log_namespace.insert_standard_vector_source_metadata(
-                                log,
-                                NatsSourceConfig::NAME,
-                                now,
-                            );
-
-                            let legacy_subject_key_field = config
-                                .subject_key_field
-                                .path
-                                .as_ref()
-                                .map(LegacyKey::InsertIfEmpty);
-                            log_namespace.insert_source_metadata(
-                                NatsSourceConfig::NAME,
-                                log,
-                                legacy_subject_key_field,
-                                &owned_value_path!("subject"),
-                                msg.subject.as_str(),
-                            )
-                        }
-                        event
-                    });
-
-                    out.send_batch(events).await.map_err(|_| {
-                        emit!(StreamClosedError { count });
-                    })?;
+        let events = decode_message(
+            &config,
+            &decoder,
+            log_namespace,
+            msg.subject.as_str(),
+            &msg.payload,
+            &events_received,
+        )
+        .await;
+
+        let count = events.len();
+        out.send_batch(events).await.map_err(|_| {
+            emit!(StreamClosedError { count });
+        })?;
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn nats_jetstream_source(
+    config: NatsSourceConfig,
+    // Take ownership of the connection so it doesn't get dropped.
+    _connection: async_nats::Client,
+    mut messages: JetStreamMessages,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let (finalizer, mut ack_stream) = UnorderedFinalizer::<jetstream::Message>::maybe_new(
+        acknowledgements,
+        Some(shutdown.clone()),
+    );
+    let events_received = register!(EventsReceived);
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            entry = ack_stream.next() => {
+                if let Some((status, message)) = entry {
+                    handle_ack(status, message).await;
                 }
-                Err(error) => {
-                    // Error is logged by `crate::codecs`, no further
-                    // handling is needed here.
-                    if !error.can_continue() {
-                        break;
+            },
+            message = messages.next() => {
+                let message = match message {
+                    Some(Ok(message)) => message,
+                    Some(Err(error)) => {
+                        emit!(NatsJetStreamReadError { error });
+                        continue;
                     }
+                    None => break,
+                };
+
+                bytes_received.emit(ByteSize(message.payload.len()));
+                let mut events = decode_message(
+                    &config,
+                    &decoder,
+                    log_namespace,
+                    message.subject.as_str(),
+                    &message.payload,
+                    &events_received,
+                )
+                .await;
+
+                let count = events.len();
+                let receiver = BatchNotifier::maybe_apply_to(finalizer.is_some(), &mut events);
+                out.send_batch(events).await.map_err(|_| {
+                    emit!(StreamClosedError { count });
+                })?;
+
+                match (&finalizer, receiver) {
+                    (Some(finalizer), Some(receiver)) => finalizer.add(message, receiver),
+                    _ => handle_ack(BatchStatus::Delivered, message).await,
                 }
-            }
+            },
         }
     }
+
     Ok(())
 }
 
+async fn handle_ack(status: BatchStatus, message: jetstream::Message) {
+    let kind = match status {
+        BatchStatus::Delivered => AckKind::Ack,
+        // Redeliver the message, as the events may be delivered on a later attempt.
+        BatchStatus::Errored => AckKind::Nak(None),
+        BatchStatus::Rejected => AckKind::Term,
+    };
+    if let Err(error) = message.ack_with(kind).await {
+        emit!(NatsJetStreamAckError { error });
+    }
+}
+
+/// Decodes the payload of a message into events, adding the source metadata to them.
+async fn decode_message(
+    config: &NatsSourceConfig,
+    decoder: &Decoder,
+    log_namespace: LogNamespace,
+    subject: &str,
+    payload: &[u8],
+    events_received: &Registered<EventsReceived>,
+) -> Vec<Event> {
+    let mut decoded = Vec::new();
+    let mut stream = FramedRead::new(payload, decoder.clone());
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((events, _byte_size)) => {
+                let count = events.len();
+                let byte_size = events.estimated_json_encoded_size_of();
+                events_received.emit(CountByteSize(count, byte_size));
+
+                let now = Utc::now();
+
+                decoded.extend(events.into_iter().map(|mut event| {
+                    if let Event::Log(ref mut log) = event {
+                        log_namespace.insert_standard_vector_source_metadata(
+                            log,
+                            NatsSourceConfig::NAME,
+                            now,
+                        );
+
+                        let legacy_subject_key_field = config
+                            .subject_key_field
+                            .path
+                            .as_ref()
+                            .map(LegacyKey::InsertIfEmpty);
+                        log_namespace.insert_source_metadata(
+                            NatsSourceConfig::NAME,
+                            log,
+                            legacy_subject_key_field,
+                            &owned_value_path!("subject"),
+                            subject,
+                        )
+                    }
+                    event
+                }));
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+        }
+    }
+    decoded
+}
+
 async fn create_subscription(
     config: &NatsSourceConfig,
 ) -> Result<(async_nats::Client, async_nats::Subscriber), BuildError> {
@@ -300,6 +535,76 @@ async fn create_subscription(
     Ok((nc, subscription))
 }
 
+async fn create_consumer(
+    config: &NatsSourceConfig,
+    jetstream: &NatsJetStreamConfig,
+) -> Result<(async_nats::Client, JetStreamMessages), BuildError> {
+    let nc = config.connect().await?;
+    let context = jetstream::new(nc.clone());
+    let stream = context
+        .get_stream(&jetstream.stream)
+        .await
+        .map_err(|error| BuildError::JetStream {
+            source: error.into(),
+        })?;
+
+    let messages = match jetstream.mode {
+        NatsJetStreamMode::Pull => {
+            let consumer_config = consumer::pull::Config {
+                durable_name: Some(jetstream.consumer.clone()),
+                filter_subject: config.subject.clone(),
+                deliver_policy: jetstream.deliver_policy.into(),
+                ack_policy: consumer::AckPolicy::Explicit,
+                ack_wait: jetstream.ack_wait,
+                ..Default::default()
+            };
+            stream
+                .get_or_create_consumer(&jetstream.consumer, consumer_config)
+                .await
+                .map_err(|error| BuildError::JetStream {
+                    source: error.into(),
+                })?
+                .messages()
+                .await
+                .map_err(|error| BuildError::JetStream {
+                    source: error.into(),
+                })?
+                .err_into()
+                .boxed()
+        }
+        NatsJetStreamMode::Push => {
+            let consumer_config = consumer::push::Config {
+                durable_name: Some(jetstream.consumer.clone()),
+                deliver_subject: jetstream
+                    .deliver_subject
+                    .clone()
+                    .ok_or(BuildError::MissingDeliverSubject)?,
+                deliver_group: config.queue.clone(),
+                filter_subject: config.subject.clone(),
+                deliver_policy: jetstream.deliver_policy.into(),
+                ack_policy: consumer::AckPolicy::Explicit,
+                ack_wait: jetstream.ack_wait,
+                ..Default::default()
+            };
+            stream
+                .get_or_create_consumer(&jetstream.consumer, consumer_config)
+                .await
+                .map_err(|error| BuildError::JetStream {
+                    source: error.into(),
+                })?
+                .messages()
+                .await
+                .map_err(|error| BuildError::JetStream {
+                    source: error.into(),
+                })?
+                .err_into()
+                .boxed()
+        }
+    };
+
+    Ok((nc, messages))
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::print_stdout)] //tests
@@ -383,11 +688,12 @@ mod integration_tests {
     use vector_lib::config::log_schema;
 
     use super::*;
+    use crate::event::EventStatus;
     use crate::nats::{NatsAuthCredentialsFile, NatsAuthNKey, NatsAuthToken, NatsAuthUserPassword};
     use crate::test_util::{
         collect_n,
         components::{assert_source_compliance, SOURCE_TAGS},
-        random_string,
+        random_string, wait_for,
     };
     use crate::tls::TlsConfig;
 
@@ -915,4 +1221,101 @@ mod integration_tests {
             r
         );
     }
+
+    async fn jetstream_consume_and_ack(mode: NatsJetStreamMode, deliver_subject: Option<String>) {
+        let subject = format!("test-{}", random_string(10));
+        let stream_name = format!("test-{}", random_string(10));
+        let url = std::env::var("NATS_JETSTREAM_ADDRESS")
+            .unwrap_or_else(|_| String::from("nats://localhost:4222"));
+        let msg = "my message";
+
+        let context = jetstream::new(async_nats::connect(&url).await.unwrap());
+        context
+            .create_stream(jetstream::stream::Config {
+                name: stream_name.clone(),
+                subjects: vec![subject.clone()],
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        // The message is published before the source starts, as if Vector was down.
+        context
+            .publish(subject.clone(), Bytes::from_static(msg.as_bytes()))
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+
+        let conf = NatsSourceConfig {
+            connection_name: "".to_owned(),
+            subject,
+            url,
+            jetstream: Some(NatsJetStreamConfig {
+                stream: stream_name.clone(),
+                consumer: "vector".to_owned(),
+                mode,
+                deliver_subject,
+                deliver_policy: NatsDeliverPolicy::All,
+                ack_wait: default_ack_wait(),
+            }),
+            subject_key_field: default_subject_key_field(),
+            ..Default::default()
+        };
+        let (connection, messages) = create_consumer(&conf, conf.jetstream.as_ref().unwrap())
+            .await
+            .unwrap();
+
+        let events = assert_source_compliance(&SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let decoder = DecodingConfig::new(
+                conf.framing.clone(),
+                conf.decoding.clone(),
+                LogNamespace::Legacy,
+            )
+            .build()
+            .unwrap();
+            tokio::spawn(nats_jetstream_source(
+                conf.clone(),
+                connection,
+                messages,
+                decoder,
+                LogNamespace::Legacy,
+                true,
+                ShutdownSignal::noop(),
+                tx,
+            ));
+
+            collect_n(rx, 1).await
+        })
+        .await;
+
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key().unwrap().to_string()],
+            msg.into()
+        );
+
+        // The message is acknowledged once its event is delivered.
+        let stream = context.get_stream(&stream_name).await.unwrap();
+        wait_for(|| {
+            let stream = stream.clone();
+            async move {
+                stream
+                    .consumer_info("vector")
+                    .await
+                    .is_ok_and(|info| info.num_pending == 0 && info.num_ack_pending == 0)
+            }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn nats_jetstream_pull() {
+        jetstream_consume_and_ack(NatsJetStreamMode::Pull, None).await;
+    }
+
+    #[tokio::test]
+    async fn nats_jetstream_push() {
+        let deliver_subject = format!("deliver-{}", random_string(10));
+        jetstream_consume_and_ack(NatsJetStreamMode::Push, Some(deliver_subject)).await;
+    }
 }
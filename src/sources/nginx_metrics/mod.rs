@@ -23,6 +23,7 @@ use crate::{
         CollectionCompleted, EndpointBytesReceived, NginxMetricsEventsReceived,
         NginxMetricsRequestError, NginxMetricsStubStatusParseError, StreamClosedError,
     },
+    oauth2,
     tls::{TlsConfig, TlsSettings},
 };
 
@@ -244,12 +245,15 @@ impl NginxMetrics {
     }
 
     async fn get_nginx_response(&self) -> crate::Result<Bytes> {
-        let mut request = Request::get(&self.endpoint).body(Body::empty())?;
+        let mut request = Request::get(&self.endpoint).body(Bytes::new())?;
         if let Some(auth) = &self.auth {
             auth.apply(&mut request);
         }
 
-        let response = self.http_client.send(request).await?;
+        let response = oauth2::send_authorized(request, |request| {
+            self.http_client.send(request.map(Body::from))
+        })
+        .await?;
         let (parts, body) = response.into_parts();
         match parts.status {
             StatusCode::OK => body_to_bytes(body).err_into().await,
@@ -8,9 +8,17 @@ use tonic::Request;
 use vector_lib::config::LogNamespace;
 use vector_lib::lookup::path;
 use vector_lib::opentelemetry::proto::{
-    collector::logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+    collector::{
+        logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+        metrics::v1::{metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest},
+    },
     common::v1::{any_value, AnyValue, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+    metrics::v1::{
+        exponential_histogram_data_point::Buckets, metric::Data, AggregationTemporality,
+        ExponentialHistogram as OtelExponentialHistogram, ExponentialHistogramDataPoint,
+        Metric as OtelMetric, ResourceMetrics, ScopeMetrics,
+    },
     resource::v1::Resource as OtelResource,
 };
 use vrl::value;
@@ -18,8 +26,12 @@ use vrl::value;
 use crate::config::OutputId;
 use crate::{
     config::{SourceConfig, SourceContext},
-    event::{into_event_stream, Event, EventStatus, LogEvent, ObjectMap, Value},
-    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS},
+    event::{
+        into_event_stream,
+        metric::{ExponentialBuckets, ExponentialHistogram},
+        Event, EventStatus, LogEvent, MetricKind, MetricValue, ObjectMap, Value,
+    },
+    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS, METRICS},
     test_util::{
         self,
         components::{assert_source_compliance, SOURCE_TAGS},
@@ -283,6 +295,121 @@ async fn receive_grpc_logs_legacy_namespace() {
     .await;
 }
 
+#[tokio::test]
+async fn receive_grpc_exponential_histogram() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let grpc_addr = next_addr();
+        let http_addr = next_addr();
+
+        let source = OpentelemetryConfig {
+            grpc: GrpcConfig {
+                address: grpc_addr,
+                tls: Default::default(),
+            },
+            http: HttpConfig {
+                address: http_addr,
+                tls: Default::default(),
+                keepalive: Default::default(),
+            },
+            acknowledgements: Default::default(),
+            log_namespace: Default::default(),
+        };
+
+        let (sender, metrics_output, _) = new_source(EventStatus::Delivered, METRICS.to_string());
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(grpc_addr).await;
+
+        let mut client = MetricsServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        let req = Request::new(ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(OtelResource {
+                    attributes: vec![KeyValue {
+                        key: "service.name".into(),
+                        value: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue("checkout".into())),
+                        }),
+                    }],
+                    dropped_attributes_count: 0,
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: None,
+                    metrics: vec![OtelMetric {
+                        name: "request.duration".into(),
+                        description: String::new(),
+                        unit: "s".into(),
+                        data: Some(Data::ExponentialHistogram(OtelExponentialHistogram {
+                            data_points: vec![ExponentialHistogramDataPoint {
+                                attributes: vec![KeyValue {
+                                    key: "route".into(),
+                                    value: Some(AnyValue {
+                                        value: Some(any_value::Value::StringValue("/".into())),
+                                    }),
+                                }],
+                                start_time_unix_nano: 1,
+                                time_unix_nano: 2,
+                                count: 7,
+                                sum: Some(12.5),
+                                scale: 4,
+                                zero_count: 1,
+                                positive: Some(Buckets {
+                                    offset: -3,
+                                    bucket_counts: vec![2, 0, 4],
+                                }),
+                                negative: None,
+                                flags: 0,
+                                exemplars: vec![],
+                                min: None,
+                                max: None,
+                                zero_threshold: 0.0,
+                            }],
+                            aggregation_temporality: AggregationTemporality::Delta as i32,
+                        })),
+                    }],
+                    schema_url: "v1".into(),
+                }],
+                schema_url: "v1".into(),
+            }],
+        });
+        _ = client.export(req).await;
+        let output = test_util::collect_ready(metrics_output).await;
+        assert_eq!(output.len(), 1);
+
+        let metric = output[0].as_metric();
+        assert_eq!(metric.name(), "request.duration");
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+        assert_eq!(metric.timestamp(), Some(Utc.timestamp_nanos(2)));
+        assert_eq!(metric.tag_value("route"), Some("/".into()));
+        assert_eq!(
+            metric.tag_value("resource.service.name"),
+            Some("checkout".into())
+        );
+        assert_eq!(
+            metric.value(),
+            &MetricValue::ExponentialHistogram {
+                histogram: ExponentialHistogram {
+                    scale: 4,
+                    zero_count: 1,
+                    zero_threshold: 0.0,
+                    positive: ExponentialBuckets {
+                        offset: -3,
+                        counts: vec![2, 0, 4],
+                    },
+                    negative: ExponentialBuckets::default(),
+                    count: 7,
+                    sum: 12.5,
+                },
+            }
+        );
+    })
+    .await;
+}
+
 pub(super) fn new_source(
     status: EventStatus,
     event_name: String,
@@ -3,10 +3,13 @@ use std::cmp::Ordering;
 use chrono::{DateTime, TimeZone, Utc};
 #[cfg(feature = "sources-prometheus-remote-write")]
 use vector_lib::prometheus::parser::proto;
-use vector_lib::prometheus::parser::{GroupKind, MetricGroup, ParserError};
+use vector_lib::prometheus::parser::{GroupKind, MetricGroup, NativeHistogramBuckets, ParserError};
 
 use crate::event::{
-    metric::{Bucket, Metric, MetricKind, MetricTags, MetricValue, Quantile},
+    metric::{
+        Bucket, ExponentialBuckets, ExponentialHistogram, Metric, MetricKind, MetricTags,
+        MetricValue, Quantile,
+    },
     Event,
 };
 
@@ -130,6 +133,32 @@ fn reparse_groups(
                     );
                 }
             }
+            GroupKind::NativeHistogram(metrics) => {
+                for (key, metric) in metrics {
+                    let tags = combine_tags(key.labels, tag_overrides.clone());
+
+                    result.push(
+                        Metric::new(
+                            group.name.clone(),
+                            metric_kind,
+                            MetricValue::ExponentialHistogram {
+                                histogram: ExponentialHistogram {
+                                    scale: metric.schema,
+                                    zero_count: metric.zero_count,
+                                    zero_threshold: metric.zero_threshold,
+                                    positive: exponential_buckets(metric.positive),
+                                    negative: exponential_buckets(metric.negative),
+                                    count: metric.count,
+                                    sum: metric.sum,
+                                },
+                            },
+                        )
+                        .with_timestamp(Some(utc_timestamp(key.timestamp, start)))
+                        .with_tags(tags.as_option())
+                        .into(),
+                    );
+                }
+            }
             GroupKind::Summary(metrics) => {
                 for (key, metric) in metrics {
                     let tags = combine_tags(key.labels, tag_overrides.clone());
@@ -164,6 +193,15 @@ fn reparse_groups(
     result
 }
 
+/// Prometheus native histogram buckets are indexed by their upper limit, while the buckets of
+/// exponential histograms are indexed by their lower limit.
+fn exponential_buckets(buckets: NativeHistogramBuckets) -> ExponentialBuckets {
+    ExponentialBuckets {
+        offset: buckets.offset - 1,
+        counts: buckets.counts,
+    }
+}
+
 fn combine_tags(
     base_tags: impl Into<MetricTags>,
     tag_overrides: impl IntoIterator<Item = (String, String)>,
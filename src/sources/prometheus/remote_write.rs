@@ -151,7 +151,10 @@ impl HttpSource for RemoteWriteSource {
 mod test {
     use chrono::{SubsecRound as _, Utc};
     use vector_lib::{
-        event::{EventStatus, Metric, MetricKind, MetricValue},
+        event::{
+            metric::{ExponentialBuckets, ExponentialHistogram},
+            EventStatus, Metric, MetricKind, MetricValue,
+        },
         metric_tags,
     };
 
@@ -256,6 +259,29 @@ mod test {
             )
             .with_timestamp(Some(timestamp()))
             .into(),
+            Metric::new(
+                "native_histogram_5",
+                MetricKind::Absolute,
+                MetricValue::ExponentialHistogram {
+                    histogram: ExponentialHistogram {
+                        scale: 3,
+                        zero_count: 2,
+                        zero_threshold: 0.001,
+                        positive: ExponentialBuckets {
+                            offset: -2,
+                            counts: vec![4, 0, 7],
+                        },
+                        negative: ExponentialBuckets {
+                            offset: 5,
+                            counts: vec![1],
+                        },
+                        count: 14,
+                        sum: 3.5,
+                    },
+                },
+            )
+            .with_timestamp(Some(timestamp()))
+            .into(),
             Metric::new(
                 "summary_4",
                 MetricKind::Absolute,
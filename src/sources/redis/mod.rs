@@ -1,6 +1,9 @@
+use std::{num::NonZeroUsize, time::Duration};
+
 use bytes::Bytes;
 use chrono::Utc;
 use futures::StreamExt;
+use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
 use tokio_util::codec::FramedRead;
 use vector_lib::codecs::{
@@ -13,7 +16,7 @@ use vector_lib::internal_event::{
 };
 use vector_lib::lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePath};
 use vector_lib::{
-    config::{LegacyKey, LogNamespace},
+    config::{LegacyKey, LogNamespace, SourceAcknowledgementsConfig},
     EstimatedJsonEncodedSizeOf,
 };
 use vrl::value::Kind;
@@ -23,11 +26,12 @@ use crate::{
     config::{log_schema, GenerateConfig, SourceConfig, SourceContext, SourceOutput},
     event::Event,
     internal_events::{EventsReceived, StreamClosedError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
 };
 
 mod channel;
 mod list;
+mod stream;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -49,6 +53,11 @@ pub enum DataTypeConfig {
     ///
     /// This is based on Redis' Pub/Sub capabilities.
     Channel,
+
+    /// The `stream` data type.
+    ///
+    /// Entries are read with a consumer group, and acknowledged once they are processed.
+    Stream,
 }
 
 /// Options for the Redis `list` data type.
@@ -74,6 +83,77 @@ pub enum Method {
     Rpop,
 }
 
+/// Options for the Redis `stream` data type.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The consumer group to read the stream with.
+    ///
+    /// The group is created if it doesn't exist yet.
+    #[serde(default = "default_group")]
+    #[derivative(Default(value = "default_group()"))]
+    #[configurable(metadata(docs::examples = "vector"))]
+    group: String,
+
+    /// The name of the consumer within the group.
+    ///
+    /// Each Vector instance reading from the same group must use a different name. Defaults to
+    /// the hostname.
+    #[configurable(metadata(docs::examples = "vector-1"))]
+    consumer: Option<String>,
+
+    /// The ID of the entry after which the group starts reading, when it's created.
+    ///
+    /// `$` only reads the entries added after the group is created, and `0` reads the whole
+    /// stream.
+    #[serde(default = "default_start_id")]
+    #[derivative(Default(value = "default_start_id()"))]
+    #[configurable(metadata(docs::examples = "$", docs::examples = "0"))]
+    start_id: String,
+
+    /// The field of the entries that holds the message to decode.
+    #[serde(default = "default_stream_field")]
+    #[derivative(Default(value = "default_stream_field()"))]
+    #[configurable(metadata(docs::examples = "message"))]
+    field: String,
+
+    /// The maximum number of entries to read at once.
+    #[serde(default = "default_batch_size")]
+    #[derivative(Default(value = "default_batch_size()"))]
+    batch_size: NonZeroUsize,
+
+    /// The time after which the pending entries of other consumers in the group are claimed.
+    ///
+    /// When set, the entries that were delivered to another consumer, but not acknowledged
+    /// for this long, are claimed and processed when the source starts. This recovers the
+    /// entries of consumers that are gone for good. The entries delivered to this consumer but
+    /// not acknowledged are always processed when the source starts.
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    #[serde(default, rename = "claim_min_idle_secs")]
+    #[configurable(metadata(docs::examples = 300))]
+    #[configurable(metadata(docs::human_name = "Claim Minimum Idle Time"))]
+    claim_min_idle: Option<Duration>,
+}
+
+fn default_group() -> String {
+    "vector".to_owned()
+}
+
+fn default_start_id() -> String {
+    "$".to_owned()
+}
+
+fn default_stream_field() -> String {
+    "message".to_owned()
+}
+
+fn default_batch_size() -> NonZeroUsize {
+    NonZeroUsize::new(100).expect("static non-zero number")
+}
+
 pub struct ConnectionInfo {
     protocol: &'static str,
     endpoint: String,
@@ -98,13 +178,16 @@ impl From<&redis::ConnectionInfo> for ConnectionInfo {
 #[derive(Clone, Debug, Derivative)]
 #[serde(deny_unknown_fields)]
 pub struct RedisSourceConfig {
-    /// The Redis data type (`list` or `channel`) to use.
+    /// The Redis data type (`list`, `channel` or `stream`) to use.
     #[serde(default)]
     data_type: DataTypeConfig,
 
     #[configurable(derived)]
     list: Option<ListOption>,
 
+    #[configurable(derived)]
+    stream: Option<StreamOption>,
+
     /// The Redis URL to connect to.
     ///
     /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be `redis` or `rediss` for connections secured using TLS.
@@ -137,6 +220,10 @@ pub struct RedisSourceConfig {
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
     log_namespace: Option<bool>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
 }
 
 impl GenerateConfig for RedisSourceConfig {
@@ -172,6 +259,7 @@ impl SourceConfig for RedisSourceConfig {
             DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
                 .build()?;
 
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
         let bytes_received = register!(BytesReceived::from(Protocol::from(
             connection_info.protocol
         )));
@@ -193,6 +281,10 @@ impl SourceConfig for RedisSourceConfig {
                 handler.watch(method).await
             }
             DataTypeConfig::Channel => handler.subscribe(connection_info).await,
+            DataTypeConfig::Stream => {
+                let option = self.stream.clone().unwrap_or_default();
+                handler.read_group(option, acknowledgements).await
+            }
         }
     }
 
@@ -224,7 +316,7 @@ impl SourceConfig for RedisSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        matches!(self.data_type, DataTypeConfig::Stream)
     }
 }
 
@@ -241,11 +333,24 @@ struct InputHandler {
 
 impl InputHandler {
     async fn handle_line(&mut self, line: String) -> Result<(), ()> {
+        let events = self.decode_line(line.as_bytes()).await;
+
+        let count = events.len();
+        if (self.cx.out.send_batch(events).await).is_err() {
+            emit!(StreamClosedError { count });
+            return Err(());
+        }
+        Ok(())
+    }
+
+    /// Decodes a message into events, adding the source metadata to them.
+    async fn decode_line(&self, line: &[u8]) -> Vec<Event> {
         let now = Utc::now();
 
         self.bytes_received.emit(ByteSize(line.len()));
 
-        let mut stream = FramedRead::new(line.as_ref(), self.decoder.clone());
+        let mut decoded = Vec::new();
+        let mut stream = FramedRead::new(line, self.decoder.clone());
         while let Some(next) = stream.next().await {
             match next {
                 Ok((events, _byte_size)) => {
@@ -253,7 +358,7 @@ impl InputHandler {
                     let byte_size = events.estimated_json_encoded_size_of();
                     self.events_received.emit(CountByteSize(count, byte_size));
 
-                    let events = events.into_iter().map(|mut event| {
+                    decoded.extend(events.into_iter().map(|mut event| {
                         if let Event::Log(ref mut log) = event {
                             self.log_namespace.insert_vector_metadata(
                                 log,
@@ -278,12 +383,7 @@ impl InputHandler {
                         };
 
                         event
-                    });
-
-                    if (self.cx.out.send_batch(events).await).is_err() {
-                        emit!(StreamClosedError { count });
-                        return Err(());
-                    }
+                    }));
                 }
                 Err(error) => {
                     // Error is logged by `crate::codecs::Decoder`, no further
@@ -294,7 +394,7 @@ impl InputHandler {
                 }
             }
         }
-        Ok(())
+        decoded
     }
 }
 
@@ -310,15 +410,16 @@ mod test {
 
 #[cfg(all(test, feature = "redis-integration-tests"))]
 mod integration_test {
-    use redis::AsyncCommands;
+    use redis::{aio::ConnectionManager, AsyncCommands};
 
     use super::*;
     use crate::{
         config::log_schema,
+        event::EventStatus,
         test_util::{
             collect_n,
             components::{run_and_assert_source_compliance_n, SOURCE_TAGS},
-            random_string,
+            random_string, wait_for,
         },
         SourceSender,
     };
@@ -345,12 +446,14 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(false),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
@@ -386,12 +489,14 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: Some(OptionalValuePath::from(owned_value_path!("remapped_key"))),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(true),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 1, &SOURCE_TAGS).await;
@@ -427,12 +532,14 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Lpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(false),
+            acknowledgements: Default::default(),
         };
 
         let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
@@ -460,12 +567,14 @@ mod integration_test {
         let config = RedisSourceConfig {
             data_type: DataTypeConfig::Channel,
             list: None,
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
             log_namespace: Some(false),
+            acknowledgements: Default::default(),
         };
 
         let (tx, rx) = SourceSender::new_test();
@@ -509,4 +618,127 @@ mod integration_test {
             );
         }
     }
+
+    fn stream_config(key: &str, stream: StreamOption) -> RedisSourceConfig {
+        RedisSourceConfig {
+            data_type: DataTypeConfig::Stream,
+            list: None,
+            stream: Some(stream),
+            url: REDIS_SERVER.to_owned(),
+            key: key.to_owned(),
+            redis_key: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            log_namespace: Some(false),
+            acknowledgements: true.into(),
+        }
+    }
+
+    async fn pending_entries(mut conn: ConnectionManager, key: String) -> usize {
+        let (count, ..): (usize, redis::Value, redis::Value, redis::Value) = redis::cmd("XPENDING")
+            .arg(key)
+            .arg("vector")
+            .query_async(&mut conn)
+            .await
+            .unwrap();
+        count
+    }
+
+    async fn run_stream_source(config: RedisSourceConfig, count: usize) -> Vec<String> {
+        let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+        let context = SourceContext::new_test(tx, None);
+        let source = config
+            .build(context)
+            .await
+            .expect("source should not fail to build");
+        tokio::spawn(source);
+
+        collect_n(rx, count)
+            .await
+            .into_iter()
+            .map(|event| {
+                event.as_log()[log_schema().message_key().unwrap().to_string()]
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn redis_source_stream_acknowledges_entries() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        for message in ["1", "2", "3"] {
+            let _: String = conn.xadd(&key, "*", &[("message", message)]).await.unwrap();
+        }
+
+        let config = stream_config(
+            &key,
+            StreamOption {
+                consumer: Some("consumer".to_owned()),
+                start_id: "0".to_owned(),
+                ..Default::default()
+            },
+        );
+        assert_eq!(run_stream_source(config, 3).await, ["1", "2", "3"]);
+
+        wait_for(|| {
+            let conn = conn.clone();
+            let key = key.clone();
+            async move { pending_entries(conn, key).await == 0 }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn redis_source_stream_recovers_pending_entries() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        let _: () = conn
+            .xgroup_create_mkstream(&key, "vector", "0")
+            .await
+            .unwrap();
+
+        // One entry is left pending by this consumer, and one by another consumer that is gone.
+        for consumer in ["consumer", "other"] {
+            let _: String = conn
+                .xadd(&key, "*", &[("message", consumer)])
+                .await
+                .unwrap();
+            let _: redis::Value = redis::cmd("XREADGROUP")
+                .arg("GROUP")
+                .arg("vector")
+                .arg(consumer)
+                .arg("COUNT")
+                .arg(1)
+                .arg("STREAMS")
+                .arg(&key)
+                .arg(">")
+                .query_async(&mut conn)
+                .await
+                .unwrap();
+        }
+        assert_eq!(pending_entries(conn.clone(), key.clone()).await, 2);
+
+        let config = stream_config(
+            &key,
+            StreamOption {
+                consumer: Some("consumer".to_owned()),
+                claim_min_idle: Some(Duration::ZERO),
+                ..Default::default()
+            },
+        );
+        assert_eq!(run_stream_source(config, 2).await, ["consumer", "other"]);
+
+        wait_for(|| {
+            let conn = conn.clone();
+            let key = key.clone();
+            async move { pending_entries(conn, key).await == 0 }
+        })
+        .await;
+    }
 }
@@ -0,0 +1,265 @@
+use futures_util::{stream::BoxStream, StreamExt};
+use redis::{
+    aio::ConnectionManager,
+    streams::{StreamId, StreamRangeReply, StreamReadOptions, StreamReadReply},
+    AsyncCommands, ErrorKind, FromRedisValue, RedisError, RedisResult, Value,
+};
+use snafu::{ResultExt, Snafu};
+use tokio::time::Duration;
+use vector_lib::finalizer::UnorderedFinalizer;
+
+use super::{InputHandler, StreamOption};
+use crate::{
+    event::{BatchNotifier, BatchStatus},
+    internal_events::{RedisReceiveEventError, StreamClosedError},
+    sources::Source,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed to create connection: {}", source))]
+    Connection { source: RedisError },
+    #[snafu(display("Failed to create consumer group: {}", source))]
+    Group { source: RedisError },
+}
+
+type Finalizer = UnorderedFinalizer<Vec<String>>;
+
+/// Where a consumer reads the entries that were delivered to it but never acknowledged.
+const PENDING_ID: &str = "0";
+
+/// Where a consumer reads the entries that were never delivered to any consumer of its group.
+const NEW_ID: &str = ">";
+
+/// The errors after which reading can't succeed until the stream or its consumer group is fixed.
+const UNRECOVERABLE_ERRORS: [&str; 2] = ["NOGROUP", "WRONGTYPE"];
+
+/// How long to wait before reading again after an error, doubled after each consecutive error.
+const RETRY_DELAY: Duration = Duration::from_millis(500);
+
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+struct StreamReader {
+    conn: ConnectionManager,
+    key: String,
+    group: String,
+    consumer: String,
+    field: String,
+    batch_size: usize,
+}
+
+impl InputHandler {
+    pub(super) async fn read_group(
+        mut self,
+        option: StreamOption,
+        acknowledgements: bool,
+    ) -> crate::Result<Source> {
+        let mut conn = self
+            .client
+            .get_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+        // Reads block the connection until entries are available, so acknowledgements are sent
+        // over a separate one.
+        let ack_conn = if acknowledgements {
+            Some(
+                self.client
+                    .get_connection_manager()
+                    .await
+                    .context(ConnectionSnafu {})?,
+            )
+        } else {
+            None
+        };
+
+        let created: RedisResult<()> = conn
+            .xgroup_create_mkstream(&self.key, &option.group, &option.start_id)
+            .await;
+        match created {
+            Err(error) if error.code() != Some("BUSYGROUP") => {
+                return Err(BuildError::Group { source: error }.into())
+            }
+            _ => {}
+        }
+
+        let claim_min_idle = option.claim_min_idle;
+        let consumer = match option.consumer {
+            Some(consumer) => consumer,
+            None => crate::get_hostname()?,
+        };
+        let mut reader = StreamReader {
+            conn,
+            key: self.key.clone(),
+            group: option.group,
+            consumer,
+            field: option.field,
+            batch_size: option.batch_size.get(),
+        };
+
+        Ok(Box::pin(async move {
+            let mut shutdown = self.cx.shutdown.clone();
+            let (finalizer, ack_stream) =
+                Finalizer::maybe_new(acknowledgements, Some(shutdown.clone()));
+            if let Some(conn) = ack_conn {
+                tokio::spawn(handle_acks(
+                    conn,
+                    reader.key.clone(),
+                    reader.group.clone(),
+                    ack_stream,
+                ));
+            }
+
+            // Entries that were read before a restart but never acknowledged are processed first.
+            let mut pending_id = Some(String::from(PENDING_ID));
+            let mut claim_id = claim_min_idle.map(|_| String::from("0-0"));
+            let mut retry_delay = RETRY_DELAY;
+            loop {
+                let result = if let Some(id) = &pending_id {
+                    reader.read(id).await
+                } else if let (Some(min_idle), Some(start)) = (claim_min_idle, &claim_id) {
+                    reader.claim(min_idle, start).await.map(|(next, entries)| {
+                        // The claim is complete once the whole pending entries list is scanned.
+                        claim_id = (next != "0-0").then_some(next);
+                        entries
+                    })
+                } else {
+                    tokio::select! {
+                        result = reader.read(NEW_ID) => result,
+                        _ = &mut shutdown => break,
+                    }
+                };
+
+                let entries = match result {
+                    Ok(entries) => {
+                        retry_delay = RETRY_DELAY;
+                        entries
+                    }
+                    Err(error) => {
+                        let unrecoverable = error
+                            .code()
+                            .is_some_and(|code| UNRECOVERABLE_ERRORS.contains(&code));
+                        emit!(RedisReceiveEventError::from(error));
+                        if unrecoverable {
+                            return Err(());
+                        }
+                        tokio::select! {
+                            _ = tokio::time::sleep(retry_delay) => {}
+                            _ = &mut shutdown => break,
+                        }
+                        retry_delay = (retry_delay * 2).min(MAX_RETRY_DELAY);
+                        continue;
+                    }
+                };
+                if pending_id.is_some() {
+                    // Pending entries are only read once, even while their acknowledgement is
+                    // still in progress.
+                    pending_id = entries.last().map(|entry| entry.id.clone());
+                    if entries.is_empty() {
+                        continue;
+                    }
+                }
+
+                let ids = entries
+                    .iter()
+                    .map(|entry| entry.id.clone())
+                    .collect::<Vec<_>>();
+                let mut events = Vec::new();
+                for entry in entries {
+                    match entry.get::<Vec<u8>>(&reader.field) {
+                        Some(message) => events.extend(self.decode_line(&message).await),
+                        None => emit!(RedisReceiveEventError::from(RedisError::from((
+                            ErrorKind::TypeError,
+                            "Stream entry is missing the message field.",
+                            entry.id,
+                        )))),
+                    }
+                }
+
+                let count = events.len();
+                let receiver = BatchNotifier::maybe_apply_to(finalizer.is_some(), &mut events);
+                if self.cx.out.send_batch(events).await.is_err() {
+                    emit!(StreamClosedError { count });
+                    break;
+                }
+                match (&finalizer, receiver) {
+                    (Some(finalizer), Some(receiver)) => finalizer.add(ids, receiver),
+                    _ => xack(&mut reader.conn, &reader.key, &reader.group, &ids).await,
+                }
+            }
+            Ok(())
+        }))
+    }
+}
+
+impl StreamReader {
+    /// Reads entries with `XREADGROUP`, starting after the given ID.
+    async fn read(&mut self, id: &str) -> RedisResult<Vec<StreamId>> {
+        let mut options = StreamReadOptions::default()
+            .group(&self.group, &self.consumer)
+            .count(self.batch_size);
+        if id == NEW_ID {
+            options = options.block(0);
+        }
+        let reply: StreamReadReply = self
+            .conn
+            .xread_options(&[&self.key], &[id], &options)
+            .await?;
+        Ok(reply.keys.into_iter().flat_map(|key| key.ids).collect())
+    }
+
+    /// Claims the entries of other consumers that have been pending for at least the given
+    /// duration with `XAUTOCLAIM`, returning the ID to continue the claim from.
+    async fn claim(
+        &mut self,
+        min_idle: Duration,
+        start: &str,
+    ) -> RedisResult<(String, Vec<StreamId>)> {
+        let reply: Vec<Value> = redis::cmd("XAUTOCLAIM")
+            .arg(&self.key)
+            .arg(&self.group)
+            .arg(&self.consumer)
+            .arg(min_idle.as_millis() as u64)
+            .arg(start)
+            .arg("COUNT")
+            .arg(self.batch_size)
+            .query_async(&mut self.conn)
+            .await?;
+
+        // The reply also lists the IDs of deleted entries since Redis 7.
+        match reply.as_slice() {
+            [next, entries, ..] => {
+                let next = String::from_redis_value(next)?;
+                let entries = StreamRangeReply::from_redis_value(entries)?;
+                Ok((next, entries.ids))
+            }
+            _ => Err(RedisError::from((
+                ErrorKind::TypeError,
+                "Unexpected reply to XAUTOCLAIM.",
+            ))),
+        }
+    }
+}
+
+async fn handle_acks(
+    mut conn: ConnectionManager,
+    key: String,
+    group: String,
+    mut ack_stream: BoxStream<'static, (BatchStatus, Vec<String>)>,
+) {
+    while let Some((status, ids)) = ack_stream.next().await {
+        // Errored entries stay pending, so they are read again when the source restarts.
+        if status != BatchStatus::Errored {
+            xack(&mut conn, &key, &group, &ids).await;
+        }
+    }
+}
+
+async fn xack(conn: &mut ConnectionManager, key: &str, group: &str, ids: &[String]) {
+    if ids.is_empty() {
+        return;
+    }
+    let result: RedisResult<usize> = conn.xack(key, group, ids).await;
+    if let Err(error) = result {
+        emit!(RedisReceiveEventError::from(error));
+    }
+}
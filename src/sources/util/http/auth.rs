@@ -1,9 +1,11 @@
 use std::convert::TryFrom;
 
+use chrono::Utc;
 use headers::{Authorization, HeaderMapExt};
+use openssl::{base64, hash::MessageDigest, memcmp, pkey, sign::Signer};
 use vector_lib::configurable::configurable_component;
 use vector_lib::sensitive_string::SensitiveString;
-use warp::http::HeaderMap;
+use warp::http::{header::AUTHORIZATION, HeaderMap};
 
 #[cfg(any(
     feature = "sources-utils-http-prelude",
@@ -11,19 +13,309 @@ use warp::http::HeaderMap;
 ))]
 use super::error::ErrorMessage;
 
-/// HTTP Basic authentication configuration.
+/// Configuration of the authentication of incoming requests.
 #[configurable_component]
 #[derive(Clone, Debug)]
-pub struct HttpSourceAuthConfig {
-    /// The username for basic authentication.
-    #[configurable(metadata(docs::examples = "AzureDiamond"))]
-    #[configurable(metadata(docs::examples = "admin"))]
-    pub username: String,
-
-    /// The password for basic authentication.
-    #[configurable(metadata(docs::examples = "hunter2"))]
-    #[configurable(metadata(docs::examples = "${PASSWORD}"))]
-    pub password: SensitiveString,
+#[serde(untagged)]
+pub enum HttpSourceAuthConfig {
+    /// HTTP Basic authentication.
+    Basic {
+        /// The username for basic authentication.
+        #[configurable(metadata(docs::examples = "AzureDiamond"))]
+        #[configurable(metadata(docs::examples = "admin"))]
+        username: String,
+
+        /// The password for basic authentication.
+        #[configurable(metadata(docs::examples = "hunter2"))]
+        #[configurable(metadata(docs::examples = "${PASSWORD}"))]
+        password: SensitiveString,
+    },
+
+    /// Verification of the HMAC signature of the requests, as sent by webhook providers.
+    Signature {
+        #[configurable(derived)]
+        signature: HttpSignatureConfig,
+    },
+}
+
+/// Configuration of the HMAC signature that requests must carry.
+///
+/// For example, GitHub webhooks are verified with `header = "X-Hub-Signature-256"` and
+/// `prefix = "sha256="`, and Stripe webhooks with `header = "Stripe-Signature"`,
+/// `signature_key = "v1"`, `timestamp_key = "t"`, and `payload = "{timestamp}.{body}"`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HttpSignatureConfig {
+    /// The name of the header that carries the signature.
+    #[configurable(metadata(docs::examples = "X-Hub-Signature-256"))]
+    #[configurable(metadata(docs::examples = "X-Shopify-Hmac-Sha256"))]
+    #[configurable(metadata(docs::examples = "Stripe-Signature"))]
+    pub header: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub algorithm: HmacAlgorithm,
+
+    /// The secret shared with the sender of the requests.
+    #[configurable(metadata(docs::examples = "${WEBHOOK_SECRET}"))]
+    pub secret: SensitiveString,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub encoding: SignatureEncoding,
+
+    /// A prefix of the header value that precedes the signature.
+    #[configurable(metadata(docs::examples = "sha256="))]
+    #[configurable(metadata(docs::examples = "v0="))]
+    pub prefix: Option<String>,
+
+    /// The key of the signature, when the header carries comma-separated `key=value` pairs.
+    ///
+    /// Requests are accepted when any of the pairs with this key holds a valid signature.
+    #[configurable(metadata(docs::examples = "v1"))]
+    pub signature_key: Option<String>,
+
+    /// The key of the timestamp, when the header carries comma-separated `key=value` pairs.
+    #[configurable(metadata(docs::examples = "t"))]
+    pub timestamp_key: Option<String>,
+
+    /// The name of the header that carries the timestamp of the request.
+    #[configurable(metadata(docs::examples = "X-Slack-Request-Timestamp"))]
+    pub timestamp_header: Option<String>,
+
+    /// The payload that is signed.
+    ///
+    /// `{body}` is replaced by the body of the request, and `{timestamp}` by its timestamp.
+    #[serde(default = "default_signature_payload")]
+    #[configurable(metadata(docs::examples = "{timestamp}.{body}"))]
+    #[configurable(metadata(docs::examples = "v0:{timestamp}:{body}"))]
+    pub payload: String,
+
+    /// The maximum difference, in seconds, between the timestamp of a request and the current
+    /// time.
+    ///
+    /// Requests outside of this window are rejected, which protects against replayed requests.
+    /// Requires `timestamp_key` or `timestamp_header`.
+    #[configurable(metadata(docs::examples = 300))]
+    #[configurable(metadata(docs::human_name = "Replay Window"))]
+    pub replay_window_secs: Option<u64>,
+}
+
+fn default_signature_payload() -> String {
+    "{body}".to_owned()
+}
+
+/// The HMAC algorithm of the signature.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HmacAlgorithm {
+    /// HMAC-SHA1.
+    Sha1,
+
+    /// HMAC-SHA256.
+    #[default]
+    Sha256,
+
+    /// HMAC-SHA512.
+    Sha512,
+}
+
+impl HmacAlgorithm {
+    fn message_digest(self) -> MessageDigest {
+        match self {
+            HmacAlgorithm::Sha1 => MessageDigest::sha1(),
+            HmacAlgorithm::Sha256 => MessageDigest::sha256(),
+            HmacAlgorithm::Sha512 => MessageDigest::sha512(),
+        }
+    }
+}
+
+/// The encoding of the signature in the header.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureEncoding {
+    /// The signature is hex-encoded.
+    #[default]
+    Hex,
+
+    /// The signature is base64-encoded.
+    Base64,
+}
+
+/// A part of the signed payload.
+#[derive(Clone, Debug, PartialEq)]
+enum PayloadPart {
+    Literal(String),
+    Body,
+    Timestamp,
+}
+
+fn parse_payload(payload: &str) -> Result<Vec<PayloadPart>, String> {
+    let mut parts = Vec::new();
+    let mut rest = payload;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| format!("Unclosed placeholder in signature payload {payload:?}"))?;
+        if start > 0 {
+            parts.push(PayloadPart::Literal(rest[..start].to_owned()));
+        }
+        parts.push(match &rest[start + 1..end] {
+            "body" => PayloadPart::Body,
+            "timestamp" => PayloadPart::Timestamp,
+            other => {
+                return Err(format!(
+                    "Unknown placeholder {{{other}}} in signature payload, expected {{body}} or {{timestamp}}"
+                ))
+            }
+        });
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(PayloadPart::Literal(rest.to_owned()));
+    }
+    Ok(parts)
+}
+
+/// Verifies the HMAC signature of requests.
+#[derive(Clone, Debug)]
+struct SignatureVerifier {
+    header: String,
+    algorithm: HmacAlgorithm,
+    key: pkey::PKey<pkey::Private>,
+    encoding: SignatureEncoding,
+    prefix: Option<String>,
+    signature_key: Option<String>,
+    timestamp_key: Option<String>,
+    timestamp_header: Option<String>,
+    payload: Vec<PayloadPart>,
+    replay_window_secs: Option<u64>,
+}
+
+impl TryFrom<&HttpSignatureConfig> for SignatureVerifier {
+    type Error = String;
+
+    fn try_from(config: &HttpSignatureConfig) -> Result<Self, Self::Error> {
+        let payload = parse_payload(&config.payload)?;
+        let has_timestamp = config.timestamp_key.is_some() || config.timestamp_header.is_some();
+        if !has_timestamp
+            && (config.replay_window_secs.is_some() || payload.contains(&PayloadPart::Timestamp))
+        {
+            return Err("A replay window or a signed {timestamp} requires `timestamp_key` or `timestamp_header`".to_owned());
+        }
+        if config.timestamp_key.is_some() && config.signature_key.is_none() {
+            return Err("`timestamp_key` requires `signature_key`".to_owned());
+        }
+        let key = pkey::PKey::hmac(config.secret.inner().as_bytes())
+            .map_err(|error| format!("Failed creating the HMAC key: {error}"))?;
+
+        Ok(SignatureVerifier {
+            header: config.header.clone(),
+            algorithm: config.algorithm,
+            key,
+            encoding: config.encoding,
+            prefix: config.prefix.clone(),
+            signature_key: config.signature_key.clone(),
+            timestamp_key: config.timestamp_key.clone(),
+            timestamp_header: config.timestamp_header.clone(),
+            payload,
+            replay_window_secs: config.replay_window_secs,
+        })
+    }
+}
+
+impl SignatureVerifier {
+    fn verify(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), String> {
+        let header = headers
+            .get(self.header.as_str())
+            .ok_or_else(|| format!("No {} header", self.header))?
+            .to_str()
+            .map_err(|_| format!("Invalid {} header", self.header))?;
+
+        let (signatures, timestamp) = match &self.signature_key {
+            Some(signature_key) => {
+                let pairs = header
+                    .split(',')
+                    .filter_map(|pair| pair.trim().split_once('='))
+                    .collect::<Vec<_>>();
+                let signatures = pairs
+                    .iter()
+                    .filter(|(key, _)| key == signature_key)
+                    .map(|(_, value)| *value)
+                    .collect::<Vec<_>>();
+                let timestamp = self.timestamp_key.as_ref().and_then(|timestamp_key| {
+                    pairs
+                        .iter()
+                        .find(|(key, _)| key == timestamp_key)
+                        .map(|(_, value)| *value)
+                });
+                (signatures, timestamp)
+            }
+            None => (vec![header], None),
+        };
+        let timestamp = match &self.timestamp_header {
+            Some(timestamp_header) => headers
+                .get(timestamp_header.as_str())
+                .and_then(|value| value.to_str().ok()),
+            None => timestamp,
+        };
+        if timestamp.is_none() && (self.timestamp_key.is_some() || self.timestamp_header.is_some())
+        {
+            return Err("No request timestamp".to_owned());
+        }
+
+        if let (Some(window), Some(timestamp)) = (self.replay_window_secs, timestamp) {
+            let timestamp = timestamp
+                .trim()
+                .parse::<i64>()
+                .map_err(|_| "Invalid request timestamp".to_owned())?;
+            if Utc::now().timestamp().abs_diff(timestamp) > window {
+                return Err("Request timestamp is outside of the replay window".to_owned());
+            }
+        }
+
+        let expected = self
+            .sign(body, timestamp.unwrap_or_default())
+            .map_err(|error| format!("Failed computing the signature: {error}"))?;
+        let valid = signatures.into_iter().any(|signature| {
+            self.decode(signature).map_or(false, |signature| {
+                signature.len() == expected.len() && memcmp::eq(&signature, &expected)
+            })
+        });
+        if valid {
+            Ok(())
+        } else {
+            Err("Invalid signature".to_owned())
+        }
+    }
+
+    fn sign(&self, body: &[u8], timestamp: &str) -> Result<Vec<u8>, openssl::error::ErrorStack> {
+        let mut signer = Signer::new(self.algorithm.message_digest(), &self.key)?;
+        for part in &self.payload {
+            match part {
+                PayloadPart::Literal(literal) => signer.update(literal.as_bytes())?,
+                PayloadPart::Body => signer.update(body)?,
+                PayloadPart::Timestamp => signer.update(timestamp.as_bytes())?,
+            }
+        }
+        signer.sign_to_vec()
+    }
+
+    fn decode(&self, signature: &str) -> Option<Vec<u8>> {
+        let signature = signature.trim();
+        let signature = match &self.prefix {
+            Some(prefix) => signature.strip_prefix(prefix.as_str())?,
+            None => signature,
+        };
+        match self.encoding {
+            SignatureEncoding::Hex => hex::decode(signature).ok(),
+            SignatureEncoding::Base64 => base64::decode_block(signature).ok(),
+        }
+    }
 }
 
 impl TryFrom<Option<&HttpSourceAuthConfig>> for HttpSourceAuth {
@@ -31,24 +323,31 @@ impl TryFrom<Option<&HttpSourceAuthConfig>> for HttpSourceAuth {
 
     fn try_from(auth: Option<&HttpSourceAuthConfig>) -> Result<Self, Self::Error> {
         match auth {
-            Some(auth) => {
+            Some(HttpSourceAuthConfig::Basic { username, password }) => {
                 let mut headers = HeaderMap::new();
-                headers.typed_insert(Authorization::basic(
-                    auth.username.as_str(),
-                    auth.password.inner(),
-                ));
+                headers.typed_insert(Authorization::basic(username.as_str(), password.inner()));
                 match headers.get("authorization") {
                     Some(value) => {
                         let token = value
                             .to_str()
                             .map_err(|error| format!("Failed stringify HeaderValue: {:?}", error))?
                             .to_owned();
-                        Ok(HttpSourceAuth { token: Some(token) })
+                        Ok(HttpSourceAuth {
+                            token: Some(token),
+                            signature: None,
+                        })
                     }
                     None => Err("Authorization headers wasn't generated".to_owned()),
                 }
             }
-            None => Ok(HttpSourceAuth { token: None }),
+            Some(HttpSourceAuthConfig::Signature { signature }) => Ok(HttpSourceAuth {
+                token: None,
+                signature: Some(SignatureVerifier::try_from(signature)?),
+            }),
+            None => Ok(HttpSourceAuth {
+                token: None,
+                signature: None,
+            }),
         }
     }
 }
@@ -57,13 +356,24 @@ impl TryFrom<Option<&HttpSourceAuthConfig>> for HttpSourceAuth {
 pub struct HttpSourceAuth {
     #[allow(unused)] // triggered by check-component-features
     pub(self) token: Option<String>,
+    #[allow(unused)] // triggered by check-component-features
+    pub(self) signature: Option<SignatureVerifier>,
 }
 
 impl HttpSourceAuth {
     #[allow(unused)] // triggered by check-component-features
-    pub fn is_valid(&self, header: &Option<String>) -> Result<(), ErrorMessage> {
+    pub fn is_valid(&self, headers: &HeaderMap, body: &[u8]) -> Result<(), ErrorMessage> {
         use warp::http::StatusCode;
 
+        if let Some(signature) = &self.signature {
+            return signature
+                .verify(headers, body)
+                .map_err(|message| ErrorMessage::new(StatusCode::UNAUTHORIZED, message));
+        }
+
+        let header = headers
+            .get(AUTHORIZATION)
+            .map(|value| value.to_str().unwrap_or_default());
         match (&self.token, header) {
             (Some(token1), Some(token2)) => {
                 if token1 == token2 {
@@ -83,3 +393,115 @@ impl HttpSourceAuth {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use warp::http::HeaderValue;
+
+    use super::*;
+
+    fn verifier(config: &str) -> SignatureVerifier {
+        let config: HttpSignatureConfig = toml::from_str(config).unwrap();
+        SignatureVerifier::try_from(&config).unwrap()
+    }
+
+    fn headers(pairs: &[(&'static str, String)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    fn hmac_hex(secret: &str, payload: &str) -> String {
+        let key = pkey::PKey::hmac(secret.as_bytes()).unwrap();
+        let mut signer = Signer::new(MessageDigest::sha256(), &key).unwrap();
+        signer.update(payload.as_bytes()).unwrap();
+        hex::encode(signer.sign_to_vec().unwrap())
+    }
+
+    #[test]
+    fn verifies_prefixed_hex_signature() {
+        let verifier = verifier(
+            r#"
+            header = "X-Hub-Signature-256"
+            prefix = "sha256="
+            secret = "It's a Secret to Everybody"
+            "#,
+        );
+        // Example from the GitHub documentation on validating webhook deliveries.
+        let signature =
+            "sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17".to_owned();
+
+        let valid = headers(&[("x-hub-signature-256", signature)]);
+        assert_eq!(verifier.verify(&valid, b"Hello, World!"), Ok(()));
+        assert_eq!(
+            verifier.verify(&valid, b"Hello, World?"),
+            Err("Invalid signature".to_owned())
+        );
+        assert_eq!(
+            verifier.verify(&HeaderMap::new(), b"Hello, World!"),
+            Err("No X-Hub-Signature-256 header".to_owned())
+        );
+    }
+
+    #[test]
+    fn verifies_base64_signature() {
+        let verifier = verifier(
+            r#"
+            header = "X-Shopify-Hmac-Sha256"
+            secret = "secret"
+            encoding = "base64"
+            "#,
+        );
+        let signature = base64::encode_block(&hex::decode(hmac_hex("secret", "body")).unwrap());
+
+        let valid = headers(&[("x-shopify-hmac-sha256", signature)]);
+        assert_eq!(verifier.verify(&valid, b"body"), Ok(()));
+    }
+
+    #[test]
+    fn verifies_timestamped_signature() {
+        let verifier = verifier(
+            r#"
+            header = "Stripe-Signature"
+            secret = "secret"
+            signature_key = "v1"
+            timestamp_key = "t"
+            payload = "{timestamp}.{body}"
+            replay_window_secs = 300
+            "#,
+        );
+        let now = Utc::now().timestamp();
+        let signature = hmac_hex("secret", &format!("{now}.body"));
+
+        let valid = headers(&[(
+            "stripe-signature",
+            format!("t={now},v1=0000,v1={signature}"),
+        )]);
+        assert_eq!(verifier.verify(&valid, b"body"), Ok(()));
+
+        let old = now - 600;
+        let signature = hmac_hex("secret", &format!("{old}.body"));
+        let replayed = headers(&[("stripe-signature", format!("t={old},v1={signature}"))]);
+        assert_eq!(
+            verifier.verify(&replayed, b"body"),
+            Err("Request timestamp is outside of the replay window".to_owned())
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_payload() {
+        assert!(parse_payload("{body}.{nonce}").is_err());
+        assert!(parse_payload("{body").is_err());
+        assert_eq!(
+            parse_payload("v0:{timestamp}:{body}"),
+            Ok(vec![
+                PayloadPart::Literal("v0:".to_owned()),
+                PayloadPart::Timestamp,
+                PayloadPart::Literal(":".to_owned()),
+                PayloadPart::Body,
+            ])
+        );
+    }
+}
@@ -3,6 +3,7 @@ use std::{
     convert::{Infallible, TryFrom},
     fmt,
     net::SocketAddr,
+    sync::Arc,
     time::Duration,
 };
 
@@ -22,9 +23,10 @@ use warp::{
         path::{FullPath, Tail},
         BoxedFilter,
     },
-    http::{HeaderMap, StatusCode},
+    http::{HeaderMap, Method, StatusCode},
     reject::Rejection,
-    Filter,
+    reply::Response,
+    Filter, Reply,
 };
 
 use crate::{
@@ -70,6 +72,17 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         decode(encoding_header, body)
     }
 
+    /// Responds to a request without building events from it, for example to answer the
+    /// verification requests of webhooks. Returns `None` for requests that carry events.
+    fn handshake(&self, _request: &HttpSourceRequest) -> Option<Response> {
+        None
+    }
+
+    /// Builds the response to a request whose events were all delivered.
+    fn build_response(&self, response_code: StatusCode, _request: &HttpSourceRequest) -> Response {
+        response_code.into_response()
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn run(
         self,
@@ -83,97 +96,48 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
         cx: SourceContext,
         acknowledgements: SourceAcknowledgementsConfig,
         keepalive_settings: KeepaliveConfig,
+    ) -> crate::Result<crate::sources::Source> {
+        let route = HttpRoute {
+            source: self,
+            path: path.to_owned(),
+            method,
+            strict_path,
+            response_code,
+            auth: auth.clone(),
+        };
+        Self::run_routes(
+            vec![route],
+            address,
+            tls,
+            cx,
+            acknowledgements,
+            keepalive_settings,
+        )
+    }
+
+    /// Serves several routes on the same address, each route with its own source.
+    fn run_routes(
+        routes: Vec<HttpRoute<Self>>,
+        address: SocketAddr,
+        tls: &Option<TlsEnableableConfig>,
+        cx: SourceContext,
+        acknowledgements: SourceAcknowledgementsConfig,
+        keepalive_settings: KeepaliveConfig,
     ) -> crate::Result<crate::sources::Source> {
         let tls = MaybeTlsSettings::from_config(tls, true)?;
         let protocol = tls.http_protocol_name();
-        let auth = HttpSourceAuth::try_from(auth.as_ref())?;
-        let path = path.to_owned();
         let acknowledgements = cx.do_acknowledgements(acknowledgements);
-        let enable_source_ip = self.enable_source_ip();
+        let enable_source_ip = routes.iter().any(|route| route.source.enable_source_ip());
+        let routes = routes
+            .into_iter()
+            .map(|route| route.filter(protocol, acknowledgements, cx.out.clone()))
+            .collect::<crate::Result<Vec<_>>>()?;
 
         Ok(Box::pin(async move {
-            let mut filter: BoxedFilter<()> = match method {
-                HttpMethod::Head => warp::head().boxed(),
-                HttpMethod::Get => warp::get().boxed(),
-                HttpMethod::Put => warp::put().boxed(),
-                HttpMethod::Post => warp::post().boxed(),
-                HttpMethod::Patch => warp::patch().boxed(),
-                HttpMethod::Delete => warp::delete().boxed(),
-                HttpMethod::Options => warp::options().boxed(),
-            };
-
-            // https://github.com/rust-lang/rust-clippy/issues/8148
-            #[allow(clippy::unnecessary_to_owned)]
-            for s in path.split('/').filter(|&x| !x.is_empty()) {
-                filter = filter.and(warp::path(s.to_string())).boxed()
-            }
-            let svc = filter
-                .and(warp::path::tail())
-                .and_then(move |tail: Tail| async move {
-                    if !strict_path || tail.as_str().is_empty() {
-                        Ok(())
-                    } else {
-                        emit!(HttpInternalError {
-                            message: "Path not found."
-                        });
-                        Err(warp::reject::custom(ErrorMessage::new(
-                            StatusCode::NOT_FOUND,
-                            "Not found".to_string(),
-                        )))
-                    }
-                })
-                .untuple_one()
-                .and(warp::path::full())
-                .and(warp::header::optional::<String>("authorization"))
-                .and(warp::header::optional::<String>("content-encoding"))
-                .and(warp::header::headers_cloned())
-                .and(warp::body::bytes())
-                .and(warp::query::<HashMap<String, String>>())
-                .and(warp::filters::ext::optional())
-                .and_then(
-                    move |path: FullPath,
-                          auth_header,
-                          encoding_header: Option<String>,
-                          headers: HeaderMap,
-                          body: Bytes,
-                          query_parameters: HashMap<String, String>,
-                          addr: Option<PeerAddr>| {
-                        debug!(message = "Handling HTTP request.", headers = ?headers);
-                        let http_path = path.as_str();
-
-                        let events = auth
-                            .is_valid(&auth_header)
-                            .and_then(|()| self.decode(encoding_header.as_deref(), body))
-                            .and_then(|body| {
-                                emit!(HttpBytesReceived {
-                                    byte_size: body.len(),
-                                    http_path,
-                                    protocol,
-                                });
-                                self.build_events(body, &headers, &query_parameters, path.as_str())
-                            })
-                            .map(|mut events| {
-                                emit!(HttpEventsReceived {
-                                    count: events.len(),
-                                    byte_size: events.estimated_json_encoded_size_of(),
-                                    http_path,
-                                    protocol,
-                                });
-
-                                self.enrich_events(
-                                    &mut events,
-                                    path.as_str(),
-                                    &headers,
-                                    &query_parameters,
-                                    addr.map(|PeerAddr(inner_addr)| inner_addr).as_ref(),
-                                );
-
-                                events
-                            });
-
-                        handle_request(events, acknowledgements, response_code, cx.out.clone())
-                    },
-                );
+            let svc = routes
+                .into_iter()
+                .reduce(|routes, route| routes.or(route).unify().boxed())
+                .ok_or_else(|| error!("An HTTP source must serve at least one route."))?;
 
             let ping = warp::get().and(warp::path("ping")).map(|| "pong");
             let routes = svc.or(ping).recover(|r: Rejection| async move {
@@ -238,6 +202,186 @@ pub trait HttpSource: Clone + Send + Sync + 'static {
     }
 }
 
+/// A request received by an HTTP source, with its body already decompressed.
+pub struct HttpSourceRequest {
+    pub method: Method,
+    pub path: String,
+    pub headers: HeaderMap,
+    pub query_parameters: HashMap<String, String>,
+    pub body: Bytes,
+}
+
+/// A route served by an HTTP source, along with the source that builds the events of its
+/// requests.
+pub struct HttpRoute<S> {
+    pub source: S,
+    /// The URL path of the route, where `{name}` segments match any segment.
+    pub path: String,
+    pub method: HttpMethod,
+    pub strict_path: bool,
+    pub response_code: StatusCode,
+    pub auth: Option<HttpSourceAuthConfig>,
+}
+
+impl<S: HttpSource> HttpRoute<S> {
+    fn filter(
+        self,
+        protocol: &'static str,
+        acknowledgements: bool,
+        out: SourceSender,
+    ) -> crate::Result<BoxedFilter<(Response,)>> {
+        let auth = HttpSourceAuth::try_from(self.auth.as_ref())?;
+        let response_code = self.response_code;
+        let strict_path = self.strict_path;
+        let source = Arc::new(self.source);
+
+        let mut filter: BoxedFilter<()> = match self.method {
+            HttpMethod::Head => warp::head().boxed(),
+            HttpMethod::Get => warp::get().boxed(),
+            HttpMethod::Put => warp::put().boxed(),
+            HttpMethod::Post => warp::post().boxed(),
+            HttpMethod::Patch => warp::patch().boxed(),
+            HttpMethod::Delete => warp::delete().boxed(),
+            HttpMethod::Options => warp::options().boxed(),
+        };
+
+        // https://github.com/rust-lang/rust-clippy/issues/8148
+        #[allow(clippy::unnecessary_to_owned)]
+        for segment in self.path.split('/').filter(|&x| !x.is_empty()) {
+            filter = if is_path_parameter(segment) {
+                filter
+                    .and(warp::path::param::<String>())
+                    .map(|_: String| ())
+                    .untuple_one()
+                    .boxed()
+            } else {
+                filter.and(warp::path(segment.to_string())).boxed()
+            };
+        }
+
+        Ok(filter
+            .and(warp::path::tail())
+            .and_then(move |tail: Tail| async move {
+                if !strict_path || tail.as_str().is_empty() {
+                    Ok(())
+                } else {
+                    emit!(HttpInternalError {
+                        message: "Path not found."
+                    });
+                    Err(warp::reject::custom(ErrorMessage::new(
+                        StatusCode::NOT_FOUND,
+                        "Not found".to_string(),
+                    )))
+                }
+            })
+            .untuple_one()
+            .and(warp::method())
+            .and(warp::path::full())
+            .and(warp::header::optional::<String>("content-encoding"))
+            .and(warp::header::headers_cloned())
+            .and(warp::body::bytes())
+            .and(warp::query::<HashMap<String, String>>())
+            .and(warp::filters::ext::optional())
+            .and_then(
+                move |method: Method,
+                      path: FullPath,
+                      encoding_header: Option<String>,
+                      headers: HeaderMap,
+                      body: Bytes,
+                      query_parameters: HashMap<String, String>,
+                      addr: Option<PeerAddr>| {
+                    debug!(message = "Handling HTTP request.", headers = ?headers);
+                    let source = Arc::clone(&source);
+                    let auth = auth.clone();
+                    let out = out.clone();
+
+                    async move {
+                        let http_path = path.as_str();
+                        let request = auth
+                            .is_valid(&headers, &body)
+                            .and_then(|()| source.decode(encoding_header.as_deref(), body))
+                            .map(|body| {
+                                emit!(HttpBytesReceived {
+                                    byte_size: body.len(),
+                                    http_path,
+                                    protocol,
+                                });
+                                HttpSourceRequest {
+                                    method,
+                                    path: http_path.to_owned(),
+                                    headers,
+                                    query_parameters,
+                                    body,
+                                }
+                            });
+                        let request = match request {
+                            Ok(request) => request,
+                            Err(error) => {
+                                emit!(HttpBadRequest::new(error.code(), error.message()));
+                                return Err(warp::reject::custom(error));
+                            }
+                        };
+
+                        if let Some(response) = source.handshake(&request) {
+                            return Ok(response);
+                        }
+
+                        let events = source
+                            .build_events(
+                                request.body.clone(),
+                                &request.headers,
+                                &request.query_parameters,
+                                &request.path,
+                            )
+                            .map(|mut events| {
+                                emit!(HttpEventsReceived {
+                                    count: events.len(),
+                                    byte_size: events.estimated_json_encoded_size_of(),
+                                    http_path,
+                                    protocol,
+                                });
+
+                                source.enrich_events(
+                                    &mut events,
+                                    &request.path,
+                                    &request.headers,
+                                    &request.query_parameters,
+                                    addr.map(|PeerAddr(inner_addr)| inner_addr).as_ref(),
+                                );
+
+                                events
+                            });
+
+                        let response_code =
+                            handle_request(events, acknowledgements, response_code, out).await?;
+                        Ok(source.build_response(response_code, &request))
+                    }
+                },
+            )
+            .boxed())
+    }
+}
+
+/// Returns whether a segment of a route path matches any segment.
+fn is_path_parameter(segment: &str) -> bool {
+    segment.len() > 2 && segment.starts_with('{') && segment.ends_with('}')
+}
+
+/// Returns the values of the `{name}` segments of a route path in a request path.
+pub fn path_parameters(route_path: &str, request_path: &str) -> HashMap<String, String> {
+    route_path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .zip(
+            request_path
+                .split('/')
+                .filter(|segment| !segment.is_empty()),
+        )
+        .filter(|(segment, _)| is_path_parameter(segment))
+        .map(|(segment, value)| (segment[1..segment.len() - 1].to_owned(), value.to_owned()))
+        .collect()
+}
+
 #[derive(Clone)]
 #[repr(transparent)]
 struct PeerAddr(SocketAddr);
@@ -263,7 +407,7 @@ async fn handle_request(
     acknowledgements: bool,
     response_code: StatusCode,
     mut out: SourceSender,
-) -> Result<impl warp::Reply, Rejection> {
+) -> Result<StatusCode, Rejection> {
     match events {
         Ok(mut events) => {
             let receiver = BatchNotifier::maybe_apply_to(acknowledgements, &mut events);
@@ -289,7 +433,7 @@ async fn handle_request(
 async fn handle_batch_status(
     success_response_code: StatusCode,
     receiver: Option<BatchStatusReceiver>,
-) -> Result<impl warp::Reply, Rejection> {
+) -> Result<StatusCode, Rejection> {
     match receiver {
         None => Ok(success_response_code),
         Some(receiver) => match receiver.await {
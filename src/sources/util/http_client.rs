@@ -9,11 +9,11 @@
 //!     context.
 
 use bytes::Bytes;
-use futures_util::{stream, FutureExt, StreamExt, TryFutureExt};
+use futures_util::{stream, StreamExt};
 use http::{response::Parts, Uri};
 use hyper::{Body, Request};
+use std::collections::HashMap;
 use std::time::Duration;
-use std::{collections::HashMap, future::ready};
 use tokio_stream::wrappers::IntervalStream;
 use vector_lib::json_size::JsonSize;
 
@@ -23,6 +23,7 @@ use crate::{
         EndpointBytesReceived, HttpClientEventsReceived, HttpClientHttpError,
         HttpClientHttpResponseError, StreamClosedError,
     },
+    oauth2,
     sources::util::http::HttpMethod,
     tls::TlsSettings,
     SourceSender,
@@ -46,6 +47,9 @@ pub(crate) struct GenericHttpClientInputs {
     pub tls: TlsSettings,
     pub proxy: ProxyConfig,
     pub shutdown: ShutdownSignal,
+    /// Whether a scrape waits for the previous ones to finish, which sources that carry state
+    /// from one scrape to the next require.
+    pub sequential: bool,
 }
 
 /// The default interval to call the HTTP endpoint if none is configured.
@@ -79,6 +83,18 @@ pub(crate) trait HttpClientContext {
     // metadata. This function should be used rather than internal enrichment so
     // that accurate byte count metrics can be emitted.
     fn enrich_events(&mut self, _events: &mut Vec<Event>) {}
+
+    /// (Optional) Called before each request of a scrape, returns the URL and the body to
+    /// request. Returning `None` ends the scrape.
+    fn request(&mut self, url: &Uri) -> Option<(Uri, Bytes)> {
+        Some((url.clone(), Bytes::new()))
+    }
+
+    /// (Optional) Called after a successful response, returns whether the scrape continues with
+    /// another request, such as for the next page of the results.
+    fn has_next_page(&mut self) -> bool {
+        false
+    }
 }
 
 /// Builds a url for the HTTP requests.
@@ -123,9 +139,10 @@ pub(crate) fn warn_if_interval_too_low(timeout: Duration, interval: Duration) {
 ///   - The HTTP request is built per the options in provided generic inputs.
 ///   - The HTTP response is decoded/parsed into events by the specific context.
 ///   - The events are then sent to the output stream.
+///   - The context can continue the scrape with more requests, such as for paginated results.
 pub(crate) async fn call<
     B: HttpClientBuilder<Context = C> + Send + Clone,
-    C: HttpClientContext + Send,
+    C: HttpClientContext + Send + 'static,
 >(
     inputs: GenericHttpClientInputs,
     context_builder: B,
@@ -136,73 +153,55 @@ pub(crate) async fn call<
     // proxy and tls settings.
     let client =
         HttpClient::new(inputs.tls.clone(), &inputs.proxy).expect("Building HTTP client failed");
+    let concurrency = inputs.sequential.then_some(1);
     let mut stream = IntervalStream::new(tokio::time::interval(inputs.interval))
         .take_until(inputs.shutdown)
         .map(move |_| stream::iter(inputs.urls.clone()))
         .flatten()
-        .map(move |url| {
+        .map(move |endpoint| {
             let client = client.clone();
-            let endpoint = url.to_string();
+            let headers = inputs.headers.clone();
+            let content_type = inputs.content_type.clone();
+            let auth = inputs.auth.clone();
+            let timeout = inputs.timeout;
 
             let context_builder = context_builder.clone();
-            let mut context = context_builder.build(&url);
+            let mut context = context_builder.build(&endpoint);
 
-            let mut builder = match http_method {
-                HttpMethod::Head => Request::head(&url),
-                HttpMethod::Get => Request::get(&url),
-                HttpMethod::Post => Request::post(&url),
-                HttpMethod::Put => Request::put(&url),
-                HttpMethod::Patch => Request::patch(&url),
-                HttpMethod::Delete => Request::delete(&url),
-                HttpMethod::Options => Request::options(&url),
-            };
-
-            // add user specified headers
-            for (header, values) in &inputs.headers {
-                for value in values {
-                    builder = builder.header(header, value);
-                }
-            }
+            async_stream::stream! {
+                while let Some((url, body)) = context.request(&endpoint) {
+                    let mut builder = match http_method {
+                        HttpMethod::Head => Request::head(&url),
+                        HttpMethod::Get => Request::get(&url),
+                        HttpMethod::Post => Request::post(&url),
+                        HttpMethod::Put => Request::put(&url),
+                        HttpMethod::Patch => Request::patch(&url),
+                        HttpMethod::Delete => Request::delete(&url),
+                        HttpMethod::Options => Request::options(&url),
+                    };
 
-            // set ACCEPT header if not user specified
-            if !inputs.headers.contains_key(http::header::ACCEPT.as_str()) {
-                builder = builder.header(http::header::ACCEPT, &inputs.content_type);
-            }
+                    // add user specified headers
+                    for (header, values) in &headers {
+                        for value in values {
+                            builder = builder.header(header, value);
+                        }
+                    }
 
-            // building an empty request should be infallible
-            let mut request = builder.body(Body::empty()).expect("error creating request");
+                    // set ACCEPT header if not user specified
+                    if !headers.contains_key(http::header::ACCEPT.as_str()) {
+                        builder = builder.header(http::header::ACCEPT, &content_type);
+                    }
 
-            if let Some(auth) = &inputs.auth {
-                auth.apply(&mut request);
-            }
+                    // building a request from a parsed URL should be infallible
+                    let mut request = builder.body(body).expect("error creating request");
 
-            tokio::time::timeout(inputs.timeout, client.send(request))
-                .then(move |result| async move {
-                    match result {
-                        Ok(Ok(response)) => Ok(response),
-                        Ok(Err(error)) => Err(error.into()),
-                        Err(_) => Err(format!(
-                            "Timeout error: request exceeded {}s",
-                            inputs.timeout.as_secs_f64()
-                        )
-                        .into()),
+                    if let Some(auth) = &auth {
+                        auth.apply(&mut request);
                     }
-                })
-                .and_then(|response| async move {
-                    let (header, body) = response.into_parts();
-                    let body = hyper::body::to_bytes(body).await?;
-                    emit!(EndpointBytesReceived {
-                        byte_size: body.len(),
-                        protocol: "http",
-                        endpoint: endpoint.as_str(),
-                    });
-                    Ok((header, body))
-                })
-                .into_stream()
-                .filter_map(move |response| {
-                    ready(match response {
+
+                    match send(&client, request, timeout, &url).await {
                         Ok((header, body)) if header.status == hyper::StatusCode::OK => {
-                            context.on_response(&url, &header, &body).map(|mut events| {
+                            if let Some(mut events) = context.on_response(&url, &header, &body) {
                                 let byte_size = if events.is_empty() {
                                     // We need to explicitly set the byte size
                                     // to 0 since
@@ -227,8 +226,10 @@ pub(crate) async fn call<
                                 // that the byte sizes are accurate.
                                 context.enrich_events(&mut events);
 
-                                stream::iter(events)
-                            })
+                                for event in events {
+                                    yield event;
+                                }
+                            }
                         }
                         Ok((header, _)) => {
                             context.on_http_response_error(&url, &header);
@@ -236,21 +237,25 @@ pub(crate) async fn call<
                                 code: header.status,
                                 url: url.to_string(),
                             });
-                            None
+                            break;
                         }
                         Err(error) => {
                             emit!(HttpClientHttpError {
                                 error,
                                 url: url.to_string()
                             });
-                            None
+                            break;
                         }
-                    })
-                })
-                .flatten()
-                .boxed()
+                    }
+
+                    if !context.has_next_page() {
+                        break;
+                    }
+                }
+            }
+            .boxed()
         })
-        .flatten_unordered(None)
+        .flatten_unordered(concurrency)
         .boxed();
 
     match out.send_event_stream(&mut stream).await {
@@ -265,3 +270,31 @@ pub(crate) async fn call<
         }
     }
 }
+
+/// Sends the request, and reads the body of the response.
+async fn send(
+    client: &HttpClient,
+    request: Request<Bytes>,
+    timeout: Duration,
+    url: &Uri,
+) -> crate::Result<(Parts, Bytes)> {
+    let response = oauth2::send_authorized(request, |request| client.send(request.map(Body::from)));
+    let response = match tokio::time::timeout(timeout, response).await {
+        Ok(Ok(response)) => response,
+        Ok(Err(error)) => return Err(error.into()),
+        Err(_) => {
+            return Err(
+                format!("Timeout error: request exceeded {}s", timeout.as_secs_f64()).into(),
+            )
+        }
+    };
+
+    let (header, body) = response.into_parts();
+    let body = hyper::body::to_bytes(body).await?;
+    emit!(EndpointBytesReceived {
+        byte_size: body.len(),
+        protocol: "http",
+        endpoint: url.to_string().as_str(),
+    });
+    Ok((header, body))
+}
@@ -0,0 +1,145 @@
+use std::num::NonZeroU64;
+
+use snafu::ResultExt;
+use vector_lib::codecs::decoding::{DeserializerConfig, FramingConfig};
+use vector_lib::config::LogNamespace;
+use vector_lib::configurable::configurable_component;
+
+use super::source::WebSocketSource;
+use crate::{
+    codecs::DecodingConfig,
+    common::websocket::{ConnectSnafu, WebSocketConnector},
+    config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
+    http::Auth,
+    serde::{default_decoding, default_framing_message_based},
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+/// Configuration for the `websocket` source.
+#[configurable_component(source(
+    "websocket",
+    "Collect observability event data from a websocket server."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketSourceConfig {
+    /// The WebSocket URI to connect to.
+    ///
+    /// This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
+    #[configurable(metadata(docs::examples = "wss://stream.example.com/feed"))]
+    pub uri: String,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    /// A text message sent to the server every time a connection is established.
+    ///
+    /// This is typically used to subscribe to the data the server should send.
+    #[configurable(metadata(
+        docs::examples = "{\"type\": \"subscribe\", \"channels\": [\"trades\"]}"
+    ))]
+    pub initial_message: Option<String>,
+
+    /// The interval, in seconds, between sending [Ping][ping]s to the remote peer.
+    ///
+    /// If this option is not configured, pings are not sent on an interval.
+    ///
+    /// If the `ping_timeout` is not set, pings are still sent but there is no expectation of pong
+    /// response times.
+    ///
+    /// [ping]: https://www.rfc-editor.org/rfc/rfc6455#section-5.5.2
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub ping_interval: Option<NonZeroU64>,
+
+    /// The number of seconds to wait for a [Pong][pong] response from the remote peer.
+    ///
+    /// If a response is not received within this time, the connection is re-established.
+    ///
+    /// [pong]: https://www.rfc-editor.org/rfc/rfc6455#section-5.5.3
+    // NOTE: this option is not relevant if the `ping_interval` is not configured.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    pub ping_timeout: Option<NonZeroU64>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    pub framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    pub decoding: DeserializerConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+}
+
+impl GenerateConfig for WebSocketSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            uri: "ws://127.0.0.1:9000/endpoint".into(),
+            tls: None,
+            auth: None,
+            initial_message: None,
+            ping_interval: None,
+            ping_timeout: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            log_namespace: None,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "websocket")]
+impl SourceConfig for WebSocketSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<crate::sources::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let tls = MaybeTlsSettings::from_config(&self.tls, false).context(ConnectSnafu)?;
+        let connector = WebSocketConnector::new(self.uri.clone(), tls, self.auth.clone())?;
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        let source = WebSocketSource {
+            connector,
+            initial_message: self.initial_message.clone(),
+            ping_interval: self.ping_interval,
+            ping_timeout: self.ping_timeout,
+            decoder,
+            log_namespace,
+        };
+        Ok(Box::pin(source.run(cx.out, cx.shutdown)))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata();
+
+        vec![SourceOutput::new_maybe_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebSocketSourceConfig>();
+    }
+}
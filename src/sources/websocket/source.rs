@@ -0,0 +1,297 @@
+use std::{num::NonZeroU64, time::Instant};
+
+use chrono::Utc;
+use futures::{Sink, SinkExt, Stream, StreamExt};
+use tokio_tungstenite::tungstenite::{error::Error as WsError, protocol::Message};
+use tokio_util::codec::FramedRead;
+use vector_lib::codecs::StreamDecodingError;
+use vector_lib::config::LogNamespace;
+use vector_lib::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+    Registered,
+};
+use vector_lib::EstimatedJsonEncodedSizeOf;
+
+use super::WebSocketSourceConfig;
+use crate::{
+    codecs::Decoder,
+    common::websocket::{check_received_pong_time, is_closed, PingInterval, WebSocketConnector},
+    event::Event,
+    internal_events::{
+        ConnectionOpen, OpenGauge, StreamClosedError, WsConnectionShutdown, WsReceiveError,
+    },
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+/// Why the source stopped reading from a connection.
+enum Disconnect {
+    /// The connection was lost, and a new one is established.
+    Reconnect,
+    /// The source is shutting down.
+    Shutdown,
+}
+
+pub(super) struct WebSocketSource {
+    pub(super) connector: WebSocketConnector,
+    pub(super) initial_message: Option<String>,
+    pub(super) ping_interval: Option<NonZeroU64>,
+    pub(super) ping_timeout: Option<NonZeroU64>,
+    pub(super) decoder: Decoder,
+    pub(super) log_namespace: LogNamespace,
+}
+
+impl WebSocketSource {
+    pub(super) async fn run(
+        self,
+        mut out: SourceSender,
+        mut shutdown: ShutdownSignal,
+    ) -> Result<(), ()> {
+        let events_received = register!(EventsReceived);
+        let bytes_received = register!(BytesReceived::from(Protocol("websocket".into())));
+
+        loop {
+            // Connection failures are retried with an exponential backoff.
+            let ws_stream = tokio::select! {
+                ws_stream = self.connector.connect_backoff() => ws_stream,
+                _ = &mut shutdown => break,
+            };
+            let (ws_sink, ws_stream) = ws_stream.split();
+            futures::pin_mut!(ws_sink);
+            futures::pin_mut!(ws_stream);
+
+            let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+
+            let disconnect = self
+                .handle_messages(
+                    &mut ws_sink,
+                    &mut ws_stream,
+                    &mut out,
+                    &mut shutdown,
+                    &events_received,
+                    &bytes_received,
+                )
+                .await;
+            if let Disconnect::Shutdown = disconnect {
+                _ = ws_sink.close().await;
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_messages<WS, O>(
+        &self,
+        ws_sink: &mut O,
+        ws_stream: &mut WS,
+        out: &mut SourceSender,
+        shutdown: &mut ShutdownSignal,
+        events_received: &Registered<EventsReceived>,
+        bytes_received: &Registered<BytesReceived>,
+    ) -> Disconnect
+    where
+        WS: Stream<Item = Result<Message, WsError>> + Unpin,
+        O: Sink<Message, Error = WsError> + Unpin,
+    {
+        const PING: &[u8] = b"PING";
+
+        if let Some(message) = &self.initial_message {
+            if let Err(error) = ws_sink.send(Message::text(message.clone())).await {
+                emit!(WsReceiveError { error });
+                return Disconnect::Reconnect;
+            }
+        }
+
+        // tokio::time::Interval panics if the period arg is zero. Since the struct members are
+        // using NonZeroU64 that is not something we need to account for.
+        let mut ping_interval = PingInterval::new(self.ping_interval.map(u64::from));
+        let mut last_pong = Instant::now();
+
+        loop {
+            let result = tokio::select! {
+                _ = &mut *shutdown => return Disconnect::Shutdown,
+
+                _ = ping_interval.tick() => {
+                    match check_received_pong_time(self.ping_timeout, last_pong) {
+                        Ok(()) => ws_sink.send(Message::Ping(PING.to_vec())).await.map(|_| None),
+                        Err(e) => Err(e)
+                    }
+                },
+
+                msg = ws_stream.next() => {
+                    // Pongs are sent automatically by tungstenite during reading from the stream.
+                    match msg {
+                        Some(Ok(Message::Text(text))) => Ok(Some(text.into_bytes())),
+                        Some(Ok(Message::Binary(data))) => Ok(Some(data)),
+                        Some(Ok(Message::Pong(_))) => {
+                            last_pong = Instant::now();
+                            Ok(None)
+                        },
+                        Some(Ok(Message::Close(_))) | None => Err(WsError::ConnectionClosed),
+                        Some(Ok(_)) => Ok(None),
+                        Some(Err(e)) => Err(e)
+                    }
+                },
+            };
+
+            match result {
+                Ok(Some(payload)) => {
+                    bytes_received.emit(ByteSize(payload.len()));
+                    let events = self.decode(&payload, events_received).await;
+                    let count = events.len();
+                    if out.send_batch(events).await.is_err() {
+                        emit!(StreamClosedError { count });
+                        return Disconnect::Shutdown;
+                    }
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    if is_closed(&error) {
+                        emit!(WsConnectionShutdown);
+                    } else {
+                        emit!(WsReceiveError { error });
+                    }
+                    return Disconnect::Reconnect;
+                }
+            }
+        }
+    }
+
+    async fn decode(
+        &self,
+        payload: &[u8],
+        events_received: &Registered<EventsReceived>,
+    ) -> Vec<Event> {
+        let mut decoded = Vec::new();
+        let mut stream = FramedRead::new(payload, self.decoder.clone());
+        while let Some(next) = stream.next().await {
+            match next {
+                Ok((events, _byte_size)) => {
+                    let count = events.len();
+                    let byte_size = events.estimated_json_encoded_size_of();
+                    events_received.emit(CountByteSize(count, byte_size));
+
+                    let now = Utc::now();
+
+                    decoded.extend(events.into_iter().map(|mut event| {
+                        if let Event::Log(ref mut log) = event {
+                            self.log_namespace.insert_standard_vector_source_metadata(
+                                log,
+                                WebSocketSourceConfig::NAME,
+                                now,
+                            );
+                        }
+                        event
+                    }));
+                }
+                Err(error) => {
+                    // Error is logged by `crate::codecs`, no further
+                    // handling is needed here.
+                    if !error.can_continue() {
+                        break;
+                    }
+                }
+            }
+        }
+        decoded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use futures::{SinkExt, StreamExt};
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::{accept_async, tungstenite::protocol::Message};
+
+    use super::*;
+    use crate::{
+        config::log_schema,
+        serde::{default_decoding, default_framing_message_based},
+        test_util::{
+            components::{run_and_assert_source_compliance_n, SOURCE_TAGS},
+            next_addr, trace_init,
+        },
+    };
+
+    fn make_config(addr: SocketAddr) -> WebSocketSourceConfig {
+        WebSocketSourceConfig {
+            uri: format!("ws://{}", addr),
+            tls: None,
+            auth: None,
+            initial_message: None,
+            ping_interval: None,
+            ping_timeout: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            log_namespace: None,
+        }
+    }
+
+    fn messages(events: &[Event]) -> Vec<String> {
+        let message_key = log_schema().message_key().unwrap().to_string();
+        events
+            .iter()
+            .map(|event| {
+                event.as_log()[message_key.as_str()]
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn receives_text_and_binary_messages() {
+        trace_init();
+
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws_stream = accept_async(stream).await.unwrap();
+            let subscription = ws_stream.next().await.unwrap().unwrap();
+            ws_stream.send(Message::text("text")).await.unwrap();
+            ws_stream
+                .send(Message::binary(b"binary".to_vec()))
+                .await
+                .unwrap();
+            (subscription, ws_stream)
+        });
+
+        let config = WebSocketSourceConfig {
+            initial_message: Some("subscribe".into()),
+            ..make_config(addr)
+        };
+        let events = run_and_assert_source_compliance_n(config, 2, &SOURCE_TAGS).await;
+
+        let (subscription, _ws_stream) = server.await.unwrap();
+        assert_eq!(subscription, Message::text("subscribe"));
+        assert_eq!(messages(&events), vec!["text", "binary"]);
+    }
+
+    #[tokio::test]
+    async fn reconnects_when_closed() {
+        trace_init();
+
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws_stream = accept_async(stream).await.unwrap();
+            ws_stream.send(Message::text("first")).await.unwrap();
+            ws_stream.close(None).await.unwrap();
+
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws_stream = accept_async(stream).await.unwrap();
+            ws_stream.send(Message::text("second")).await.unwrap();
+            ws_stream
+        });
+
+        let events = run_and_assert_source_compliance_n(make_config(addr), 2, &SOURCE_TAGS).await;
+
+        let _ws_stream = server.await.unwrap();
+        assert_eq!(messages(&events), vec!["first", "second"]);
+    }
+}
@@ -34,6 +34,7 @@ use vector_lib::{
 };
 
 use super::{
+    dropped,
     fanout::{self, Fanout},
     schema,
     task::{Task, TaskOutput, TaskResult},
@@ -577,6 +578,8 @@ impl<'a> Builder<'a> {
                 extra_context: self.extra_context.clone(),
             };
 
+            let dropped_output = sink.dropped_output(key);
+
             let (sink, healthcheck) = match sink.inner.build(cx).await {
                 Err(error) => {
                     self.errors.push(format!("Sink \"{}\": {}", key, error));
@@ -585,6 +588,16 @@ impl<'a> Builder<'a> {
                 Ok(built) => built,
             };
 
+            let (dropped_tracker, reroute_dropped) = match dropped_output {
+                Some(output_id) => {
+                    let (tracker, control, task) =
+                        dropped::reroute_dropped(output_id.clone(), typetag);
+                    self.outputs.insert(output_id, control);
+                    (Some(tracker), Some(task))
+                }
+                None => (None, None),
+            };
+
             let (trigger, tripwire) = Tripwire::new();
 
             let sink = async move {
@@ -605,7 +618,7 @@ impl<'a> Builder<'a> {
                 let mut rx = wrap(rx);
 
                 let events_received = register!(EventsReceived);
-                sink.run(
+                let run = sink.run(
                     rx.by_ref()
                         .filter(|events: &EventArray| ready(filter_events_type(events, input_type)))
                         .inspect(|events| {
@@ -614,17 +627,35 @@ impl<'a> Builder<'a> {
                                 events.estimated_json_encoded_size_of(),
                             ))
                         })
+                        .then(move |events| match &dropped_tracker {
+                            Some(tracker) => tracker.track(events).left_future(),
+                            None => ready(events).right_future(),
+                        })
                         .take_until_if(tripwire),
-                )
-                .await
-                .map(|_| {
-                    debug!("Sink finished normally.");
-                    TaskOutput::Sink(rx)
-                })
-                .map_err(|_| {
-                    debug!("Sink finished with an error.");
-                    TaskError::Opaque
-                })
+                );
+                // The events rejected by the sink can only be rerouted once it has finished
+                // processing them, so the rerouting outlives the sink itself.
+                let reroute_dropped = async move {
+                    match reroute_dropped {
+                        Some(task) => task.await,
+                        None => Ok(()),
+                    }
+                };
+
+                match futures::join!(run, reroute_dropped) {
+                    (Ok(()), Ok(())) => {
+                        debug!("Sink finished normally.");
+                        Ok(TaskOutput::Sink(rx))
+                    }
+                    (Err(()), _) => {
+                        debug!("Sink finished with an error.");
+                        Err(TaskError::Opaque)
+                    }
+                    (Ok(()), Err(error)) => {
+                        debug!("Sink finished with an error rerouting dropped events.");
+                        Err(TaskError::wrapped(error))
+                    }
+                }
             };
 
             let task = Task::new(key.clone(), typetag, sink);
@@ -1,15 +1,21 @@
 use std::sync::Arc;
 
+use futures::Future;
 use futures_util::FutureExt as _;
-
-use tokio::sync::{Mutex, MutexGuard};
+use snafu::Snafu;
+use tokio::sync::{mpsc, oneshot, Mutex, MutexGuard};
+use tracing_subscriber::filter::ParseError;
 
 #[cfg(feature = "api")]
 use crate::api;
 use crate::extra_context::ExtraContext;
 use crate::internal_events::{VectorRecoveryError, VectorReloadError, VectorReloaded};
 
-use crate::{config, signal::ShutdownError, topology::RunningTopology};
+use crate::{
+    config::{self, ComponentKey},
+    signal::ShutdownError,
+    topology::RunningTopology,
+};
 
 #[derive(Clone, Debug)]
 pub struct SharedTopologyController(Arc<Mutex<TopologyController>>);
@@ -32,12 +38,61 @@ impl SharedTopologyController {
     }
 }
 
+/// A request to change the state of a running Vector instance, as issued through the API.
+#[derive(Debug)]
+pub enum ControlRequest {
+    /// Reloads the configuration from disk.
+    ReloadConfig,
+
+    /// Pauses a source, holding back the events it sends to downstream components.
+    PauseSource(ComponentKey),
+
+    /// Resumes a paused source.
+    ResumeSource(ComponentKey),
+
+    /// Replaces the filter applied to internal logs.
+    SetLogFilter(String),
+
+    /// Disconnects a sink from its inputs and waits for it to process the events it has buffered.
+    DrainSink(ComponentKey),
+}
+
+#[derive(Debug, Snafu)]
+pub enum ControlError {
+    #[snafu(display("Source {:?} doesn't exist.", key.id()))]
+    UnknownSource { key: ComponentKey },
+    #[snafu(display("Source {:?} is already paused.", key.id()))]
+    SourceAlreadyPaused { key: ComponentKey },
+    #[snafu(display("Source {:?} isn't paused.", key.id()))]
+    SourceNotPaused { key: ComponentKey },
+    #[snafu(display("Sink {:?} doesn't exist.", key.id()))]
+    UnknownSink { key: ComponentKey },
+    #[snafu(display(
+        "Sink {:?} can't be drained while other components consume its `dropped` output.",
+        key.id()
+    ))]
+    SinkHasConsumers { key: ComponentKey },
+    #[snafu(display("Sink {:?} failed while draining.", key.id()))]
+    DrainFailed { key: ComponentKey },
+    #[snafu(display("Invalid log filter: {}", source))]
+    InvalidLogFilter { source: ParseError },
+    #[snafu(display("Configuration errors: {}", errors.join(" ")))]
+    InvalidConfig { errors: Vec<String> },
+    #[snafu(display("Failed to reload the configuration, see the logs for details."))]
+    ReloadFailed,
+}
+
+pub type ControlReply = oneshot::Sender<Result<(), ControlError>>;
+pub type ControlTx = mpsc::UnboundedSender<(ControlRequest, ControlReply)>;
+pub type ControlRx = mpsc::UnboundedReceiver<(ControlRequest, ControlReply)>;
+
 pub struct TopologyController {
     pub topology: RunningTopology,
     pub config_paths: Vec<config::ConfigPath>,
     pub require_healthy: Option<bool>,
     #[cfg(feature = "api")]
     pub api_server: Option<api::Server>,
+    pub control_tx: ControlTx,
     pub extra_context: ExtraContext,
 }
 
@@ -82,6 +137,7 @@ impl TopologyController {
                 self.topology.config(),
                 self.topology.watch(),
                 Arc::<AtomicBool>::clone(&self.topology.running),
+                self.control_tx.clone(),
                 &Handle::current(),
             ) {
                 Ok(api_server) => {
@@ -131,6 +187,33 @@ impl TopologyController {
         }
     }
 
+    pub fn pause_source(&mut self, key: &ComponentKey) -> Result<(), ControlError> {
+        self.topology.pause_source(key)
+    }
+
+    pub fn resume_source(&mut self, key: &ComponentKey) -> Result<(), ControlError> {
+        self.topology.resume_source(key)
+    }
+
+    /// Drains the given sink, returning a future that resolves once the sink has processed all of
+    /// its buffered events.
+    ///
+    /// The sink is removed from the running configuration, so that it's started again by the next
+    /// reload of the configuration.
+    pub fn drain_sink(
+        &mut self,
+        key: &ComponentKey,
+    ) -> Result<impl Future<Output = Result<(), ControlError>> + Send + 'static, ControlError> {
+        let drained = self.topology.drain_sink(key)?;
+
+        #[cfg(feature = "api")]
+        if let Some(ref api_server) = self.api_server {
+            api_server.update_config(self.topology.config());
+        }
+
+        Ok(drained)
+    }
+
     pub async fn stop(self) {
         self.topology.stop().await;
     }
@@ -0,0 +1,391 @@
+//! Rerouting of the events rejected by a sink to its `dropped` output.
+
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use futures::{stream::FuturesUnordered, StreamExt};
+use tokio::sync::mpsc;
+use vector_lib::config::LogNamespace;
+use vector_lib::internal_event::{self, CountByteSize, EventsSent, InternalEventHandle as _};
+use vector_lib::lookup::{metadata_path, owned_value_path, PathPrefix};
+use vector_lib::EstimatedJsonEncodedSizeOf;
+use vrl::{path, value::Kind};
+
+use super::fanout::{ControlChannel, ControlMessage, Fanout};
+use crate::{
+    config::{log_schema, OutputId, DROPPED_OUTPUT},
+    event::{
+        BatchNotifier, BatchStatus, BatchStatusReceiver, Event, EventArray, EventContainer,
+        EventFinalizer, EventMutRef, EventStatus, LogEvent, Metric, TraceEvent,
+    },
+    schema::Definition,
+};
+
+/// The number of batches of events that can be queued for tracking before the sink is held back.
+const TRACKED_BATCHES: usize = 16;
+
+/// The number of events sent to the sink that can be awaiting their status before the sink is
+/// held back.
+const MAX_PENDING_EVENTS: usize = 100_000;
+
+type Tracked = Vec<(BatchStatusReceiver, Event)>;
+
+/// Keeps track of the events sent to a sink, so that the ones it rejects can be rerouted.
+///
+/// Each event is attached to its own batch notifier, while a copy of it takes over the finalizers
+/// the event arrived with. The copy is then finalized with the status reported by the sink, unless
+/// the event was rejected, in which case the copy is forwarded to the `dropped` output instead.
+///
+/// Events are only tracked while the `dropped` output has consumers.
+pub(super) struct DroppedTracker {
+    tx: mpsc::Sender<Tracked>,
+    consumers: Arc<AtomicUsize>,
+}
+
+impl DroppedTracker {
+    pub(super) fn track(
+        &self,
+        mut events: EventArray,
+    ) -> impl Future<Output = EventArray> + Send + 'static {
+        let tracked = (self.consumers.load(Ordering::Relaxed) > 0).then(|| {
+            events
+                .iter_events_mut()
+                .map(|mut event| {
+                    let finalizers = event.metadata_mut().take_finalizers();
+                    let mut copy = match &event {
+                        EventMutRef::Log(log) => Event::from(LogEvent::clone(log)),
+                        EventMutRef::Metric(metric) => Event::from(Metric::clone(metric)),
+                        EventMutRef::Trace(trace) => Event::from(TraceEvent::clone(trace)),
+                    };
+                    copy.metadata_mut().merge_finalizers(finalizers);
+
+                    let (batch, receiver) = BatchNotifier::new_with_receiver();
+                    event
+                        .metadata_mut()
+                        .add_finalizer(EventFinalizer::new(batch));
+                    (receiver, copy)
+                })
+                .collect::<Tracked>()
+        });
+        let tx = self.tx.clone();
+
+        async move {
+            if let Some(tracked) = tracked {
+                // The receiving end only goes away once the sink has finished, so there is nothing
+                // left to track at that point.
+                _ = tx.send(tracked).await;
+            }
+            events
+        }
+    }
+}
+
+/// Creates the tracker for the events sent to a sink, along with the control channel of the
+/// `dropped` output and the task forwarding the events rejected by the sink to that output.
+///
+/// The task finishes once the tracker has been dropped and every tracked event has been
+/// finalized.
+pub(super) fn reroute_dropped(
+    output_id: OutputId,
+    component_type: &'static str,
+) -> (
+    DroppedTracker,
+    ControlChannel,
+    impl Future<Output = crate::Result<()>>,
+) {
+    let (mut fanout, fanout_control) = Fanout::new();
+    let (control, mut control_rx) = mpsc::unbounded_channel();
+    let (tx, mut rx) = mpsc::channel::<Tracked>(TRACKED_BATCHES);
+    let consumers = Arc::new(AtomicUsize::new(0));
+
+    // Counts the consumers of the output as they are wired up, so the tracker knows whether the
+    // events need to be copied at all.
+    let forward_control = {
+        let consumers = Arc::clone(&consumers);
+        async move {
+            while let Some(message) = control_rx.recv().await {
+                match &message {
+                    ControlMessage::Add(..) => {
+                        consumers.fetch_add(1, Ordering::Relaxed);
+                    }
+                    ControlMessage::Remove(_) => {
+                        consumers.fetch_sub(1, Ordering::Relaxed);
+                    }
+                    ControlMessage::Pause(_) | ControlMessage::Replace(..) => {}
+                }
+                if fanout_control.send(message).is_err() {
+                    break;
+                }
+            }
+        }
+    };
+
+    let reroute = async move {
+        let output_id = Arc::new(output_id);
+        let events_sent = register!(EventsSent::from(internal_event::Output(Some(
+            DROPPED_OUTPUT.into()
+        ))));
+        let mut pending = FuturesUnordered::new();
+        let mut tracking = true;
+
+        loop {
+            tokio::select! {
+                // Events stop being accepted while too many of them are awaiting their status,
+                // which in turn holds back the sink.
+                tracked = rx.recv(), if tracking && pending.len() < MAX_PENDING_EVENTS => {
+                    match tracked {
+                        Some(tracked) => pending.extend(
+                            tracked.into_iter().map(|(receiver, event)| async move {
+                                (receiver.with_reason().await, event)
+                            }),
+                        ),
+                        None => tracking = false,
+                    }
+                }
+                Some(((status, reason), mut event)) = pending.next(), if !pending.is_empty() => {
+                    match status {
+                        BatchStatus::Delivered => {
+                            event.metadata().update_status(EventStatus::Delivered);
+                        }
+                        BatchStatus::Errored => {
+                            event.metadata().update_status(EventStatus::Errored);
+                        }
+                        BatchStatus::Rejected => {
+                            let dropped = Dropped {
+                                output_id: &output_id,
+                                component_type,
+                                status,
+                                reason: reason.as_deref(),
+                            };
+                            dropped.annotate(&mut event);
+                            event.set_upstream_id(Arc::clone(&output_id));
+                            events_sent.emit(CountByteSize(
+                                1,
+                                event.estimated_json_encoded_size_of(),
+                            ));
+                            fanout.send(event.into(), None).await?;
+                        }
+                    }
+                }
+                else => break,
+            }
+        }
+
+        Ok(())
+    };
+
+    let task = async move {
+        tokio::pin!(reroute, forward_control);
+        tokio::select! {
+            result = &mut reroute => result,
+            () = &mut forward_control => reroute.await,
+        }
+    };
+
+    (DroppedTracker { tx, consumers }, control, task)
+}
+
+/// Returns the schema definition of the events a sink reroutes to its `dropped` output, given the
+/// definition of the events it receives.
+pub(super) fn schema_definition(definition: &Definition) -> Definition {
+    let data = Kind::object(BTreeMap::from([
+        ("reason".into(), Kind::bytes()),
+        ("message".into(), Kind::bytes()),
+        ("component_id".into(), Kind::bytes()),
+        ("component_type".into(), Kind::bytes()),
+        ("component_kind".into(), Kind::bytes()),
+    ]));
+
+    let legacy = match log_schema().metadata_key() {
+        Some(metadata_key) => definition.clone().with_event_field(
+            &metadata_key.concat(path!("dropped")),
+            data.clone(),
+            None,
+        ),
+        None => definition.clone(),
+    };
+    Definition::combine_log_namespaces(
+        definition.log_namespaces(),
+        legacy,
+        definition
+            .clone()
+            .with_metadata_field(&owned_value_path!("vector", "dropped"), data, None),
+    )
+}
+
+/// Describes why an event ended up on the `dropped` output of a sink.
+struct Dropped<'a> {
+    output_id: &'a OutputId,
+    component_type: &'static str,
+    status: BatchStatus,
+    reason: Option<&'a str>,
+}
+
+impl Dropped<'_> {
+    const fn status(&self) -> &'static str {
+        match self.status {
+            BatchStatus::Delivered => "delivered",
+            BatchStatus::Errored => "errored",
+            BatchStatus::Rejected => "rejected",
+        }
+    }
+
+    fn message(&self) -> &str {
+        self.reason.unwrap_or("Event was rejected by the sink.")
+    }
+
+    fn data(&self) -> serde_json::Value {
+        serde_json::json!({
+            "reason": self.status(),
+            "message": self.message(),
+            "component_id": self.output_id.component,
+            "component_type": self.component_type,
+            "component_kind": "sink",
+        })
+    }
+
+    /// Annotates an event in the same way as the `dropped` output of the `remap` transform.
+    fn annotate(&self, event: &mut Event) {
+        match event {
+            Event::Log(ref mut log) => match log.namespace() {
+                LogNamespace::Legacy => {
+                    if let Some(metadata_key) = log_schema().metadata_key() {
+                        log.insert(
+                            (PathPrefix::Event, metadata_key.concat(path!("dropped"))),
+                            self.data(),
+                        );
+                    }
+                }
+                LogNamespace::Vector => {
+                    log.insert(metadata_path!("vector", "dropped"), self.data());
+                }
+            },
+            Event::Metric(ref mut metric) => {
+                if let Some(metadata_key) = log_schema().metadata_key() {
+                    metric.replace_tag(
+                        format!("{}.dropped.reason", metadata_key),
+                        self.status().into(),
+                    );
+                    metric.replace_tag(
+                        format!("{}.dropped.component_id", metadata_key),
+                        self.output_id.component.to_string(),
+                    );
+                    metric.replace_tag(
+                        format!("{}.dropped.component_type", metadata_key),
+                        self.component_type.into(),
+                    );
+                    metric.replace_tag(
+                        format!("{}.dropped.component_kind", metadata_key),
+                        "sink".into(),
+                    );
+                }
+            }
+            Event::Trace(ref mut trace) => {
+                trace.maybe_insert(log_schema().metadata_key_target_path(), || {
+                    self.data().into()
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZeroUsize, task::Poll};
+
+    use futures::{poll, Stream};
+    use tracing::Span;
+    use vector_lib::buffers::{topology::builder::TopologyBuilder, WhenFull};
+
+    use super::*;
+    use crate::config::ComponentKey;
+
+    async fn dead_letter(control: &ControlChannel) -> impl Stream<Item = EventArray> + Unpin {
+        let (sender, receiver) = TopologyBuilder::standalone_memory(
+            NonZeroUsize::new(10).unwrap(),
+            WhenFull::Block,
+            &Span::none(),
+        )
+        .await;
+        control
+            .send(ControlMessage::Add(
+                ComponentKey::from("dead_letter"),
+                sender,
+            ))
+            .unwrap();
+        // Let the control message reach the tracker.
+        tokio::task::yield_now().await;
+        receiver.into_stream()
+    }
+
+    #[tokio::test]
+    async fn reroutes_only_rejected_events() {
+        let output_id = OutputId::from((&ComponentKey::from("out"), DROPPED_OUTPUT.to_string()));
+        let (tracker, control, task) = reroute_dropped(output_id, "test");
+        let task = tokio::spawn(task);
+        let mut receiver = dead_letter(&control).await;
+
+        let (batch, source_receiver) = BatchNotifier::new_with_receiver();
+        let events = vec![
+            LogEvent::from("delivered").with_batch_notifier(&batch),
+            LogEvent::from("rejected").with_batch_notifier(&batch),
+        ];
+        drop(batch);
+
+        let mut tracked = tracker.track(EventArray::from(events)).await;
+        drop(tracker);
+        assert_eq!(poll!(source_receiver), Poll::Pending);
+
+        for (index, event) in tracked.iter_events_mut().enumerate() {
+            let finalizers = event.metadata_mut().take_finalizers();
+            if index == 0 {
+                finalizers.update_status(EventStatus::Delivered);
+            } else {
+                finalizers.update_status_with_reason(EventStatus::Rejected, "Invalid field.");
+            }
+        }
+        drop(tracked);
+
+        let rerouted = receiver.next().await.unwrap();
+        assert_eq!(rerouted.len(), 1);
+        let log = rerouted.into_events().next().unwrap().into_log();
+        assert_eq!(log["message"], "rejected".into());
+        assert_eq!(log["metadata.dropped.reason"], "rejected".into());
+        assert_eq!(log["metadata.dropped.message"], "Invalid field.".into());
+        assert_eq!(log["metadata.dropped.component_id"], "out".into());
+        assert_eq!(log["metadata.dropped.component_kind"], "sink".into());
+
+        // The source is only notified once the rerouted event has been finalized.
+        drop(log);
+        task.await.unwrap().unwrap();
+        assert_eq!(source_receiver.await, BatchStatus::Delivered);
+    }
+
+    #[tokio::test]
+    async fn skips_tracking_without_consumers() {
+        let output_id = OutputId::from((&ComponentKey::from("out"), DROPPED_OUTPUT.to_string()));
+        let (tracker, control, task) = reroute_dropped(output_id, "test");
+        let task = tokio::spawn(task);
+
+        let (batch, source_receiver) = BatchNotifier::new_with_receiver();
+        let event = LogEvent::from("rejected").with_batch_notifier(&batch);
+        drop(batch);
+
+        let tracked = tracker.track(EventArray::from(vec![event])).await;
+        for event in tracked.iter_events() {
+            event.metadata().update_status(EventStatus::Rejected);
+        }
+        drop(tracked);
+        assert_eq!(source_receiver.await, BatchStatus::Rejected);
+
+        drop(tracker);
+        drop(control);
+        task.await.unwrap().unwrap();
+    }
+}
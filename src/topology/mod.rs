@@ -12,6 +12,7 @@ pub mod schema;
 
 pub mod builder;
 mod controller;
+mod dropped;
 mod ready_arrays;
 mod running;
 mod task;
@@ -29,7 +30,10 @@ use tokio::sync::mpsc;
 use vector_lib::buffers::topology::channel::{BufferReceiverStream, BufferSender};
 
 pub use self::builder::TopologyPieces;
-pub use self::controller::{ReloadOutcome, SharedTopologyController, TopologyController};
+pub use self::controller::{
+    ControlError, ControlReply, ControlRequest, ControlRx, ControlTx, ReloadOutcome,
+    SharedTopologyController, TopologyController,
+};
 pub use self::running::{RunningTopology, ShutdownErrorReceiver};
 
 use self::task::{Task, TaskError, TaskResult};
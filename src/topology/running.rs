@@ -9,6 +9,7 @@ use std::{
 use super::{
     builder,
     builder::TopologyPieces,
+    controller::ControlError,
     fanout::{ControlChannel, ControlMessage},
     handle_errors, retain, take_healthchecks,
     task::TaskOutput,
@@ -44,6 +45,7 @@ pub struct RunningTopology {
     tasks: HashMap<ComponentKey, TaskHandle>,
     shutdown_coordinator: SourceShutdownCoordinator,
     detach_triggers: HashMap<ComponentKey, DisabledTrigger>,
+    paused_sources: HashSet<ComponentKey>,
     pub(crate) config: Config,
     pub(crate) abort_tx: mpsc::UnboundedSender<ShutdownError>,
     watch: (WatchTx, WatchRx),
@@ -60,6 +62,7 @@ impl RunningTopology {
             outputs_tap_metadata: HashMap::new(),
             shutdown_coordinator: SourceShutdownCoordinator::default(),
             detach_triggers: HashMap::new(),
+            paused_sources: HashSet::new(),
             source_tasks: HashMap::new(),
             tasks: HashMap::new(),
             abort_tx,
@@ -106,9 +109,13 @@ impl RunningTopology {
     /// poll for when the tasks have completed. Once the returned future is
     /// dropped then everything from this RunningTopology instance is fully
     /// dropped.
-    pub fn stop(self) -> impl Future<Output = ()> {
+    pub fn stop(mut self) -> impl Future<Output = ()> {
         // Update the API's health endpoint to signal shutdown
         self.running.store(false, Ordering::Relaxed);
+        // Paused sources would otherwise be stuck sending their last events.
+        for key in std::mem::take(&mut self.paused_sources) {
+            self.set_consumers_paused(&key, false);
+        }
         // Create handy handles collections of all tasks for the subsequent
         // operations.
         let mut wait_handles = Vec::new();
@@ -235,6 +242,13 @@ impl RunningTopology {
             return Ok(false);
         }
 
+        // Rewiring the topology expects every connection to a fanout to be live, so paused sources
+        // are resumed during the reload, and paused again afterwards.
+        let paused_sources = std::mem::take(&mut self.paused_sources);
+        for key in &paused_sources {
+            self.set_consumers_paused(key, false);
+        }
+
         // Calculate the change between the current configuration and the new configuration, and
         // shutdown any components that are changing so that we can reclaim their buffers before
         // spawning the new version of the component.
@@ -272,6 +286,7 @@ impl RunningTopology {
                 self.connect_diff(&diff, &mut new_pieces).await;
                 self.spawn_diff(&diff, new_pieces);
                 self.config = new_config;
+                self.pause_sources(paused_sources);
 
                 info!("New configuration loaded successfully.");
 
@@ -295,6 +310,7 @@ impl RunningTopology {
             {
                 self.connect_diff(&diff, &mut new_pieces).await;
                 self.spawn_diff(&diff, new_pieces);
+                self.pause_sources(paused_sources);
 
                 info!("Old configuration restored successfully.");
 
@@ -466,6 +482,7 @@ impl RunningTopology {
         for key in &diff.sinks.to_remove {
             debug!(component = %key, "Removing sink.");
             self.remove_inputs(key, diff, new_config).await;
+            self.remove_outputs(key);
         }
 
         // After that, for any changed sinks, we temporarily detach their inputs (not remove) so
@@ -494,6 +511,7 @@ impl RunningTopology {
                 buffer_tx.insert(key.clone(), self.inputs.get(key).unwrap().clone());
             }
             self.remove_inputs(key, diff, new_config).await;
+            self.remove_outputs(key);
         }
 
         // Now that we've disconnected or temporarily detached the inputs to all changed/removed
@@ -563,7 +581,8 @@ impl RunningTopology {
             }
 
             for key in &diff.sinks.to_remove {
-                // Sinks only have inputs
+                // Sinks can have a `dropped` output in addition to their inputs
+                self.outputs_tap_metadata.remove(key);
                 self.inputs_tap_metadata.remove(key);
             }
 
@@ -581,6 +600,15 @@ impl RunningTopology {
                 }
             }
 
+            for key in diff.sinks.changed_and_added() {
+                if !new_pieces.outputs.contains_key(key) {
+                    self.outputs_tap_metadata.remove(key);
+                } else if let Some(task) = new_pieces.tasks.get(key) {
+                    self.outputs_tap_metadata
+                        .insert(key.clone(), ("sink", task.typetag().to_string()));
+                }
+            }
+
             for (key, input) in &new_pieces.inputs {
                 self.inputs_tap_metadata
                     .insert(key.clone(), input.1.clone());
@@ -601,6 +629,15 @@ impl RunningTopology {
             self.setup_outputs(key, new_pieces).await;
         }
 
+        // Sinks rerouting the events they reject have a `dropped` output, which also needs to be
+        // available before wiring up any inputs.
+        for key in diff.sinks.changed_and_added() {
+            if new_pieces.outputs.contains_key(key) {
+                debug!(component = %key, "Configuring outputs for sink.");
+                self.setup_outputs(key, new_pieces).await;
+            }
+        }
+
         // Now that all possible outputs are configured, we can start wiring up inputs, starting
         // with transforms.
         for key in diff.transforms.changed_and_added() {
@@ -627,6 +664,12 @@ impl RunningTopology {
         self.reattach_severed_inputs(diff);
 
         // Broadcast any topology changes to subscribers.
+        self.broadcast_changes(diff);
+    }
+
+    /// Broadcasts the changes made to the topology by the given configuration diff to the
+    /// subscribers of the topology, such as tap.
+    fn broadcast_changes(&self, diff: &ConfigDiff) {
         if !self.watch.0.is_closed() {
             let outputs = self
                 .outputs
@@ -650,6 +693,7 @@ impl RunningTopology {
 
             let mut removals = diff.sources.to_remove.clone();
             removals.extend(diff.transforms.to_remove.iter().cloned());
+            removals.extend(diff.sinks.to_remove.iter().cloned());
             self.watch
                 .0
                 .send(TapResource {
@@ -665,8 +709,8 @@ impl RunningTopology {
                         .changed_and_added()
                         .map(|key| key.to_string())
                         .collect(),
-                    // Note, only sources and transforms are relevant. Sinks do
-                    // not have outputs to tap.
+                    // Note, only sources, transforms, and sinks rerouting dropped events are
+                    // relevant. Other sinks do not have outputs to tap.
                     removals,
                 })
                 .expect("Couldn't broadcast config changes.");
@@ -738,6 +782,140 @@ impl RunningTopology {
             .map(|trigger| self.detach_triggers.insert(key.clone(), trigger.into()));
     }
 
+    /// Pauses a source by holding back the events it sends to the components consuming its
+    /// outputs.
+    ///
+    /// The source itself keeps running, but is subject to backpressure until it is resumed.
+    pub(crate) fn pause_source(&mut self, key: &ComponentKey) -> Result<(), ControlError> {
+        if self.config.source(key).is_none() {
+            return Err(ControlError::UnknownSource { key: key.clone() });
+        }
+        if !self.paused_sources.insert(key.clone()) {
+            return Err(ControlError::SourceAlreadyPaused { key: key.clone() });
+        }
+
+        info!(message = "Pausing source.", component = %key);
+        self.set_consumers_paused(key, true);
+        Ok(())
+    }
+
+    /// Resumes a source previously paused with [`RunningTopology::pause_source`].
+    pub(crate) fn resume_source(&mut self, key: &ComponentKey) -> Result<(), ControlError> {
+        if self.config.source(key).is_none() {
+            return Err(ControlError::UnknownSource { key: key.clone() });
+        }
+        if !self.paused_sources.remove(key) {
+            return Err(ControlError::SourceNotPaused { key: key.clone() });
+        }
+
+        info!(message = "Resuming source.", component = %key);
+        self.set_consumers_paused(key, false);
+        Ok(())
+    }
+
+    /// Pauses the given sources again after a reload, as long as they're still part of the
+    /// topology.
+    fn pause_sources(&mut self, keys: HashSet<ComponentKey>) {
+        for key in keys {
+            if self.config.source(&key).is_some() {
+                self.set_consumers_paused(&key, true);
+                self.paused_sources.insert(key);
+            }
+        }
+    }
+
+    /// Pauses, or replaces when resuming, the connection of every component consuming the outputs
+    /// of the given component in their respective fanout.
+    fn set_consumers_paused(&mut self, key: &ComponentKey, paused: bool) {
+        let consumers = self
+            .config
+            .transforms()
+            .map(|(consumer, _)| consumer)
+            .chain(self.config.sinks().map(|(consumer, _)| consumer))
+            .flat_map(|consumer| {
+                self.config
+                    .inputs_for_node(consumer)
+                    .into_iter()
+                    .flatten()
+                    .filter(|input| &input.component == key)
+                    .map(move |input| (input.clone(), consumer.clone()))
+            })
+            .collect::<Vec<_>>();
+
+        for (input, consumer) in consumers {
+            let Some(output) = self.outputs.get_mut(&input) else {
+                continue;
+            };
+            if paused {
+                debug!(component = %consumer, fanout_id = %input, "Pausing component input in fanout.");
+                _ = output.send(ControlMessage::Pause(consumer));
+            } else if let Some(tx) = self.inputs.get(&consumer) {
+                debug!(component = %consumer, fanout_id = %input, "Replacing component input in fanout.");
+                _ = output.send(ControlMessage::Replace(consumer, tx.clone()));
+            }
+        }
+    }
+
+    /// Drains a sink by disconnecting it from its inputs, returning a future that resolves once the
+    /// sink has processed the events left in its buffer and shut down.
+    ///
+    /// The sink is removed from the configuration of this running topology, so that it's added
+    /// back by the next reload.
+    pub(crate) fn drain_sink(
+        &mut self,
+        key: &ComponentKey,
+    ) -> Result<impl Future<Output = Result<(), ControlError>> + Send + 'static, ControlError> {
+        if self.config.sink(key).is_none() {
+            return Err(ControlError::UnknownSink { key: key.clone() });
+        }
+        // The consumers of the `dropped` output would be left dangling when the sink comes back.
+        let has_consumers = self
+            .config
+            .transforms()
+            .map(|(consumer, _)| consumer)
+            .chain(self.config.sinks().map(|(consumer, _)| consumer))
+            .filter_map(|consumer| self.config.inputs_for_node(consumer))
+            .flatten()
+            .any(|input| &input.component == key);
+        if has_consumers {
+            return Err(ControlError::SinkHasConsumers { key: key.clone() });
+        }
+
+        info!(message = "Draining sink.", component = %key);
+
+        let mut diff = ConfigDiff::default();
+        diff.sinks.to_remove.insert(key.clone());
+
+        self.inputs.remove(key);
+        self.detach_triggers.remove(key);
+        for input in self.config.inputs_for_node(key).expect("node exists") {
+            if let Some(output) = self.outputs.get_mut(input) {
+                debug!(component = %key, fanout_id = %input, "Removing component input from fanout.");
+                _ = output.send(ControlMessage::Remove(key.clone()));
+            }
+        }
+        self.remove_outputs(key);
+        self.config.remove_sink(key);
+
+        if !self.watch.0.is_closed() {
+            self.outputs_tap_metadata.remove(key);
+            self.inputs_tap_metadata.remove(key);
+        }
+        self.broadcast_changes(&diff);
+
+        let task = self.tasks.remove(key).expect("sink task exists");
+        let key = key.clone();
+        Ok(async move {
+            match task.await {
+                Ok(Ok(_)) => {
+                    info!(message = "Sink drained.", component = %key);
+                    Ok(())
+                }
+                _ => Err(ControlError::DrainFailed { key }),
+            }
+        })
+    }
+
     fn remove_outputs(&mut self, key: &ComponentKey) {
         self.outputs.retain(|id, _output| &id.component != key);
     }
@@ -1067,5 +1245,14 @@ fn get_changed_outputs(diff: &ConfigDiff, output_ids: Inputs<OutputId>) -> Vec<O
         );
     }
 
+    for sink_key in &diff.sinks.to_change {
+        changed_outputs.extend(
+            output_ids
+                .iter()
+                .filter(|id| &id.component == sink_key)
+                .cloned(),
+        );
+    }
+
     changed_outputs
 }
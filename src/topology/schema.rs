@@ -5,6 +5,7 @@ use vector_lib::config::SourceOutput;
 
 pub(super) use crate::schema::Definition;
 
+use super::dropped;
 use crate::{
     config::{ComponentKey, Config, OutputId, SinkOuter, TransformOutput},
     topology,
@@ -90,6 +91,21 @@ pub fn possible_definitions(
 
             definitions.append(&mut transform_definition);
         }
+
+        // If the input is the `dropped` output of a sink, the events are the ones the sink
+        // received, annotated with the reason they were dropped.
+        if let Some(inputs) = config.sink_inputs(key) {
+            let input_definitions =
+                possible_definitions(inputs, config, enrichment_tables.clone(), cache)?;
+
+            definitions.append(
+                &mut input.with_definitions(
+                    input_definitions
+                        .iter()
+                        .map(|(_, definition)| dropped::schema_definition(definition)),
+                ),
+            );
+        }
     }
 
     Ok(definitions)
@@ -193,6 +209,20 @@ pub(super) fn expanded_definitions(
             // Append whatever number of additional pipelines we created to the existing
             // pipeline definitions.
             definitions.append(&mut transform_definition);
+
+        // The `dropped` output of a sink forwards the events the sink received, so each of its
+        // inputs is expanded to a new pipeline as well.
+        } else if let Some(inputs) = config.sink_inputs(key) {
+            let input_definitions =
+                possible_definitions(inputs, config, enrichment_tables.clone(), &mut merged_cache)?;
+
+            definitions.append(
+                &mut input.with_definitions(
+                    input_definitions
+                        .iter()
+                        .map(|(_, definition)| dropped::schema_definition(definition)),
+                ),
+            );
         }
     }
 
@@ -283,6 +313,21 @@ pub(crate) fn input_definitions(
 
             definitions.append(&mut transform_definitions);
         }
+
+        // If the input is the `dropped` output of a sink we recurse to the inputs of the sink,
+        // whose events are forwarded to that output.
+        if let Some(inputs) = config.sink_inputs(key) {
+            let sink_definitions =
+                input_definitions(inputs, config, enrichment_tables.clone(), cache)?;
+
+            definitions.append(
+                &mut input.with_definitions(
+                    sink_definitions
+                        .iter()
+                        .map(|(_, definition)| dropped::schema_definition(definition)),
+                ),
+            );
+        }
     }
 
     Ok(definitions)
@@ -349,6 +394,10 @@ pub trait ComponentContainer {
 
     fn transform_inputs(&self, key: &ComponentKey) -> Option<&[OutputId]>;
 
+    /// Gets the inputs of the sink with the given key, whose events are forwarded to its
+    /// `dropped` output.
+    fn sink_inputs(&self, key: &ComponentKey) -> Option<&[OutputId]>;
+
     fn transform_outputs(
         &self,
         key: &ComponentKey,
@@ -421,6 +470,10 @@ impl ComponentContainer for Config {
         self.transform(key).map(|transform| &transform.inputs[..])
     }
 
+    fn sink_inputs(&self, key: &ComponentKey) -> Option<&[OutputId]> {
+        self.sink(key).map(|sink| &sink.inputs[..])
+    }
+
     fn transform_outputs(
         &self,
         key: &ComponentKey,
@@ -471,6 +524,10 @@ mod tests {
                 self.transforms.get(key.id()).map(|v| v.0.as_slice())
             }
 
+            fn sink_inputs(&self, _: &ComponentKey) -> Option<&[OutputId]> {
+                None
+            }
+
             fn transform_outputs(
                 &self,
                 key: &ComponentKey,
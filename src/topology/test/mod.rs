@@ -374,6 +374,81 @@ async fn topology_remove_one_sink() {
     assert_eq!(Vec::<Event>::new(), res2);
 }
 
+#[tokio::test]
+async fn topology_pause_and_resume_source() {
+    trace_init();
+
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_sink("out1", &["in1"], sink1);
+
+    let (mut topology, _) = start_topology(config.build().unwrap(), false).await;
+    let mut out1 = out1.flat_map(into_event_stream).map(into_message).boxed();
+
+    let key = ComponentKey::from("in1");
+    topology.pause_source(&key).unwrap();
+    assert!(topology.pause_source(&key).is_err());
+
+    in1.send_event(Event::Log(LogEvent::from("this")))
+        .await
+        .unwrap();
+    assert!(
+        tokio::time::timeout(Duration::from_millis(100), out1.next())
+            .await
+            .is_err()
+    );
+
+    topology.resume_source(&key).unwrap();
+    assert_eq!(out1.next().await.unwrap(), "this");
+
+    topology.stop().await;
+}
+
+#[tokio::test]
+async fn topology_drain_sink() {
+    trace_init();
+
+    let (mut in1, source1) = basic_source();
+    let (out1, sink1) = basic_sink(10);
+    let (out2, sink2) = basic_sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_sink("out1", &["in1"], sink1);
+    config.add_sink("out2", &["in1"], sink2);
+
+    let (mut topology, _) = start_topology(config.build().unwrap(), false).await;
+    let mut out2 = out2.flat_map(into_event_stream).map(into_message).boxed();
+
+    in1.send_event(Event::Log(LogEvent::from("before")))
+        .await
+        .unwrap();
+    assert_eq!(out2.next().await.unwrap(), "before");
+
+    let key = ComponentKey::from("out1");
+    topology.drain_sink(&key).unwrap().await.unwrap();
+    assert!(topology.config().sink(&key).is_none());
+
+    in1.send_event(Event::Log(LogEvent::from("after")))
+        .await
+        .unwrap();
+
+    topology.stop().await;
+
+    let res1 = out1
+        .flat_map(into_event_stream)
+        .map(into_message)
+        .collect::<Vec<_>>()
+        .await;
+    let res2 = out2.collect::<Vec<_>>().await;
+
+    assert_eq!(vec!["before"], res1);
+    assert_eq!(vec!["after"], res2);
+}
+
 #[tokio::test]
 async fn topology_remove_one_transform() {
     trace_init();
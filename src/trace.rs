@@ -19,9 +19,10 @@ use tokio_stream::wrappers::BroadcastStream;
 use tracing::{Event, Subscriber};
 use tracing_limit::RateLimitedLayer;
 use tracing_subscriber::{
-    filter::LevelFilter,
+    filter::{LevelFilter, ParseError, Targets},
     layer::{Context, SubscriberExt},
     registry::LookupSpan,
+    reload,
     util::SubscriberInitExt,
     Layer,
 };
@@ -52,12 +53,40 @@ static SUBSCRIBERS: Mutex<Option<Vec<oneshot::Sender<Vec<LogEvent>>>>> =
 /// has been initialized.
 static SENDER: OnceLock<Sender<LogEvent>> = OnceLock::new();
 
+type FilterReloader = Box<dyn Fn(Targets) -> Result<(), reload::Error> + Send + Sync>;
+
+/// FILTER_RELOADERS holds a function for each of the layers filtered by the logging filter targets, replacing the
+/// filter of that layer, so that the filter can be changed at runtime.
+static FILTER_RELOADERS: Mutex<Vec<FilterReloader>> = Mutex::new(Vec::new());
+
 fn metrics_layer_enabled() -> bool {
     !matches!(std::env::var("DISABLE_INTERNAL_METRICS_TRACING_INTEGRATION"), Ok(x) if x == "true")
 }
 
+/// Wraps the given filter so that it can be replaced with [`set_log_filter`].
+fn reloadable_filter<S: 'static>(filter: Targets) -> reload::Layer<Targets, S> {
+    let (filter, handle) = reload::Layer::new(filter);
+    FILTER_RELOADERS
+        .lock()
+        .expect("Couldn't acquire lock on filter reloaders")
+        .push(Box::new(move |filter| handle.reload(filter)));
+    filter
+}
+
+/// Replaces the filter applied to internal logs, using the same format as the `VECTOR_LOG` environment variable.
+pub fn set_log_filter(levels: &str) -> Result<(), ParseError> {
+    let filter = Targets::from_str(levels)?;
+    // Layers that are no longer part of a subscriber, such as those of a subscriber that failed to be set as the
+    // global default, can't be reloaded and are dropped.
+    FILTER_RELOADERS
+        .lock()
+        .expect("Couldn't acquire lock on filter reloaders")
+        .retain(|reload| reload(filter.clone()).is_ok());
+    Ok(())
+}
+
 pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64) {
-    let fmt_filter = tracing_subscriber::filter::Targets::from_str(levels).expect(
+    let fmt_filter = Targets::from_str(levels).expect(
         "logging filter targets were not formatted correctly or did not specify a valid level",
     );
 
@@ -66,7 +95,7 @@ pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64)
 
     let broadcast_layer = RateLimitedLayer::new(BroadcastLayer::new())
         .with_default_limit(internal_log_rate_limit)
-        .with_filter(fmt_filter.clone());
+        .with_filter(reloadable_filter(fmt_filter.clone()));
 
     let subscriber = tracing_subscriber::registry()
         .with(metrics_layer)
@@ -97,7 +126,7 @@ pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64)
 
         let rate_limited =
             RateLimitedLayer::new(formatter).with_default_limit(internal_log_rate_limit);
-        let subscriber = subscriber.with(rate_limited.with_filter(fmt_filter));
+        let subscriber = subscriber.with(rate_limited.with_filter(reloadable_filter(fmt_filter)));
 
         _ = subscriber.try_init();
     } else {
@@ -110,7 +139,7 @@ pub fn init(color: bool, json: bool, levels: &str, internal_log_rate_limit: u64)
 
         let rate_limited =
             RateLimitedLayer::new(formatter).with_default_limit(internal_log_rate_limit);
-        let subscriber = subscriber.with(rate_limited.with_filter(fmt_filter));
+        let subscriber = subscriber.with(rate_limited.with_filter(reloadable_filter(fmt_filter)));
 
         _ = subscriber.try_init();
     }
@@ -370,7 +370,7 @@ mod tests {
     use crate::schema::Definition;
     use crate::{
         event::{
-            metric::{MetricKind, MetricValue},
+            metric::{ExponentialBuckets, ExponentialHistogram, MetricKind, MetricValue},
             Event, Metric,
         },
         test_util::components::assert_transform_compliance,
@@ -466,6 +466,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn incremental_exponential_histogram() {
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 1000_u64,
+            mode: AggregationMode::Auto,
+        })
+        .unwrap();
+
+        let histogram = |scale, offset, counts: Vec<u64>| {
+            let count = counts.iter().sum();
+            make_metric(
+                "histogram_a",
+                MetricKind::Incremental,
+                MetricValue::ExponentialHistogram {
+                    histogram: ExponentialHistogram {
+                        scale,
+                        positive: ExponentialBuckets { offset, counts },
+                        count,
+                        sum: count as f64,
+                        ..Default::default()
+                    },
+                },
+            )
+        };
+
+        // The histograms are merged at the lower of the two scales instead of being converted
+        // into fixed buckets.
+        agg.record(histogram(1, 0, vec![1, 1, 1, 1]));
+        agg.record(histogram(0, 1, vec![2]));
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert_eq!(1, out.len());
+        assert_eq!(&histogram(0, 0, vec![2, 4]), &out[0]);
+    }
+
     #[test]
     fn absolute_auto() {
         let mut agg = Aggregate::new(&AggregateConfig {
@@ -44,11 +44,18 @@ impl GenerateConfig for DedupeConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "dedupe")]
 impl TransformConfig for DedupeConfig {
-    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::event_task(Dedupe::new(
-            self.cache.num_events,
-            fill_default_fields_match(self.fields.as_ref()),
-        )))
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let mut dedupe = Dedupe::new(&self.cache, fill_default_fields_match(self.fields.as_ref()));
+        if self.cache.persist {
+            let id = context
+                .key
+                .as_ref()
+                .ok_or("Persisting the cache requires a component ID.")?
+                .id();
+            let data_dir = context.globals.resolve_and_make_data_subdir(None, id)?;
+            dedupe = dedupe.persisted_to(data_dir.join("dedupe_cache.msgpack"));
+        }
+        Ok(Transform::event_task(dedupe))
     }
 
     fn input(&self) -> Input {
@@ -100,6 +107,8 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                ttl_secs: None,
+                persist: false,
             },
             fields: Some(FieldMatchConfig::MatchFields(fields)),
         }
@@ -116,6 +125,8 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                ttl_secs: None,
+                persist: false,
             },
             fields: Some(FieldMatchConfig::IgnoreFields(fields)),
         }
@@ -6,24 +6,49 @@ pub mod transform;
 
 #[cfg(feature = "transforms-impl-dedupe")]
 pub mod common {
-    use std::num::NonZeroUsize;
+    use std::{num::NonZeroUsize, time::Duration};
 
+    use serde_with::serde_as;
     use vector_lib::{configurable::configurable_component, lookup::lookup_v2::ConfigTargetPath};
 
     use crate::config::log_schema;
 
     /// Caching configuration for deduplication.
+    #[serde_as]
     #[configurable_component]
     #[derive(Clone, Debug)]
     #[serde(deny_unknown_fields)]
     pub struct CacheConfig {
         /// Number of events to cache and use for comparing incoming events to previously seen events.
         pub num_events: NonZeroUsize,
+
+        /// The maximum age of a cached event, in seconds.
+        ///
+        /// When set, an event is only dropped if a matching event was let through within this
+        /// period, and older events are evicted from the cache. Otherwise, events are only evicted
+        /// once the cache holds more than `num_events` events.
+        #[serde(default)]
+        #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+        #[configurable(metadata(docs::examples = 600))]
+        #[configurable(metadata(docs::human_name = "Time To Live"))]
+        pub ttl_secs: Option<Duration>,
+
+        /// Whether to persist the cache to disk, so that it survives restarts of Vector.
+        ///
+        /// The cache is stored in a subdirectory of the global [`data_dir`][global_data_dir] named
+        /// after the component. It is written at most once a minute while it changes, and when the
+        /// transform is stopped.
+        ///
+        /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+        #[serde(default)]
+        pub persist: bool,
     }
 
     pub fn default_cache_config() -> CacheConfig {
         CacheConfig {
             num_events: NonZeroUsize::new(5000).expect("static non-zero number"),
+            ttl_secs: None,
+            persist: false,
         }
     }
 
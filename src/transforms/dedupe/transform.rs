@@ -1,23 +1,94 @@
-use std::{future::ready, num::NonZeroUsize, pin::Pin};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    pin::Pin,
+    process,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+};
 
 use bytes::Bytes;
-use futures::{Stream, StreamExt};
+use chrono::{DateTime, Utc};
+use futures::Stream;
 use lru::LruCache;
-use vector_lib::lookup::lookup_v2::ConfigTargetPath;
-use vrl::path::OwnedTargetPath;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    task::JoinHandle,
+    time::{Duration, Instant},
+};
+use tracing::Span;
+use vector_lib::{
+    lookup::lookup_v2::ConfigTargetPath,
+    stream::expiration_map::{map_with_expiration, Emitter},
+};
 
 use crate::{
     event::{Event, Value},
-    internal_events::DedupeEventsDropped,
+    internal_events::{
+        DedupeCacheIoError, DedupeCacheLookup, DedupeCacheSize, DedupeEventsDropped,
+    },
     transforms::TaskTransform,
 };
 
-use super::common::FieldMatchConfig;
+use super::common::{CacheConfig, FieldMatchConfig};
+
+/// How often expired entries are evicted and the cache size is reported.
+const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How often the cache is persisted at most while it keeps changing, as the whole cache is written
+/// each time.
+const PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The persisted caches of the running transforms, by path.
+///
+/// A transform reloaded with a new configuration is built while the one it replaces is still
+/// running, so it takes over the live cache rather than loading a snapshot that is outdated.
+static LIVE_CACHES: Lazy<Mutex<HashMap<PathBuf, Weak<Mutex<Cache>>>>> = Lazy::new(Default::default);
+
+/// Numbers the transforms persisting their cache, so that each writes its own temporary file.
+static NEXT_INSTANCE: AtomicU64 = AtomicU64::new(0);
 
-#[derive(Clone)]
 pub struct Dedupe {
     fields: FieldMatchConfig,
-    cache: LruCache<CacheEntry, bool>,
+    cache: Arc<Mutex<Cache>>,
+    ttl: Option<Duration>,
+    /// The file snapshots are written to before replacing the persisted cache, if it is persisted.
+    tmp_path: Option<PathBuf>,
+}
+
+struct Cache {
+    /// Maps each cached entry to the time at which its event was let through.
+    entries: LruCache<CacheEntry, Instant>,
+    /// Whether the cache changed since it was last persisted.
+    dirty: bool,
+    persistence: Option<Persistence>,
+}
+
+/// Where and when the cache is persisted.
+///
+/// Snapshots are written on the blocking thread pool, one at a time.
+struct Persistence {
+    path: PathBuf,
+    /// When the last snapshot was taken.
+    persisted_at: Instant,
+    /// The number of snapshots taken so far.
+    generation: u64,
+    /// The last snapshot that was written, which is locked while it is being written.
+    written: Arc<Mutex<u64>>,
+}
+
+impl Persistence {
+    /// Whether a snapshot is still waiting to be written.
+    fn is_writing(&self) -> bool {
+        self.written
+            .try_lock()
+            .map_or(true, |written| *written < self.generation)
+    }
 }
 
 type TypeId = u8;
@@ -45,10 +116,21 @@ type TypeId = u8;
 /// iterating over the fields of the incoming Events, we know that the
 /// CacheEntries for 2 equivalent events will always contain the fields in the
 /// same order.
-#[derive(Clone, PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 enum CacheEntry {
     Match(Vec<Option<(TypeId, Bytes)>>),
-    Ignore(Vec<(OwnedTargetPath, TypeId, Bytes)>),
+    Ignore(Vec<(ConfigTargetPath, TypeId, Bytes)>),
+}
+
+/// The cache as persisted to disk.
+#[derive(Deserialize, Serialize)]
+struct CacheSnapshot {
+    /// When the snapshot was written, to account for the time Vector was stopped when loading it.
+    saved_at: DateTime<Utc>,
+
+    /// The cached entries, from least to most recently used, along with their age in
+    /// milliseconds.
+    entries: Vec<(CacheEntry, u64)>,
 }
 
 /// Assigns a unique number to each of the types supported by Event::Value.
@@ -67,22 +149,205 @@ const fn type_id_for_value(val: &Value) -> TypeId {
 }
 
 impl Dedupe {
-    pub fn new(num_entries: NonZeroUsize, fields: FieldMatchConfig) -> Self {
+    pub fn new(cache: &CacheConfig, fields: FieldMatchConfig) -> Self {
         Self {
             fields,
-            cache: LruCache::new(num_entries),
+            cache: Arc::new(Mutex::new(Cache {
+                entries: LruCache::new(cache.num_events),
+                dirty: false,
+                persistence: None,
+            })),
+            ttl: cache.ttl_secs,
+            tmp_path: None,
         }
     }
 
+    /// Persists the cache to the given file.
+    ///
+    /// The cache of a running transform persisted to the same file is taken over, otherwise the
+    /// entries previously persisted to it are loaded.
+    pub fn persisted_to(mut self, path: PathBuf) -> Self {
+        let instance = NEXT_INSTANCE.fetch_add(1, Ordering::Relaxed);
+        self.tmp_path = Some(path.with_extension(format!("{}-{instance}.tmp", process::id())));
+
+        let mut live_caches = LIVE_CACHES.lock().expect("poisoned lock");
+        live_caches.retain(|_, cache| cache.strong_count() > 0);
+        if let Some(live_cache) = live_caches.get(&path).and_then(Weak::upgrade) {
+            let capacity = self.cache.lock().expect("poisoned lock").entries.cap();
+            live_cache
+                .lock()
+                .expect("poisoned lock")
+                .entries
+                .resize(capacity);
+            self.cache = live_cache;
+            return self;
+        }
+
+        let mut cache = self.cache.lock().expect("poisoned lock");
+        match load_snapshot(&path) {
+            Ok(Some(snapshot)) => {
+                let now = Instant::now();
+                let downtime = (Utc::now() - snapshot.saved_at)
+                    .to_std()
+                    .unwrap_or_default();
+                for (entry, age) in snapshot.entries {
+                    let age = Duration::from_millis(age) + downtime;
+                    if self.ttl.is_some_and(|ttl| age >= ttl) {
+                        continue;
+                    }
+                    // Entries seen before the monotonic clock started can't be represented, and
+                    // are the oldest ones anyway.
+                    if let Some(seen_at) = now.checked_sub(age) {
+                        cache.entries.put(entry, seen_at);
+                    }
+                }
+            }
+            Ok(None) => {}
+            Err(error) => emit!(DedupeCacheIoError { error, path: &path }),
+        }
+        cache.persistence = Some(Persistence {
+            path: path.clone(),
+            persisted_at: Instant::now(),
+            generation: 0,
+            written: Arc::default(),
+        });
+        drop(cache);
+
+        live_caches.insert(path, Arc::downgrade(&self.cache));
+        self
+    }
+
     pub fn transform_one(&mut self, event: Event) -> Option<Event> {
         let cache_entry = build_cache_entry(&event, &self.fields);
-        if self.cache.put(cache_entry, true).is_some() {
+        let now = Instant::now();
+        let mut guard = self.cache.lock().expect("poisoned lock");
+        let cache = &mut *guard;
+        let hit = match self.ttl {
+            // Without a time to live, hits are refreshed so that the cache evicts the least
+            // recently seen events. That only changes the order of the entries, which isn't worth
+            // persisting the cache for.
+            None => {
+                let hit = cache.entries.put(cache_entry, now).is_some();
+                cache.dirty |= !hit;
+                hit
+            }
+            // With one, an event is a duplicate for a fixed period after its first occurrence, and
+            // the cache is kept in that order so that expired entries can be evicted first.
+            Some(ttl) => match cache.entries.peek(&cache_entry) {
+                Some(seen_at) if now.duration_since(*seen_at) < ttl => true,
+                _ => {
+                    cache.entries.put(cache_entry, now);
+                    cache.dirty = true;
+                    false
+                }
+            },
+        };
+        drop(guard);
+
+        emit!(DedupeCacheLookup { hit });
+        if hit {
             emit!(DedupeEventsDropped { count: 1 });
             None
         } else {
             Some(event)
         }
     }
+
+    /// Evicts expired entries, reports the size of the cache and persists it if it changed and
+    /// wasn't persisted recently.
+    fn maintain(&mut self) {
+        let mut cache = self.cache.lock().expect("poisoned lock");
+        if let Some(ttl) = self.ttl {
+            let now = Instant::now();
+            while cache
+                .entries
+                .peek_lru()
+                .is_some_and(|(_, seen_at)| now.duration_since(*seen_at) >= ttl)
+            {
+                cache.entries.pop_lru();
+                cache.dirty = true;
+            }
+        }
+        emit!(DedupeCacheSize {
+            size: cache.entries.len()
+        });
+        let persist_due = cache.persistence.as_ref().is_some_and(|persistence| {
+            persistence.persisted_at.elapsed() >= PERSIST_INTERVAL && !persistence.is_writing()
+        });
+        drop(cache);
+        if persist_due {
+            self.persist();
+        }
+    }
+
+    /// Persists the cache if it changed, returning the task writing it to disk.
+    fn persist(&self) -> Option<JoinHandle<()>> {
+        let tmp_path = self.tmp_path.clone()?;
+        let mut guard = self.cache.lock().expect("poisoned lock");
+        let cache = &mut *guard;
+        let persistence = cache.persistence.as_mut().filter(|_| cache.dirty)?;
+
+        let now = Instant::now();
+        let snapshot = CacheSnapshot {
+            saved_at: Utc::now(),
+            entries: cache
+                .entries
+                .iter()
+                .rev()
+                .map(|(entry, seen_at)| {
+                    let age = now.duration_since(*seen_at).as_millis() as u64;
+                    (entry.clone(), age)
+                })
+                .collect(),
+        };
+        persistence.persisted_at = now;
+        persistence.generation += 1;
+
+        let generation = persistence.generation;
+        let path = persistence.path.clone();
+        let written = Arc::clone(&persistence.written);
+        cache.dirty = false;
+        drop(guard);
+
+        // The cache is kept alive until the snapshot is written, so that a transform replacing
+        // this one meanwhile takes it over instead of loading an older snapshot.
+        let live_cache = Arc::clone(&self.cache);
+        let span = Span::current();
+        Some(tokio::task::spawn_blocking(move || {
+            let _enter = span.enter();
+            let mut written = written.lock().expect("poisoned lock");
+            // A newer snapshot may have been written already, when the transform is stopped while
+            // an older one was pending.
+            if *written < generation {
+                if let Err(error) = save_snapshot(&path, &tmp_path, &snapshot) {
+                    emit!(DedupeCacheIoError { error, path: &path });
+                }
+                *written = generation;
+            }
+            drop(live_cache);
+        }))
+    }
+}
+
+fn load_snapshot(path: &Path) -> io::Result<Option<CacheSnapshot>> {
+    match fs::read(path) {
+        Ok(contents) => rmp_serde::from_slice(&contents)
+            .map(Some)
+            .map_err(|error| io::Error::new(ErrorKind::InvalidData, error)),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Writes the snapshot to a temporary file first, so that a crash can't leave a partial cache
+/// behind.
+///
+/// Snapshots are encoded with MessagePack, which keeps the field values as raw bytes.
+fn save_snapshot(path: &Path, tmp_path: &Path, snapshot: &CacheSnapshot) -> io::Result<()> {
+    let contents = rmp_serde::to_vec(snapshot)
+        .map_err(|error| io::Error::new(ErrorKind::InvalidData, error))?;
+    fs::write(tmp_path, contents)?;
+    fs::rename(tmp_path, path)
 }
 
 /// Takes in an Event and returns a CacheEntry to place into the LRU cache
@@ -110,7 +375,7 @@ fn build_cache_entry(event: &Event, fields: &FieldMatchConfig) -> CacheEntry {
                         if let Ok(path) = ConfigTargetPath::try_from(field_name) {
                             if !fields.contains(&path) {
                                 entry.push((
-                                    path.0,
+                                    path,
                                     type_id_for_value(value),
                                     value.coerce_to_bytes(),
                                 ));
@@ -133,7 +398,112 @@ impl TaskTransform<Event> for Dedupe {
     where
         Self: 'static,
     {
-        let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+        Box::pin(map_with_expiration(
+            self,
+            task,
+            MAINTENANCE_INTERVAL,
+            |me: &mut Box<Dedupe>, event, emitter: &mut Emitter<Event>| {
+                if let Some(event) = me.transform_one(event) {
+                    emitter.emit(event);
+                }
+            },
+            |me: &mut Box<Dedupe>, _: &mut Emitter<Event>| me.maintain(),
+            |me: &mut Box<Dedupe>, _: &mut Emitter<Event>| {
+                me.persist();
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::{event::LogEvent, test_util::temp_dir};
+
+    fn cache_config(ttl_secs: Option<u64>) -> CacheConfig {
+        CacheConfig {
+            num_events: NonZeroUsize::new(5).unwrap(),
+            ttl_secs: ttl_secs.map(Duration::from_secs),
+            persist: false,
+        }
+    }
+
+    fn fields() -> FieldMatchConfig {
+        FieldMatchConfig::MatchFields(vec!["message".into()])
+    }
+
+    fn event(message: &str) -> Event {
+        Event::Log(LogEvent::from(message))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn ttl_expires_entries() {
+        let mut dedupe = Dedupe::new(&cache_config(Some(10)), fields());
+
+        assert!(dedupe.transform_one(event("first")).is_some());
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert!(dedupe.transform_one(event("first")).is_none());
+        assert!(dedupe.transform_one(event("second")).is_some());
+
+        // Duplicates don't extend the period during which an event is considered a duplicate.
+        tokio::time::advance(Duration::from_secs(5)).await;
+        assert!(dedupe.transform_one(event("first")).is_some());
+        assert!(dedupe.transform_one(event("second")).is_none());
+
+        tokio::time::advance(Duration::from_secs(5)).await;
+        dedupe.maintain();
+        assert_eq!(dedupe.cache.lock().unwrap().entries.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn persists_cache() {
+        let dir = temp_dir();
+        fs::create_dir(&dir).unwrap();
+        let path = dir.join("dedupe_cache.msgpack");
+
+        let mut dedupe = Dedupe::new(&cache_config(Some(600)), fields()).persisted_to(path.clone());
+        assert!(dedupe.transform_one(event("first")).is_some());
+        assert!(dedupe.transform_one(event("second")).is_some());
+        dedupe.persist().unwrap().await.unwrap();
+        drop(dedupe);
+
+        let mut dedupe = Dedupe::new(&cache_config(Some(600)), fields()).persisted_to(path.clone());
+        assert_eq!(dedupe.cache.lock().unwrap().entries.len(), 2);
+        assert!(dedupe.transform_one(event("first")).is_none());
+        assert!(dedupe.transform_one(event("third")).is_some());
+        drop(dedupe);
+
+        // Entries older than the time to live aren't loaded.
+        let mut snapshot = load_snapshot(&path).unwrap().unwrap();
+        snapshot.saved_at -= chrono::Duration::seconds(600);
+        save_snapshot(&path, &dir.join("dedupe_cache.tmp"), &snapshot).unwrap();
+        let dedupe = Dedupe::new(&cache_config(Some(600)), fields()).persisted_to(path);
+        assert_eq!(dedupe.cache.lock().unwrap().entries.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn reload_takes_over_live_cache() {
+        let dir = temp_dir();
+        fs::create_dir(&dir).unwrap();
+        let path = dir.join("dedupe_cache.msgpack");
+
+        let mut old = Dedupe::new(&cache_config(None), fields()).persisted_to(path.clone());
+        assert!(old.transform_one(event("first")).is_some());
+
+        // The replacing transform sees the events let through by the one still running, which
+        // hasn't persisted them yet.
+        let mut new = Dedupe::new(&cache_config(None), fields()).persisted_to(path.clone());
+        assert!(new.transform_one(event("first")).is_none());
+        assert!(old.transform_one(event("second")).is_some());
+        assert!(new.transform_one(event("second")).is_none());
+        assert_ne!(old.tmp_path, new.tmp_path);
+
+        old.persist().unwrap().await.unwrap();
+        drop(old);
+        drop(new);
+        let snapshot = load_snapshot(&path).unwrap().unwrap();
+        assert_eq!(snapshot.entries.len(), 2);
     }
 }
@@ -120,6 +120,17 @@ impl TransformConfig for MetricToLogConfig {
     }
 }
 
+fn exponential_buckets_kind() -> Kind {
+    Kind::object(
+        Collection::empty()
+            .with_known("offset", Kind::integer())
+            .with_known(
+                "counts",
+                Kind::array(Collection::empty().with_unknown(Kind::integer())),
+            ),
+    )
+}
+
 fn schema_definition(log_namespace: LogNamespace) -> Definition {
     let mut schema_definition = Definition::default_for_namespace(&BTreeSet::from([log_namespace]))
         .with_event_field(&owned_value_path!("name"), Kind::bytes(), None)
@@ -212,6 +223,26 @@ fn schema_definition(log_namespace: LogNamespace) -> Definition {
             .or_undefined(),
             None,
         )
+        .with_event_field(
+            &owned_value_path!("exponential_histogram"),
+            Kind::object(
+                Collection::empty().with_known(
+                    "histogram",
+                    Kind::object(
+                        Collection::empty()
+                            .with_known("scale", Kind::integer())
+                            .with_known("zero_count", Kind::integer())
+                            .with_known("zero_threshold", Kind::float())
+                            .with_known("positive", exponential_buckets_kind())
+                            .with_known("negative", exponential_buckets_kind())
+                            .with_known("count", Kind::integer())
+                            .with_known("sum", Kind::float()),
+                    ),
+                ),
+            )
+            .or_undefined(),
+            None,
+        )
         .with_event_field(
             &owned_value_path!("sketch"),
             Kind::any().or_undefined(),
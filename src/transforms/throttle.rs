@@ -1,21 +1,33 @@
-use std::{num::NonZeroU32, pin::Pin, time::Duration};
+use std::{
+    collections::HashMap,
+    num::NonZeroU32,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
 use async_stream::stream;
 use futures::{Stream, StreamExt};
-use governor::{clock, Quota, RateLimiter};
+use governor::{
+    clock, middleware::NoOpMiddleware, state::keyed::DashMapStateStore, Quota, RateLimiter,
+};
 use serde_with::serde_as;
 use snafu::Snafu;
 use vector_lib::config::{clone_input_definitions, LogNamespace};
 use vector_lib::configurable::configurable_component;
+use vector_lib::EstimatedJsonEncodedSizeOf;
 
 use crate::{
     conditions::{AnyCondition, Condition},
-    config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
+    config::{
+        DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput,
+        DROPPED_OUTPUT,
+    },
     event::Event,
-    internal_events::{TemplateRenderingError, ThrottleEventDiscarded},
+    internal_events::{TemplateRenderingError, ThrottleEventDiscarded, ThrottleLimitParseError},
     schema,
     template::Template,
-    transforms::{TaskTransform, Transform},
+    transforms::{SyncTransform, TaskTransform, Transform, TransformOutputsBuf},
 };
 
 /// Configuration of internal metrics for the Throttle transform.
@@ -36,6 +48,64 @@ pub struct ThrottleInternalMetricsConfig {
     pub emit_events_discarded_per_key: bool,
 }
 
+/// A limit of the throttle.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(untagged)]
+pub enum ThrottleLimit {
+    /// A fixed limit, shared by all the buckets.
+    Fixed(u32),
+
+    /// A template rendered from each event, so that each bucket can have its own limit.
+    ///
+    /// Events whose limit can't be rendered as a number are not limited by it.
+    Template(Template),
+}
+
+/// Limits on the number of events and on their size.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ThrottleLimits {
+    /// The limit on the number of events.
+    events: Option<ThrottleLimit>,
+
+    /// The limit on the number of bytes, as the estimated JSON-encoded size of the events.
+    json_bytes: Option<ThrottleLimit>,
+}
+
+/// Limits of the throttle, on the number of events, on their size, or on both.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(untagged)]
+pub enum ThrottleThreshold {
+    /// A limit on the number of events.
+    Events(ThrottleLimit),
+
+    /// Limits on the number of events and on their size.
+    ///
+    /// An event is throttled as soon as it exceeds one of the limits.
+    Limits(ThrottleLimits),
+}
+
+impl Default for ThrottleThreshold {
+    fn default() -> Self {
+        Self::Events(ThrottleLimit::Fixed(0))
+    }
+}
+
+impl ThrottleThreshold {
+    fn limits(&self) -> ThrottleLimits {
+        match self {
+            Self::Events(events) => ThrottleLimits {
+                events: Some(events.clone()),
+                json_bytes: None,
+            },
+            Self::Limits(limits) => limits.clone(),
+        }
+    }
+}
+
 /// Configuration for the `throttle` transform.
 #[serde_as]
 #[configurable_component(transform("throttle", "Rate limit logs passing through a topology."))]
@@ -45,13 +115,29 @@ pub struct ThrottleConfig {
     /// The number of events allowed for a given bucket per configured `window_secs`.
     ///
     /// Each unique key has its own `threshold`.
-    threshold: u32,
+    ///
+    /// The number of bytes allowed, as the estimated JSON-encoded size of the events, can be set
+    /// with `threshold.json_bytes`, alone or along with the number of events in
+    /// `threshold.events`. An event throttled by its size still counts against the number of
+    /// events. Limits can be templates, rendered from each event, so that each bucket has its own
+    /// limits.
+    #[configurable(metadata(docs::examples = 100, docs::examples = "{{ rate_limit }}"))]
+    threshold: ThrottleThreshold,
 
     /// The time window in which the configured `threshold` is applied, in seconds.
     #[serde_as(as = "serde_with::DurationSecondsWithFrac<f64>")]
     #[configurable(metadata(docs::human_name = "Time Window"))]
     window_secs: Duration,
 
+    /// The number of events, or of bytes, that a bucket allows at once.
+    ///
+    /// Buckets are refilled at the rate set by `threshold` and `window_secs`, up to their burst.
+    /// This allows spikes above the sustained rate, after quieter periods.
+    ///
+    /// Defaults to the `threshold`. The limits set here must also be set in `threshold`, and can
+    /// be templates as well. Events larger than the burst of bytes are always throttled.
+    burst: Option<ThrottleThreshold>,
+
     /// The value to group events into separate buckets to be rate limited independently.
     ///
     /// If left unspecified, or if the event doesn't have `key_field`, then the event is not rate
@@ -62,6 +148,14 @@ pub struct ThrottleConfig {
     /// A logical condition used to exclude events from sampling.
     exclude: Option<AnyCondition>,
 
+    /// Reroutes throttled events to a named output instead of discarding them.
+    ///
+    /// When set to `true`, the throttled events are sent to the `dropped` output, for instance to
+    /// route them to cheaper storage.
+    #[serde(default = "crate::serde::default_false")]
+    #[configurable(metadata(docs::human_name = "Reroute Throttled Events"))]
+    reroute_dropped: bool,
+
     #[configurable(derived)]
     #[serde(default)]
     internal_metrics: ThrottleInternalMetricsConfig,
@@ -73,7 +167,14 @@ impl_generate_config_from_default!(ThrottleConfig);
 #[typetag::serde(name = "throttle")]
 impl TransformConfig for ThrottleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Throttle::new(self, context, clock::MonotonicClock).map(Transform::event_task)
+        let throttle = Throttle::new(self, context, clock::MonotonicClock)?;
+        // Task transforms have a single output, so only a synchronous transform can send the
+        // throttled events to the `dropped` output.
+        Ok(if self.reroute_dropped {
+            Transform::synchronous(throttle)
+        } else {
+            Transform::event_task(throttle)
+        })
     }
 
     fn input(&self) -> Input {
@@ -87,20 +188,168 @@ impl TransformConfig for ThrottleConfig {
         _: LogNamespace,
     ) -> Vec<TransformOutput> {
         // The event is not modified, so the definition is passed through as-is
-        vec![TransformOutput::new(
-            DataType::Log,
-            clone_input_definitions(input_definitions),
-        )]
+        let output =
+            TransformOutput::new(DataType::Log, clone_input_definitions(input_definitions));
+
+        if self.reroute_dropped {
+            vec![output.clone(), output.with_port(DROPPED_OUTPUT)]
+        } else {
+            vec![output]
+        }
+    }
+}
+
+type KeyedRateLimiter<C> = RateLimiter<
+    Option<String>,
+    DashMapStateStore<Option<String>>,
+    C,
+    NoOpMiddleware<<C as clock::Clock>::Instant>,
+>;
+
+/// A limit of the throttle, with its burst.
+///
+/// Each threshold and burst rendered from the events has its own rate limiter, keyed by bucket.
+#[derive(Clone)]
+struct Limit<C: clock::Clock> {
+    threshold: ThrottleLimit,
+    burst: Option<ThrottleLimit>,
+    threshold_field: String,
+    burst_field: String,
+    window: Duration,
+    clock: C,
+    limiters: Arc<Mutex<HashMap<(NonZeroU32, NonZeroU32), KeyedRateLimiter<C>>>>,
+}
+
+impl<C: clock::Clock> Limit<C> {
+    fn new(
+        name: &'static str,
+        threshold: Option<ThrottleLimit>,
+        burst: Option<ThrottleLimit>,
+        window: Duration,
+        clock: &C,
+    ) -> Result<Option<Self>, ConfigError> {
+        let threshold = match (threshold, &burst) {
+            (None, None) => return Ok(None),
+            (None, Some(_)) => return Err(ConfigError::BurstWithoutThreshold { limit: name }),
+            (Some(threshold), _) => threshold,
+        };
+        if let ThrottleLimit::Fixed(threshold) = threshold {
+            NonZeroU32::new(threshold)
+                .and_then(|threshold| quota(window, threshold, threshold))
+                .ok_or(ConfigError::NonZero)?;
+        }
+        if matches!(burst, Some(ThrottleLimit::Fixed(0))) {
+            return Err(ConfigError::NonZero);
+        }
+
+        Ok(Some(Self {
+            threshold,
+            burst,
+            threshold_field: format!("threshold.{name}"),
+            burst_field: format!("burst.{name}"),
+            window,
+            clock: clock.clone(),
+            limiters: Default::default(),
+        }))
     }
+
+    /// Returns whether the bucket allows `amount` of the limit for the event, taking it.
+    ///
+    /// Events whose limit can't be rendered are not limited by it.
+    fn check(&self, key: &Option<String>, event: &Event, amount: u32) -> bool {
+        let Some(threshold) = render_limit(&self.threshold, event, &self.threshold_field) else {
+            return true;
+        };
+        let burst = match &self.burst {
+            Some(burst) => match render_limit(burst, event, &self.burst_field) {
+                Some(burst) => burst,
+                None => return true,
+            },
+            None => threshold,
+        };
+        let Some(amount) = NonZeroU32::new(amount) else {
+            return true;
+        };
+        let (Some(threshold), Some(burst)) = (NonZeroU32::new(threshold), NonZeroU32::new(burst))
+        else {
+            // A limit rendered as zero allows nothing.
+            return false;
+        };
+        let Some(quota) = quota(self.window, threshold, burst) else {
+            // The rate is too high to be measured, so it can't be exceeded.
+            return true;
+        };
+
+        let mut limiters = self.limiters.lock().expect("poisoned lock");
+        let limiter = limiters
+            .entry((threshold, burst))
+            .or_insert_with(|| RateLimiter::dashmap_with_clock(quota, &self.clock));
+        // The amount can exceed the burst, in which case it's never allowed.
+        matches!(limiter.check_key_n(key, amount), Ok(Ok(())))
+    }
+
+    /// Forgets the buckets that are full again, and the rate limiters left without buckets.
+    fn retain_recent(&self) {
+        self.limiters
+            .lock()
+            .expect("poisoned lock")
+            .retain(|_, limiter| {
+                limiter.retain_recent();
+                !limiter.is_empty()
+            });
+    }
+}
+
+fn quota(window: Duration, threshold: NonZeroU32, burst: NonZeroU32) -> Option<Quota> {
+    Quota::with_period(Duration::from_secs_f64(
+        window.as_secs_f64() / f64::from(threshold.get()),
+    ))
+    .map(|quota| quota.allow_burst(burst))
+}
+
+fn render_limit(limit: &ThrottleLimit, event: &Event, field: &str) -> Option<u32> {
+    let template = match limit {
+        ThrottleLimit::Fixed(limit) => return Some(*limit),
+        ThrottleLimit::Template(template) => template,
+    };
+    let value = template
+        .render_string(event)
+        .map_err(|error| {
+            emit!(TemplateRenderingError {
+                error,
+                field: Some(field),
+                drop_event: false,
+            })
+        })
+        .ok()?;
+    value
+        .trim()
+        .parse()
+        .map_err(|_| {
+            emit!(ThrottleLimitParseError {
+                field,
+                value: &value,
+            })
+        })
+        .ok()
+}
+
+/// Whether an event was allowed through the throttle.
+enum Outcome {
+    Allowed(Event),
+    Throttled(Event, Option<String>),
 }
 
 #[derive(Clone)]
 pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
-    quota: Quota,
+    events: Option<Limit<C>>,
+    json_bytes: Option<Limit<C>>,
     flush_keys_interval: Duration,
     key_field: Option<Template>,
     exclude: Option<Condition>,
+    reroute_dropped: bool,
     clock: C,
+    flushed: I,
     internal_metrics: ThrottleInternalMetricsConfig,
 }
 
@@ -115,18 +364,37 @@ where
         clock: C,
     ) -> crate::Result<Self> {
         let flush_keys_interval = config.window_secs;
+        if flush_keys_interval.is_zero() {
+            return Err(Box::new(ConfigError::NonZero));
+        }
 
-        let threshold = match NonZeroU32::new(config.threshold) {
-            Some(threshold) => threshold,
-            None => return Err(Box::new(ConfigError::NonZero)),
-        };
+        let threshold = config.threshold.limits();
+        let burst = config
+            .burst
+            .as_ref()
+            .map(ThrottleThreshold::limits)
+            .unwrap_or(ThrottleLimits {
+                events: None,
+                json_bytes: None,
+            });
+        let events = Limit::new(
+            "events",
+            threshold.events,
+            burst.events,
+            flush_keys_interval,
+            &clock,
+        )?;
+        let json_bytes = Limit::new(
+            "json_bytes",
+            threshold.json_bytes,
+            burst.json_bytes,
+            flush_keys_interval,
+            &clock,
+        )?;
+        if events.is_none() && json_bytes.is_none() {
+            return Err(Box::new(ConfigError::NoLimit));
+        }
 
-        let quota = match Quota::with_period(Duration::from_secs_f64(
-            flush_keys_interval.as_secs_f64() / f64::from(threshold.get()),
-        )) {
-            Some(quota) => quota.allow_burst(threshold),
-            None => return Err(Box::new(ConfigError::NonZero)),
-        };
         let exclude = config
             .exclude
             .as_ref()
@@ -134,14 +402,69 @@ where
             .transpose()?;
 
         Ok(Self {
-            quota,
-            clock,
+            events,
+            json_bytes,
             flush_keys_interval,
             key_field: config.key_field.clone(),
             exclude,
+            reroute_dropped: config.reroute_dropped,
+            flushed: clock.now(),
+            clock,
             internal_metrics: config.internal_metrics.clone(),
         })
     }
+
+    fn flush_keys(&self) {
+        for limit in [&self.events, &self.json_bytes].into_iter().flatten() {
+            limit.retain_recent();
+        }
+    }
+
+    fn throttle(&self, event: Event) -> Outcome {
+        let (throttle, event) = match self.exclude.as_ref() {
+            Some(condition) => {
+                let (result, event) = condition.check(event);
+                (!result, event)
+            }
+            _ => (true, event),
+        };
+        if !throttle {
+            return Outcome::Allowed(event);
+        }
+
+        let key = self.key_field.as_ref().and_then(|t| {
+            t.render_string(&event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("key_field"),
+                        drop_event: false,
+                    })
+                })
+                .ok()
+        });
+
+        let allowed = self
+            .events
+            .as_ref()
+            .map_or(true, |limit| limit.check(&key, &event, 1))
+            && self.json_bytes.as_ref().map_or(true, |limit| {
+                let size = event.estimated_json_encoded_size_of().get();
+                limit.check(&key, &event, u32::try_from(size).unwrap_or(u32::MAX))
+            });
+        if allowed {
+            Outcome::Allowed(event)
+        } else {
+            Outcome::Throttled(event, key)
+        }
+    }
+
+    fn discard(&self, key: Option<String>) {
+        emit!(ThrottleEventDiscarded {
+            key: key.unwrap_or_else(|| "None".to_string()),
+            emit_events_discarded_per_key: self.internal_metrics.emit_events_discarded_per_key
+        });
+    }
 }
 
 impl<C, I> TaskTransform<Event> for Throttle<C, I>
@@ -158,8 +481,6 @@ where
     {
         let mut flush_keys = tokio::time::interval(self.flush_keys_interval * 2);
 
-        let limiter = RateLimiter::dashmap_with_clock(self.quota, &self.clock);
-
         Box::pin(stream! {
           loop {
             let done = tokio::select! {
@@ -169,50 +490,18 @@ where
                     match maybe_event {
                         None => true,
                         Some(event) => {
-                            let (throttle, event) = match self.exclude.as_ref() {
-                                Some(condition) => {
-                                    let (result, event) = condition.check(event);
-                                    (!result, event)
-                                },
-                                _ => (true, event)
-                            };
-                            let output = if throttle {
-                                let key = self.key_field.as_ref().and_then(|t| {
-                                    t.render_string(&event)
-                                        .map_err(|error| {
-                                            emit!(TemplateRenderingError {
-                                                error,
-                                                field: Some("key_field"),
-                                                drop_event: false,
-                                            })
-                                        })
-                                        .ok()
-                                });
-
-                                match limiter.check_key(&key) {
-                                    Ok(()) => {
-                                        Some(event)
-                                    }
-                                    _ => {
-                                        emit!(ThrottleEventDiscarded{
-                                            key: key.unwrap_or_else(|| "None".to_string()),
-                                            emit_events_discarded_per_key: self.internal_metrics.emit_events_discarded_per_key
-                                        });
-                                        None
-                                    }
+                            match self.throttle(event) {
+                                Outcome::Allowed(event) => {
+                                    yield event;
                                 }
-                            } else {
-                                Some(event)
-                            };
-                            if let Some(event) = output {
-                                yield event;
+                                Outcome::Throttled(_, key) => self.discard(key),
                             }
                             false
                         }
                     }
                 }
                 _ = flush_keys.tick() => {
-                    limiter.retain_recent();
+                    self.flush_keys();
                     false
                 }
             };
@@ -222,10 +511,36 @@ where
     }
 }
 
+impl<C, I> SyncTransform for Throttle<C, I>
+where
+    C: clock::Clock<Instant = I> + Send + Sync + 'static,
+    I: clock::Reference + Send + Sync + 'static,
+{
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let now = self.clock.now();
+        if Duration::from(now.duration_since(self.flushed)) >= self.flush_keys_interval * 2 {
+            self.flush_keys();
+            self.flushed = now;
+        }
+
+        match self.throttle(event) {
+            Outcome::Allowed(event) => output.push(None, event),
+            Outcome::Throttled(event, _) if self.reroute_dropped => {
+                output.push(Some(DROPPED_OUTPUT), event)
+            }
+            Outcome::Throttled(_, key) => self.discard(key),
+        }
+    }
+}
+
 #[derive(Debug, Snafu)]
 pub enum ConfigError {
     #[snafu(display("`threshold`, and `window_secs` must be non-zero"))]
     NonZero,
+    #[snafu(display("`threshold` must limit the number of events, of bytes, or both"))]
+    NoLimit,
+    #[snafu(display("`burst.{}` can't be set without `threshold.{}`", limit, limit))]
+    BurstWithoutThreshold { limit: &'static str },
 }
 
 #[cfg(test)]
@@ -242,6 +557,62 @@ mod tests {
     use tokio::sync::mpsc;
     use tokio_stream::wrappers::ReceiverStream;
 
+    #[derive(Debug, PartialEq)]
+    enum Outcome {
+        Passed,
+        Dropped,
+        Discarded,
+    }
+    use Outcome::*;
+
+    type FakeThrottle =
+        Throttle<clock::FakeRelativeClock, <clock::FakeRelativeClock as clock::Clock>::Instant>;
+
+    fn throttle(config: &str, clock: &clock::FakeRelativeClock) -> FakeThrottle {
+        let config = toml::from_str::<ThrottleConfig>(config).unwrap();
+        Throttle::new(&config, &TransformContext::default(), clock.clone()).unwrap()
+    }
+
+    /// Sends the event through the throttle, returning where it went.
+    fn send<C, I>(throttle: &mut Throttle<C, I>, event: impl Into<Event>) -> Outcome
+    where
+        C: clock::Clock<Instant = I> + Send + Sync + 'static,
+        I: clock::Reference + Send + Sync + 'static,
+    {
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            vec![
+                TransformOutput::new(DataType::Log, HashMap::new()),
+                TransformOutput::new(DataType::Log, HashMap::new()).with_port(DROPPED_OUTPUT),
+            ],
+            1,
+        );
+        SyncTransform::transform(throttle, event.into(), &mut outputs);
+
+        match (
+            outputs.drain().count(),
+            outputs.drain_named(DROPPED_OUTPUT).count(),
+        ) {
+            (1, 0) => Passed,
+            (0, 1) => Dropped,
+            (0, 0) => Discarded,
+            counts => panic!("unexpected outputs {counts:?}"),
+        }
+    }
+
+    /// Counts the buckets of the events limit of the throttle.
+    fn buckets(throttle: &FakeThrottle) -> usize {
+        let limiters = throttle.events.as_ref().unwrap().limiters.lock().unwrap();
+        limiters.values().map(|limiter| limiter.len()).sum()
+    }
+
+    fn log(fields: &[(&str, &str)]) -> LogEvent {
+        let mut log = LogEvent::default();
+        for (field, value) in fields {
+            log.insert(*field, *value);
+        }
+        log
+    }
+
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<ThrottleConfig>();
@@ -436,14 +807,157 @@ key_field = "{{ bucket }}"
         assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
     }
 
+    #[test]
+    fn throttle_burst() {
+        let clock = clock::FakeRelativeClock::default();
+        let mut throttle = throttle(
+            r#"
+threshold = 1
+window_secs = 1
+burst = 3
+"#,
+            &clock,
+        );
+
+        for _ in 0..3 {
+            assert_eq!(send(&mut throttle, LogEvent::default()), Passed);
+        }
+        assert_eq!(send(&mut throttle, LogEvent::default()), Discarded);
+
+        // The bucket is refilled at the sustained rate
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(send(&mut throttle, LogEvent::default()), Passed);
+        assert_eq!(send(&mut throttle, LogEvent::default()), Discarded);
+
+        // And up to the burst
+        clock.advance(Duration::from_secs(10));
+        for _ in 0..3 {
+            assert_eq!(send(&mut throttle, LogEvent::default()), Passed);
+        }
+        assert_eq!(send(&mut throttle, LogEvent::default()), Discarded);
+    }
+
+    #[test]
+    fn throttle_json_bytes() {
+        let event = LogEvent::from("hello world");
+        let size = event.estimated_json_encoded_size_of().get();
+
+        let clock = clock::FakeRelativeClock::default();
+        let mut throttle = throttle(
+            &format!(
+                r#"
+threshold.events = 10
+threshold.json_bytes = {}
+window_secs = 1
+"#,
+                size * 2
+            ),
+            &clock,
+        );
+
+        assert_eq!(send(&mut throttle, event.clone()), Passed);
+        assert_eq!(send(&mut throttle, event.clone()), Passed);
+        assert_eq!(send(&mut throttle, event.clone()), Discarded);
+
+        // Larger events need more of the bucket to be refilled
+        let large = LogEvent::from("hello world, and everyone else in it");
+        clock.advance(Duration::from_millis(600));
+        assert_eq!(send(&mut throttle, large), Discarded);
+        assert_eq!(send(&mut throttle, event), Passed);
+    }
+
+    #[test]
+    fn throttle_templated_threshold() {
+        let clock = clock::FakeRelativeClock::default();
+        let mut throttle = throttle(
+            r#"
+threshold = "{{ limit }}"
+window_secs = 5
+key_field = "{{ tenant }}"
+"#,
+            &clock,
+        );
+
+        let small = log(&[("tenant", "small"), ("limit", "1")]);
+        let large = log(&[("tenant", "large"), ("limit", "3")]);
+        assert_eq!(send(&mut throttle, small.clone()), Passed);
+        assert_eq!(send(&mut throttle, small), Discarded);
+        for _ in 0..3 {
+            assert_eq!(send(&mut throttle, large.clone()), Passed);
+        }
+        assert_eq!(send(&mut throttle, large), Discarded);
+
+        // Events without a valid limit are not limited
+        for _ in 0..5 {
+            assert_eq!(send(&mut throttle, log(&[("limit", "none")])), Passed);
+        }
+    }
+
+    #[test]
+    fn throttle_reroute_dropped() {
+        let clock = clock::FakeRelativeClock::default();
+        let mut throttle = throttle(
+            r#"
+threshold = 1
+window_secs = 5
+reroute_dropped = true
+"#,
+            &clock,
+        );
+
+        assert_eq!(send(&mut throttle, LogEvent::default()), Passed);
+        assert_eq!(send(&mut throttle, LogEvent::default()), Dropped);
+    }
+
+    #[test]
+    fn rejects_invalid_limits() {
+        for config in [
+            "threshold = 0\nwindow_secs = 1",
+            "threshold = 1\nwindow_secs = 0",
+            "threshold = 1\nwindow_secs = 1\nburst = 0",
+            "threshold = {}\nwindow_secs = 1",
+            "threshold = 1\nwindow_secs = 1\nburst.json_bytes = 100",
+        ] {
+            let config = toml::from_str::<ThrottleConfig>(config).unwrap();
+            let clock = clock::FakeRelativeClock::default();
+            assert!(
+                Throttle::new(&config, &TransformContext::default(), clock).is_err(),
+                "{config:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn flushes_full_buckets() {
+        let clock = clock::FakeRelativeClock::default();
+        let mut throttle = throttle(
+            r#"
+threshold = 1
+window_secs = 1
+key_field = "{{ bucket }}"
+"#,
+            &clock,
+        );
+
+        assert_eq!(send(&mut throttle, log(&[("bucket", "a")])), Passed);
+        assert_eq!(send(&mut throttle, log(&[("bucket", "b")])), Passed);
+        assert_eq!(buckets(&throttle), 2);
+
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(send(&mut throttle, log(&[("bucket", "c")])), Passed);
+        assert_eq!(buckets(&throttle), 1);
+    }
+
     #[tokio::test]
     async fn emits_internal_events() {
         assert_transform_compliance(async move {
             let config = ThrottleConfig {
-                threshold: 1,
+                threshold: ThrottleThreshold::Events(ThrottleLimit::Fixed(1)),
                 window_secs: Duration::from_secs_f64(1.0),
+                burst: None,
                 key_field: None,
                 exclude: None,
+                reroute_dropped: false,
                 internal_metrics: Default::default(),
             };
             let (tx, rx) = mpsc::channel(1);
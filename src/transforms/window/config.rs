@@ -0,0 +1,169 @@
+use std::num::NonZeroUsize;
+
+use vector_lib::{
+    config::{clone_input_definitions, LogNamespace},
+    configurable::configurable_component,
+};
+
+use crate::{
+    conditions::AnyCondition,
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    schema,
+    transforms::Transform,
+};
+
+use super::transform::Window;
+
+/// Configuration for the `window` transform.
+#[configurable_component(transform(
+    "window",
+    "Apply a buffered sliding window over the stream of events and flush it based on supplied criteria."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WindowConfig {
+    /// A condition used to pass events through the transform without buffering.
+    ///
+    /// If the condition resolves to `true` for an event, the event is immediately forwarded
+    /// without buffering and without preserving the original order of events. Use with caution
+    /// if the sink cannot handle out of order events.
+    pub forward_when: Option<AnyCondition>,
+
+    /// A condition used to flush the events.
+    ///
+    /// If the condition resolves to `true` for an event, the window of the event's group is
+    /// immediately flushed, including the event itself, and any following events of that group
+    /// if `num_events_after` is more than zero.
+    pub flush_when: AnyCondition,
+
+    /// The maximum number of events to keep before the event matched by the `flush_when` condition.
+    ///
+    /// Older events are dropped as newer ones arrive.
+    #[serde(default = "default_events_before")]
+    pub num_events_before: usize,
+
+    /// The maximum number of events to forward after the event matched by the `flush_when` condition.
+    #[serde(default = "default_events_after")]
+    pub num_events_after: usize,
+
+    /// An ordered list of fields by which to group events.
+    ///
+    /// Each group with matching values gets its own window, which is only flushed by events of
+    /// that group. For example, if `group_by = ["host"]`, an error from one host only releases the
+    /// events buffered for that host.
+    ///
+    /// Each group is buffered independently, so the memory used grows with the number of distinct
+    /// values of these fields, up to `max_groups`. If empty, all events share a single window.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::examples = "request_id",
+        docs::examples = "user_id",
+        docs::examples = "transaction_id",
+    ))]
+    pub group_by: Vec<String>,
+
+    /// The maximum number of groups to keep windows for.
+    ///
+    /// Once reached, the group that least recently received an event is evicted to make room for
+    /// a new one, and the events buffered for it are dropped.
+    #[serde(default = "default_max_groups")]
+    #[configurable(metadata(docs::type_unit = "groups"))]
+    pub max_groups: NonZeroUsize,
+}
+
+const fn default_events_before() -> usize {
+    100
+}
+
+const fn default_events_after() -> usize {
+    0
+}
+
+fn default_max_groups() -> NonZeroUsize {
+    NonZeroUsize::new(10_000).expect("static non-zero number")
+}
+
+impl GenerateConfig for WindowConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"flush_when = ".level == \"error\"""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "window")]
+impl TransformConfig for WindowConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(Window::new(
+            self.forward_when
+                .as_ref()
+                .map(|condition| condition.build(&context.enrichment_tables))
+                .transpose()?,
+            self.flush_when.build(&context.enrichment_tables)?,
+            self.num_events_before,
+            self.num_events_after,
+            self.group_by.clone(),
+            self.max_groups,
+        )))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: vector_lib::enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        vec![TransformOutput::new(
+            DataType::Log,
+            clone_input_definitions(input_definitions),
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        event::LogEvent, test_util::components::assert_transform_compliance,
+        transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WindowConfig>();
+    }
+
+    #[tokio::test]
+    async fn emits_internal_events() {
+        assert_transform_compliance(async move {
+            let config = toml::from_str::<WindowConfig>(
+                r#"
+                flush_when = ".level == \"error\""
+                "#,
+            )
+            .unwrap();
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            let mut log = LogEvent::from("hello world");
+            log.insert("level", "error");
+            tx.send(log.into()).await.unwrap();
+
+            _ = out.recv().await;
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await
+    }
+}
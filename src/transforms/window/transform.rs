@@ -0,0 +1,282 @@
+use std::{collections::VecDeque, num::NonZeroUsize};
+
+use lru::LruCache;
+
+use crate::{
+    conditions::Condition,
+    event::{discriminant::Discriminant, Event},
+    internal_events::WindowEventsDropped,
+    transforms::{FunctionTransform, OutputBuffer},
+};
+
+/// The buffered events of a single group.
+///
+/// Groups only have a state while they buffer events, or forward the events following a flush.
+#[derive(Clone, Debug, Default)]
+struct WindowState {
+    /// The events preceding the next flush, oldest first.
+    events_before: VecDeque<Event>,
+
+    /// The number of events still to be forwarded after the last flush.
+    events_after: usize,
+}
+
+#[derive(Clone)]
+pub struct Window {
+    // Options
+    forward_when: Option<Condition>,
+    flush_when: Condition,
+    num_events_before: usize,
+    num_events_after: usize,
+    group_by: Vec<String>,
+
+    // State
+    windows: LruCache<Discriminant, WindowState>,
+}
+
+impl Window {
+    pub fn new(
+        forward_when: Option<Condition>,
+        flush_when: Condition,
+        num_events_before: usize,
+        num_events_after: usize,
+        group_by: Vec<String>,
+        max_groups: NonZeroUsize,
+    ) -> Self {
+        Self {
+            forward_when,
+            flush_when,
+            num_events_before,
+            num_events_after,
+            group_by,
+            windows: LruCache::new(max_groups),
+        }
+    }
+
+    /// Starts tracking the state of a group, evicting the least recently used group if there are
+    /// already `max_groups` of them.
+    fn insert(&mut self, discriminant: Discriminant, state: WindowState) {
+        if let Some((_, evicted)) = self.windows.push(discriminant, state) {
+            if !evicted.events_before.is_empty() {
+                emit!(WindowEventsDropped {
+                    count: evicted.events_before.len()
+                });
+            }
+        }
+    }
+}
+
+impl FunctionTransform for Window {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        let (forward, event) = match self.forward_when.as_ref() {
+            Some(condition) => condition.check(event),
+            None => (false, event),
+        };
+        if forward {
+            output.push(event);
+            return;
+        }
+
+        let (flush, event) = self.flush_when.check(event);
+        let discriminant = Discriminant::from_log_event(event.as_log(), &self.group_by);
+
+        if flush {
+            // Only keep the state around if there are still events to forward.
+            let state = self.windows.pop(&discriminant).unwrap_or_default();
+            output.extend(state.events_before.into_iter());
+            output.push(event);
+            if self.num_events_after > 0 {
+                self.insert(
+                    discriminant,
+                    WindowState {
+                        events_before: VecDeque::new(),
+                        events_after: self.num_events_after,
+                    },
+                );
+            }
+            return;
+        }
+
+        if let Some(state) = self.windows.get_mut(&discriminant) {
+            // Nothing is buffered while the events following a flush are forwarded, so the state
+            // is empty once they all are.
+            if state.events_after > 0 {
+                state.events_after -= 1;
+                if state.events_after == 0 {
+                    self.windows.pop(&discriminant);
+                }
+                output.push(event);
+                return;
+            }
+        }
+
+        if self.num_events_before == 0 {
+            emit!(WindowEventsDropped { count: 1 });
+            return;
+        }
+        if !self.windows.contains(&discriminant) {
+            self.insert(discriminant.clone(), WindowState::default());
+        }
+        let state = self
+            .windows
+            .get_mut(&discriminant)
+            .expect("the state of the group was just inserted");
+        if state.events_before.len() == self.num_events_before {
+            state.events_before.pop_front();
+            emit!(WindowEventsDropped { count: 1 });
+        }
+        state.events_before.push_back(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        conditions::{ConditionalConfig, VrlConfig},
+        event::LogEvent,
+    };
+
+    fn condition(source: &str) -> Condition {
+        VrlConfig {
+            source: source.to_string(),
+            runtime: Default::default(),
+        }
+        .build(&Default::default())
+        .expect("should not fail to build VRL condition")
+    }
+
+    fn event(host: &str, message: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert("host", host);
+        log.into()
+    }
+
+    fn transform_all(window: &mut Window, events: Vec<Event>) -> Vec<String> {
+        events
+            .into_iter()
+            .flat_map(|event| {
+                let mut buf = OutputBuffer::with_capacity(1);
+                window.transform(&mut buf, event);
+                buf.into_events().collect::<Vec<_>>()
+            })
+            .map(|event| event.as_log()["message"].to_string_lossy().into_owned())
+            .collect()
+    }
+
+    fn window(num_events_before: usize, num_events_after: usize, group_by: &[&str]) -> Window {
+        window_with_max_groups(num_events_before, num_events_after, group_by, 100)
+    }
+
+    fn window_with_max_groups(
+        num_events_before: usize,
+        num_events_after: usize,
+        group_by: &[&str],
+        max_groups: usize,
+    ) -> Window {
+        Window::new(
+            Some(condition(r#"starts_with!(.message, "forward")"#)),
+            condition(r#"starts_with!(.message, "error")"#),
+            num_events_before,
+            num_events_after,
+            group_by.iter().map(ToString::to_string).collect(),
+            NonZeroUsize::new(max_groups).unwrap(),
+        )
+    }
+
+    #[test]
+    fn flushes_events_around_trigger() {
+        let mut window = window(2, 1, &[]);
+        let output = transform_all(
+            &mut window,
+            vec![
+                event("a", "one"),
+                event("a", "two"),
+                event("a", "three"),
+                event("a", "error"),
+                event("a", "four"),
+                event("a", "five"),
+            ],
+        );
+        assert_eq!(output, vec!["two", "three", "error", "four"]);
+    }
+
+    #[test]
+    fn forwards_matching_events() {
+        let mut window = window(1, 0, &[]);
+        let output = transform_all(
+            &mut window,
+            vec![
+                event("a", "one"),
+                event("a", "forward"),
+                event("a", "two"),
+                event("a", "error"),
+            ],
+        );
+        assert_eq!(output, vec!["forward", "two", "error"]);
+    }
+
+    #[test]
+    fn flushes_only_the_triggering_group() {
+        let mut window = window(2, 1, &["host"]);
+        let output = transform_all(
+            &mut window,
+            vec![
+                event("a", "a1"),
+                event("b", "b1"),
+                event("a", "a2"),
+                event("b", "error b"),
+                event("a", "a3"),
+                event("b", "b2"),
+                event("b", "b3"),
+                event("a", "error a"),
+            ],
+        );
+        assert_eq!(output, vec!["b1", "error b", "b2", "a2", "a3", "error a"]);
+    }
+
+    #[test]
+    fn forwards_only_trigger_without_buffer() {
+        let mut window = window(0, 0, &[]);
+        let output = transform_all(
+            &mut window,
+            vec![event("a", "one"), event("a", "error"), event("a", "two")],
+        );
+        assert_eq!(output, vec!["error"]);
+    }
+
+    #[test]
+    fn keeps_no_state_for_unbuffered_groups() {
+        let mut window = window(0, 1, &["host"]);
+        transform_all(
+            &mut window,
+            vec![event("a", "one"), event("b", "two"), event("c", "three")],
+        );
+        assert_eq!(window.windows.len(), 0);
+
+        let output = transform_all(
+            &mut window,
+            vec![event("a", "error"), event("a", "four"), event("a", "five")],
+        );
+        assert_eq!(output, vec!["error", "four"]);
+        assert_eq!(window.windows.len(), 0);
+    }
+
+    #[test]
+    fn evicts_least_recently_used_groups() {
+        let mut window = window_with_max_groups(2, 0, &["host"], 2);
+        let output = transform_all(
+            &mut window,
+            vec![
+                event("a", "a1"),
+                event("b", "b1"),
+                event("a", "a2"),
+                event("c", "c1"),
+                event("b", "error b"),
+                event("a", "error a"),
+            ],
+        );
+        assert_eq!(output, vec!["error b", "a1", "a2", "error a"]);
+        assert_eq!(window.windows.len(), 1);
+    }
+}